@@ -0,0 +1,102 @@
+//! Code generation for the `GeneratorMetaData` derive.
+
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::{field_parameters, filter_description, helpers};
+
+/// Options of the struct-level `#[generator(...)]` attribute.
+struct GeneratorOptions {
+    type_id: String,
+    name: Option<String>,
+    outputs: usize,
+    envelope: bool,
+}
+
+fn generator_options(input: &DeriveInput) -> GeneratorOptions {
+    let mut type_id = None;
+    let mut name = None;
+    let mut outputs = 1;
+    let mut envelope = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("generator"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type_id") {
+                type_id = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.path.is_ident("outputs") {
+                outputs = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+            } else if meta.path.is_ident("envelope") {
+                envelope = true;
+            } else {
+                return Err(meta.error("unknown generator option"));
+            }
+            Ok(())
+        })
+        .unwrap_or_else(|e| panic!("Invalid generator attribute: {e}"));
+    }
+
+    GeneratorOptions {
+        type_id: type_id.expect("GeneratorMetaData requires #[generator(type_id = \"...\")]"),
+        name,
+        outputs,
+        envelope,
+    }
+}
+
+pub fn derive(input: &DeriveInput) -> proc_macro2::TokenStream {
+    if !matches!(input.data, syn::Data::Struct(_)) {
+        panic!("GeneratorMetaData can only be derived for structs");
+    }
+
+    let options = generator_options(input);
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let type_id = options.type_id;
+    let name = options
+        .name
+        .unwrap_or_else(|| helpers::to_natural(&struct_name.to_string()));
+    let description = filter_description(input);
+    let outputs = options.outputs;
+    let parameters = field_parameters(input, "generator_parameter")
+        .into_iter()
+        .map(|(parameter, _)| parameter);
+    let envelope = options
+        .envelope
+        .then(|| quote! { parameters.extend(crate::meta::envelope_parameters()); });
+
+    quote! {
+        impl #impl_generics rustic_meta::GeneratorInfo for #struct_name #ty_generics #where_clause {
+            fn metadata() -> rustic_meta::MetaGenerator {
+                #[allow(unused_mut)]
+                let mut parameters = vec![#(#parameters),*];
+                #envelope
+                rustic_meta::MetaGenerator {
+                    name: #name,
+                    type_id: #type_id,
+                    description: #description,
+                    parameters,
+                    output_count: #outputs,
+                }
+            }
+        }
+
+        inventory::submit! {
+            crate::meta::GeneratorRegistration {
+                info: <#struct_name as rustic_meta::GeneratorInfo>::metadata,
+                create: (|sample_rate| {
+                    crate::meta::traits::GeneratorFactory::create_source(
+                        &<#struct_name as Default>::default(),
+                        sample_rate,
+                    )
+                }),
+            }
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use quote::{format_ident, quote};
 use syn::{DeriveInput, parse_macro_input};
 
+mod generator;
 mod helpers;
 mod parameters;
 
-use parameters::extract_parameter;
+use parameters::{extract_parameter, is_integer_type};
 use rustic_meta::Parameter;
 
 /// Extracts the description from the filter's
@@ -64,16 +65,43 @@ fn filter_input_ports(input: &DeriveInput) -> usize {
     }
 }
 
+/// Reads the number of output ports from the struct-level
+/// `#[filter(outputs = N)]` attribute, defaulting to 1.
+fn filter_output_ports(input: &DeriveInput) -> (usize, Option<syn::Ident>) {
+    let mut outputs = 1;
+    let mut outputs_from = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("filter")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("outputs") {
+                outputs = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+            } else if meta.path.is_ident("outputs_from") {
+                outputs_from = Some(meta.value()?.parse::<syn::Ident>()?);
+            } else {
+                return Err(meta.error("unknown filter option"));
+            }
+            Ok(())
+        })
+        .unwrap_or_else(|e| panic!("Invalid filter attribute: {e}"));
+    }
+    (outputs, outputs_from)
+}
+
 /// Extracts the parameters from the filter structure,
 /// returning each parameter alongside its field type for code generation.
 fn filter_parameters(input: &DeriveInput) -> Vec<(Parameter<String>, syn::Type)> {
+    field_parameters(input, "filter_parameter")
+}
+
+/// Extracts the parameters declared with the `attribute` attribute
+/// (e.g. `filter_parameter`) on the fields of a structure.
+fn field_parameters(input: &DeriveInput, attribute: &str) -> Vec<(Parameter<String>, syn::Type)> {
     let mut parameters = vec![];
     if let syn::Data::Struct(filter_structure) = &input.data {
         for field in filter_structure.fields.iter() {
             if let Some(position) = field
                 .attrs
                 .iter()
-                .position(|e| e.path().is_ident("filter_parameter"))
+                .position(|e| e.path().is_ident(attribute))
                 && let syn::Meta::List(token_list) = &field.attrs[position].meta
             {
                 let field_name = field
@@ -91,22 +119,12 @@ fn filter_parameters(input: &DeriveInput) -> Vec<(Parameter<String>, syn::Type)>
     parameters
 }
 
-/// Returns the field_name string from any Parameter variant.
-fn get_param_field_name(param: &Parameter<String>) -> &str {
-    match param {
-        Parameter::Toggle { field_name, .. } => field_name,
-        Parameter::Range { field_name, .. } => field_name,
-        Parameter::Float { field_name, .. } => field_name,
-        Parameter::Int { field_name, .. } => field_name,
-        Parameter::List { field_name, .. } => field_name,
-    }
-}
-
 fn build_filter_info(
     parameters: &[(Parameter<String>, syn::Type)],
     name: &str,
     description: &str,
     source_amount: usize,
+    output_amount: usize,
 ) -> proc_macro2::TokenStream {
     // One audio FilterInput per audio source port
     let audio_inputs: Vec<proc_macro2::TokenStream> = (0..source_amount)
@@ -125,7 +143,7 @@ fn build_filter_info(
             if matches!(param, Parameter::List { .. }) {
                 return None;
             }
-            let label = get_param_field_name(param);
+            let label = param.field_name();
             Some(quote! {
                 rustic_meta::FilterInput {
                     label: Some(#label),
@@ -144,23 +162,27 @@ fn build_filter_info(
                 #(#audio_inputs,)*
                 #(#param_inputs,)*
             ],
-            outputs: 1,
+            outputs: #output_amount,
         }
     }
 }
 
-/// Generates the `impl MetaFilter` block containing both `set_parameter` and `metadata`.
+/// Generates the `impl MetaFilter` block containing `set_parameter`, `get_parameter`,
+/// `filter_info` and `metadata`.
 ///
 /// `set_parameter` match arms per parameter type:
 /// - Range: cast to field type with clamping to min/max
 /// - Float/Val(float): cast to field type
 /// - Toggle: `bool` via `value != 0.0`
 /// - Int/Val(int): cast to field type with optional clamping
+/// - Choice: option index, clamped to the option count; non-integer fields
+///   (e.g. an enum) are converted with `From<usize>` and read back with `usize::from`
 /// - List: skipped (can't set with a single f32)
 fn generate_meta_filter_impl(
     struct_name: &syn::Ident,
     parameters: &[(Parameter<String>, syn::Type)],
     filter_info: proc_macro2::TokenStream,
+    outputs_from: Option<&syn::Ident>,
     impl_generics: &syn::ImplGenerics,
     ty_generics: &syn::TypeGenerics,
     where_clause: Option<&syn::WhereClause>,
@@ -209,11 +231,69 @@ fn generate_meta_filter_impl(
                     #field_name => { self.#ident = #assignment; }
                 })
             }
+            Parameter::Choice {
+                field_name,
+                options,
+                ..
+            } => {
+                let ident = format_ident!("{}", field_name);
+                let last = options.len() - 1;
+                let index = quote! { (value.round().max(0.0) as usize).min(#last) };
+                let assignment = if is_integer_type(field_type) {
+                    quote! { #index as #field_type }
+                } else {
+                    quote! { <#field_type as From<usize>>::from(#index) }
+                };
+                Some(quote! {
+                    #field_name => { self.#ident = #assignment; }
+                })
+            }
+            Parameter::List { .. } => None,
+        })
+        .collect();
+
+    let get_arms: Vec<proc_macro2::TokenStream> = parameters
+        .iter()
+        .filter_map(|(param, field_type)| match param {
+            Parameter::Range { field_name, .. }
+            | Parameter::Float { field_name, .. }
+            | Parameter::Int { field_name, .. } => {
+                let ident = format_ident!("{}", field_name);
+                Some(quote! {
+                    #field_name => Some(self.#ident as f32),
+                })
+            }
+            Parameter::Toggle { field_name, .. } => {
+                let ident = format_ident!("{}", field_name);
+                Some(quote! {
+                    #field_name => Some(if self.#ident { 1.0 } else { 0.0 }),
+                })
+            }
+            Parameter::Choice { field_name, .. } => {
+                let ident = format_ident!("{}", field_name);
+                let index = if is_integer_type(field_type) {
+                    quote! { self.#ident as f32 }
+                } else {
+                    quote! { usize::from(self.#ident.clone()) as f32 }
+                };
+                Some(quote! {
+                    #field_name => Some(#index),
+                })
+            }
             Parameter::List { .. } => None,
         })
         .collect();
 
     let filter_name = struct_name.to_string();
+    let instance_info = match outputs_from {
+        Some(method) => quote! {
+            rustic_meta::FilterInfo {
+                outputs: self.#method(),
+                ..Self::metadata()
+            }
+        },
+        None => quote! { Self::metadata() },
+    };
 
     quote! {
         impl #impl_generics rustic_meta::MetaFilter for #struct_name #ty_generics #where_clause {
@@ -226,6 +306,17 @@ fn generate_meta_filter_impl(
                 }
             }
 
+            fn get_parameter(&self, name: &str) -> Option<f32> {
+                match name {
+                    #(#get_arms)*
+                    _ => None,
+                }
+            }
+
+            fn filter_info(&self) -> rustic_meta::FilterInfo {
+                #instance_info
+            }
+
             fn metadata() -> rustic_meta::FilterInfo {
                 #filter_info
             }
@@ -236,24 +327,37 @@ fn generate_meta_filter_impl(
 /// Derives the metadata from a filter structure.
 /// This metadata is used to generate the required
 /// data for the frontend to render the filter.
-#[proc_macro_derive(FilterMetaData, attributes(filter_source, filter_parameter))]
+/// Filters with several output ports declare them with
+/// `#[filter(outputs = 2)]` on the structure. When the number of ports
+/// depends on the instance, `#[filter(outputs = 2, outputs_from = method)]`
+/// reports `self.method()` ports instead, `outputs` remaining the count of
+/// the type's default instance.
+#[proc_macro_derive(FilterMetaData, attributes(filter, filter_source, filter_parameter))]
 pub fn derive_metadata(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
 
     let name = input.ident.clone().to_string();
     let description = filter_description(&input);
     let source_amount = filter_input_ports(&input);
+    let (output_amount, outputs_from) = filter_output_ports(&input);
     let parameter_infos = filter_parameters(&input);
 
     let struct_name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let filter_info = build_filter_info(&parameter_infos, &name, &description, source_amount);
+    let filter_info = build_filter_info(
+        &parameter_infos,
+        &name,
+        &description,
+        source_amount,
+        output_amount,
+    );
 
     let meta_filter_impl = generate_meta_filter_impl(
         struct_name,
         &parameter_infos,
         filter_info,
+        outputs_from.as_ref(),
         &impl_generics,
         &ty_generics,
         where_clause,
@@ -280,3 +384,18 @@ pub fn derive_metadata(item: proc_macro::TokenStream) -> proc_macro::TokenStream
 
     proc_macro::TokenStream::from(tokens)
 }
+
+/// Derives the metadata from a generator structure and registers it so
+/// it can be instantiated by type id through the `GeneratorRegistry`.
+///
+/// The structure must implement `Default` and `GeneratorFactory`. Fields are
+/// described with `#[generator_parameter(...)]`, which accepts the same forms
+/// as `filter_parameter`, and the structure itself with
+/// `#[generator(type_id = "sine", name = "Sine Wave", outputs = 1, envelope)]`,
+/// where only `type_id` is required and `envelope` appends the shared ADSR
+/// parameters.
+#[proc_macro_derive(GeneratorMetaData, attributes(generator, generator_parameter))]
+pub fn derive_generator_metadata(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    proc_macro::TokenStream::from(generator::derive(&input))
+}
@@ -1,5 +1,5 @@
 use proc_macro2::{TokenStream, TokenTree};
-use rustic_meta::Parameter;
+use rustic_meta::{Parameter, ParameterScale};
 
 use convert_case::{Case, Casing};
 
@@ -12,7 +12,7 @@ fn type_name(ty: &syn::Type) -> Option<String> {
     }
 }
 
-fn is_integer_type(ty: &syn::Type) -> bool {
+pub(crate) fn is_integer_type(ty: &syn::Type) -> bool {
     type_name(ty).is_some_and(|name| {
         matches!(
             name.as_str(),
@@ -97,6 +97,8 @@ fn extract_range_parameter(
         max: maximum,
         default,
         value: default,
+        unit: None,
+        scale: ParameterScale::Linear,
     }
 }
 
@@ -145,6 +147,7 @@ fn extract_float_parameter(
         field_name,
         default: value,
         value,
+        unit: None,
     }
 }
 
@@ -187,6 +190,8 @@ fn extract_int_parameter(
         value: default,
         min,
         max,
+        unit: None,
+        scale: ParameterScale::Linear,
     }
 }
 
@@ -344,9 +349,133 @@ pub fn extract_vector_parameter(
     }
 }
 
+/// Extracts a choice parameter from the token stream.
+/// Syntax: `filter_parameter(choices = ["Label", ...])`, optionally followed by
+/// `default = <index>`.
+fn extract_choice_parameter(
+    field_name: String,
+    title: String,
+    stream: TokenStream,
+) -> Parameter<String> {
+    let mut values = stream.into_iter();
+    match values.next() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {}
+        _ => panic!("Expected `choices = [...]`"),
+    }
+    let options = match values.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == proc_macro2::Delimiter::Bracket => {
+            group
+                .stream()
+                .into_iter()
+                .filter(|token| !matches!(token, TokenTree::Punct(p) if p.as_char() == ','))
+                .map(|token| {
+                    syn::parse_str::<syn::LitStr>(&token.to_string())
+                        .unwrap_or_else(|_| panic!("Expected a string literal, found {token}"))
+                        .value()
+                })
+                .collect::<Vec<String>>()
+        }
+        _ => panic!("Expected a bracketed list of option labels after `choices =`"),
+    };
+    if options.is_empty() {
+        panic!("A choice parameter needs at least one option");
+    }
+
+    Parameter::Choice {
+        title,
+        field_name,
+        options,
+        default: 0,
+        value: 0,
+    }
+}
+
+/// Splits trailing `key = value` options (e.g. `unit = "Hz"`) from the
+/// positional values of a filter_parameter attribute.
+fn split_options(values: Vec<TokenTree>) -> (TokenStream, Vec<(String, TokenTree)>) {
+    let mut positional = Vec::new();
+    let mut options = Vec::new();
+    let mut values = values.into_iter().peekable();
+
+    while let Some(token) = values.next() {
+        let is_option = matches!(&token, TokenTree::Ident(_))
+            && matches!(values.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=');
+        if is_option {
+            values.next();
+            let value = values
+                .next()
+                .unwrap_or_else(|| panic!("No value found for option `{token}`"));
+            options.push((token.to_string(), value));
+        } else {
+            positional.push(token);
+        }
+    }
+
+    (positional.into_iter().collect(), options)
+}
+
+/// Applies a `key = value` option to an extracted parameter.
+fn apply_option(parameter: &mut Parameter<String>, key: &str, value: TokenTree) {
+    match key {
+        "unit" => {
+            let unit = syn::parse_str::<syn::LitStr>(&value.to_string())
+                .unwrap_or_else(|_| panic!("Expected a string literal for unit, found {value}"))
+                .value();
+            match parameter {
+                Parameter::Range { unit: u, .. }
+                | Parameter::Float { unit: u, .. }
+                | Parameter::Int { unit: u, .. } => *u = Some(unit),
+                _ => panic!("The unit option is only supported on numeric parameters"),
+            }
+        }
+        "scale" => {
+            let scale = match value.to_string().as_str() {
+                "linear" => ParameterScale::Linear,
+                "log" | "logarithmic" => ParameterScale::Logarithmic,
+                "exp" | "exponential" => ParameterScale::Exponential,
+                other => panic!("Unknown parameter scale: {other}"),
+            };
+            match parameter {
+                Parameter::Range { scale: s, .. } | Parameter::Int { scale: s, .. } => *s = scale,
+                _ => panic!("The scale option is only supported on range and int parameters"),
+            }
+        }
+        "default" => {
+            let index: usize = value
+                .to_string()
+                .parse()
+                .unwrap_or_else(|_| panic!("Expected an index for default, found {value}"));
+            match parameter {
+                Parameter::Choice {
+                    options,
+                    default,
+                    value,
+                    ..
+                } => {
+                    if index >= options.len() {
+                        panic!(
+                            "Default index {index} is out of bounds for {} options",
+                            options.len()
+                        );
+                    }
+                    *default = index;
+                    *value = index;
+                }
+                _ => panic!("The default option is only supported on choice parameters"),
+            }
+        }
+        any => panic!("Unknown parameter option: {any}"),
+    }
+}
+
 /// Iterates through the token stream of a filter_parameter
 /// attribute to extract the parameter name and any additional
 /// information.
+///
+/// Positional values may be followed by `key = value` options, e.g.
+/// `filter_parameter(range, 1.0, 20000.0, 1000.0, unit = "Hz", scale = log)`.
+/// `scale` accepts `linear`, `log` or `exp`. Choice parameters use the
+/// `filter_parameter(choices = ["Hard", "Soft"], default = 1)` form.
 pub fn extract_parameter(
     name: String,
     field_type: &syn::Type,
@@ -368,16 +497,164 @@ pub fn extract_parameter(
             }
         })
         .collect();
+    let (values, options) = split_options(values);
 
     let parameter_title: String = name.to_case(Case::Title);
 
-    match param_type.as_str() {
+    let mut parameter = match param_type.as_str() {
         "range" => extract_range_parameter(name, parameter_title, values),
         "toggle" => extract_toggle_parameter(name, parameter_title, values),
         "float" => extract_float_parameter(name, parameter_title, values),
         "int" => extract_int_parameter(name, parameter_title, values),
         "val" => extract_val_parameter(name, parameter_title, field_type, values),
         "list" | "vec" => extract_vector_parameter(name, parameter_title, values),
+        "choices" => extract_choice_parameter(name, parameter_title, values),
         any => panic!("Unknown parameter type: {any}"),
+    };
+
+    for (key, value) in options {
+        apply_option(&mut parameter, &key, value);
+    }
+    parameter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(field_type: &str, attribute: &str) -> Parameter<String> {
+        extract_parameter(
+            "cutoff_frequency".to_string(),
+            &syn::parse_str(field_type).unwrap(),
+            attribute.parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn range_carries_declared_bounds_and_unit() {
+        let parameter = parse("f32", r#"range, 1.0, 20000.0, 1000.0, unit = "Hz""#);
+
+        match parameter {
+            Parameter::Range {
+                min,
+                max,
+                default,
+                value,
+                unit,
+                ..
+            } => {
+                assert_eq!(min, 1.0);
+                assert_eq!(max, 20000.0);
+                assert_eq!(default, 1000.0);
+                assert_eq!(value, 1000.0);
+                assert_eq!(unit.as_deref(), Some("Hz"));
+            }
+            other => panic!("Expected a range parameter, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn range_with_negative_minimum() {
+        let parameter = parse("f32", r#"range, -60.0, 12.0, 0.0, unit = "dB""#);
+
+        assert!(matches!(
+            parameter,
+            Parameter::Range { min: -60.0, max: 12.0, default: 0.0, ref unit, .. }
+                if unit.as_deref() == Some("dB")
+        ));
+    }
+
+    #[test]
+    fn unit_is_optional() {
+        let parameter = parse("f32", "range, 0.0, 1.0, 0.5");
+
+        assert!(matches!(
+            parameter,
+            Parameter::Range {
+                min: 0.0,
+                max: 1.0,
+                default: 0.5,
+                unit: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn int_and_float_accept_a_unit() {
+        let int = parse("u8", r#"val, 4, unit = "voices""#);
+        assert!(matches!(
+            int,
+            Parameter::Int { default: 4, min: Some(0), max: Some(255), ref unit, .. }
+                if unit.as_deref() == Some("voices")
+        ));
+
+        let float = parse("f32", r#"float, 0.5, unit = "s""#);
+        assert!(matches!(
+            float,
+            Parameter::Float { default: 0.5, ref unit, .. } if unit.as_deref() == Some("s")
+        ));
+    }
+
+    #[test]
+    fn scale_option_is_parsed() {
+        let parameter = parse(
+            "f32",
+            r#"range, 20.0, 20000.0, 1000.0, unit = "Hz", scale = log"#,
+        );
+        assert!(matches!(
+            parameter,
+            Parameter::Range {
+                scale: ParameterScale::Logarithmic,
+                ..
+            }
+        ));
+
+        let parameter = parse("f32", "range, 0.0, 1.0, 0.5");
+        assert!(matches!(
+            parameter,
+            Parameter::Range {
+                scale: ParameterScale::Linear,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn choices_carry_labels_and_default() {
+        let parameter = parse("ClipMode", r#"choices = ["Hard", "Soft"], default = 1"#);
+        match parameter {
+            Parameter::Choice {
+                options,
+                default,
+                value,
+                ..
+            } => {
+                assert_eq!(options, vec!["Hard".to_string(), "Soft".to_string()]);
+                assert_eq!(default, 1);
+                assert_eq!(value, 1);
+            }
+            other => panic!("Expected a choice parameter, got {other:?}"),
+        }
+
+        let parameter = parse(
+            "usize",
+            r#"choices = ["Low pass", "High pass", "Band pass"]"#,
+        );
+        assert!(
+            matches!(parameter, Parameter::Choice { default: 0, ref options, .. } if options.len() == 3)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn choice_default_must_be_an_option() {
+        parse("usize", r#"choices = ["Hard", "Soft"], default = 2"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown parameter option")]
+    fn unknown_option_is_rejected() {
+        parse("f32", r#"range, 0.0, 1.0, 0.5, units = "Hz""#);
     }
 }
@@ -74,7 +74,9 @@ fn main() {
     app.start(EventFilter::default())
         .expect("Failed to start audio engine");
 
-    println!("Q-P → octave 5 (C–A)   |   A-L → octave 4 (C–G#)   |   Ctrl+C to quit");
+    println!(
+        "Q-P → octave 5 (C–A)   |   A-L → octave 4 (C–G#)   |   -/= and ,/. shift octaves   |   Space sustain   |   Ctrl+C to quit"
+    );
 
     KeyboardPlayer::new().run_with_device(device, &app);
 }
@@ -2,18 +2,30 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::KeyboardError;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LiveCommand {
     OctaveUp(u8),
     OctaveDown(u8),
-    SetOctave { octave: u8, row: u8 },
+    /// Shifts every row up an octave, keeping the interval between rows.
+    AllOctavesUp,
+    /// Shifts every row down an octave, keeping the interval between rows.
+    AllOctavesDown,
+    SetOctave {
+        octave: u8,
+        row: u8,
+    },
     LinkOctaves,
     UnlinkOctaves,
-    SelectInstrument { index: usize, row: u8 },
+    SelectInstrument {
+        index: usize,
+        row: u8,
+    },
     NextInstrument(u8),
     PreviousInstrument(u8),
     LinkInstruments,
     UnlinkInstruments,
+    /// Holds released notes until sustain is toggled off.
+    ToggleSustain,
 }
 
 impl LiveCommand {
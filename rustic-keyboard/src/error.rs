@@ -6,4 +6,6 @@ pub enum KeyboardError {
     RowOutOfBounds(u8),
     #[error("Invalid octave: {0}")]
     InvalidOctave(u8),
+    #[error("Invalid key map: {0}")]
+    KeyMapParse(String),
 }
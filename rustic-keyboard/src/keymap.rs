@@ -0,0 +1,275 @@
+//! [`KeyMap`] — configurable mapping from physical keys to notes and live commands.
+//!
+//! Keys are identified by their Linux input event code (the value of
+//! `evdev::Key::code()`), so maps can be built and tested without a device.
+//! A map can be loaded from a TOML file listing its bindings:
+//!
+//! ```toml
+//! [[keys]]
+//! key = 16 # KEY_Q
+//! action = { Note = { row = 0, note = 0 } }
+//!
+//! [[keys]]
+//! key = 13 # KEY_EQUAL
+//! action = { Command = { OctaveUp = 0 } }
+//! ```
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::live::LiveCommand;
+use crate::error::KeyboardError;
+
+/// Linux input event codes of the keys used by the default map.
+pub mod codes {
+    pub const KEY_2: u16 = 3;
+    pub const KEY_3: u16 = 4;
+    pub const KEY_5: u16 = 6;
+    pub const KEY_6: u16 = 7;
+    pub const KEY_7: u16 = 8;
+    pub const KEY_MINUS: u16 = 12;
+    pub const KEY_EQUAL: u16 = 13;
+    pub const KEY_Q: u16 = 16;
+    pub const KEY_W: u16 = 17;
+    pub const KEY_E: u16 = 18;
+    pub const KEY_R: u16 = 19;
+    pub const KEY_T: u16 = 20;
+    pub const KEY_Y: u16 = 21;
+    pub const KEY_U: u16 = 22;
+    pub const KEY_I: u16 = 23;
+    pub const KEY_O: u16 = 24;
+    pub const KEY_P: u16 = 25;
+    pub const KEY_A: u16 = 30;
+    pub const KEY_S: u16 = 31;
+    pub const KEY_D: u16 = 32;
+    pub const KEY_F: u16 = 33;
+    pub const KEY_G: u16 = 34;
+    pub const KEY_H: u16 = 35;
+    pub const KEY_J: u16 = 36;
+    pub const KEY_K: u16 = 37;
+    pub const KEY_L: u16 = 38;
+    pub const KEY_Z: u16 = 44;
+    pub const KEY_X: u16 = 45;
+    pub const KEY_C: u16 = 46;
+    pub const KEY_V: u16 = 47;
+    pub const KEY_B: u16 = 48;
+    pub const KEY_N: u16 = 49;
+    pub const KEY_M: u16 = 50;
+    pub const KEY_COMMA: u16 = 51;
+    pub const KEY_DOT: u16 = 52;
+    pub const KEY_SPACE: u16 = 57;
+}
+
+/// What pressing a mapped key does.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KeyAction {
+    /// Plays the chromatic note `note` (0 = C) in the octave of row `row`.
+    Note { row: u8, note: u8 },
+    /// Applies a live command on press.
+    Command(LiveCommand),
+}
+
+/// A single `key → action` binding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub key: u16,
+    pub action: KeyAction,
+}
+
+/// Maps physical keys to [`KeyAction`]s. Keys without a binding are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyMap {
+    #[serde(default)]
+    keys: Vec<KeyBinding>,
+}
+
+impl Default for KeyMap {
+    /// The QWERTY layout described in [`crate::player`], with `-`/`=` shifting
+    /// the octave of row 0, `,`/`.` that of row 1 and space toggling sustain.
+    fn default() -> Self {
+        use codes::*;
+
+        let mut map = Self::from_rows([
+            &[
+                KEY_Q, KEY_W, KEY_E, KEY_R, KEY_T, KEY_Y, KEY_U, KEY_I, KEY_O, KEY_P,
+            ],
+            &[
+                KEY_A, KEY_S, KEY_D, KEY_F, KEY_G, KEY_H, KEY_J, KEY_K, KEY_L,
+            ],
+        ]);
+        map.set_octave_keys(0, KEY_MINUS, KEY_EQUAL);
+        map.set_octave_keys(1, KEY_COMMA, KEY_DOT);
+        map.bind(KEY_SPACE, KeyAction::Command(LiveCommand::ToggleSustain));
+        map
+    }
+}
+
+impl KeyMap {
+    /// A map without any binding.
+    pub fn empty() -> Self {
+        Self { keys: Vec::new() }
+    }
+
+    /// The tracker-style two-row piano: white keys on `Q W E R T Y U` and
+    /// `Z X C V B N M`, black keys on the row above each.  `-`/`=` shift
+    /// both rows an octave and space toggles sustain.
+    ///
+    /// ```text
+    /// Row 0:  2 3   5 6 7         Row 1:  S D   G H J
+    ///        Q W E R T Y U               Z X C V B N M
+    /// ```
+    pub fn tracker() -> Self {
+        use codes::*;
+
+        let mut map = Self::from_rows([
+            &[
+                KEY_Q, KEY_2, KEY_W, KEY_3, KEY_E, KEY_R, KEY_5, KEY_T, KEY_6, KEY_Y, KEY_7, KEY_U,
+            ],
+            &[
+                KEY_Z, KEY_S, KEY_X, KEY_D, KEY_C, KEY_V, KEY_G, KEY_B, KEY_H, KEY_N, KEY_J, KEY_M,
+            ],
+        ]);
+        map.bind(KEY_MINUS, KeyAction::Command(LiveCommand::AllOctavesDown));
+        map.bind(KEY_EQUAL, KeyAction::Command(LiveCommand::AllOctavesUp));
+        map.bind(KEY_SPACE, KeyAction::Command(LiveCommand::ToggleSustain));
+        map
+    }
+
+    /// Binds the keys of each row chromatically from C.
+    fn from_rows(rows: [&[u16]; 2]) -> Self {
+        let mut map = Self::empty();
+        for (row, keys) in rows.iter().enumerate() {
+            for (note, &key) in keys.iter().enumerate() {
+                map.bind(
+                    key,
+                    KeyAction::Note {
+                        row: row as u8,
+                        note: note as u8,
+                    },
+                );
+            }
+        }
+        map
+    }
+
+    /// Parses a map from the TOML format shown in the [module docs](self).
+    pub fn from_toml(contents: &str) -> Result<Self, KeyboardError> {
+        toml::from_str(contents).map_err(|e| KeyboardError::KeyMapParse(e.to_string()))
+    }
+
+    /// Loads a map from a TOML file.
+    pub fn from_file(path: &Path) -> Result<Self, KeyboardError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| KeyboardError::KeyMapParse(format!("{}: {e}", path.display())))?;
+        Self::from_toml(&contents)
+    }
+
+    /// Binds `key` to `action`, replacing its previous binding.
+    pub fn bind(&mut self, key: u16, action: KeyAction) {
+        self.unbind(key);
+        self.keys.push(KeyBinding { key, action });
+    }
+
+    /// Removes the binding of `key`, if any.
+    pub fn unbind(&mut self, key: u16) {
+        self.keys.retain(|binding| binding.key != key);
+    }
+
+    /// Moves the octave down/up commands of `row` to the given keys.
+    pub fn set_octave_keys(&mut self, row: u8, down: u16, up: u16) {
+        self.keys.retain(|binding| {
+            !matches!(
+                binding.action,
+                KeyAction::Command(LiveCommand::OctaveDown(r) | LiveCommand::OctaveUp(r)) if r == row
+            )
+        });
+        self.bind(down, KeyAction::Command(LiveCommand::OctaveDown(row)));
+        self.bind(up, KeyAction::Command(LiveCommand::OctaveUp(row)));
+    }
+
+    /// The action bound to `key`.
+    pub fn get(&self, key: u16) -> Option<&KeyAction> {
+        self.keys
+            .iter()
+            .find(|binding| binding.key == key)
+            .map(|binding| &binding.action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::codes::*;
+    use super::*;
+
+    #[test]
+    fn test_default_matches_qwerty_rows() {
+        let map = KeyMap::default();
+        assert_eq!(map.get(KEY_Q), Some(&KeyAction::Note { row: 0, note: 0 }));
+        assert_eq!(map.get(KEY_P), Some(&KeyAction::Note { row: 0, note: 9 }));
+        assert_eq!(map.get(KEY_L), Some(&KeyAction::Note { row: 1, note: 8 }));
+        assert_eq!(
+            map.get(KEY_EQUAL),
+            Some(&KeyAction::Command(LiveCommand::OctaveUp(0)))
+        );
+        assert_eq!(map.get(KEY_COMMA + 100), None);
+    }
+
+    #[test]
+    fn test_tracker_layout() {
+        let map = KeyMap::tracker();
+        assert_eq!(map.get(KEY_Z), Some(&KeyAction::Note { row: 1, note: 0 }));
+        assert_eq!(map.get(KEY_J), Some(&KeyAction::Note { row: 1, note: 10 }));
+        assert_eq!(map.get(KEY_U), Some(&KeyAction::Note { row: 0, note: 11 }));
+        assert_eq!(
+            map.get(KEY_SPACE),
+            Some(&KeyAction::Command(LiveCommand::ToggleSustain))
+        );
+        assert_eq!(map.get(KEY_A), None);
+    }
+
+    #[test]
+    fn test_reassign_octave_keys() {
+        let mut map = KeyMap::default();
+        map.set_octave_keys(0, KEY_Y, KEY_U);
+        assert_eq!(map.get(KEY_MINUS), None);
+        assert_eq!(map.get(KEY_EQUAL), None);
+        assert_eq!(
+            map.get(KEY_Y),
+            Some(&KeyAction::Command(LiveCommand::OctaveDown(0)))
+        );
+        assert_eq!(
+            map.get(KEY_U),
+            Some(&KeyAction::Command(LiveCommand::OctaveUp(0)))
+        );
+        // Row 1 keeps its keys
+        assert_eq!(
+            map.get(KEY_DOT),
+            Some(&KeyAction::Command(LiveCommand::OctaveUp(1)))
+        );
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let map = KeyMap::from_toml(
+            r#"
+            [[keys]]
+            key = 44
+            action = { Note = { row = 1, note = 4 } }
+
+            [[keys]]
+            key = 45
+            action = { Command = { OctaveUp = 1 } }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(map.get(44), Some(&KeyAction::Note { row: 1, note: 4 }));
+        assert_eq!(
+            map.get(45),
+            Some(&KeyAction::Command(LiveCommand::OctaveUp(1)))
+        );
+        assert_eq!(map.get(KEY_Q), None);
+
+        assert!(KeyMap::from_toml("keys = 3").is_err());
+    }
+}
@@ -4,6 +4,7 @@
 //!
 //! - [`row`]: The `Row` abstraction mapping physical keyboard rows to instruments + octaves
 //! - [`commands`]: `LiveCommand` for real-time octave/instrument switching
+//! - [`keymap`]: [`KeyMap`] — configurable physical key → note/command bindings
 //! - [`inputs`]: evdev-based keyboard input detection (requires `input` feature)
 //! - [`player`]: [`KeyboardPlayer`] — holds row state, maps key events to audio commands
 //! - [`error`]: Keyboard-specific error types
@@ -11,7 +12,9 @@
 pub mod commands;
 pub mod error;
 pub mod inputs;
+pub mod keymap;
 pub mod player;
 pub mod row;
 
+pub use keymap::KeyMap;
 pub use player::KeyboardPlayer;
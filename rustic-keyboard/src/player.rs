@@ -10,21 +10,34 @@
 //!                 C  C# D  D# E  F  F# G  G#
 //! ```
 //!
-//! Row 0 defaults to octave 5, row 1 to octave 4.
+//! Row 0 defaults to octave 5, row 1 to octave 4.  `-`/`=` shift the octave
+//! of row 0 and `,`/`.` that of row 1.  Space toggles sustain: while it is on,
+//! released notes keep playing until it is toggled off.  The layout can be
+//! changed with a [`KeyMap`], e.g. [`KeyMap::tracker`].
 
+use rustic::Note;
+use rustic::prelude::AudioCommand;
 #[cfg(feature = "input")]
-use rustic::prelude::App;
+use rustic::prelude::{App, Command};
 
 use crate::commands::live::LiveCommand;
 use crate::error::KeyboardError;
+use crate::keymap::{KeyAction, KeyMap};
 use crate::row::Row;
 
 /// Holds the live keyboard state and drives playback by translating key
 /// events into [`rustic::app::commands::AudioCommand`]s sent through the app.
 pub struct KeyboardPlayer {
     rows: [Row; 2],
+    keymap: KeyMap,
     octaves_linked: bool,
     instruments_linked: bool,
+    sustain: bool,
+    /// Notes started by keys still down, as `(key, instrument_idx, note)`.
+    /// Releases stop these rather than the note the key maps to now.
+    held: Vec<(u16, usize, Note)>,
+    /// Released notes kept playing by sustain.
+    sustained: Vec<(usize, Note)>,
 }
 
 impl Default for KeyboardPlayer {
@@ -46,11 +59,34 @@ impl KeyboardPlayer {
                     octave: 4,
                 }, // row 1 — lower (ASDF)
             ],
+            keymap: KeyMap::default(),
             octaves_linked: false,
             instruments_linked: false,
+            sustain: false,
+            held: Vec::new(),
+            sustained: Vec::new(),
         }
     }
 
+    /// Replaces the default QWERTY layout with `keymap`.
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    pub fn keymap(&self) -> &KeyMap {
+        &self.keymap
+    }
+
+    /// The current octave of `row`.
+    pub fn octave(&self, row: u8) -> Option<u8> {
+        self.rows.get(row as usize).map(|r| r.octave)
+    }
+
+    pub fn is_sustained(&self) -> bool {
+        self.sustain
+    }
+
     /// Apply a [`LiveCommand`] to mutate row state (octave, instrument, linking).
     pub fn apply(&mut self, cmd: LiveCommand) -> Result<(), KeyboardError> {
         cmd.validate()?;
@@ -69,6 +105,16 @@ impl KeyboardPlayer {
                     self.rows[1 - r].octave = self.rows[r].octave;
                 }
             }
+            LiveCommand::AllOctavesUp => {
+                if self.rows.iter().all(|r| r.octave < 8) {
+                    self.rows.iter_mut().for_each(|r| r.octave += 1);
+                }
+            }
+            LiveCommand::AllOctavesDown => {
+                if self.rows.iter().all(|r| r.octave > 0) {
+                    self.rows.iter_mut().for_each(|r| r.octave -= 1);
+                }
+            }
             LiveCommand::SetOctave { octave, row } => {
                 let r = row as usize;
                 self.rows[r].octave = octave;
@@ -118,22 +164,77 @@ impl KeyboardPlayer {
             LiveCommand::UnlinkInstruments => {
                 self.instruments_linked = false;
             }
+            LiveCommand::ToggleSustain => {
+                self.sustain = !self.sustain;
+            }
         }
         Ok(())
     }
 
-    #[cfg(feature = "input")]
-    fn note_on(&self, app: &App, row: usize, note_idx: u8) {
-        let note = self.rows[row].get_note(note_idx);
-        let instrument_idx = self.rows[row].instrument;
-        let _ = app.note_on(instrument_idx, note, 1.0);
-    }
+    /// Translate a key press or release into the audio commands it triggers.
+    ///
+    /// `key` is a Linux input event code. Keys bound to a [`LiveCommand`] are
+    /// applied on press; turning sustain off stops the notes it held.
+    /// Unmapped keys are ignored.
+    pub fn translate(&mut self, key: u16, pressed: bool) -> Vec<AudioCommand> {
+        let mut commands = Vec::new();
+        match self.keymap.get(key).cloned() {
+            Some(KeyAction::Note { row, note }) if pressed => {
+                let Some(row) = self.rows.get(row as usize) else {
+                    return commands;
+                };
+                let (instrument_idx, note) = (row.instrument, row.get_note(note));
+                // Restart a note still ringing from sustain
+                if let Some(idx) = self
+                    .sustained
+                    .iter()
+                    .position(|&sustained| sustained == (instrument_idx, note))
+                {
+                    self.sustained.remove(idx);
+                    commands.push(AudioCommand::NoteStop {
+                        instrument_idx,
+                        note,
+                    });
+                }
+                self.held.retain(|&(k, ..)| k != key);
+                self.held.push((key, instrument_idx, note));
+                commands.push(AudioCommand::NoteStart {
+                    instrument_idx,
+                    note,
+                    velocity: 1.0,
+                });
+            }
+            Some(KeyAction::Note { .. }) => {
+                let Some(idx) = self.held.iter().position(|&(k, ..)| k == key) else {
+                    return commands;
+                };
+                let (_, instrument_idx, note) = self.held.remove(idx);
+                if self.sustain {
+                    self.sustained.push((instrument_idx, note));
+                } else {
+                    commands.push(AudioCommand::NoteStop {
+                        instrument_idx,
+                        note,
+                    });
+                }
+            }
+            Some(KeyAction::Command(cmd)) => {
+                if pressed && let Err(e) = self.apply(cmd) {
+                    log::warn!("Ignoring key command: {e}");
+                }
+            }
+            None => {}
+        }
 
-    #[cfg(feature = "input")]
-    fn note_off(&self, app: &App, row: usize, note_idx: u8) {
-        let note = self.rows[row].get_note(note_idx);
-        let instrument_idx = self.rows[row].instrument;
-        let _ = app.note_off(instrument_idx, note);
+        if !self.sustain {
+            commands.extend(self.sustained.drain(..).map(|(instrument_idx, note)| {
+                AudioCommand::NoteStop {
+                    instrument_idx,
+                    note,
+                }
+            }));
+        }
+        commands
     }
 
     /// Block on keyboard events and translate them into audio commands.
@@ -177,55 +278,155 @@ impl KeyboardPlayer {
 
             for event in events {
                 if let InputEventKind::Key(key) = event.kind() {
-                    match event.value() {
-                        1 /* pressed */ => {
-                            if let Some((row, note_idx)) = Self::key_to_note(key) {
-                                self.note_on(app, row, note_idx);
-                            }
-                        }
-                        0 /* released */ => {
-                            if let Some((row, note_idx)) = Self::key_to_note(key) {
-                                self.note_off(app, row, note_idx);
-                            }
-                        }
-                        _ => {} // auto-repeat, ignore
+                    let pressed = match event.value() {
+                        1 /* pressed */ => true,
+                        0 /* released */ => false,
+                        _ => continue, // auto-repeat, ignore
+                    };
+                    for cmd in self.translate(key.code(), pressed) {
+                        let _ = app.send(Command::Audio(cmd));
                     }
                 }
             }
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustic::NOTES;
+
+    use super::*;
+    use crate::keymap::codes::*;
+
+    /// `(is_start, instrument_idx, note)` of each command.
+    fn sent(commands: Vec<AudioCommand>) -> Vec<(bool, usize, Note)> {
+        commands
+            .into_iter()
+            .map(|cmd| match cmd {
+                AudioCommand::NoteStart {
+                    instrument_idx,
+                    note,
+                    ..
+                } => (true, instrument_idx, note),
+                AudioCommand::NoteStop {
+                    instrument_idx,
+                    note,
+                } => (false, instrument_idx, note),
+                AudioCommand::Shutdown => panic!("unexpected shutdown"),
+            })
+            .collect()
+    }
 
-    /// Map a physical key to `(row, note_index)`.
-    ///
-    /// `note_index` is a chromatic offset from C (0 = C, 1 = C#, …, 11 = B).
-    #[cfg(feature = "input")]
-    fn key_to_note(key: evdev::Key) -> Option<(usize, u8)> {
-        use evdev::Key;
-        match key {
-            // Row 0 — QWERTY (octave 5 by default)
-            Key::KEY_Q => Some((0, 0)), // C
-            Key::KEY_W => Some((0, 1)), // C#
-            Key::KEY_E => Some((0, 2)), // D
-            Key::KEY_R => Some((0, 3)), // D#
-            Key::KEY_T => Some((0, 4)), // E
-            Key::KEY_Y => Some((0, 5)), // F
-            Key::KEY_U => Some((0, 6)), // F#
-            Key::KEY_I => Some((0, 7)), // G
-            Key::KEY_O => Some((0, 8)), // G#
-            Key::KEY_P => Some((0, 9)), // A
-
-            // Row 1 — ASDF (octave 4 by default)
-            Key::KEY_A => Some((1, 0)), // C
-            Key::KEY_S => Some((1, 1)), // C#
-            Key::KEY_D => Some((1, 2)), // D
-            Key::KEY_F => Some((1, 3)), // D#
-            Key::KEY_G => Some((1, 4)), // E
-            Key::KEY_H => Some((1, 5)), // F
-            Key::KEY_J => Some((1, 6)), // F#
-            Key::KEY_K => Some((1, 7)), // G
-            Key::KEY_L => Some((1, 8)), // G#
-
-            _ => None,
-        }
+    #[test]
+    fn test_default_layout() {
+        let mut player = KeyboardPlayer::new();
+        assert_eq!(
+            sent(player.translate(KEY_Q, true)),
+            vec![(true, 0, Note(NOTES::C, 5))]
+        );
+        assert_eq!(
+            sent(player.translate(KEY_G, true)),
+            vec![(true, 0, Note(NOTES::E, 4))]
+        );
+        assert_eq!(
+            sent(player.translate(KEY_G, false)),
+            vec![(false, 0, Note(NOTES::E, 4))]
+        );
+    }
+
+    #[test]
+    fn test_custom_mapping_routes_key_to_note() {
+        // Z plays the D of the lower row
+        let mut keymap = KeyMap::empty();
+        keymap.bind(KEY_Z, KeyAction::Note { row: 1, note: 2 });
+        let mut player = KeyboardPlayer::new().with_keymap(keymap);
+
+        assert_eq!(
+            sent(player.translate(KEY_Z, true)),
+            vec![(true, 0, Note(NOTES::D, 4))]
+        );
+    }
+
+    #[test]
+    fn test_unmapped_keys_are_ignored() {
+        let mut keymap = KeyMap::empty();
+        keymap.bind(KEY_A, KeyAction::Note { row: 1, note: 0 });
+        let mut player = KeyboardPlayer::new().with_keymap(keymap);
+
+        assert!(player.translate(KEY_Q, true).is_empty());
+        assert!(player.translate(KEY_Q, false).is_empty());
+        assert!(player.translate(KEY_EQUAL, true).is_empty());
+        // The unbound octave key did not shift the row
+        assert_eq!(
+            sent(player.translate(KEY_A, true)),
+            vec![(true, 0, Note(NOTES::C, 4))]
+        );
+    }
+
+    #[test]
+    fn test_reassigned_octave_keys() {
+        let mut keymap = KeyMap::default();
+        keymap.set_octave_keys(1, KEY_MINUS, KEY_EQUAL);
+        let mut player = KeyboardPlayer::new().with_keymap(keymap);
+
+        assert!(player.translate(KEY_EQUAL, true).is_empty());
+        assert_eq!(
+            sent(player.translate(KEY_A, true)),
+            vec![(true, 0, Note(NOTES::C, 5))]
+        );
+        // Row 0 has no octave keys left
+        assert!(player.translate(KEY_DOT, true).is_empty());
+        assert_eq!(
+            sent(player.translate(KEY_Q, true)),
+            vec![(true, 0, Note(NOTES::C, 5))]
+        );
+    }
+
+    #[test]
+    fn test_octave_up_shifts_notes_by_an_octave() {
+        let mut player = KeyboardPlayer::new().with_keymap(KeyMap::tracker());
+        let midi = |commands: Vec<AudioCommand>| -> Vec<u8> {
+            sent(commands).iter().map(|(_, _, n)| n.to_midi()).collect()
+        };
+
+        let low = midi(player.translate(KEY_Z, true));
+        let high = midi(player.translate(KEY_Q, true));
+        player.translate(KEY_EQUAL, true);
+        assert_eq!(player.octave(0), Some(6));
+        assert_eq!(player.octave(1), Some(5));
+        assert_eq!(midi(player.translate(KEY_X, true)), vec![low[0] + 14]);
+        assert_eq!(midi(player.translate(KEY_2, true)), vec![high[0] + 13]);
+
+        // Keys pressed before the shift still stop their own note
+        assert_eq!(midi(player.translate(KEY_Z, false)), low);
+
+        player.translate(KEY_MINUS, true);
+        player.translate(KEY_MINUS, true);
+        assert_eq!(midi(player.translate(KEY_Z, true)), vec![low[0] - 12]);
+    }
+
+    #[test]
+    fn test_sustain_defers_note_offs() {
+        let mut player = KeyboardPlayer::new().with_keymap(KeyMap::tracker());
+        let (c, e) = (Note(NOTES::C, 4), Note(NOTES::E, 4));
+
+        player.translate(KEY_SPACE, true);
+        assert!(player.is_sustained());
+        assert!(player.translate(KEY_SPACE, false).is_empty());
+        player.translate(KEY_Z, true);
+        player.translate(KEY_C, true);
+        assert!(player.translate(KEY_Z, false).is_empty());
+        assert!(player.translate(KEY_C, false).is_empty());
+
+        // Retriggering a sustained note stops it first
+        assert_eq!(
+            sent(player.translate(KEY_Z, true)),
+            vec![(false, 0, c), (true, 0, c)]
+        );
+
+        assert_eq!(sent(player.translate(KEY_SPACE, true)), vec![(false, 0, e)]);
+        assert!(!player.is_sustained());
+        assert_eq!(sent(player.translate(KEY_Z, false)), vec![(false, 0, c)]);
     }
 }
@@ -9,6 +9,20 @@ pub struct MiniNotation {
     pub sequence: Sequence,
 }
 
+impl MiniNotation {
+    /// Number of top-level steps, counting `!N` as N steps and `@N` as N.
+    pub fn steps(&self) -> u32 {
+        self.sequence
+            .steps
+            .iter()
+            .map(|step| match step.modifier {
+                Some(Modifier::Replicate(n)) | Some(Modifier::Weight(n)) => n,
+                _ => 1,
+            })
+            .sum()
+    }
+}
+
 /// An ordered list of steps that share their parent's time equally
 /// (unless weights `@N` are present).
 #[derive(Debug, Clone, PartialEq)]
@@ -51,6 +65,13 @@ pub struct Note {
     pub octave: u8,
 }
 
+impl Note {
+    /// MIDI note number, with `c4` = 60.
+    pub fn midi(&self) -> i32 {
+        12 * (self.octave as i32 + 1) + self.letter.semitone() + self.accidental.offset()
+    }
+}
+
 /// A bracketed group `[...]`.
 /// If `layers.len() == 1`, it's a simple subdivision.
 /// If `layers.len() > 1`, the layers play simultaneously (chord).
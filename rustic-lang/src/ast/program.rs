@@ -13,12 +13,21 @@ pub struct Program {
 pub enum SourceLine {
     /// `bpm <integer>`
     Bpm(u32),
+    /// `bpm <from> -> <to> over <bars>`
+    BpmRamp { from: u32, to: u32, bars: u32 },
     /// `sig <num>/<den>`
     Sig(u8, u8),
     /// `scale <root> <mode>`
     Scale(PitchRoot, ScaleMode),
     /// `load "<path>"`
     Load(String),
+    /// `set <pattern> <param> <value>` — sets a parameter of the pattern's
+    /// instrument.
+    Set {
+        pattern: String,
+        param: String,
+        value: f64,
+    },
     /// A pattern line (possibly muted).
     Pattern(PatternDef),
     /// A comment (kept for round-tripping, not evaluated).
@@ -44,6 +53,13 @@ pub struct PitchRoot {
     pub accidental: Accidental,
 }
 
+impl PitchRoot {
+    /// Semitones above C, from 0 to 11.
+    pub fn semitone(&self) -> i32 {
+        (self.name.semitone() + self.accidental.offset()).rem_euclid(12)
+    }
+}
+
 /// One of the seven note letters.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NoteLetter {
@@ -56,6 +72,21 @@ pub enum NoteLetter {
     B,
 }
 
+impl NoteLetter {
+    /// Semitones above C of the natural note.
+    pub fn semitone(&self) -> i32 {
+        match self {
+            NoteLetter::C => 0,
+            NoteLetter::D => 2,
+            NoteLetter::E => 4,
+            NoteLetter::F => 5,
+            NoteLetter::G => 7,
+            NoteLetter::A => 9,
+            NoteLetter::B => 11,
+        }
+    }
+}
+
 /// Accidental applied to a pitch.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Accidental {
@@ -67,6 +98,19 @@ pub enum Accidental {
     DoubleFlat,
 }
 
+impl Accidental {
+    /// Semitone offset applied by the accidental.
+    pub fn offset(&self) -> i32 {
+        match self {
+            Accidental::Natural => 0,
+            Accidental::Sharp => 1,
+            Accidental::DoubleSharp => 2,
+            Accidental::Flat => -1,
+            Accidental::DoubleFlat => -2,
+        }
+    }
+}
+
 /// Musical scale / mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScaleMode {
@@ -83,6 +127,24 @@ pub enum ScaleMode {
     Blues,
 }
 
+impl ScaleMode {
+    /// Semitone offsets of the scale degrees from the root, within one octave.
+    pub fn intervals(&self) -> &'static [i32] {
+        match self {
+            ScaleMode::Major => &[0, 2, 4, 5, 7, 9, 11],
+            ScaleMode::Minor | ScaleMode::Aeolian => &[0, 2, 3, 5, 7, 8, 10],
+            ScaleMode::Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            ScaleMode::Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            ScaleMode::Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            ScaleMode::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            ScaleMode::Locrian => &[0, 1, 3, 5, 6, 8, 10],
+            ScaleMode::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            ScaleMode::Pentatonic => &[0, 2, 4, 7, 9],
+            ScaleMode::Blues => &[0, 3, 5, 6, 7, 10],
+        }
+    }
+}
+
 /// A transform applied after the mini-notation via `|`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Transform {
@@ -91,6 +153,8 @@ pub enum Transform {
     Slow(f64),
     Every(u32, Box<Transform>),
     Arp(ArpMode),
+    /// Resolves scale degrees in this scale and snaps notes to its nearest
+    /// pitch.
     Scale(PitchRoot, ScaleMode),
     Oct(i32),
     Gain(f64),
@@ -98,6 +162,18 @@ pub enum Transform {
     Hpf(f64),
     Delay(f64, f64),
     Reverb(f64),
+    /// Random nudges of up to `timing_ms` milliseconds on each onset and up
+    /// to `velocity` on each velocity.
+    Humanize {
+        timing_ms: f64,
+        velocity: f64,
+    },
+    /// Plays the top-level steps at `n` steps per cycle instead of fitting
+    /// them in one cycle, so patterns of different lengths drift against
+    /// each other (polymeter).
+    Steps(u32),
+    /// Layers another mini-notation on top of the pattern: `| with "~ x"`.
+    With(MiniNotation),
 }
 
 /// Arpeggiator mode.
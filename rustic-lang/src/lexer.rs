@@ -0,0 +1,255 @@
+//! Token stream for syntax highlighting.
+//!
+//! [`tokenize`] classifies every word of a source without parsing it, so it
+//! works on half-typed lines: malformed input still yields the tokens that
+//! could be recognised, and it never fails.  Spans are byte offsets into the
+//! source; whitespace is not covered by any token.
+
+/// What a token is, for colouring purposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `bpm`, `sig`, `scale`, `load` or `set` at the start of a line.
+    Directive,
+    /// The name of a pattern line, or of the pattern a `set` line targets.
+    PatternName,
+    /// The instrument of a pattern line.
+    Instrument,
+    /// A double-quoted string: mini-notation or a `load` path.  An unclosed
+    /// quote runs to the end of the line.
+    Pattern,
+    /// A transform keyword following `|`.
+    Transform,
+    /// The `|` transform separator.
+    Pipe,
+    /// A numeric literal (`120`, `-1`, `0.5`, `4/4`).
+    Number,
+    /// The `;` muting a pattern line.
+    Mute,
+    /// `-- ...` up to the end of the line.
+    Comment,
+    /// Any other word: scale roots and modes, arp modes, parameter names...
+    Identifier,
+}
+
+/// A byte range of the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns `true` if byte `offset` falls in the span.
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+const DIRECTIVES: [&str; 5] = ["bpm", "sig", "scale", "load", "set"];
+
+/// Splits `source` into classified tokens, in order.
+pub fn tokenize(source: &str) -> Vec<(TokenKind, Span)> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    for line in source.split_inclusive('\n') {
+        tokenize_line(line.trim_end_matches(['\n', '\r']), start, &mut tokens);
+        start += line.len();
+    }
+    tokens
+}
+
+/// Position of a word within a line, deciding its kind.
+#[derive(PartialEq)]
+enum State {
+    LineStart,
+    /// After a directive: `set` names a pattern first.
+    Directive {
+        set: bool,
+    },
+    DirectiveArgs,
+    Instrument,
+    Notation,
+    /// After the notation, or after a transform's arguments.
+    Transforms,
+    TransformKeyword,
+}
+
+fn tokenize_line(line: &str, offset: usize, tokens: &mut Vec<(TokenKind, Span)>) {
+    let bytes = line.as_bytes();
+    let mut state = State::LineStart;
+    let mut i = 0;
+
+    while i < line.len() {
+        let c = bytes[i];
+        let ch = line[i..].chars().next().unwrap_or(' ');
+        if ch.is_whitespace() {
+            i += ch.len_utf8();
+            continue;
+        }
+        let span = |start: usize, end: usize| Span {
+            start: offset + start,
+            end: offset + end,
+        };
+
+        if line[i..].starts_with("--") {
+            tokens.push((TokenKind::Comment, span(i, line.len())));
+            return;
+        }
+        if c == b'"' {
+            let end = line[i + 1..]
+                .find('"')
+                .map_or(line.len(), |end| i + end + 2);
+            tokens.push((TokenKind::Pattern, span(i, end)));
+            if state == State::Notation {
+                state = State::Transforms;
+            }
+            i = end;
+            continue;
+        }
+        if c == b'|' {
+            tokens.push((TokenKind::Pipe, span(i, i + 1)));
+            state = State::TransformKeyword;
+            i += 1;
+            continue;
+        }
+        if c == b';' && state == State::LineStart {
+            tokens.push((TokenKind::Mute, span(i, i + 1)));
+            i += 1;
+            continue;
+        }
+
+        // A word runs up to whitespace or a character starting another token
+        let end = line[i..]
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '|')
+            .map_or(line.len(), |end| i + end);
+        let word = &line[i..end];
+        let kind = if is_number(word) {
+            TokenKind::Number
+        } else {
+            match state {
+                State::LineStart if DIRECTIVES.contains(&word) => {
+                    state = State::Directive { set: word == "set" };
+                    TokenKind::Directive
+                }
+                State::LineStart => {
+                    state = State::Instrument;
+                    TokenKind::PatternName
+                }
+                State::Directive { set: true } => {
+                    state = State::DirectiveArgs;
+                    TokenKind::PatternName
+                }
+                State::Instrument => {
+                    state = State::Notation;
+                    TokenKind::Instrument
+                }
+                State::TransformKeyword => {
+                    state = State::Transforms;
+                    TokenKind::Transform
+                }
+                _ => TokenKind::Identifier,
+            }
+        };
+        tokens.push((kind, span(i, end)));
+        i = end;
+    }
+}
+
+fn is_number(word: &str) -> bool {
+    let digits = word.strip_prefix('-').unwrap_or(word);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == '/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(kind, text)` of each token, for readable assertions.
+    fn words(source: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(source)
+            .into_iter()
+            .map(|(kind, span)| (kind, &source[span.start..span.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_pattern_line() {
+        use TokenKind::*;
+        assert_eq!(
+            words("lead saw \"c4 [e4 g4]\" | every 2 rev | scale C minor -- lead"),
+            vec![
+                (PatternName, "lead"),
+                (Instrument, "saw"),
+                (Pattern, "\"c4 [e4 g4]\""),
+                (Pipe, "|"),
+                (Transform, "every"),
+                (Number, "2"),
+                (Identifier, "rev"),
+                (Pipe, "|"),
+                (Transform, "scale"),
+                (Identifier, "C"),
+                (Identifier, "minor"),
+                (Comment, "-- lead"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_directives_and_spans() {
+        use TokenKind::*;
+        let source = "bpm 128\nsig 3/4\n; set bass cutoff 800";
+        assert_eq!(
+            words(source),
+            vec![
+                (Directive, "bpm"),
+                (Number, "128"),
+                (Directive, "sig"),
+                (Number, "3/4"),
+                (Mute, ";"),
+                (Directive, "set"),
+                (PatternName, "bass"),
+                (Identifier, "cutoff"),
+                (Number, "800"),
+            ]
+        );
+        let tokens = tokenize(source);
+        assert_eq!(tokens[2].1, Span { start: 8, end: 11 });
+        assert_eq!(tokens[3].1.len(), 3);
+    }
+
+    #[test]
+    fn test_malformed_input_yields_partial_tokens() {
+        use TokenKind::*;
+        assert_eq!(
+            words("  kick kick \"x ~ x"),
+            vec![
+                (PatternName, "kick"),
+                (Instrument, "kick"),
+                (Pattern, "\"x ~ x")
+            ]
+        );
+        assert_eq!(words("hats|"), vec![(PatternName, "hats"), (Pipe, "|")]);
+        assert_eq!(words("|| \"\" é --"), {
+            vec![
+                (Pipe, "|"),
+                (Pipe, "|"),
+                (Pattern, "\"\""),
+                (Transform, "é"),
+                (Comment, "--"),
+            ]
+        });
+        assert!(tokenize("").is_empty());
+        assert!(tokenize("\r\n\n \u{a0} ").is_empty());
+    }
+}
@@ -1,13 +1,17 @@
 //! Rustic Live — a live-coding music DSL.
 //!
-//! This crate provides the parser and session engine for the Rustic Live
-//! language.  See `LANGUAGE.md` for the full specification.
+//! This crate provides the parser, pattern runtime and session engine for
+//! the Rustic Live language.  See `LANGUAGE.md` for the full specification.
 
 pub mod ast;
 pub mod error;
+pub mod lexer;
 pub mod parser;
+pub mod runtime;
 pub mod session;
 
 pub use ast::{MiniNotation, PatternDef, Program, SourceLine};
 pub use error::{CompileError, CompileErrorKind, SourceLocation};
-pub use session::Session;
+pub use lexer::{Span, TokenKind, tokenize};
+pub use runtime::{EventValue, MiniEvent, QueryContext};
+pub use session::{Session, Transport, TransportEvent};
@@ -2,11 +2,52 @@
 //!
 //! Each source line is parsed independently into a [`SourceLine`].
 
+use std::fmt;
+
 use super::mini::parse_mini;
 use crate::ast::program::*;
 
+/// A line that failed to parse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    pub message: String,
+    /// Byte offset of the offending input in the line, when known.
+    pub offset: Option<usize>,
+    /// A hint on how to fix the line.
+    pub suggestion: Option<String>,
+}
+
+impl LineError {
+    /// Moves the offset from a slice of the line to the whole line.
+    fn shifted(mut self, by: usize) -> Self {
+        self.offset = self.offset.map(|offset| offset + by);
+        self
+    }
+}
+
+impl From<String> for LineError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            offset: None,
+            suggestion: None,
+        }
+    }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Byte offset of `inner` in `outer`, which must contain it.
+fn offset_in(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
 /// Parse a single source line into a [`SourceLine`].
-pub fn parse_line(line: &str) -> Result<SourceLine, String> {
+pub fn parse_line(line: &str) -> Result<SourceLine, LineError> {
     let trimmed = line.trim();
 
     // Blank
@@ -21,25 +62,28 @@ pub fn parse_line(line: &str) -> Result<SourceLine, String> {
 
     // Directives
     if let Some(rest) = strip_keyword(trimmed, "bpm") {
-        return parse_bpm(rest);
+        return Ok(parse_bpm(rest)?);
     }
     if let Some(rest) = strip_keyword(trimmed, "sig") {
-        return parse_sig(rest);
+        return Ok(parse_sig(rest)?);
     }
     if let Some(rest) = strip_keyword(trimmed, "scale") {
-        return parse_scale(rest);
+        return Ok(parse_scale(rest)?);
     }
     if let Some(rest) = strip_keyword(trimmed, "load") {
-        return parse_load(rest);
+        return Ok(parse_load(rest)?);
+    }
+    if let Some(rest) = strip_keyword(trimmed, "set") {
+        return Ok(parse_set(rest)?);
     }
 
     // Muted pattern
     if let Some(rest) = trimmed.strip_prefix(';') {
-        return parse_pattern_line(rest, true);
+        return parse_pattern_line(rest, true).map_err(|e| e.shifted(offset_in(line, rest)));
     }
 
     // Pattern line
-    parse_pattern_line(trimmed, false)
+    parse_pattern_line(trimmed, false).map_err(|e| e.shifted(offset_in(line, trimmed)))
 }
 
 /// Strip a keyword prefix followed by whitespace. Returns the rest.
@@ -56,14 +100,48 @@ fn strip_keyword<'a>(input: &'a str, keyword: &str) -> Option<&'a str> {
 // --- Directive parsers ---
 
 fn parse_bpm(rest: &str) -> Result<SourceLine, String> {
-    let val: u32 = rest
+    if let Some((from, ramp)) = rest.split_once("->") {
+        return parse_bpm_ramp(from, ramp);
+    }
+    Ok(SourceLine::Bpm(parse_bpm_value(rest)?))
+}
+
+/// `<from> -> <to> over <bars>`, split at the arrow.
+fn parse_bpm_ramp(from: &str, ramp: &str) -> Result<SourceLine, String> {
+    let mut tokens = ramp.split_whitespace();
+    let to = tokens
+        .next()
+        .ok_or_else(|| "expected target bpm after '->'".to_string())?;
+    if tokens.next() != Some("over") {
+        return Err("expected 'over <bars>' after target bpm".to_string());
+    }
+    let bars_str = tokens
+        .next()
+        .ok_or_else(|| "expected bar count after 'over'".to_string())?;
+    let bars: u32 = bars_str
+        .parse()
+        .ok()
+        .filter(|&bars| bars > 0)
+        .ok_or_else(|| format!("invalid bar count: '{}'", bars_str))?;
+    if let Some(extra) = tokens.next() {
+        return Err(format!("unexpected '{}' after bpm ramp", extra));
+    }
+    Ok(SourceLine::BpmRamp {
+        from: parse_bpm_value(from)?,
+        to: parse_bpm_value(to)?,
+        bars,
+    })
+}
+
+fn parse_bpm_value(input: &str) -> Result<u32, String> {
+    let val: u32 = input
         .trim()
         .parse()
-        .map_err(|_| format!("invalid bpm value: '{}'", rest.trim()))?;
+        .map_err(|_| format!("invalid bpm value: '{}'", input.trim()))?;
     if !(20..=999).contains(&val) {
         return Err(format!("bpm must be between 20 and 999, got {}", val));
     }
-    Ok(SourceLine::Bpm(val))
+    Ok(val)
 }
 
 fn parse_sig(rest: &str) -> Result<SourceLine, String> {
@@ -121,9 +199,38 @@ fn parse_load(rest: &str) -> Result<SourceLine, String> {
     }
 }
 
+fn parse_set(rest: &str) -> Result<SourceLine, String> {
+    let mut tokens = rest.split_whitespace();
+    let pattern = tokens
+        .next()
+        .ok_or_else(|| "set: expected pattern name".to_string())?;
+    validate_identifier(pattern)?;
+    let param = tokens
+        .next()
+        .ok_or_else(|| "set: expected parameter name".to_string())?;
+    validate_identifier(param)?;
+    let value_str = tokens
+        .next()
+        .ok_or_else(|| "set: expected value".to_string())?;
+    let value: f64 = value_str
+        .parse()
+        .ok()
+        .filter(|v: &f64| v.is_finite())
+        .ok_or_else(|| format!("set: invalid value '{}'", value_str))?;
+    if let Some(extra) = tokens.next() {
+        return Err(format!("set: unexpected '{}'", extra));
+    }
+    Ok(SourceLine::Set {
+        pattern: pattern.to_string(),
+        param: param.to_string(),
+        value,
+    })
+}
+
 // --- Pattern line parser ---
 
-fn parse_pattern_line(input: &str, muted: bool) -> Result<SourceLine, String> {
+/// Errors carry offsets into `input`.
+fn parse_pattern_line(input: &str, muted: bool) -> Result<SourceLine, LineError> {
     let mut tokens = SplitKeepQuotes::new(input);
 
     let name = tokens
@@ -141,13 +248,21 @@ fn parse_pattern_line(input: &str, muted: bool) -> Result<SourceLine, String> {
         .ok_or_else(|| "expected quoted mini-notation".to_string())?;
 
     if !notation_str.starts_with('"') || !notation_str.ends_with('"') || notation_str.len() < 2 {
-        return Err(format!(
-            "expected double-quoted mini-notation, got '{}'",
-            notation_str
-        ));
+        return Err(LineError {
+            message: format!(
+                "expected double-quoted mini-notation, got '{}'",
+                notation_str
+            ),
+            offset: Some(offset_in(input, notation_str)),
+            suggestion: Some("close the pattern with '\"'".to_string()),
+        });
     }
     let inner = &notation_str[1..notation_str.len() - 1];
-    let notation = parse_mini(inner)?;
+    let notation = parse_mini(inner).map_err(|e| LineError {
+        message: format!("{} in pattern", e.message),
+        offset: Some(offset_in(input, inner) + e.offset),
+        suggestion: Some(e.suggestion),
+    })?;
 
     // Parse transforms: everything after the closing quote, split by `|`
     let remainder: String = tokens.collect::<Vec<&str>>().join(" ");
@@ -280,6 +395,38 @@ fn parse_single_transform(input: &str) -> Result<Transform, String> {
             let val = parse_transform_f64(&mut parts, "reverb")?;
             Ok(Transform::Reverb(val))
         }
+        "humanize" => {
+            let timing_ms = parse_transform_f64(&mut parts, "humanize timing")?;
+            let velocity = parse_transform_f64(&mut parts, "humanize velocity")?;
+            if timing_ms < 0.0 || velocity < 0.0 {
+                return Err("humanize: amounts must not be negative".to_string());
+            }
+            Ok(Transform::Humanize {
+                timing_ms,
+                velocity,
+            })
+        }
+        "with" => {
+            let layer = input[keyword.len()..].trim();
+            if !layer.starts_with('"') || !layer.ends_with('"') || layer.len() < 2 {
+                return Err(format!(
+                    "with: expected double-quoted mini-notation, got '{}'",
+                    layer
+                ));
+            }
+            let notation =
+                parse_mini(&layer[1..layer.len() - 1]).map_err(|e| format!("with: {}", e))?;
+            Ok(Transform::With(notation))
+        }
+        "steps" => {
+            let n_str = parts
+                .next()
+                .ok_or_else(|| "steps: expected step count".to_string())?;
+            match n_str.parse::<u32>() {
+                Ok(n) if n > 0 => Ok(Transform::Steps(n)),
+                _ => Err(format!("steps: invalid step count '{}'", n_str)),
+            }
+        }
         other => Err(format!("unknown transform: '{}'", other)),
     }
 }
@@ -422,6 +569,35 @@ mod tests {
         assert!(parse_line("bpm 1000").is_err()); // above 999
     }
 
+    #[test]
+    fn test_bpm_ramp() {
+        assert_eq!(
+            parse_line("bpm 120 -> 140 over 8").unwrap(),
+            SourceLine::BpmRamp {
+                from: 120,
+                to: 140,
+                bars: 8
+            }
+        );
+        assert_eq!(
+            parse_line("bpm 140->90 over 2").unwrap(),
+            SourceLine::BpmRamp {
+                from: 140,
+                to: 90,
+                bars: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_bpm_ramp_invalid() {
+        assert!(parse_line("bpm 120 -> 140").is_err());
+        assert!(parse_line("bpm 120 -> 140 over 0").is_err());
+        assert!(parse_line("bpm 120 -> 1400 over 4").is_err());
+        assert!(parse_line("bpm 120 -> 140 during 4").is_err());
+        assert!(parse_line("bpm 120 -> 140 over 4 bars").is_err());
+    }
+
     #[test]
     fn test_sig() {
         assert_eq!(parse_line("sig 4/4").unwrap(), SourceLine::Sig(4, 4));
@@ -573,6 +749,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_line() {
+        assert_eq!(
+            parse_line("set bass cutoff 800").unwrap(),
+            SourceLine::Set {
+                pattern: "bass".into(),
+                param: "cutoff".into(),
+                value: 800.0,
+            }
+        );
+        assert_eq!(
+            parse_line("set lead attack 0.01").unwrap(),
+            SourceLine::Set {
+                pattern: "lead".into(),
+                param: "attack".into(),
+                value: 0.01,
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_line_errors() {
+        assert!(parse_line("set bass cutoff").is_err());
+        assert!(parse_line("set bass cutoff loud").is_err());
+        assert!(parse_line("set bass cutoff 800 900").is_err());
+        assert!(parse_line("set 1bass cutoff 800").is_err());
+    }
+
+    #[test]
+    fn test_pattern_with_humanize() {
+        let result = parse_line("hats hihat \"x*8\" | humanize 10 0.1").unwrap();
+        if let SourceLine::Pattern(p) = result {
+            assert_eq!(
+                p.transforms[0],
+                Transform::Humanize {
+                    timing_ms: 10.0,
+                    velocity: 0.1
+                }
+            );
+        } else {
+            panic!("expected pattern");
+        }
+    }
+
+    #[test]
+    fn test_humanize_requires_two_positive_amounts() {
+        assert!(parse_line("hats hihat \"x*8\" | humanize 10").is_err());
+        assert!(parse_line("hats hihat \"x*8\" | humanize -5 0.1").is_err());
+    }
+
+    #[test]
+    fn test_pattern_with_steps() {
+        let result = parse_line("bass saw \"c2 eb2 g2\" | steps 4").unwrap();
+        if let SourceLine::Pattern(p) = result {
+            assert_eq!(p.transforms[0], Transform::Steps(4));
+            assert_eq!(p.notation.steps(), 3);
+        } else {
+            panic!("expected pattern");
+        }
+        assert!(parse_line("bass saw \"c2\" | steps 0").is_err());
+    }
+
+    #[test]
+    fn test_pattern_with_layer() {
+        let result = parse_line("drums kick \"x ~ x ~\" | with \"~ x ~ x\" | gain 0.8").unwrap();
+        if let SourceLine::Pattern(p) = result {
+            assert_eq!(p.transforms.len(), 2);
+            assert_eq!(
+                p.transforms[0],
+                Transform::With(parse_mini("~ x ~ x").unwrap())
+            );
+        } else {
+            panic!("expected pattern");
+        }
+        assert!(parse_line("drums kick \"x\" | with x").is_err());
+    }
+
     // ---- Error cases ----
 
     #[test]
@@ -3,12 +3,15 @@
 //! Parses the content inside double-quoted pattern strings into a
 //! [`MiniNotation`] AST.
 
+use std::fmt;
+
 use nom::{
     IResult, Parser,
     branch::alt,
     bytes::complete::tag,
     character::complete::{char, digit1, one_of, space0, space1},
     combinator::{map, map_res, opt, value},
+    error::{ErrorKind, FromExternalError, ParseError},
     multi::separated_list1,
     sequence::{delimited, preceded},
 };
@@ -16,24 +19,142 @@ use nom::{
 use crate::ast::mini::*;
 use crate::ast::program::{Accidental, NoteLetter};
 
+/// A mini-notation syntax error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiniError {
+    pub message: String,
+    /// Byte offset of the offending character in the parsed string.
+    pub offset: usize,
+    /// A hint on how to fix the pattern.
+    pub suggestion: String,
+}
+
+impl MiniError {
+    /// Describes a failure at byte `offset` of `input`.
+    fn at(input: &str, offset: usize) -> Self {
+        let rest = &input[offset..];
+        let token = rest.split_whitespace().next().unwrap_or("");
+        let message = if token.is_empty() {
+            "unexpected end of pattern".to_string()
+        } else {
+            format!("unexpected '{}'", token)
+        };
+        Self {
+            message,
+            offset,
+            suggestion: suggest(&input[..offset], rest.chars().next()),
+        }
+    }
+}
+
+impl fmt::Display for MiniError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for MiniError {}
+
+/// Hint for a failure on `next` (`None` at the end of the pattern), given
+/// the pattern parsed so far.
+fn suggest(before: &str, next: Option<char>) -> String {
+    let closer = |open: char| match open {
+        '[' => ']',
+        '<' => '>',
+        _ => ')',
+    };
+    let mut open = Vec::new();
+    for c in before.chars() {
+        match c {
+            '[' | '<' | '(' => open.push(c),
+            ']' | '>' | ')' => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+
+    match (next, open.last()) {
+        (None, Some(&bracket)) => format!("add a closing '{}'", closer(bracket)),
+        (None, None) => "the pattern ends too early".to_string(),
+        (Some(c @ (']' | '>' | ')')), Some(&bracket)) if c != closer(bracket) => {
+            format!("expected '{}' to close '{}'", closer(bracket), bracket)
+        }
+        (Some(c @ (']' | '>' | ')')), None) => format!("remove the unmatched '{}'", c),
+        (Some(c @ ('*' | '/' | '!' | '@')), _) => {
+            format!(
+                "'{}' must follow a step and be followed by a number, e.g. 'c4{}2'",
+                c, c
+            )
+        }
+        (Some(','), _) => "',' separates chord notes and only works inside '[ ]'".to_string(),
+        (Some('('), _) => {
+            "write Euclidean rhythms as (beats,steps) or (beats,steps,offset)".to_string()
+        }
+        (Some('?'), _) => "'?' must directly follow a step".to_string(),
+        _ => "expected a note name (c4, eb3), a scale degree, 'x', '~' or '_'".to_string(),
+    }
+}
+
+/// nom error keeping the furthest position any branch reached, which is
+/// where the pattern actually stops making sense.
+#[derive(Debug)]
+struct Furthest<'a>(&'a str);
+
+impl<'a> ParseError<&'a str> for Furthest<'a> {
+    fn from_error_kind(input: &'a str, _: ErrorKind) -> Self {
+        Furthest(input)
+    }
+
+    fn append(input: &'a str, _: ErrorKind, other: Self) -> Self {
+        Furthest(input).or(other)
+    }
+
+    fn or(self, other: Self) -> Self {
+        if other.0.len() < self.0.len() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Furthest<'a> {
+    fn from_external_error(input: &'a str, _: ErrorKind, _: E) -> Self {
+        Furthest(input)
+    }
+}
+
+type PResult<'a, O> = IResult<&'a str, O, Furthest<'a>>;
+
 /// Parse a mini-notation string into a [`MiniNotation`].
-pub fn parse_mini(input: &str) -> Result<MiniNotation, String> {
+pub fn parse_mini(input: &str) -> Result<MiniNotation, MiniError> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return Ok(MiniNotation {
             sequence: Sequence { steps: vec![] },
         });
     }
+    let error_at =
+        |rest: &str| MiniError::at(input, rest.as_ptr() as usize - input.as_ptr() as usize);
     match parse_sequence(trimmed) {
         Ok(("", seq)) => Ok(MiniNotation { sequence: seq }),
-        Ok((rest, _)) => Err(format!("unexpected trailing input: '{}'", rest)),
-        Err(e) => Err(format!("mini-notation parse error: {}", e)),
+        Ok((rest, _)) => {
+            // Re-parse the leftover step to find where it actually fails
+            let rest = rest.trim_start();
+            match parse_step(rest) {
+                Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(error_at(e.0)),
+                _ => Err(error_at(rest)),
+            }
+        }
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(error_at(e.0)),
+        Err(nom::Err::Incomplete(_)) => Err(error_at(&trimmed[trimmed.len()..])),
     }
 }
 
 // --- Sequence ---
 
-fn parse_sequence(input: &str) -> IResult<&str, Sequence> {
+fn parse_sequence(input: &str) -> PResult<'_, Sequence> {
     let (input, _) = space0(input)?;
     let (input, steps) = separated_list1(space1, parse_step).parse(input)?;
     let (input, _) = space0(input)?;
@@ -42,15 +163,31 @@ fn parse_sequence(input: &str) -> IResult<&str, Sequence> {
 
 // --- Step = Atom [ Modifier ] ---
 
-fn parse_step(input: &str) -> IResult<&str, Step> {
+fn parse_step(input: &str) -> PResult<'_, Step> {
+    if let Ok(result) = parse_euclid_step(input) {
+        return Ok(result);
+    }
     let (input, atom) = parse_atom(input)?;
     let (input, modifier) = opt(parse_modifier).parse(input)?;
     Ok((input, Step { atom, modifier }))
 }
 
+/// `euclid(3,8)` / `e(3,8[,rotation])` — shorthand for `x(3,8[,rotation])`.
+fn parse_euclid_step(input: &str) -> PResult<'_, Step> {
+    let (input, _) = alt((tag("euclid"), tag("e"))).parse(input)?;
+    let (input, modifier) = parse_euclidean(input)?;
+    Ok((
+        input,
+        Step {
+            atom: Atom::Trigger,
+            modifier: Some(modifier),
+        },
+    ))
+}
+
 // --- Atom ---
 
-fn parse_atom(input: &str) -> IResult<&str, Atom> {
+fn parse_atom(input: &str) -> PResult<'_, Atom> {
     alt((
         parse_group,
         parse_alternation,
@@ -63,11 +200,11 @@ fn parse_atom(input: &str) -> IResult<&str, Atom> {
     .parse(input)
 }
 
-fn parse_note_atom(input: &str) -> IResult<&str, Atom> {
+fn parse_note_atom(input: &str) -> PResult<'_, Atom> {
     map(parse_note, Atom::Note).parse(input)
 }
 
-fn parse_note(input: &str) -> IResult<&str, Note> {
+fn parse_note(input: &str) -> PResult<'_, Note> {
     let (input, letter) = parse_note_letter(input)?;
     let (input, accidental) = parse_accidental(input, letter)?;
     let (input, octave) = parse_octave(input)?;
@@ -81,7 +218,7 @@ fn parse_note(input: &str) -> IResult<&str, Note> {
     ))
 }
 
-fn parse_note_letter(input: &str) -> IResult<&str, NoteLetter> {
+fn parse_note_letter(input: &str) -> PResult<'_, NoteLetter> {
     alt((
         value(NoteLetter::C, char('c')),
         value(NoteLetter::D, char('d')),
@@ -97,7 +234,7 @@ fn parse_note_letter(input: &str) -> IResult<&str, NoteLetter> {
 /// Parse accidental after a note letter.
 ///
 /// Context-sensitive for the note B: `bb3` = B-flat 3, `b4` = B natural 4.
-fn parse_accidental(input: &str, letter: NoteLetter) -> IResult<&str, Accidental> {
+fn parse_accidental(input: &str, letter: NoteLetter) -> PResult<'_, Accidental> {
     if letter == NoteLetter::B {
         // After 'b': '#'/'##' for sharp
         if let Ok((rest, _)) = tag::<&str, &str, nom::error::Error<&str>>("##").parse(input) {
@@ -115,7 +252,7 @@ fn parse_accidental(input: &str, letter: NoteLetter) -> IResult<&str, Accidental
         Ok((input, Accidental::Natural))
     } else {
         // Non-B note
-        let result: IResult<&str, Accidental> = alt((
+        let result: PResult<'_, Accidental> = alt((
             value(Accidental::DoubleSharp, tag("##")),
             value(Accidental::Sharp, char('#')),
             value(Accidental::DoubleFlat, tag("bb")),
@@ -129,32 +266,32 @@ fn parse_accidental(input: &str, letter: NoteLetter) -> IResult<&str, Accidental
     }
 }
 
-fn parse_octave(input: &str) -> IResult<&str, u8> {
+fn parse_octave(input: &str) -> PResult<'_, u8> {
     map_res(one_of("0123456789"), |c: char| {
         c.to_digit(10).map(|d| d as u8).ok_or("invalid octave")
     })
     .parse(input)
 }
 
-fn parse_degree_atom(input: &str) -> IResult<&str, Atom> {
+fn parse_degree_atom(input: &str) -> PResult<'_, Atom> {
     // Negative degrees: -N
     if input.starts_with('-') {
         let (input, _) = char('-').parse(input)?;
         let (input, digits) = digit1(input)?;
-        let n: i32 = digits.parse().map_err(|_| {
-            nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Digit))
-        })?;
+        let n: i32 = digits
+            .parse()
+            .map_err(|_| nom::Err::Error(Furthest::from_error_kind(input, ErrorKind::Digit)))?;
         return Ok((input, Atom::Degree(-n)));
     }
     // Positive degrees: just digits
     let (input, digits) = digit1(input)?;
-    let n: i32 = digits.parse().map_err(|_| {
-        nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Digit))
-    })?;
+    let n: i32 = digits
+        .parse()
+        .map_err(|_| nom::Err::Error(Furthest::from_error_kind(input, ErrorKind::Digit)))?;
     Ok((input, Atom::Degree(n)))
 }
 
-fn parse_trigger(input: &str) -> IResult<&str, Atom> {
+fn parse_trigger(input: &str) -> PResult<'_, Atom> {
     let (input, _) = char('x').parse(input)?;
     // Ensure not followed by an alphanumeric char
     if input
@@ -163,25 +300,25 @@ fn parse_trigger(input: &str) -> IResult<&str, Atom> {
         .map(|c| c.is_ascii_alphanumeric())
         .unwrap_or(false)
     {
-        return Err(nom::Err::Error(nom::error::Error::new(
+        return Err(nom::Err::Error(Furthest::from_error_kind(
             input,
-            nom::error::ErrorKind::Char,
+            ErrorKind::Char,
         )));
     }
     Ok((input, Atom::Trigger))
 }
 
-fn parse_rest(input: &str) -> IResult<&str, Atom> {
+fn parse_rest(input: &str) -> PResult<'_, Atom> {
     value(Atom::Rest, char('~')).parse(input)
 }
 
-fn parse_hold(input: &str) -> IResult<&str, Atom> {
+fn parse_hold(input: &str) -> PResult<'_, Atom> {
     value(Atom::Hold, char('_')).parse(input)
 }
 
 // --- Group: [ sequence {, sequence} ] ---
 
-fn parse_group(input: &str) -> IResult<&str, Atom> {
+fn parse_group(input: &str) -> PResult<'_, Atom> {
     let (input, _) = char('[').parse(input)?;
     let (input, _) = space0(input)?;
     let (input, layers) =
@@ -193,7 +330,7 @@ fn parse_group(input: &str) -> IResult<&str, Atom> {
 
 // --- Alternation: < sequence > ---
 
-fn parse_alternation(input: &str) -> IResult<&str, Atom> {
+fn parse_alternation(input: &str) -> PResult<'_, Atom> {
     let (input, _) = char('<').parse(input)?;
     let (input, _) = space0(input)?;
     let (input, sequence) = parse_sequence(input)?;
@@ -204,7 +341,7 @@ fn parse_alternation(input: &str) -> IResult<&str, Atom> {
 
 // --- Modifiers ---
 
-fn parse_modifier(input: &str) -> IResult<&str, Modifier> {
+fn parse_modifier(input: &str) -> PResult<'_, Modifier> {
     alt((
         parse_repeat,
         parse_slow_mod,
@@ -216,25 +353,25 @@ fn parse_modifier(input: &str) -> IResult<&str, Modifier> {
     .parse(input)
 }
 
-fn parse_repeat(input: &str) -> IResult<&str, Modifier> {
+fn parse_repeat(input: &str) -> PResult<'_, Modifier> {
     let (input, _) = char('*').parse(input)?;
     let (input, n) = parse_u32(input)?;
     Ok((input, Modifier::Repeat(n)))
 }
 
-fn parse_slow_mod(input: &str) -> IResult<&str, Modifier> {
+fn parse_slow_mod(input: &str) -> PResult<'_, Modifier> {
     let (input, _) = char('/').parse(input)?;
     let (input, n) = parse_u32(input)?;
     Ok((input, Modifier::Slow(n)))
 }
 
-fn parse_replicate(input: &str) -> IResult<&str, Modifier> {
+fn parse_replicate(input: &str) -> PResult<'_, Modifier> {
     let (input, _) = char('!').parse(input)?;
     let (input, n) = parse_u32(input)?;
     Ok((input, Modifier::Replicate(n)))
 }
 
-fn parse_euclidean(input: &str) -> IResult<&str, Modifier> {
+fn parse_euclidean(input: &str) -> PResult<'_, Modifier> {
     let (input, _) = char('(').parse(input)?;
     let (input, _) = space0(input)?;
     let (input, beats) = parse_u32(input)?;
@@ -247,17 +384,17 @@ fn parse_euclidean(input: &str) -> IResult<&str, Modifier> {
     Ok((input, Modifier::Euclidean(beats, steps, offset)))
 }
 
-fn parse_drop(input: &str) -> IResult<&str, Modifier> {
+fn parse_drop(input: &str) -> PResult<'_, Modifier> {
     value(Modifier::Drop, char('?')).parse(input)
 }
 
-fn parse_weight(input: &str) -> IResult<&str, Modifier> {
+fn parse_weight(input: &str) -> PResult<'_, Modifier> {
     let (input, _) = char('@').parse(input)?;
     let (input, n) = parse_u32(input)?;
     Ok((input, Modifier::Weight(n)))
 }
 
-fn parse_u32(input: &str) -> IResult<&str, u32> {
+fn parse_u32(input: &str) -> PResult<'_, u32> {
     map_res(digit1, |s: &str| s.parse::<u32>()).parse(input)
 }
 
@@ -418,6 +555,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_euclid_shorthand() {
+        let m = parse_mini("e(3,8) euclid(5, 8, 2) e4").unwrap();
+        let steps = &m.sequence.steps;
+        assert_eq!(steps[0].atom, Atom::Trigger);
+        assert_eq!(steps[0].modifier, Some(Modifier::Euclidean(3, 8, None)));
+        assert_eq!(steps[1].modifier, Some(Modifier::Euclidean(5, 8, Some(2))));
+        assert_eq!(steps[2].atom, nat(NoteLetter::E, 4));
+    }
+
+    #[test]
+    fn test_error_offsets_and_suggestions() {
+        let err = parse_mini("c4 e4 q4").unwrap_err();
+        assert_eq!(err.offset, 6);
+        assert_eq!(err.message, "unexpected 'q4'");
+
+        let err = parse_mini(" <c4 e4] ").unwrap_err();
+        assert_eq!(err.offset, 7);
+        assert_eq!(err.suggestion, "expected '>' to close '<'");
+
+        let err = parse_mini("c4 ]").unwrap_err();
+        assert_eq!(err.offset, 3);
+        assert_eq!(err.suggestion, "remove the unmatched ']'");
+    }
+
     #[test]
     fn test_drop() {
         let m = parse_mini("c4?").unwrap();
@@ -6,8 +6,8 @@
 mod lines;
 mod mini;
 
-pub use lines::parse_line;
-pub use mini::parse_mini;
+pub use lines::{LineError, parse_line};
+pub use mini::{MiniError, parse_mini};
 
 use crate::ast::{Program, SourceLine};
 use crate::error::{CompileError, CompileErrorKind, SourceLocation};
@@ -24,16 +24,20 @@ pub fn parse_program(source: &str) -> (Program, Vec<CompileError>) {
     for (line_idx, raw) in source.lines().enumerate() {
         match parse_line(raw) {
             Ok(source_line) => lines.push(source_line),
-            Err(msg) => {
+            Err(err) => {
+                // Columns count characters, from 1
+                let column = err
+                    .offset
+                    .map_or(1, |offset| raw[..offset].chars().count() + 1);
                 errors.push(CompileError {
                     kind: CompileErrorKind::ParseError,
                     location: SourceLocation {
                         line: line_idx + 1,
-                        column: 1,
+                        column,
                         file: None,
                     },
-                    message: msg,
-                    suggestion: None,
+                    message: err.message,
+                    suggestion: err.suggestion,
                 });
                 // Keep the line as a comment so we don't lose it
                 lines.push(SourceLine::Comment(raw.to_string()));
@@ -118,4 +122,34 @@ kick kick \"x ~ x ~\"";
         // Third line still valid
         assert!(matches!(prog.lines[2], SourceLine::Pattern(_)));
     }
+
+    /// Column and suggestion reported for a single-line source.
+    fn first_error(source: &str) -> (usize, String) {
+        let (_, errs) = parse_program(source);
+        assert_eq!(errs.len(), 1, "errors: {:?}", errs);
+        let suggestion = errs[0].suggestion.clone().expect("missing suggestion");
+        (errs[0].location.column, suggestion)
+    }
+
+    #[test]
+    fn test_mini_notation_errors_point_at_the_bad_character() {
+        // `q4` starts at the 17th character
+        let (column, suggestion) = first_error("lead saw \"c4 e4 q4\"");
+        assert_eq!(column, 17);
+        assert!(suggestion.contains("note name"), "{suggestion}");
+
+        // Inside a group, after indentation and a mute prefix
+        let (column, _) = first_error("  ; lead saw \"[c4 e4 %] g4\" | rev");
+        assert_eq!(column, 22);
+
+        // An unclosed group fails at the end of the pattern
+        let (column, suggestion) = first_error("lead saw \"c4 [e4 g4\"");
+        assert_eq!(column, 20);
+        assert!(suggestion.contains(']'), "{suggestion}");
+
+        // Columns count characters, not bytes
+        let (column, suggestion) = first_error("-- é\nlead saw \"c4*\" -- é");
+        assert_eq!(column, 13);
+        assert!(suggestion.contains("number"), "{suggestion}");
+    }
 }
@@ -0,0 +1,68 @@
+//! Euclidean rhythms.
+
+/// Distributes `beats` hits as evenly as possible over `steps` steps with
+/// Bjorklund's algorithm, then rotates the result left by `rotation` steps.
+///
+/// `bjorklund(3, 8, 0)` gives `x..x..x.`.  Extra beats are clamped to the
+/// number of steps.
+pub fn bjorklund(beats: u32, steps: u32, rotation: u32) -> Vec<bool> {
+    let steps = steps as usize;
+    let beats = (beats as usize).min(steps);
+    if steps == 0 {
+        return Vec::new();
+    }
+
+    // Pair the remainders with the leading sequences until at most one
+    // remainder is left
+    let mut front: Vec<Vec<bool>> = vec![vec![true]; beats];
+    let mut back: Vec<Vec<bool>> = vec![vec![false]; steps - beats];
+    while front.len().min(back.len()) > 1 {
+        let paired = front.len().min(back.len());
+        let remainder = if front.len() > paired {
+            front.split_off(paired)
+        } else {
+            back.split_off(paired)
+        };
+        for (sequence, tail) in front.iter_mut().zip(back) {
+            sequence.extend(tail);
+        }
+        back = remainder;
+    }
+
+    let mut pattern: Vec<bool> = front.into_iter().chain(back).flatten().collect();
+    pattern.rotate_left(rotation as usize % steps);
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(pattern: &[bool]) -> String {
+        pattern
+            .iter()
+            .map(|&hit| if hit { 'x' } else { '.' })
+            .collect()
+    }
+
+    #[test]
+    fn test_canonical_distributions() {
+        assert_eq!(render(&bjorklund(3, 8, 0)), "x..x..x.");
+        assert_eq!(render(&bjorklund(5, 8, 0)), "x.xx.xx.");
+        assert_eq!(render(&bjorklund(2, 5, 0)), "x.x..");
+        assert_eq!(render(&bjorklund(4, 4, 0)), "xxxx");
+        assert_eq!(render(&bjorklund(0, 4, 0)), "....");
+    }
+
+    #[test]
+    fn test_rotation_shifts_left() {
+        assert_eq!(render(&bjorklund(3, 8, 2)), ".x..x.x.");
+        assert_eq!(render(&bjorklund(3, 8, 10)), render(&bjorklund(3, 8, 2)));
+    }
+
+    #[test]
+    fn test_degenerate_arguments() {
+        assert!(bjorklund(3, 0, 0).is_empty());
+        assert_eq!(render(&bjorklund(9, 4, 1)), "xxxx");
+    }
+}
@@ -0,0 +1,169 @@
+//! Mini-notation evaluation: one cycle of a [`MiniNotation`] into events.
+
+use crate::ast::mini::*;
+
+use super::euclid::bjorklund;
+use super::rng::Rng;
+use super::{EventValue, MiniEvent};
+
+/// Tolerance used when comparing event times.
+pub(super) const EPSILON: f64 = 1e-9;
+
+/// Returns the events of `notation` for cycle `cycle`, with onsets in
+/// `cycle..cycle + 1`.
+pub(super) fn cycle_events(notation: &MiniNotation, cycle: i64, seed: u64) -> Vec<MiniEvent> {
+    let mut events = Vec::new();
+    sequence_events(
+        &notation.sequence,
+        cycle as f64,
+        1.0,
+        cycle,
+        seed,
+        &mut events,
+    );
+    events
+}
+
+/// Lays the steps of `sequence` out over `start..start + duration`.
+fn sequence_events(
+    sequence: &Sequence,
+    start: f64,
+    duration: f64,
+    cycle: i64,
+    seed: u64,
+    out: &mut Vec<MiniEvent>,
+) {
+    // `!N` replicates a step into N plain steps; `@N` weights its slot
+    let slots: Vec<(&Atom, Option<&Modifier>, u32)> = sequence
+        .steps
+        .iter()
+        .flat_map(|step| match step.modifier {
+            Some(Modifier::Replicate(n)) => vec![(&step.atom, None, 1); n as usize],
+            Some(Modifier::Weight(n)) => vec![(&step.atom, None, n)],
+            _ => vec![(&step.atom, step.modifier.as_ref(), 1)],
+        })
+        .collect();
+    let total: u32 = slots.iter().map(|&(_, _, weight)| weight).sum();
+    if total == 0 {
+        return;
+    }
+
+    let mut position = start;
+    let mut previous = out.len()..out.len();
+    for (atom, modifier, weight) in slots {
+        let slot = duration * weight as f64 / total as f64;
+        if *atom == Atom::Hold {
+            // Extend the events of the previous step that end here
+            for event in &mut out[previous.clone()] {
+                if (event.onset + event.duration - position).abs() < EPSILON {
+                    event.duration += slot;
+                }
+            }
+        } else {
+            let first = out.len();
+            step_events(atom, modifier, position, slot, cycle, seed, out);
+            previous = first..out.len();
+        }
+        position += slot;
+    }
+}
+
+/// Evaluates one step, applying its modifier.
+fn step_events(
+    atom: &Atom,
+    modifier: Option<&Modifier>,
+    start: f64,
+    duration: f64,
+    cycle: i64,
+    seed: u64,
+    out: &mut Vec<MiniEvent>,
+) {
+    match modifier {
+        Some(Modifier::Repeat(n)) => {
+            let n = (*n).max(1);
+            let slot = duration / n as f64;
+            for i in 0..n {
+                let start = start + i as f64 * slot;
+                atom_events(atom, start, slot, cycle * n as i64 + i as i64, seed, out);
+            }
+        }
+        Some(Modifier::Slow(n)) => {
+            // Play the part of the stretched atom that falls in this cycle
+            let n = (*n).max(1) as i64;
+            let part = cycle.rem_euclid(n) as f64;
+            let mut stretched = Vec::new();
+            atom_events(
+                atom,
+                start - part * duration,
+                duration * n as f64,
+                cycle.div_euclid(n),
+                seed,
+                &mut stretched,
+            );
+            out.extend(stretched.into_iter().filter(|event| {
+                event.onset >= start - EPSILON && event.onset < start + duration - EPSILON
+            }));
+        }
+        Some(Modifier::Drop) => {
+            let mut rng = Rng::from_parts(&[seed, cycle as u64, start.to_bits()]);
+            if rng.next_f64() >= 0.5 {
+                atom_events(atom, start, duration, cycle, seed, out);
+            }
+        }
+        Some(Modifier::Euclidean(beats, steps, rotation)) => {
+            let hits = bjorklund(*beats, *steps, rotation.unwrap_or(0));
+            let slot = duration / hits.len().max(1) as f64;
+            for (i, _) in hits.iter().enumerate().filter(|(_, hit)| **hit) {
+                atom_events(atom, start + i as f64 * slot, slot, cycle, seed, out);
+            }
+        }
+        _ => atom_events(atom, start, duration, cycle, seed, out),
+    }
+}
+
+/// Evaluates an atom over `start..start + duration`.
+fn atom_events(
+    atom: &Atom,
+    start: f64,
+    duration: f64,
+    cycle: i64,
+    seed: u64,
+    out: &mut Vec<MiniEvent>,
+) {
+    let value = match atom {
+        Atom::Note(note) => EventValue::Pitch(note.midi()),
+        Atom::Degree(degree) => EventValue::Degree(*degree),
+        Atom::Trigger => EventValue::Trigger,
+        Atom::Rest | Atom::Hold => return,
+        Atom::Group(group) => {
+            for layer in &group.layers {
+                sequence_events(layer, start, duration, cycle, seed, out);
+            }
+            return;
+        }
+        Atom::Alternation(alternation) => {
+            let steps = &alternation.sequence.steps;
+            if !steps.is_empty() {
+                let len = steps.len() as i64;
+                let step = &steps[cycle.rem_euclid(len) as usize];
+                step_events(
+                    &step.atom,
+                    step.modifier.as_ref(),
+                    start,
+                    duration,
+                    cycle.div_euclid(len),
+                    seed,
+                    out,
+                );
+            }
+            return;
+        }
+    };
+
+    out.push(MiniEvent {
+        onset: start,
+        duration,
+        value,
+        velocity: 1.0,
+    });
+}
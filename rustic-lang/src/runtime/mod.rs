@@ -0,0 +1,440 @@
+//! Pattern runtime: turns pattern definitions into timed events.
+//!
+//! Time is measured in **cycles**: one cycle is one loop of a pattern's
+//! mini-notation, i.e. one bar of the session's time signature.  Querying a
+//! pattern over a span of cycles evaluates the mini-notation of every cycle
+//! it overlaps, then applies the transform pipeline left to right.  The
+//! audio engine converts onsets to samples with the session tempo (see
+//! [`Transport`](crate::session::Transport)).
+//!
+//! Random features (`?`, `arp random`, `humanize`) are driven by the seed of
+//! the evaluation that produced the pattern, so the same source always
+//! yields the same events.
+
+mod euclid;
+mod mini;
+mod rng;
+mod transform;
+
+use crate::ast::mini::MiniNotation;
+use crate::ast::program::{PatternDef, PitchRoot, ScaleMode, Transform};
+
+pub use euclid::bjorklund;
+pub(crate) use rng::hash_str;
+
+/// What an event plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventValue {
+    /// A pitched note, as a MIDI note number (`c4` = 60).
+    Pitch(i32),
+    /// A scale degree that no scale has resolved yet.
+    Degree(i32),
+    /// A drum trigger.
+    Trigger,
+}
+
+impl EventValue {
+    /// Ordering used by the arpeggiator: lowest pitch first.
+    fn sort_key(&self) -> (u8, i32) {
+        match *self {
+            EventValue::Pitch(pitch) => (0, pitch),
+            EventValue::Degree(degree) => (1, degree),
+            EventValue::Trigger => (2, 0),
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        let (kind, value) = self.sort_key();
+        ((kind as u64) << 32) | value as u32 as u64
+    }
+}
+
+/// A single timed event produced by a pattern.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MiniEvent {
+    /// Start time, in cycles since the start of the session.
+    pub onset: f64,
+    /// Length, in cycles.
+    pub duration: f64,
+    pub value: EventValue,
+    /// Velocity, from 0 to 1.
+    pub velocity: f64,
+}
+
+/// Session state a query depends on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryContext {
+    pub bpm: u32,
+    pub sig: (u8, u8),
+    /// Default scale resolving degrees of patterns without a `scale` transform.
+    pub scale: Option<(PitchRoot, ScaleMode)>,
+    /// Seed of the evaluation, driving every random feature.
+    pub seed: u64,
+}
+
+impl QueryContext {
+    pub fn new(bpm: u32, sig: (u8, u8)) -> Self {
+        Self {
+            bpm,
+            sig,
+            scale: None,
+            seed: 0,
+        }
+    }
+
+    /// Builder-style setter for the evaluation seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Builder-style setter for the default scale.
+    pub fn with_scale(mut self, root: PitchRoot, mode: ScaleMode) -> Self {
+        self.scale = Some((root, mode));
+        self
+    }
+
+    /// Length of a cycle (one bar), in seconds.
+    pub fn cycle_seconds(&self) -> f64 {
+        self.sig.0.max(1) as f64 * 60.0 / self.bpm.max(1) as f64
+    }
+}
+
+/// Returns the events of `pattern` starting in `begin..end` (in cycles),
+/// ordered by onset.  Muted patterns produce no events.
+pub fn query(pattern: &PatternDef, begin: f64, end: f64, context: &QueryContext) -> Vec<MiniEvent> {
+    if pattern.muted || end <= begin {
+        return Vec::new();
+    }
+
+    let seed = context.seed ^ hash_str(&pattern.name);
+    // A `scale` transform overrides the default scale
+    let mut context = *context;
+    if pattern
+        .transforms
+        .iter()
+        .any(|t| matches!(t, Transform::Scale(..)))
+    {
+        context.scale = None;
+    }
+    let base = |b, e| notation_events(&pattern.notation, b, e, &context, seed);
+
+    let transforms: Vec<Transform> = pattern
+        .transforms
+        .iter()
+        .map(|t| resolve_steps(t, pattern.notation.steps()))
+        .collect();
+    let mut events = transform::apply(&transforms, &base, begin, end, &context, seed);
+    events.sort_by(|a, b| a.onset.total_cmp(&b.onset));
+    events
+}
+
+/// Events of `notation` starting in `begin..end`, with scale degrees
+/// resolved by the default scale of `context`.
+fn notation_events(
+    notation: &MiniNotation,
+    begin: f64,
+    end: f64,
+    context: &QueryContext,
+    seed: u64,
+) -> Vec<MiniEvent> {
+    ((begin.floor() as i64)..(end.ceil() as i64))
+        .flat_map(|cycle| mini::cycle_events(notation, cycle, seed))
+        .filter(|event| event.onset >= begin - mini::EPSILON && event.onset < end - mini::EPSILON)
+        .map(|event| match context.scale {
+            Some((root, mode)) => transform::with_scale(event, root, mode),
+            None => event,
+        })
+        .collect()
+}
+
+/// Turns `steps n` into the speed-up playing `length` steps at `n` per cycle.
+fn resolve_steps(transform: &Transform, length: u32) -> Transform {
+    match transform {
+        Transform::Steps(n) if length > 0 => Transform::Fast(*n as f64 / length as f64),
+        Transform::Every(k, inner) => Transform::Every(*k, Box::new(resolve_steps(inner, length))),
+        other => other.clone(),
+    }
+}
+
+/// Returns the events of `pattern` in cycle `cycle`.
+pub fn query_cycle(pattern: &PatternDef, cycle: u64, context: &QueryContext) -> Vec<MiniEvent> {
+    query(pattern, cycle as f64, cycle as f64 + 1.0, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::SourceLine;
+    use crate::parser::parse_line;
+
+    fn pattern(line: &str) -> PatternDef {
+        match parse_line(line) {
+            Ok(SourceLine::Pattern(def)) => def,
+            other => panic!("expected a pattern, got {other:?}"),
+        }
+    }
+
+    fn context() -> QueryContext {
+        QueryContext::new(120, (4, 4))
+    }
+
+    /// `(onset, duration, value)` of each event, for compact assertions.
+    fn timeline(events: &[MiniEvent]) -> Vec<(f64, f64, EventValue)> {
+        events
+            .iter()
+            .map(|e| (e.onset, e.duration, e.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_sequence_and_groups() {
+        let events = query_cycle(&pattern("lead saw \"c4 [e4 g4] ~ c5\""), 0, &context());
+        assert_eq!(
+            timeline(&events),
+            vec![
+                (0.0, 0.25, EventValue::Pitch(60)),
+                (0.25, 0.125, EventValue::Pitch(64)),
+                (0.375, 0.125, EventValue::Pitch(67)),
+                (0.75, 0.25, EventValue::Pitch(72)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hold_weight_and_replicate() {
+        let held = query_cycle(&pattern("b saw \"c4 _ _ e4\""), 0, &context());
+        assert_eq!(held[0].duration, 0.75);
+        assert_eq!(held[1].onset, 0.75);
+
+        let weighted = query_cycle(&pattern("b saw \"c4@3 e4\""), 0, &context());
+        assert_eq!(weighted[1].onset, 0.75);
+
+        let replicated = query_cycle(&pattern("b saw \"c4!3 e4\""), 0, &context());
+        assert_eq!(replicated.len(), 4);
+        assert_eq!(replicated[3].onset, 0.75);
+    }
+
+    #[test]
+    fn test_repeat_alternation_and_slow() {
+        let repeated = query_cycle(&pattern("hats hihat \"x*4\""), 2, &context());
+        let onsets: Vec<f64> = repeated.iter().map(|e| e.onset).collect();
+        assert_eq!(onsets, vec![2.0, 2.25, 2.5, 2.75]);
+
+        let alternating = pattern("lead saw \"c4 <e4 g4>\"");
+        assert_eq!(
+            query_cycle(&alternating, 0, &context())[1].value,
+            EventValue::Pitch(64)
+        );
+        assert_eq!(
+            query_cycle(&alternating, 1, &context())[1].value,
+            EventValue::Pitch(67)
+        );
+
+        let slow = pattern("lead saw \"[c4 e4 g4 b4]/2\"");
+        let second = query_cycle(&slow, 1, &context());
+        assert_eq!(
+            timeline(&second),
+            vec![
+                (1.0, 0.5, EventValue::Pitch(67)),
+                (1.5, 0.5, EventValue::Pitch(71))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_euclidean_steps() {
+        let onsets = |line: &str| -> Vec<f64> {
+            query_cycle(&pattern(line), 0, &context())
+                .iter()
+                .map(|e| e.onset * 8.0)
+                .collect()
+        };
+        assert_eq!(onsets("k kick \"e(3,8)\""), vec![0.0, 3.0, 6.0]);
+        assert_eq!(onsets("k kick \"euclid(3,8,2)\""), vec![1.0, 4.0, 6.0]);
+        // Works on any atom, within its slot
+        let events = query_cycle(&pattern("b saw \"c2(3,8) ~\""), 0, &context());
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| e.duration == 1.0 / 16.0));
+        assert_eq!(events[1].onset, 3.0 / 16.0);
+    }
+
+    #[test]
+    fn test_polymeter_realigns_after_lcm_steps() {
+        let three = pattern("a saw \"c4 e4 g4\" | steps 4");
+        let four = pattern("b saw \"c5 d5 e5 f5\" | steps 4");
+        let a = query(&three, 0.0, 6.0, &context());
+        let b = query(&four, 0.0, 6.0, &context());
+
+        // Both rows keep the same step length, a quarter cycle
+        assert_eq!(a.len(), 24);
+        assert_eq!(b.len(), 24);
+        for (i, event) in a.iter().enumerate() {
+            assert!((event.onset - i as f64 / 4.0).abs() < 1e-9);
+            assert!((event.duration - 0.25).abs() < 1e-9);
+            assert_eq!(event.value, EventValue::Pitch([60, 64, 67][i % 3]));
+        }
+
+        // Both rows restart together every 12 steps, i.e. every 3 cycles
+        let starts = |events: &[MiniEvent], first: i32| -> Vec<f64> {
+            events
+                .iter()
+                .filter(|e| e.value == EventValue::Pitch(first))
+                .map(|e| e.onset)
+                .collect()
+        };
+        let (a_starts, b_starts) = (starts(&a, 60), starts(&b, 72));
+        let together: Vec<f64> = a_starts
+            .iter()
+            .copied()
+            .filter(|t| b_starts.iter().any(|u| (t - u).abs() < 1e-9))
+            .collect();
+        assert_eq!(together.len(), 2);
+        assert!(together[0].abs() < 1e-9 && (together[1] - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_with_layers_patterns() {
+        let stacked = pattern("drums kick \"x ~ x ~\" | with \"~ x ~ x\"");
+        let events = query_cycle(&stacked, 1, &context());
+        let onsets: Vec<f64> = events.iter().map(|e| e.onset).collect();
+        assert_eq!(onsets, vec![1.0, 1.25, 1.5, 1.75]);
+
+        // Later transforms apply to every layer; earlier ones only to the
+        // layers before them
+        let def = pattern("m saw \"c4\" | oct 1 | with \"[e4 g4]\" | fast 2");
+        let values: Vec<(f64, EventValue)> = query_cycle(&def, 0, &context())
+            .iter()
+            .map(|e| (e.onset, e.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (0.0, EventValue::Pitch(72)),
+                (0.0, EventValue::Pitch(64)),
+                (0.25, EventValue::Pitch(67)),
+                (0.5, EventValue::Pitch(72)),
+                (0.5, EventValue::Pitch(64)),
+                (0.75, EventValue::Pitch(67)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rev_fast_and_every() {
+        let rev = query_cycle(&pattern("l saw \"c4 e4 g4 c5\" | rev"), 0, &context());
+        let values: Vec<EventValue> = rev.iter().map(|e| e.value).collect();
+        assert_eq!(values, [72, 67, 64, 60].map(EventValue::Pitch));
+
+        let fast = query_cycle(&pattern("l saw \"c4 e4\" | fast 2"), 0, &context());
+        assert_eq!(fast.len(), 4);
+        assert_eq!(fast[3].onset, 0.75);
+
+        let every = pattern("l saw \"c4 e4\" | every 2 rev");
+        assert_eq!(
+            query_cycle(&every, 0, &context())[0].value,
+            EventValue::Pitch(64)
+        );
+        assert_eq!(
+            query_cycle(&every, 1, &context())[0].value,
+            EventValue::Pitch(60)
+        );
+    }
+
+    #[test]
+    fn test_arp_scale_oct_and_gain() {
+        let arp = query_cycle(&pattern("p piano \"[g3,c3,e3]\" | arp up"), 0, &context());
+        let values: Vec<EventValue> = arp.iter().map(|e| e.value).collect();
+        assert_eq!(values, [48, 52, 55].map(EventValue::Pitch));
+        assert!((arp[2].onset - 2.0 / 3.0).abs() < 1e-9);
+
+        let scaled = query_cycle(
+            &pattern("m saw \"0 2 7\" | scale C minor | oct -1 | gain 0.5"),
+            0,
+            &context(),
+        );
+        let values: Vec<EventValue> = scaled.iter().map(|e| e.value).collect();
+        assert_eq!(values, [48, 51, 60].map(EventValue::Pitch));
+        assert!(scaled.iter().all(|e| e.velocity == 0.5));
+
+        // The session scale resolves degrees without a scale transform
+        let root = PitchRoot {
+            name: crate::ast::NoteLetter::D,
+            accidental: crate::ast::Accidental::Natural,
+        };
+        let context = context().with_scale(root, ScaleMode::Major);
+        let degrees = query_cycle(&pattern("m saw \"0 1\""), 0, &context);
+        assert_eq!(degrees[1].value, EventValue::Pitch(64));
+    }
+
+    #[test]
+    fn test_humanize_stays_within_bounds() {
+        // One cycle lasts 2 s at 120 BPM in 4/4: 10 ms = 0.005 cycles
+        let def = pattern("hats hihat \"x*16\" | gain 0.5 | humanize 10 0.1");
+        let context = context().with_seed(42);
+        let straight = query(&pattern("hats hihat \"x*16\""), 0.0, 8.0, &context);
+        let human = query(&def, 0.0, 8.0, &context);
+
+        // Hits on the span edges may be pushed in or out of it
+        assert!(human.len().abs_diff(straight.len()) <= 2);
+        let mut moved = 0;
+        for event in &human {
+            let grid = (event.onset * 16.0).round() / 16.0;
+            assert!((event.onset - grid).abs() <= 0.005 + 1e-12);
+            assert!((0.4..=0.6).contains(&event.velocity));
+            if (event.onset - grid).abs() > 1e-9 {
+                moved += 1;
+            }
+        }
+        assert!(moved > human.len() / 2);
+    }
+
+    #[test]
+    fn test_random_features_are_reproducible() {
+        let def = pattern("hats hihat \"[x? x? x? x?]*4\" | humanize 20 0.2");
+        let context = context().with_seed(7);
+        assert_eq!(
+            query(&def, 0.0, 4.0, &context),
+            query(&def, 0.0, 4.0, &context)
+        );
+        // Splitting the query span does not change the result
+        let mut split = query(&def, 0.0, 1.5, &context);
+        split.extend(query(&def, 1.5, 4.0, &context));
+        assert_eq!(split, query(&def, 0.0, 4.0, &context));
+        // Another seed gives another result
+        assert_ne!(
+            query(&def, 0.0, 4.0, &context),
+            query(&def, 0.0, 4.0, &context.with_seed(8))
+        );
+    }
+
+    #[test]
+    fn test_scale_snaps_notes() {
+        let pitches = |line: &str| -> Vec<i32> {
+            query_cycle(&pattern(line), 0, &context())
+                .iter()
+                .map(|e| match e.value {
+                    EventValue::Pitch(pitch) => pitch,
+                    other => panic!("expected a pitch, got {other:?}"),
+                })
+                .collect()
+        };
+        // E natural is a semitone from both Eb and F: ties go down
+        assert_eq!(
+            pitches("m saw \"e4 f#4 b4\" | scale C minor"),
+            vec![63, 65, 70]
+        );
+        // In-scale notes are left alone
+        assert_eq!(
+            pitches("m saw \"c4 d4 eb4 f4 g4 ab4 bb4\" | scale C minor"),
+            vec![60, 62, 63, 65, 67, 68, 70]
+        );
+        assert_eq!(pitches("m saw \"c#3 f4\" | scale D major"), vec![49, 64]);
+        assert_eq!(pitches("m saw \"b3\" | scale C pentatonic"), vec![60]);
+    }
+
+    #[test]
+    fn test_muted_pattern_is_silent() {
+        assert!(query_cycle(&pattern("; k kick \"x x\""), 0, &context()).is_empty());
+    }
+}
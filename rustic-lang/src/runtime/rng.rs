@@ -0,0 +1,53 @@
+//! Small deterministic random number generator.
+//!
+//! Random pattern features (`?`, `arp random`, `humanize`) must give the same
+//! result every time the same source is evaluated, whatever span is queried,
+//! so each decision draws from a generator seeded by the evaluation seed and
+//! the time and value it applies to.
+
+/// SplitMix64 generator.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds a generator from several values.
+    pub(crate) fn from_parts(parts: &[u64]) -> Self {
+        let mut rng = Self::new(0x9E37_79B9_7F4A_7C15);
+        for &part in parts {
+            rng.state ^= part;
+            rng.next_u64();
+        }
+        rng
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..1`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform value in `-amount..amount`.
+    pub(crate) fn jitter(&mut self, amount: f64) -> f64 {
+        (self.next_f64() * 2.0 - 1.0) * amount
+    }
+}
+
+/// FNV-1a hash of a string, used to derive seeds from source text and names.
+pub(crate) fn hash_str(s: &str) -> u64 {
+    s.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3)
+    })
+}
@@ -0,0 +1,245 @@
+//! Transform pipeline: each [`Transform`] wraps the query of the pattern
+//! before it.
+
+use std::collections::BTreeMap;
+
+use crate::ast::program::{ArpMode, PitchRoot, ScaleMode, Transform};
+
+use super::mini::EPSILON;
+use super::rng::Rng;
+use super::{EventValue, MiniEvent, QueryContext, notation_events};
+
+/// A query over `begin..end` (in cycles) returning the events starting in it.
+pub(super) type Query<'a> = dyn Fn(f64, f64) -> Vec<MiniEvent> + 'a;
+
+/// Applies `transforms` left to right on top of `base` and queries the
+/// result over `begin..end`.
+pub(super) fn apply(
+    transforms: &[Transform],
+    base: &Query,
+    begin: f64,
+    end: f64,
+    context: &QueryContext,
+    seed: u64,
+) -> Vec<MiniEvent> {
+    match transforms.split_last() {
+        None => base(begin, end),
+        Some((last, rest)) => {
+            let inner = |b, e| apply(rest, base, b, e, context, seed);
+            transform(last, &inner, begin, end, context, seed)
+        }
+    }
+}
+
+/// Cycles overlapping `begin..end`.
+fn cycles(begin: f64, end: f64) -> impl Iterator<Item = i64> {
+    (begin.floor() as i64)..(end.ceil() as i64)
+}
+
+fn in_span(event: &MiniEvent, begin: f64, end: f64) -> bool {
+    event.onset >= begin - EPSILON && event.onset < end - EPSILON
+}
+
+/// Queries whole cycles around `begin..end` and keeps the events starting
+/// in it, for transforms that need to see complete cycles.
+fn whole_cycles(inner: &Query, begin: f64, end: f64) -> Vec<MiniEvent> {
+    cycles(begin, end)
+        .flat_map(|cycle| inner(cycle as f64, cycle as f64 + 1.0))
+        .filter(|event| in_span(event, begin, end))
+        .collect()
+}
+
+fn transform(
+    transform: &Transform,
+    inner: &Query,
+    begin: f64,
+    end: f64,
+    context: &QueryContext,
+    seed: u64,
+) -> Vec<MiniEvent> {
+    match transform {
+        Transform::Rev => cycles(begin, end)
+            .flat_map(|cycle| {
+                let mirror = 2.0 * cycle as f64 + 1.0;
+                inner(cycle as f64, cycle as f64 + 1.0)
+                    .into_iter()
+                    .map(move |event| MiniEvent {
+                        onset: mirror - event.onset - event.duration,
+                        ..event
+                    })
+            })
+            .filter(|event| in_span(event, begin, end))
+            .collect(),
+        Transform::Fast(factor) => fast(inner, *factor, begin, end),
+        Transform::Slow(factor) => fast(inner, 1.0 / factor, begin, end),
+        Transform::Every(n, nested) => cycles(begin, end)
+            .flat_map(|cycle| {
+                let (b, e) = (begin.max(cycle as f64), end.min(cycle as f64 + 1.0));
+                if *n > 0 && cycle.rem_euclid(*n as i64) == 0 {
+                    self::transform(nested, inner, b, e, context, seed)
+                } else {
+                    inner(b, e)
+                }
+            })
+            .collect(),
+        Transform::Arp(mode) => arpeggiate(whole_cycles(inner, begin, end), *mode, seed),
+        Transform::Scale(root, mode) => map_values(
+            inner(begin, end)
+                .into_iter()
+                .map(|event| with_scale(event, *root, *mode))
+                .collect(),
+            |value| match value {
+                EventValue::Pitch(pitch) => EventValue::Pitch(snap_to_scale(pitch, *root, *mode)),
+                other => other,
+            },
+        ),
+        Transform::Oct(offset) => map_values(inner(begin, end), |value| match value {
+            EventValue::Pitch(pitch) => EventValue::Pitch(pitch + 12 * offset),
+            other => other,
+        }),
+        Transform::Gain(gain) => inner(begin, end)
+            .into_iter()
+            .map(|event| MiniEvent {
+                velocity: event.velocity * gain,
+                ..event
+            })
+            .collect(),
+        Transform::Humanize {
+            timing_ms,
+            velocity,
+        } => {
+            let timing = timing_ms / 1000.0 / context.cycle_seconds();
+            inner(begin - timing, end + timing)
+                .into_iter()
+                .map(|event| {
+                    let mut rng =
+                        Rng::from_parts(&[seed, event.onset.to_bits(), event.value.hash()]);
+                    MiniEvent {
+                        onset: event.onset + rng.jitter(timing),
+                        velocity: (event.velocity + rng.jitter(*velocity)).clamp(0.0, 1.0),
+                        ..event
+                    }
+                })
+                .filter(|event| in_span(event, begin, end))
+                .collect()
+        }
+        Transform::With(layer) => {
+            let mut events = inner(begin, end);
+            // Decorrelate the random choices of the layer from the pattern's
+            events.extend(notation_events(layer, begin, end, context, !seed));
+            events
+        }
+        // Resolved to `fast` by `query`, which knows the pattern length
+        Transform::Steps(_) => inner(begin, end),
+        // Effects do not change the events; the engine applies them
+        Transform::Lpf(_) | Transform::Hpf(_) | Transform::Delay(..) | Transform::Reverb(_) => {
+            inner(begin, end)
+        }
+    }
+}
+
+/// Speeds the pattern up by `factor`.
+fn fast(inner: &Query, factor: f64, begin: f64, end: f64) -> Vec<MiniEvent> {
+    if !factor.is_finite() || factor <= 0.0 {
+        return Vec::new();
+    }
+    inner(begin * factor, end * factor)
+        .into_iter()
+        .map(|event| MiniEvent {
+            onset: event.onset / factor,
+            duration: event.duration / factor,
+            ..event
+        })
+        .collect()
+}
+
+fn map_values<F: Fn(EventValue) -> EventValue>(events: Vec<MiniEvent>, f: F) -> Vec<MiniEvent> {
+    events
+        .into_iter()
+        .map(|event| MiniEvent {
+            value: f(event.value),
+            ..event
+        })
+        .collect()
+}
+
+/// Resolves scale degrees to pitches, degree 0 being the root in octave 4.
+pub(super) fn with_scale(event: MiniEvent, root: PitchRoot, mode: ScaleMode) -> MiniEvent {
+    let EventValue::Degree(degree) = event.value else {
+        return event;
+    };
+    let intervals = mode.intervals();
+    let len = intervals.len() as i32;
+    let pitch = 60
+        + root.semitone()
+        + 12 * degree.div_euclid(len)
+        + intervals[degree.rem_euclid(len) as usize];
+    MiniEvent {
+        value: EventValue::Pitch(pitch),
+        ..event
+    }
+}
+
+/// Moves `pitch` to the nearest pitch of the scale, downwards on ties.
+pub(super) fn snap_to_scale(pitch: i32, root: PitchRoot, mode: ScaleMode) -> i32 {
+    let intervals = mode.intervals();
+    let class = (pitch - root.semitone()).rem_euclid(12);
+    // Also consider the root of the next octave when snapping up
+    let nearest = intervals
+        .iter()
+        .copied()
+        .chain(std::iter::once(12))
+        .min_by_key(|&interval| (interval - class).abs())
+        .unwrap_or(class);
+    pitch + nearest - class
+}
+
+/// Spreads the notes of each chord (events sharing onset and duration) over
+/// the chord's time slot.
+fn arpeggiate(events: Vec<MiniEvent>, mode: ArpMode, seed: u64) -> Vec<MiniEvent> {
+    let mut chords: BTreeMap<(u64, u64), Vec<MiniEvent>> = BTreeMap::new();
+    for event in events {
+        chords
+            .entry((event.onset.to_bits(), event.duration.to_bits()))
+            .or_default()
+            .push(event);
+    }
+
+    chords
+        .into_values()
+        .flat_map(|mut chord| {
+            chord.sort_by_key(|event| event.value.sort_key());
+            let order: Vec<MiniEvent> = match mode {
+                ArpMode::Up => chord,
+                ArpMode::Down => chord.into_iter().rev().collect(),
+                ArpMode::UpDown => {
+                    let down = chord
+                        .iter()
+                        .rev()
+                        .skip(1)
+                        .take(chord.len().saturating_sub(2));
+                    let down: Vec<MiniEvent> = down.cloned().collect();
+                    chord.into_iter().chain(down).collect()
+                }
+                ArpMode::Random => {
+                    let mut rng = Rng::from_parts(&[seed, chord[0].onset.to_bits()]);
+                    for i in (1..chord.len()).rev() {
+                        chord.swap(i, (rng.next_u64() % (i as u64 + 1)) as usize);
+                    }
+                    chord
+                }
+            };
+
+            let slot = order[0].duration / order.len() as f64;
+            let onset = order[0].onset;
+            order
+                .into_iter()
+                .enumerate()
+                .map(move |(i, event)| MiniEvent {
+                    onset: onset + i as f64 * slot,
+                    duration: slot,
+                    ..event
+                })
+        })
+        .collect()
+}
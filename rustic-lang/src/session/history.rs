@@ -0,0 +1,183 @@
+//! Evaluation history for recording and replaying live sets.
+//!
+//! Every call to [`Session::evaluate`](super::Session::evaluate) appends a
+//! [`HistoryEntry`] with the time elapsed since the session started, the
+//! evaluated source and the resulting deltas.  A [`History`] can be written
+//! to a file and replayed later with [`Session::replay`](super::Session::replay)
+//! to rebuild the same set deterministically.
+//!
+//! ## File format
+//!
+//! ```text
+//! # rustic-lang history v1
+//! @ <elapsed_micros> <source_len_bytes>
+//! <source, exactly source_len_bytes long>
+//! = <Add|Modify|Remove|Mute|Unmute> <pattern name>
+//! = SetParameter <pattern name> <parameter> <value>
+//! ```
+
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use super::Delta;
+
+const HEADER: &str = "# rustic-lang history v1";
+
+/// A single recorded evaluation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Time elapsed since the session started.
+    pub at: Duration,
+    /// The evaluated source.
+    pub source: String,
+    /// Deltas produced by the evaluation.
+    pub deltas: Vec<Delta>,
+}
+
+/// Ordered log of evaluations.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an entry to the log.
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Recorded entries, oldest first.
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serializes the history to its text representation.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{HEADER}");
+        for entry in &self.entries {
+            let _ = writeln!(out, "@ {} {}", entry.at.as_micros(), entry.source.len());
+            out.push_str(&entry.source);
+            out.push('\n');
+            for delta in &entry.deltas {
+                let _ = writeln!(out, "= {}", delta_text(delta));
+            }
+        }
+        out
+    }
+
+    /// Parses a history from its text representation.
+    pub fn from_text(text: &str) -> io::Result<Self> {
+        let rest = text
+            .strip_prefix(HEADER)
+            .and_then(|r| r.strip_prefix('\n'))
+            .ok_or_else(|| invalid("missing history header"))?;
+
+        let mut history = History::new();
+        let mut rest = rest;
+        while !rest.is_empty() {
+            let (line, tail) = rest.split_once('\n').unwrap_or((rest, ""));
+            if let Some(header) = line.strip_prefix("@ ") {
+                let (micros, len) = header
+                    .split_once(' ')
+                    .ok_or_else(|| invalid("malformed entry header"))?;
+                let micros: u64 = micros
+                    .parse()
+                    .map_err(|_| invalid("invalid entry timestamp"))?;
+                let len: usize = len.parse().map_err(|_| invalid("invalid source length"))?;
+                let source = tail
+                    .get(..len)
+                    .ok_or_else(|| invalid("truncated entry source"))?;
+                history.push(HistoryEntry {
+                    at: Duration::from_micros(micros),
+                    source: source.to_string(),
+                    deltas: Vec::new(),
+                });
+                rest = tail[len..].strip_prefix('\n').unwrap_or(&tail[len..]);
+            } else if let Some(delta) = line.strip_prefix("= ") {
+                let entry = history
+                    .entries
+                    .last_mut()
+                    .ok_or_else(|| invalid("delta without entry"))?;
+                entry.deltas.push(parse_delta(delta)?);
+                rest = tail;
+            } else if line.is_empty() {
+                rest = tail;
+            } else {
+                return Err(invalid("unexpected line in history"));
+            }
+        }
+        Ok(history)
+    }
+
+    /// Writes the history to `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_text())
+    }
+
+    /// Reads a history from `path`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_text(&std::fs::read_to_string(path)?)
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn delta_text(delta: &Delta) -> String {
+    match delta {
+        Delta::Add(name) => format!("Add {name}"),
+        Delta::Modify(name) => format!("Modify {name}"),
+        Delta::Remove(name) => format!("Remove {name}"),
+        Delta::Mute(name) => format!("Mute {name}"),
+        Delta::Unmute(name) => format!("Unmute {name}"),
+        Delta::SetParameter {
+            pattern,
+            param,
+            value,
+        } => format!("SetParameter {pattern} {param} {value}"),
+    }
+}
+
+fn parse_delta(text: &str) -> io::Result<Delta> {
+    let (kind, name) = text
+        .split_once(' ')
+        .ok_or_else(|| invalid("malformed delta"))?;
+    let name = name.to_string();
+    match kind {
+        "Add" => Ok(Delta::Add(name)),
+        "Modify" => Ok(Delta::Modify(name)),
+        "Remove" => Ok(Delta::Remove(name)),
+        "Mute" => Ok(Delta::Mute(name)),
+        "Unmute" => Ok(Delta::Unmute(name)),
+        "SetParameter" => {
+            let mut parts = name.split(' ');
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(pattern), Some(param), Some(value), None) => Ok(Delta::SetParameter {
+                    pattern: pattern.to_string(),
+                    param: param.to_string(),
+                    value: value
+                        .parse()
+                        .map_err(|_| invalid("invalid parameter value"))?,
+                }),
+                _ => Err(invalid("malformed parameter delta")),
+            }
+        }
+        _ => Err(invalid("unknown delta kind")),
+    }
+}
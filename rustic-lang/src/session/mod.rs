@@ -4,12 +4,26 @@
 //! signature, etc.  The TUI calls [`Session::evaluate`] on save, which
 //! parses the source, diffs against the previous state, and queues
 //! changes for the next loop boundary.
+//!
+//! A [`Transport`] turns sample counts into bars, beats and ticks at the
+//! session tempo.
+//!
+//! Every evaluation is recorded in a [`History`] which can be exported and
+//! replayed with [`Session::replay`] to reconstruct a set deterministically.
+
+mod history;
+mod transport;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
 
-use crate::ast::{PatternDef, Program, SourceLine};
+use crate::ast::{PatternDef, PitchRoot, Program, ScaleMode, SourceLine};
 use crate::error::CompileError;
 use crate::parser::parse_program;
+use crate::runtime::{self, MiniEvent, QueryContext};
+
+pub use history::{History, HistoryEntry};
+pub use transport::{BpmRamp, DEFAULT_TICKS_PER_BEAT, Position, Transport, TransportEvent};
 
 /// A change that will be applied at the next loop boundary.
 #[derive(Debug, Clone, PartialEq)]
@@ -24,6 +38,13 @@ pub enum Delta {
     Mute(String),
     /// A pattern was unmuted.
     Unmute(String),
+    /// A `set` line gave a new value to a parameter of a pattern's
+    /// instrument.
+    SetParameter {
+        pattern: String,
+        param: String,
+        value: f64,
+    },
 }
 
 /// Result returned by [`Session::evaluate`].
@@ -36,83 +57,175 @@ pub struct EvalResult {
     /// Summary counts.
     pub patterns_active: usize,
     pub patterns_muted: usize,
+    /// Seed driving the random features of this evaluation, derived from the
+    /// source so replaying it yields the same events.
+    pub seed: u64,
 }
 
 /// Live session state.
 pub struct Session {
-    /// Current BPM.
+    /// Current BPM, or the target BPM of a ramp.
     pub bpm: u32,
+    /// Tempo ramp set by a `bpm <from> -> <to> over <bars>` line.
+    pub ramp: Option<BpmRamp>,
     /// Current time signature (numerator, denominator).
     pub sig: (u8, u8),
+    /// Default scale for scale degrees, set by a `scale` line.
+    pub scale: Option<(PitchRoot, ScaleMode)>,
+    /// Seed of the last evaluation.
+    pub seed: u64,
     /// Active patterns by name.
     patterns: HashMap<String, PatternDef>,
+    /// Instrument parameters set by `set` lines, by pattern and parameter.
+    parameters: BTreeMap<(String, String), f64>,
     /// Pending deltas (queued for next loop boundary).
     pending: Vec<Delta>,
     /// Last successfully parsed program (for diffing).
     last_program: Option<Program>,
+    /// Time origin for history timestamps.
+    started: Instant,
+    /// Log of every evaluation.
+    history: History,
 }
 
 impl Session {
     pub fn new() -> Self {
         Self {
             bpm: 120,
+            ramp: None,
             sig: (4, 4),
+            scale: None,
+            seed: 0,
             patterns: HashMap::new(),
+            parameters: BTreeMap::new(),
             pending: Vec::new(),
             last_program: None,
+            started: Instant::now(),
+            history: History::new(),
         }
     }
 
     /// Evaluate a source string, parse it, diff against previous state,
     /// and return the result.
     pub fn evaluate(&mut self, source: &str) -> EvalResult {
+        // Microsecond resolution, matching the history file format.
+        let at = Duration::from_micros(self.started.elapsed().as_micros() as u64);
+        self.evaluate_at(source, at)
+    }
+
+    /// Rebuilds a session by evaluating every entry of `history` in order.
+    ///
+    /// Before each entry, `tick_fn` receives the time to wait since the
+    /// previous entry, so callers can sleep or advance a transport to keep the
+    /// original timing.  The replayed session records the original timestamps,
+    /// so its history matches the one that was replayed, and later
+    /// evaluations are timestamped after the last replayed entry.
+    pub fn replay<F: FnMut(Duration)>(history: &History, mut tick_fn: F) -> Self {
+        let mut session = Self::new();
+        let mut previous = Duration::ZERO;
+        for entry in history.entries() {
+            tick_fn(entry.at.saturating_sub(previous));
+            previous = entry.at;
+            session.evaluate_at(&entry.source, entry.at);
+        }
+        if let Some(started) = Instant::now().checked_sub(previous) {
+            session.started = started;
+        }
+        session
+    }
+
+    /// The log of every evaluation performed on this session.
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Evaluates `source` and records it in the history at timestamp `at`.
+    fn evaluate_at(&mut self, source: &str, at: Duration) -> EvalResult {
         let (program, errors) = parse_program(source);
 
         // Extract state from the new program
         let mut new_bpm = self.bpm;
+        let mut new_ramp = None;
         let mut new_sig = self.sig;
+        let mut new_scale = None;
         let mut new_patterns: HashMap<String, PatternDef> = HashMap::new();
+        let mut new_parameters = BTreeMap::new();
 
         for line in &program.lines {
             match line {
-                SourceLine::Bpm(val) => new_bpm = *val,
+                SourceLine::Bpm(val) => {
+                    new_bpm = *val;
+                    new_ramp = None;
+                }
+                SourceLine::BpmRamp { from, to, bars } => {
+                    new_bpm = *to;
+                    new_ramp = Some(BpmRamp {
+                        from: *from,
+                        to: *to,
+                        bars: *bars,
+                    });
+                }
                 SourceLine::Sig(num, den) => new_sig = (*num, *den),
+                SourceLine::Scale(root, mode) => new_scale = Some((*root, *mode)),
                 SourceLine::Pattern(def) => {
                     new_patterns.insert(def.name.clone(), def.clone());
                 }
+                SourceLine::Set {
+                    pattern,
+                    param,
+                    value,
+                } => {
+                    new_parameters.insert((pattern.clone(), param.clone()), *value);
+                }
                 _ => {}
             }
         }
 
         // Compute deltas
-        let deltas = self.diff(&new_patterns);
+        let mut deltas = self.diff(&new_patterns);
+        deltas.extend(self.diff_parameters(&new_parameters));
 
         // Apply immediate directives
         self.bpm = new_bpm;
+        self.ramp = new_ramp;
         self.sig = new_sig;
+        self.scale = new_scale;
+        self.seed = runtime::hash_str(source);
 
         // Update pattern state
         self.pending = deltas.clone();
         self.patterns = new_patterns;
+        self.parameters = new_parameters;
         self.last_program = Some(program);
 
         let patterns_active = self.patterns.values().filter(|p| !p.muted).count();
         let patterns_muted = self.patterns.values().filter(|p| p.muted).count();
 
+        self.history.push(HistoryEntry {
+            at,
+            source: source.to_string(),
+            deltas: deltas.clone(),
+        });
+
         EvalResult {
             errors,
             deltas,
             patterns_active,
             patterns_muted,
+            seed: self.seed,
         }
     }
 
     /// Diff new patterns against current state.
+    /// Patterns are visited in name order so the delta stream is deterministic.
     fn diff(&self, new_patterns: &HashMap<String, PatternDef>) -> Vec<Delta> {
         let mut deltas = Vec::new();
 
         // Check for added or modified patterns
-        for (name, new_def) in new_patterns {
+        let mut new_names: Vec<&String> = new_patterns.keys().collect();
+        new_names.sort();
+        for name in new_names {
+            let new_def = &new_patterns[name];
             match self.patterns.get(name) {
                 None => deltas.push(Delta::Add(name.clone())),
                 Some(old_def) => {
@@ -128,7 +241,9 @@ impl Session {
         }
 
         // Check for removed patterns
-        for name in self.patterns.keys() {
+        let mut old_names: Vec<&String> = self.patterns.keys().collect();
+        old_names.sort();
+        for name in old_names {
             if !new_patterns.contains_key(name) {
                 deltas.push(Delta::Remove(name.clone()));
             }
@@ -137,6 +252,20 @@ impl Session {
         deltas
     }
 
+    /// Parameters whose value is new or changed.  Parameters whose `set` line
+    /// was removed keep their last value, so they produce no delta.
+    fn diff_parameters(&self, new_parameters: &BTreeMap<(String, String), f64>) -> Vec<Delta> {
+        new_parameters
+            .iter()
+            .filter(|(key, value)| self.parameters.get(*key) != Some(*value))
+            .map(|((pattern, param), value)| Delta::SetParameter {
+                pattern: pattern.clone(),
+                param: param.clone(),
+                value: *value,
+            })
+            .collect()
+    }
+
     /// Get the currently pending deltas.
     pub fn pending_deltas(&self) -> &[Delta] {
         &self.pending
@@ -156,6 +285,33 @@ impl Session {
     pub fn all_patterns(&self) -> &HashMap<String, PatternDef> {
         &self.patterns
     }
+
+    /// Value of `param` on the instrument of `pattern`, as set by the last
+    /// evaluation.
+    pub fn parameter(&self, pattern: &str, param: &str) -> Option<f64> {
+        self.parameters
+            .get(&(pattern.to_string(), param.to_string()))
+            .copied()
+    }
+
+    /// The tempo, time signature, scale and seed patterns are queried with.
+    pub fn context(&self) -> QueryContext {
+        QueryContext {
+            bpm: self.bpm,
+            sig: self.sig,
+            scale: self.scale,
+            seed: self.seed,
+        }
+    }
+
+    /// Events of pattern `name` in cycle `cycle`; empty if the pattern is
+    /// unknown or muted.
+    pub fn events(&self, name: &str, cycle: u64) -> Vec<MiniEvent> {
+        self.patterns
+            .get(name)
+            .map(|def| runtime::query_cycle(def, cycle, &self.context()))
+            .unwrap_or_default()
+    }
 }
 
 impl Default for Session {
@@ -298,4 +454,170 @@ lead piano \"c4 eb4 g4 bb4\" | slow 2
         assert_eq!(result.patterns_muted, 1);
         assert_eq!(result.deltas.len(), 6); // all new = 6 adds
     }
+
+    #[test]
+    fn test_history_records_evaluations() {
+        let mut session = Session::new();
+        session.evaluate("kick kick \"x ~ x ~\"");
+        session.evaluate("kick kick \"x x x x\"");
+
+        let entries = session.history().entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].source, "kick kick \"x x x x\"");
+        assert_eq!(entries[1].deltas, vec![Delta::Modify("kick".into())]);
+        assert!(entries[0].at <= entries[1].at);
+    }
+
+    #[test]
+    fn test_replay_reproduces_state_and_deltas() {
+        let mut session = Session::new();
+        session.evaluate("bpm 128\nkick kick \"x ~ x ~\"\nbass saw \"c2 eb2\"");
+        session.evaluate("bpm 132\nkick kick \"x x x x\"\n; bass saw \"c2 eb2\"");
+        session.evaluate("sig 3/4\nkick kick \"x x x x\"\nbass saw \"c2 eb2\"\nhats hihat \"x*8\"");
+        session.evaluate("kick kick \"x x x x\"\nhats hihat \"x*8\"");
+
+        // Round-trip through the text export to check it is lossless
+        let exported = session.history().to_text();
+        let history = History::from_text(&exported).unwrap();
+        assert_eq!(&history, session.history());
+
+        let mut waits = Vec::new();
+        let replayed = Session::replay(&history, |wait| waits.push(wait));
+
+        assert_eq!(waits.len(), 4);
+        assert_eq!(replayed.bpm, session.bpm);
+        assert_eq!(replayed.sig, session.sig);
+        assert_eq!(replayed.all_patterns(), session.all_patterns());
+        assert_eq!(replayed.history(), session.history());
+    }
+
+    #[test]
+    fn test_evaluating_after_a_replay_keeps_time_moving_forward() {
+        let mut history = History::new();
+        history.push(HistoryEntry {
+            at: Duration::from_secs(90),
+            source: "kick kick \"x ~ x ~\"".into(),
+            deltas: vec![Delta::Add("kick".into())],
+        });
+
+        let mut replayed = Session::replay(&history, |_| {});
+        let result = replayed.evaluate("kick kick \"x x x x\"");
+        assert_eq!(result.deltas, vec![Delta::Modify("kick".into())]);
+
+        let entries = replayed.history().entries();
+        assert_eq!(entries.len(), 2);
+        assert!(
+            entries[1].at >= Duration::from_secs(90),
+            "{:?}",
+            entries[1].at
+        );
+    }
+
+    #[test]
+    fn test_events_are_seeded_by_source() {
+        let source = "scale C major\nhats hihat \"[x? x? x? x?]*2\"\nbass saw \"0 2\"";
+        let mut first = Session::new();
+        let mut second = Session::new();
+        assert_eq!(first.evaluate(source).seed, second.evaluate(source).seed);
+        assert_eq!(first.events("hats", 3), second.events("hats", 3));
+
+        // Degrees resolve through the session scale
+        let bass = first.events("bass", 0);
+        assert_eq!(bass[1].value, crate::runtime::EventValue::Pitch(64));
+        assert!(first.events("missing", 0).is_empty());
+    }
+
+    #[test]
+    fn test_humanize_is_seeded_by_source() {
+        let source = "hats hihat \"x*8\" | humanize 15 0.2";
+        let mut first = Session::new();
+        let mut second = Session::new();
+        assert_eq!(first.evaluate(source).seed, second.evaluate(source).seed);
+        let events = first.events("hats", 3);
+        assert_eq!(events, second.events("hats", 3));
+
+        // The nudges are drawn from the seed, not left on the grid
+        assert!(
+            events
+                .iter()
+                .enumerate()
+                .any(|(i, event)| event.onset != 3.0 + i as f64 / 8.0)
+        );
+    }
+
+    #[test]
+    fn test_changing_a_layer_modifies_the_pattern() {
+        let mut session = Session::new();
+        session.evaluate("drums kick \"x ~ x ~\" | with \"~ x ~ x\"");
+        assert_eq!(session.events("drums", 0).len(), 4);
+
+        let result = session.evaluate("drums kick \"x ~ x ~\" | with \"~ x x x\"");
+        assert_eq!(result.deltas, vec![Delta::Modify("drums".into())]);
+        assert_eq!(session.events("drums", 0).len(), 5);
+    }
+
+    #[test]
+    fn test_set_lines_produce_parameter_deltas() {
+        let mut session = Session::new();
+        let result =
+            session.evaluate("bass saw \"c2 eb2\"\nset bass cutoff 800\nset bass attack 0.01");
+        assert_eq!(
+            result.deltas,
+            vec![
+                Delta::Add("bass".into()),
+                Delta::SetParameter {
+                    pattern: "bass".into(),
+                    param: "attack".into(),
+                    value: 0.01,
+                },
+                Delta::SetParameter {
+                    pattern: "bass".into(),
+                    param: "cutoff".into(),
+                    value: 800.0,
+                },
+            ]
+        );
+        assert_eq!(session.parameter("bass", "cutoff"), Some(800.0));
+
+        // Only changed values are reported
+        let result =
+            session.evaluate("bass saw \"c2 eb2\"\nset bass cutoff 1200\nset bass attack 0.01");
+        assert_eq!(
+            result.deltas,
+            vec![Delta::SetParameter {
+                pattern: "bass".into(),
+                param: "cutoff".into(),
+                value: 1200.0,
+            }]
+        );
+
+        // The history keeps parameter deltas
+        let history = History::from_text(&session.history().to_text()).unwrap();
+        assert_eq!(&history, session.history());
+    }
+
+    #[test]
+    fn test_bpm_ramp_is_kept_apart_from_static_bpm() {
+        let mut session = Session::new();
+        session.evaluate("bpm 100 -> 130 over 4");
+        assert_eq!(session.bpm, 130);
+        assert_eq!(
+            session.ramp,
+            Some(BpmRamp {
+                from: 100,
+                to: 130,
+                bars: 4
+            })
+        );
+
+        session.evaluate("bpm 90");
+        assert_eq!(session.bpm, 90);
+        assert_eq!(session.ramp, None);
+    }
+
+    #[test]
+    fn test_history_rejects_malformed_text() {
+        assert!(History::from_text("not a history").is_err());
+        assert!(History::from_text("# rustic-lang history v1\n@ 10 500\nshort").is_err());
+    }
 }
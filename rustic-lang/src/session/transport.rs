@@ -0,0 +1,463 @@
+//! Sample-accurate musical clock.
+//!
+//! A [`Transport`] turns a running sample count into musical time using the
+//! tempo and time signature of a [`Session`](super::Session).  The audio
+//! engine advances it once per block and receives the [`TransportEvent`]s
+//! falling inside that block, each with the exact sample it lands on, to
+//! schedule pattern events.
+//!
+//! A beat is one `1/denominator` note of the time signature and the BPM
+//! counts those beats.  Each beat is divided into `ticks_per_beat` ticks.
+//!
+//! A [`BpmRamp`] moves the tempo linearly from one BPM to another over a
+//! number of bars, updating it on every tick.
+
+use std::fmt;
+
+use super::Session;
+
+/// Default tick resolution, in ticks per beat.
+pub const DEFAULT_TICKS_PER_BEAT: u32 = 96;
+
+/// Slowest tempo the transport runs at.  Lower tempos, which `Session`'s
+/// public fields allow but the parser does not, are raised to it so that the
+/// tick length stays finite.
+const MIN_BPM: f64 = 1.0;
+
+/// A musical position.  All fields are zero-based; [`fmt::Display`] prints
+/// bars and beats one-based, as `bars:beats:ticks`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub bar: u64,
+    pub beat: u32,
+    pub tick: u32,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.bar + 1, self.beat + 1, self.tick)
+    }
+}
+
+/// A gradual tempo change: `bpm <from> -> <to> over <bars>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BpmRamp {
+    pub from: u32,
+    pub to: u32,
+    pub bars: u32,
+}
+
+impl BpmRamp {
+    /// Tempo after `progress` of the ramp, from 0 (start) to 1 (end).
+    pub fn bpm_at(&self, progress: f64) -> f64 {
+        let progress = progress.clamp(0.0, 1.0);
+        self.from as f64 + (self.to as f64 - self.from as f64) * progress
+    }
+}
+
+/// A timing event, with the absolute sample index it falls on.
+///
+/// Events sharing a sample are emitted as `Bar`, then `Beat`, then `Tick`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportEvent {
+    /// The first beat of a bar.
+    Bar { bar: u64, sample: u64 },
+    /// The first tick of a beat.
+    Beat { bar: u64, beat: u32, sample: u64 },
+    /// Every tick.
+    Tick { position: Position, sample: u64 },
+}
+
+impl TransportEvent {
+    /// The sample index the event falls on.
+    pub fn sample(&self) -> u64 {
+        match *self {
+            Self::Bar { sample, .. } | Self::Beat { sample, .. } | Self::Tick { sample, .. } => {
+                sample
+            }
+        }
+    }
+}
+
+/// Clock advancing by sample count and emitting bar, beat and tick events.
+///
+/// Tempo changes picked up by [`Transport::sync`] take effect from the next
+/// tick; time signature changes wait for the next bar.  A new ramp starts
+/// from the next tick and, while it runs, overrides the session tempo.
+#[derive(Debug, Clone)]
+pub struct Transport {
+    sample_rate: u32,
+    bpm: f64,
+    sig: (u8, u8),
+    /// Last ramp picked up from the session, and the tick it started on
+    /// while it is running.
+    ramp: Option<BpmRamp>,
+    ramp_start: Option<u64>,
+    pending_sig: Option<(u8, u8)>,
+    ticks_per_beat: u32,
+    /// Samples elapsed since the transport started.
+    sample: u64,
+    /// Fractional sample of the tick at `origin_tick`.
+    origin_sample: f64,
+    origin_tick: u64,
+    /// Index and position of the next tick to be emitted.
+    next_tick: u64,
+    next: Position,
+    /// Position of the last emitted tick.
+    current: Position,
+}
+
+impl Transport {
+    /// Creates a transport at `sample_rate` following the tempo, tempo ramp
+    /// and time signature of `session`.
+    pub fn new(sample_rate: u32, session: &Session) -> Self {
+        let mut transport = Self {
+            sample_rate,
+            bpm: (session.bpm as f64).max(MIN_BPM),
+            sig: session.sig,
+            ramp: None,
+            ramp_start: None,
+            pending_sig: None,
+            ticks_per_beat: DEFAULT_TICKS_PER_BEAT,
+            sample: 0,
+            origin_sample: 0.0,
+            origin_tick: 0,
+            next_tick: 0,
+            next: Position::default(),
+            current: Position::default(),
+        };
+        transport.sync(session);
+        transport
+    }
+
+    /// Builder-style setter for the tick resolution.
+    pub fn with_ticks_per_beat(mut self, ticks_per_beat: u32) -> Self {
+        self.ticks_per_beat = ticks_per_beat.max(1);
+        self
+    }
+
+    /// Picks up tempo, tempo ramp and time signature changes from `session`.
+    pub fn sync(&mut self, session: &Session) {
+        if session.ramp != self.ramp {
+            self.ramp = session.ramp;
+            self.ramp_start = session.ramp.map(|_| self.next_tick);
+            if let Some(ramp) = session.ramp {
+                self.set_bpm(ramp.from as f64);
+            }
+        }
+        let bpm = (session.bpm as f64).max(MIN_BPM);
+        if self.ramp_start.is_none() && bpm != self.bpm {
+            self.set_bpm(bpm);
+        }
+        if session.sig != self.sig {
+            self.pending_sig = Some(session.sig);
+        }
+    }
+
+    /// Rewinds to the start of the first bar.  A running ramp restarts.
+    pub fn reset(&mut self) {
+        if let (Some(ramp), Some(_)) = (self.ramp, self.ramp_start) {
+            self.bpm = (ramp.from as f64).max(MIN_BPM);
+            self.ramp_start = Some(0);
+        }
+        self.sample = 0;
+        self.origin_sample = 0.0;
+        self.origin_tick = 0;
+        self.next_tick = 0;
+        self.next = Position::default();
+        self.current = Position::default();
+    }
+
+    /// Samples elapsed since the transport started.
+    pub fn sample(&self) -> u64 {
+        self.sample
+    }
+
+    /// Position of the last tick reached.
+    pub fn position(&self) -> Position {
+        self.current
+    }
+
+    /// Current tempo, which is fractional during a ramp.
+    pub fn bpm(&self) -> f64 {
+        self.bpm
+    }
+
+    /// Returns `true` while a tempo ramp is running.
+    pub fn is_ramping(&self) -> bool {
+        self.ramp_start.is_some()
+    }
+
+    pub fn sig(&self) -> (u8, u8) {
+        self.sig
+    }
+
+    /// Length of a beat, in samples.
+    pub fn samples_per_beat(&self) -> f64 {
+        if self.bpm <= 0.0 {
+            f64::INFINITY
+        } else {
+            self.sample_rate as f64 * 60.0 / self.bpm
+        }
+    }
+
+    /// Advances by `frames` samples and returns the events falling inside
+    /// them, in order.
+    pub fn advance(&mut self, frames: usize) -> Vec<TransportEvent> {
+        let end = self.sample + frames as u64;
+        let mut events = Vec::new();
+
+        loop {
+            let sample = self.tick_sample(self.next_tick).round() as u64;
+            if sample >= end {
+                break;
+            }
+            self.emit(sample, &mut events);
+        }
+
+        self.sample = end;
+        events
+    }
+
+    /// Changes the tempo from the next tick on, keeping that tick where the
+    /// old tempo put it.
+    fn set_bpm(&mut self, bpm: f64) {
+        self.origin_sample = self.tick_sample(self.next_tick);
+        self.origin_tick = self.next_tick;
+        self.bpm = bpm.max(MIN_BPM);
+    }
+
+    /// Moves a running ramp to the tempo of the next tick.
+    fn update_ramp(&mut self) {
+        let (Some(ramp), Some(start)) = (self.ramp, self.ramp_start) else {
+            return;
+        };
+        let ticks_per_bar = self.ticks_per_beat as f64 * self.sig.0.max(1) as f64;
+        let length = ramp.bars as f64 * ticks_per_bar;
+        let progress = if length > 0.0 {
+            (self.next_tick - start) as f64 / length
+        } else {
+            1.0
+        };
+        self.set_bpm(ramp.bpm_at(progress));
+        if progress >= 1.0 {
+            self.ramp_start = None;
+        }
+    }
+
+    /// Fractional sample of tick `tick` at the current tempo.
+    fn tick_sample(&self, tick: u64) -> f64 {
+        let samples_per_tick = self.samples_per_beat() / self.ticks_per_beat as f64;
+        self.origin_sample + (tick - self.origin_tick) as f64 * samples_per_tick
+    }
+
+    /// Emits the events of the next tick and moves on to the following one.
+    fn emit(&mut self, sample: u64, events: &mut Vec<TransportEvent>) {
+        let Position { bar, beat, tick } = self.next;
+        if tick == 0 {
+            if beat == 0 {
+                events.push(TransportEvent::Bar { bar, sample });
+            }
+            events.push(TransportEvent::Beat { bar, beat, sample });
+        }
+        events.push(TransportEvent::Tick {
+            position: self.next,
+            sample,
+        });
+
+        self.current = self.next;
+        self.next_tick += 1;
+        self.next.tick += 1;
+        if self.next.tick == self.ticks_per_beat {
+            self.next.tick = 0;
+            self.next.beat += 1;
+            if self.next.beat >= u32::from(self.sig.0.max(1)) {
+                self.next.beat = 0;
+                self.next.bar += 1;
+                if let Some(sig) = self.pending_sig.take() {
+                    self.sig = sig;
+                }
+            }
+        }
+        self.update_ramp();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_at(bpm: u32, sig: (u8, u8)) -> Session {
+        let mut session = Session::new();
+        session.bpm = bpm;
+        session.sig = sig;
+        session
+    }
+
+    /// Runs `blocks` blocks of `block_size` samples and collects the events.
+    fn run(transport: &mut Transport, blocks: usize, block_size: usize) -> Vec<TransportEvent> {
+        (0..blocks)
+            .flat_map(|_| transport.advance(block_size))
+            .collect()
+    }
+
+    #[test]
+    fn test_beats_land_on_expected_samples() {
+        let mut transport = Transport::new(48000, &session_at(120, (4, 4)));
+        // 2 seconds = one bar of 4/4 at 120 BPM, plus the next downbeat
+        let events = run(&mut transport, 189, 512);
+
+        let beats: Vec<(u64, u32, u64)> = events
+            .iter()
+            .filter_map(|e| match *e {
+                TransportEvent::Beat { bar, beat, sample } => Some((bar, beat, sample)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            beats,
+            vec![
+                (0, 0, 0),
+                (0, 1, 24000),
+                (0, 2, 48000),
+                (0, 3, 72000),
+                (1, 0, 96000)
+            ]
+        );
+
+        let bars: Vec<u64> = events
+            .iter()
+            .filter(|e| matches!(e, TransportEvent::Bar { .. }))
+            .map(TransportEvent::sample)
+            .collect();
+        assert_eq!(bars, vec![0, 96000]);
+    }
+
+    #[test]
+    fn test_ticks_and_position() {
+        let mut transport = Transport::new(48000, &session_at(120, (4, 4))).with_ticks_per_beat(4);
+        let events = transport.advance(30000);
+
+        let ticks: Vec<u64> = events
+            .iter()
+            .filter(|e| matches!(e, TransportEvent::Tick { .. }))
+            .map(TransportEvent::sample)
+            .collect();
+        assert_eq!(ticks, vec![0, 6000, 12000, 18000, 24000]);
+        assert_eq!(
+            transport.position(),
+            Position {
+                bar: 0,
+                beat: 1,
+                tick: 0
+            }
+        );
+        assert_eq!(transport.position().to_string(), "1:2:0");
+        assert_eq!(transport.sample(), 30000);
+    }
+
+    #[test]
+    fn test_events_are_ordered_within_a_sample() {
+        let mut transport = Transport::new(44100, &session_at(120, (3, 4)));
+        let events = transport.advance(1);
+        assert!(matches!(events[0], TransportEvent::Bar { bar: 0, .. }));
+        assert!(matches!(events[1], TransportEvent::Beat { beat: 0, .. }));
+        assert!(matches!(events[2], TransportEvent::Tick { .. }));
+    }
+
+    #[test]
+    fn test_ramp_interpolates_tempo() {
+        let mut session = Session::new();
+        session.evaluate("bpm 120 -> 140 over 8");
+        let mut transport = Transport::new(48000, &session).with_ticks_per_beat(24);
+        transport.sync(&session);
+        assert!(transport.is_ramping());
+        assert_eq!(transport.bpm(), 120.0);
+
+        // Run up to the downbeat of bar 5, halfway through the ramp
+        let mut bar_samples = Vec::new();
+        while transport.position().bar < 4 {
+            for event in transport.advance(64) {
+                if let TransportEvent::Bar { sample, .. } = event {
+                    bar_samples.push(sample);
+                }
+            }
+        }
+        assert!((transport.bpm() - 130.0).abs() < 0.2, "{}", transport.bpm());
+        // Bars get shorter as the tempo rises
+        assert!(bar_samples[1] - bar_samples[0] > bar_samples[4] - bar_samples[3]);
+
+        // Past the end the tempo stays at the target, even after a re-sync
+        // with the same source
+        while transport.position().bar < 9 {
+            transport.advance(4096);
+        }
+        session.evaluate("bpm 120 -> 140 over 8");
+        transport.sync(&session);
+        assert!(!transport.is_ramping());
+        assert_eq!(transport.bpm(), 140.0);
+        assert!((transport.samples_per_beat() - 48000.0 * 60.0 / 140.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zero_bpm_is_clamped() {
+        let transport = Transport::new(48000, &session_at(0, (4, 4)));
+        assert_eq!(transport.bpm(), MIN_BPM);
+        assert!(transport.samples_per_beat().is_finite());
+
+        let mut transport = Transport::new(48000, &session_at(120, (4, 4)));
+        transport.advance(1000);
+        transport.sync(&session_at(0, (4, 4)));
+        assert_eq!(transport.bpm(), MIN_BPM);
+        // The next tick stays where 120 BPM put it, and none is emitted early
+        let events = transport.advance(1000);
+        assert!(events.iter().all(|e| e.sample() == 1000), "{events:?}");
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_new_transport_starts_the_session_ramp() {
+        let mut session = Session::new();
+        session.evaluate("bpm 100 -> 140 over 2");
+        let mut transport = Transport::new(48000, &session);
+        assert!(transport.is_ramping());
+        assert_eq!(transport.bpm(), 100.0);
+
+        // Syncing with the same session does not restart the ramp
+        transport.advance(48000);
+        let bpm = transport.bpm();
+        assert!(bpm > 100.0, "{bpm}");
+        transport.sync(&session);
+        assert_eq!(transport.bpm(), bpm);
+    }
+
+    #[test]
+    fn test_sync_changes_tempo_and_signature() {
+        let mut session = session_at(120, (4, 4));
+        let mut transport = Transport::new(48000, &session).with_ticks_per_beat(1);
+        transport.advance(24000);
+
+        // Next beat keeps its old time, later beats follow 60 BPM
+        session.bpm = 60;
+        session.sig = (3, 4);
+        transport.sync(&session);
+        let beats: Vec<u64> = transport
+            .advance(48000 * 5)
+            .iter()
+            .filter(|e| matches!(e, TransportEvent::Beat { .. }))
+            .map(TransportEvent::sample)
+            .collect();
+        assert_eq!(beats, vec![24000, 72000, 120000, 168000, 216000]);
+
+        // The 4/4 bar finishes before 3/4 applies
+        let mut transport = Transport::new(48000, &session_at(120, (4, 4))).with_ticks_per_beat(1);
+        transport.sync(&session_at(120, (3, 4)));
+        let bars: Vec<u64> = run(&mut transport, 8, 24000)
+            .iter()
+            .filter(|e| matches!(e, TransportEvent::Bar { .. }))
+            .map(TransportEvent::sample)
+            .collect();
+        assert_eq!(bars, vec![0, 96000, 168000]);
+        assert_eq!(transport.sig(), (3, 4));
+    }
+}
@@ -2,7 +2,7 @@ mod filter;
 mod parameters;
 
 pub use filter::{FilterInfo, FilterInput};
-pub use parameters::{ListSize, Literal, Parameter};
+pub use parameters::{ListSize, Literal, Parameter, ParameterScale};
 
 /// Strategy for combining multiple audio blocks arriving at the same input port.
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
@@ -56,10 +56,27 @@ pub trait MetaFilter {
     /// Sets a parameter by name. The default implementation is a no-op.
     fn set_parameter(&mut self, _name: &str, _value: f32) {}
 
+    /// Returns the current value of a scalar parameter by name, as passed to
+    /// `set_parameter`. The default implementation knows no parameters.
+    fn get_parameter(&self, _name: &str) -> Option<f32> {
+        None
+    }
+
+    /// Returns the static metadata of this filter's type. Unlike `metadata`,
+    /// this can be called on a trait object.
+    fn filter_info(&self) -> FilterInfo;
+
     /// Returns the static metadata for this filter type.
     fn metadata() -> FilterInfo
     where
         Self: Sized;
 }
 
+/// Trait for generators exposing metadata to the frontend.
+/// Implemented automatically by the `GeneratorMetaData` derive macro.
+pub trait GeneratorInfo {
+    /// Returns the static metadata for this generator type.
+    fn metadata() -> MetaGenerator;
+}
+
 use serde::{Deserialize, Serialize};
@@ -58,6 +58,60 @@ pub enum ListSize<S> {
     Constant(usize),
 }
 
+/// How a bounded parameter maps onto a 0–1 control position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum ParameterScale {
+    /// Equal steps of the control move the value by equal amounts.
+    #[default]
+    Linear,
+    /// Equal steps multiply the value by equal ratios (e.g. cutoff
+    /// frequencies). Falls back to linear when the minimum is not positive.
+    Logarithmic,
+    /// The value follows the square of the control position, giving finer
+    /// resolution near the minimum (e.g. times starting at zero).
+    Exponential,
+}
+
+impl ParameterScale {
+    /// Converts `value` in `min..=max` to a control position in `0..=1`.
+    pub fn normalize(self, value: f32, min: f32, max: f32) -> f32 {
+        if max <= min {
+            return 0.0;
+        }
+        let value = value.clamp(min, max);
+        match self {
+            ParameterScale::Logarithmic if min > 0.0 => (value / min).ln() / (max / min).ln(),
+            ParameterScale::Exponential => ((value - min) / (max - min)).sqrt(),
+            _ => (value - min) / (max - min),
+        }
+    }
+
+    /// Converts a control position in `0..=1` to a value in `min..=max`.
+    pub fn denormalize(self, position: f32, min: f32, max: f32) -> f32 {
+        if max <= min {
+            return min;
+        }
+        let position = position.clamp(0.0, 1.0);
+        let value = match self {
+            ParameterScale::Logarithmic if min > 0.0 => min * (max / min).powf(position),
+            ParameterScale::Exponential => min + (max - min) * position * position,
+            _ => min + (max - min) * position,
+        };
+        value.clamp(min, max)
+    }
+}
+
+impl ToTokens for ParameterScale {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(match self {
+            ParameterScale::Linear => quote! { rustic_meta::ParameterScale::Linear },
+            ParameterScale::Logarithmic => quote! { rustic_meta::ParameterScale::Logarithmic },
+            ParameterScale::Exponential => quote! { rustic_meta::ParameterScale::Exponential },
+        });
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub enum Parameter<S> {
@@ -74,12 +128,19 @@ pub enum Parameter<S> {
         max: f32,
         default: f32,
         value: f32,
+        /// Display unit, e.g. "Hz" or "dB"
+        #[serde(default)]
+        unit: Option<S>,
+        #[serde(default)]
+        scale: ParameterScale,
     },
     Float {
         title: S,
         field_name: S,
         default: f32,
         value: f32,
+        #[serde(default)]
+        unit: Option<S>,
     },
     Int {
         title: S,
@@ -88,6 +149,10 @@ pub enum Parameter<S> {
         value: i32,
         min: Option<i32>,
         max: Option<i32>,
+        #[serde(default)]
+        unit: Option<S>,
+        #[serde(default)]
+        scale: ParameterScale,
     },
     List {
         title: S,
@@ -95,6 +160,72 @@ pub enum Parameter<S> {
         size: ListSize<S>,
         ltype: Literal,
     },
+    /// A discrete selection among labelled options, set by index.
+    Choice {
+        title: S,
+        field_name: S,
+        options: Vec<S>,
+        default: usize,
+        value: usize,
+    },
+}
+
+impl<S> Parameter<S> {
+    /// Name of the struct field this parameter maps to.
+    pub fn field_name(&self) -> &S {
+        match self {
+            Parameter::Toggle { field_name, .. }
+            | Parameter::Range { field_name, .. }
+            | Parameter::Float { field_name, .. }
+            | Parameter::Int { field_name, .. }
+            | Parameter::List { field_name, .. }
+            | Parameter::Choice { field_name, .. } => field_name,
+        }
+    }
+
+    /// Display unit of the parameter, if any.
+    pub fn unit(&self) -> Option<&S> {
+        match self {
+            Parameter::Range { unit, .. }
+            | Parameter::Float { unit, .. }
+            | Parameter::Int { unit, .. } => unit.as_ref(),
+            Parameter::Toggle { .. } | Parameter::List { .. } | Parameter::Choice { .. } => None,
+        }
+    }
+
+    /// Converts `value` to a 0–1 control position according to the
+    /// parameter's bounds and scale. Returns `None` for parameters without
+    /// both bounds (floats, unbounded ints, toggles and lists).
+    pub fn normalize(&self, value: f32) -> Option<f32> {
+        let (min, max, scale) = self.bounds()?;
+        Some(scale.normalize(value, min, max))
+    }
+
+    /// Converts a 0–1 control position back to a parameter value; the inverse
+    /// of [`normalize`](Self::normalize). Integer parameters are rounded.
+    pub fn denormalize(&self, position: f32) -> Option<f32> {
+        let (min, max, scale) = self.bounds()?;
+        let value = scale.denormalize(position, min, max);
+        Some(match self {
+            Parameter::Int { .. } => value.round(),
+            _ => value,
+        })
+    }
+
+    fn bounds(&self) -> Option<(f32, f32, ParameterScale)> {
+        match self {
+            Parameter::Range {
+                min, max, scale, ..
+            } => Some((*min, *max, *scale)),
+            Parameter::Int {
+                min: Some(min),
+                max: Some(max),
+                scale,
+                ..
+            } => Some((*min as f32, *max as f32, *scale)),
+            _ => None,
+        }
+    }
 }
 
 impl<T: ToTokens> ToTokens for ListSize<T> {
@@ -114,6 +245,14 @@ impl<T: ToTokens> ToTokens for ListSize<T> {
     }
 }
 
+/// Quotes an `Option` as `Some(value)` or `None`.
+fn option_tokens<T: ToTokens>(option: &Option<T>) -> proc_macro2::TokenStream {
+    match option {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
 impl<T: ToTokens> ToTokens for Parameter<T> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         match self {
@@ -139,7 +278,10 @@ impl<T: ToTokens> ToTokens for Parameter<T> {
                 max,
                 default,
                 value,
+                unit,
+                scale,
             } => {
+                let unit_tokens = option_tokens(unit);
                 tokens.extend(quote! {
                     rustic_meta::Parameter::Range {
                         title: #title,
@@ -147,7 +289,9 @@ impl<T: ToTokens> ToTokens for Parameter<T> {
                         min: #min,
                         max: #max,
                         default: #default,
-                        value: #value
+                        value: #value,
+                        unit: #unit_tokens,
+                        scale: #scale
                     }
                 });
             }
@@ -156,13 +300,16 @@ impl<T: ToTokens> ToTokens for Parameter<T> {
                 field_name,
                 default,
                 value,
+                unit,
             } => {
+                let unit_tokens = option_tokens(unit);
                 tokens.extend(quote! {
                     rustic_meta::Parameter::Float {
                         title: #title,
                         field_name: #field_name,
                         default: #default,
-                        value: #value
+                        value: #value,
+                        unit: #unit_tokens
                     }
                 });
             }
@@ -173,7 +320,10 @@ impl<T: ToTokens> ToTokens for Parameter<T> {
                 value,
                 min,
                 max,
+                unit,
+                scale,
             } => {
+                let unit_tokens = option_tokens(unit);
                 let min_tokens = match min {
                     Some(v) => quote! { Some(#v) },
                     None => quote! { None },
@@ -189,7 +339,9 @@ impl<T: ToTokens> ToTokens for Parameter<T> {
                         default: #default,
                         value: #value,
                         min: #min_tokens,
-                        max: #max_tokens
+                        max: #max_tokens,
+                        unit: #unit_tokens,
+                        scale: #scale
                     }
                 });
             }
@@ -208,6 +360,23 @@ impl<T: ToTokens> ToTokens for Parameter<T> {
                     }
                 });
             }
+            Parameter::Choice {
+                title,
+                field_name,
+                options,
+                default,
+                value,
+            } => {
+                tokens.extend(quote! {
+                    rustic_meta::Parameter::Choice {
+                        title: #title,
+                        field_name: #field_name,
+                        options: vec![#(#options),*],
+                        default: #default,
+                        value: #value
+                    }
+                });
+            }
         }
     }
 }
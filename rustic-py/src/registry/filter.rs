@@ -16,19 +16,48 @@ pub fn build_filter(spec: &FilterSpec, sample_rate: f32) -> Result<Box<dyn Filte
             get_f32(p, "cutoff_frequency", 1000.0),
             sample_rate,
         )),
+        "LadderFilter" => Box::new(LadderFilter::new(
+            get_f32(p, "cutoff", 1000.0),
+            get_f32(p, "resonance", 0.1),
+            sample_rate,
+        )),
         "BandPass" => Box::new(BandPass::new(
             get_f32(p, "low", 200.0),
             get_f32(p, "high", 4000.0),
             sample_rate,
         )),
+        "Crossover" => {
+            let frequencies: Vec<f32> = match p.get("frequencies").and_then(|v| v.as_array()) {
+                Some(values) => values
+                    .iter()
+                    .filter_map(|v| v.as_f64())
+                    .map(|v| v as f32)
+                    .collect(),
+                None => vec![1000.0],
+            };
+            Box::new(Crossover::new(&frequencies, sample_rate))
+        }
         "ResonantBandpassFilter" => Box::new(ResonantBandpassFilter::new(
             get_f32(p, "center", 1000.0),
             get_f32(p, "quality", 1.0),
             sample_rate,
         )),
+        "CombFilter" => Box::new(CombFilter::new(
+            get_usize(p, "delay", 441),
+            get_f32(p, "gain", 0.5),
+            CombMode::from(get_usize(p, "mode", 0)),
+        )),
+        "AllPassFilter" => Box::new(AllPassFilter::new(
+            get_usize(p, "delay", 221),
+            get_f32(p, "coefficient", 0.5),
+        )),
         "MovingAverage" => Box::new(MovingAverage::new(get_usize(p, "size", 5))),
         "GainFilter" => Box::new(GainFilter::new(get_f32(p, "factor", 1.0))),
         "Clipper" => Box::new(Clipper::new(get_f32(p, "max_ampl", 0.8))),
+        "BitCrusher" => Box::new(BitCrusher::new(
+            get_usize(p, "bits", 8) as u32,
+            get_usize(p, "downsample", 1),
+        )),
         "Compressor" => {
             let mut c = Compressor::default();
             for (k, v) in p {
@@ -38,13 +67,60 @@ pub fn build_filter(spec: &FilterSpec, sample_rate: f32) -> Result<Box<dyn Filte
             }
             Box::new(c)
         }
+        "MultibandCompressor" => {
+            let mut c = MultibandCompressor::new(
+                get_f32(p, "low_crossover", 200.0),
+                get_f32(p, "high_crossover", 3000.0),
+                sample_rate,
+            );
+            for (k, v) in p {
+                if let Some(f) = v.as_f64() {
+                    c.set_parameter(k.as_str(), f as f32);
+                }
+            }
+            Box::new(c)
+        }
+        "EnvelopeFollower" => Box::new(EnvelopeFollower::new(
+            get_f32(p, "attack", 0.01),
+            get_f32(p, "release", 0.1),
+            sample_rate,
+        )),
         "Tremolo" => Box::new(Tremolo::new(
             get_f32(p, "frequency", 5.0),
             get_f32(p, "depth", 0.5),
             sample_rate,
         )),
-        "DelayFilter" => Box::new(DelayFilter::new(sample_rate, get_f32(p, "delay_for", 0.5))),
+        "Phaser" => Box::new(Phaser::new(
+            get_usize(p, "stages", 4),
+            get_f32(p, "rate", 0.5),
+            get_f32(p, "depth", 1.0),
+            get_f32(p, "feedback", 0.0),
+            sample_rate,
+        )),
+        "DelayFilter" => Box::new(DelayFilter::new(sample_rate, get_f32(p, "delay", 0.5))),
+        "PingPongDelay" => Box::new(PingPongDelay::new(
+            get_f32(p, "delay_time", 0.3),
+            get_f32(p, "feedback", 0.5),
+            get_f32(p, "mix", 0.5),
+            sample_rate,
+        )),
         "PanFilter" => Box::new(PanFilter::new(get_f32(p, "direction", 0.0))),
+        "MonoToStereo" => Box::new(MonoToStereo::new()),
+        "StereoToMono" => Box::new(StereoToMono::new()),
+        "ConvolutionReverb" => {
+            let mix = get_f32(p, "mix", 0.3);
+            match p.get("ir_path").and_then(|v| v.as_str()) {
+                Some(path) => Box::new(
+                    ConvolutionReverb::from_wav(path, sample_rate as u32, mix)
+                        .map_err(|e| format!("Cannot load impulse response '{path}': {e}"))?,
+                ),
+                None => {
+                    let mut reverb = ConvolutionReverb::default();
+                    reverb.set_parameter("mix", mix);
+                    Box::new(reverb)
+                }
+            }
+        }
         other => return Err(format!("Unknown filter type: '{other}'")),
     };
     Ok(filter)
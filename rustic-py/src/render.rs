@@ -72,7 +72,9 @@ pub fn render_graph(spec: &GraphSpec) -> Result<Vec<[f32; 2]>, String> {
                 } => {
                     check_filter_index(*filter_out, &nodes)?;
                     check_filter_index(*filter_in, &nodes)?;
-                    system.connect(nodes[*filter_out], nodes[*filter_in], 0, 0);
+                    system
+                        .connect(nodes[*filter_out], nodes[*filter_in], 0, 0)
+                        .map_err(|e| format!("Graph connection error: {e}"))?;
                 }
             }
         }
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a bounded parameter maps onto a 0–1 control position.
+ */
+export type ParameterScale = "Linear" | "Logarithmic" | "Exponential";
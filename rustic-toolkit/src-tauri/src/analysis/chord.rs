@@ -0,0 +1,177 @@
+use std::fmt;
+
+use rustic::core::utils::NOTES;
+
+/// Matches scoring below this are not reported as chords
+pub const MIN_CHORD_CONFIDENCE: f32 = 0.75;
+
+const PITCH_CLASS_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Kind of chord recognised by [`detect_chord`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Dominant7,
+    Major7,
+    Minor7,
+}
+
+impl ChordQuality {
+    const ALL: [ChordQuality; 5] = [
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Dominant7,
+        ChordQuality::Major7,
+        ChordQuality::Minor7,
+    ];
+
+    /// Semitones above the root of each note of the chord
+    fn intervals(self) -> &'static [usize] {
+        match self {
+            ChordQuality::Major => &[0, 4, 7],
+            ChordQuality::Minor => &[0, 3, 7],
+            ChordQuality::Dominant7 => &[0, 4, 7, 10],
+            ChordQuality::Major7 => &[0, 4, 7, 11],
+            ChordQuality::Minor7 => &[0, 3, 7, 10],
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            ChordQuality::Major => "maj",
+            ChordQuality::Minor => "min",
+            ChordQuality::Dominant7 => "7",
+            ChordQuality::Major7 => "maj7",
+            ChordQuality::Minor7 => "min7",
+        }
+    }
+}
+
+/// A chord matched by [`detect_chord`], displayed as e.g. `Cmaj`, `Amin` or
+/// `G7`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChordName {
+    pub root: NOTES,
+    pub quality: ChordQuality,
+    /// Cosine similarity between the chroma and the chord's template, from
+    /// 0 to 1
+    pub confidence: f32,
+}
+
+impl fmt::Display for ChordName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            PITCH_CLASS_NAMES[self.root as usize],
+            self.quality.suffix()
+        )
+    }
+}
+
+/// Recognises the chord whose notes best explain `chroma`, a 12-class
+/// energy vector starting on C such as a frame of
+/// [`compute_chromagram`](super::compute_chromagram).
+///
+/// The chroma is compared to the binary templates of the major, minor,
+/// dominant seventh, major seventh and minor seventh chords on every root,
+/// by cosine similarity. The best match is returned with its similarity as
+/// confidence, or `None` when it stays below [`MIN_CHORD_CONFIDENCE`]; on a
+/// tie, triads win over seventh chords.
+pub fn detect_chord(chroma: &[f32; 12]) -> Option<ChordName> {
+    let norm = chroma.iter().map(|e| e * e).sum::<f32>().sqrt();
+    if norm <= 0.0 {
+        return None;
+    }
+
+    let mut best: Option<ChordName> = None;
+    for quality in ChordQuality::ALL {
+        let intervals = quality.intervals();
+        for root in 0..12 {
+            let dot: f32 = intervals
+                .iter()
+                .map(|interval| chroma[(root + interval) % 12])
+                .sum();
+            let confidence = dot / (norm * (intervals.len() as f32).sqrt());
+            if best.is_none_or(|best| confidence > best.confidence) {
+                best = Some(ChordName {
+                    root: NOTES::from(root as u8),
+                    quality,
+                    confidence,
+                });
+            }
+        }
+    }
+    best.filter(|chord| chord.confidence >= MIN_CHORD_CONFIDENCE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::compute_chromagram;
+    use std::f32::consts::PI;
+
+    fn template(classes: &[usize]) -> [f32; 12] {
+        let mut chroma = [0.0; 12];
+        for &class in classes {
+            chroma[class] = 1.0;
+        }
+        chroma
+    }
+
+    #[test]
+    fn test_c_major_template_is_cmaj() {
+        let chord = detect_chord(&template(&[0, 4, 7])).expect("no chord");
+        assert_eq!(chord.to_string(), "Cmaj");
+        assert_eq!(chord.root, NOTES::C);
+        assert!((chord.confidence - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_a_minor_template_is_amin() {
+        let chord = detect_chord(&template(&[9, 0, 4])).expect("no chord");
+        assert_eq!(chord.to_string(), "Amin");
+        assert_eq!(chord.quality, ChordQuality::Minor);
+    }
+
+    #[test]
+    fn test_seventh_chords() {
+        let names = [
+            (template(&[7, 11, 2, 5]), "G7"),
+            (template(&[5, 9, 0, 4]), "Fmaj7"),
+            (template(&[2, 5, 9, 0]), "Dmin7"),
+        ];
+        for (chroma, name) in names {
+            assert_eq!(
+                detect_chord(&chroma).map(|c| c.to_string()),
+                Some(name.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_chord_below_confidence() {
+        assert_eq!(detect_chord(&[0.0; 12]), None);
+        assert_eq!(detect_chord(&[1.0; 12]), None);
+    }
+
+    #[test]
+    fn test_chord_from_audio() {
+        // E minor: E4, G4 and B4
+        let samples: Vec<f32> = (0..44100)
+            .map(|i| {
+                let t = i as f32 / 44100.0;
+                [329.63, 392.00, 493.88]
+                    .iter()
+                    .map(|f| 0.3 * (2.0 * PI * f * t).sin())
+                    .sum()
+            })
+            .collect();
+        let chromagram = compute_chromagram(&samples, 44100);
+        let chord = detect_chord(&chromagram[chromagram.len() / 2]).expect("no chord");
+        assert_eq!(chord.to_string(), "Emin");
+    }
+}
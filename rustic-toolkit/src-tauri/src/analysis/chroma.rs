@@ -0,0 +1,160 @@
+use rustic::core::utils::TONES_FREQ;
+
+use super::spectrum::compute_spectrum_with;
+use super::window::WindowType;
+
+/// Default STFT frame size of the chromagram, fine enough to tell apart the
+/// semitones of the lower octaves
+pub const DEFAULT_CHROMA_FFT_SIZE: usize = 4096;
+/// Default STFT hop size of the chromagram (75% overlap)
+pub const DEFAULT_CHROMA_HOP_SIZE: usize = DEFAULT_CHROMA_FFT_SIZE / 4;
+
+/// Pitch class (0 for C to 11 for B) of the `TONES_FREQ` entry closest to
+/// `frequency` on a log scale, or `None` when it lies more than a quarter
+/// tone outside of the table.
+fn pitch_class(frequency: f32) -> Option<usize> {
+    let quarter_tone = 2f32.powf(1.0 / 24.0);
+    // The table goes from C0 up to B8
+    let (lowest, highest) = (TONES_FREQ[0][0], TONES_FREQ[11][TONES_FREQ[11].len() - 1]);
+    if frequency < lowest / quarter_tone || frequency > highest * quarter_tone {
+        return None;
+    }
+    TONES_FREQ
+        .iter()
+        .enumerate()
+        .flat_map(|(class, octaves)| octaves.iter().map(move |&centre| (class, centre)))
+        .min_by(|(_, a), (_, b)| {
+            (frequency / a)
+                .ln()
+                .abs()
+                .total_cmp(&(frequency / b).ln().abs())
+        })
+        .map(|(class, _)| class)
+}
+
+/// Computes a chromagram of `samples`: for each 4096-sample frame (hopping by
+/// 1024), the spectral energy folded into the 12 pitch classes, C first,
+/// normalised so that the strongest class of the frame is 1.
+pub fn compute_chromagram(samples: &[f32], sample_rate: u32) -> Vec<[f32; 12]> {
+    compute_chromagram_with(
+        samples,
+        sample_rate,
+        DEFAULT_CHROMA_FFT_SIZE,
+        DEFAULT_CHROMA_HOP_SIZE,
+        true,
+    )
+}
+
+/// Computes a chromagram with an explicit STFT configuration.
+///
+/// Each FFT bin's energy goes to the pitch class of the nearest note of
+/// `TONES_FREQ`; bins outside of the table's range are left out. With
+/// `normalize`, every frame is scaled so that its strongest class is 1,
+/// which makes frames of different loudness comparable. Silent frames stay
+/// at zero either way.
+pub fn compute_chromagram_with(
+    samples: &[f32],
+    sample_rate: u32,
+    fft_size: usize,
+    hop_size: usize,
+    normalize: bool,
+) -> Vec<[f32; 12]> {
+    let spectrogram =
+        compute_spectrum_with(samples, sample_rate, fft_size, hop_size, WindowType::Hann);
+    let padded_size = fft_size.max(1).next_power_of_two();
+    let bin_width = sample_rate as f32 / padded_size as f32;
+    let classes: Vec<Option<usize>> = (0..padded_size / 2)
+        .map(|bin| pitch_class(bin as f32 * bin_width))
+        .collect();
+
+    spectrogram
+        .iter()
+        .map(|magnitudes| {
+            let mut chroma = [0.0; 12];
+            for (magnitude, class) in magnitudes.iter().zip(&classes) {
+                if let Some(class) = class {
+                    chroma[*class] += magnitude * magnitude;
+                }
+            }
+            let peak = chroma.iter().copied().fold(0.0, f32::max);
+            if normalize && peak > 0.0 {
+                chroma.iter_mut().for_each(|energy| *energy /= peak);
+            }
+            chroma
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    fn chord(frequencies: &[f32]) -> Vec<f32> {
+        (0..SAMPLE_RATE as usize)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                frequencies
+                    .iter()
+                    .map(|f| 0.3 * (2.0 * PI * f * t).sin())
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Chroma averaged over all frames
+    fn mean_chroma(chromagram: &[[f32; 12]]) -> [f32; 12] {
+        let mut mean = [0.0; 12];
+        for chroma in chromagram {
+            for (total, energy) in mean.iter_mut().zip(chroma) {
+                *total += energy / chromagram.len() as f32;
+            }
+        }
+        mean
+    }
+
+    #[test]
+    fn test_c_major_chord_lights_c_e_and_g() {
+        // C4, E4 and G4
+        let chromagram = compute_chromagram(&chord(&[261.63, 329.63, 392.00]), SAMPLE_RATE);
+        assert!(!chromagram.is_empty());
+        let mean = mean_chroma(&chromagram);
+
+        let chord_energy = mean[0] + mean[4] + mean[7];
+        let total: f32 = mean.iter().sum();
+        assert!(chord_energy > 0.9 * total, "{mean:?}");
+        for class in [0, 4, 7] {
+            assert!(mean[class] > 0.5, "{mean:?}");
+        }
+    }
+
+    #[test]
+    fn test_octaves_fold_into_one_class() {
+        // A2, A3 and A5
+        let chromagram = compute_chromagram(&chord(&[110.0, 220.0, 880.0]), SAMPLE_RATE);
+        let mean = mean_chroma(&chromagram);
+        // Semitones are barely two bins apart around A2, so some of its
+        // energy leaks into the neighbouring classes
+        let total: f32 = mean.iter().sum();
+        assert!(mean[9] > 0.8 * total, "{mean:?}");
+    }
+
+    #[test]
+    fn test_normalization() {
+        let samples = chord(&[261.63]);
+        let normalized = compute_chromagram(&samples, SAMPLE_RATE);
+        let raw = compute_chromagram_with(&samples, SAMPLE_RATE, 4096, 1024, false);
+        assert_eq!(normalized.len(), raw.len());
+        assert!(normalized.iter().all(|chroma| chroma[0] == 1.0));
+        assert!(raw.iter().any(|chroma| chroma[0] > 1.0));
+
+        let silence = compute_chromagram(&vec![0.0; 8192], SAMPLE_RATE);
+        assert!(
+            silence
+                .iter()
+                .all(|chroma| chroma.iter().all(|&e| e == 0.0))
+        );
+    }
+}
@@ -1,9 +1,10 @@
 use log::info;
 use rustfft::{FftPlanner, num_complex::Complex};
 use serde::{Deserialize, Serialize};
-use std::f32::consts::PI;
 use ts_rs::TS;
 
+use super::window::WindowType;
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "../../src/types/")]
 pub struct FrequencyData {
@@ -12,31 +13,45 @@ pub struct FrequencyData {
     pub phase: f32,
 }
 
-/// Computes the Fast Fourier Transform for the given samples
+/// Computes the Fast Fourier Transform for the given samples, using a Hann
+/// window over the whole input
 pub fn compute_fft(samples: &[f32], sample_rate: u32) -> Vec<FrequencyData> {
+    compute_fft_with(samples, sample_rate, samples.len(), WindowType::Hann)
+}
+
+/// Computes the Fast Fourier Transform of the first `fft_size` samples.
+///
+/// `fft_size` sets the frequency resolution (`sample_rate / fft_size` Hz per
+/// bin); it is rounded up to the next power of two and the input is
+/// zero-padded to that length after windowing.
+pub fn compute_fft_with(
+    samples: &[f32],
+    sample_rate: u32,
+    fft_size: usize,
+    window: WindowType,
+) -> Vec<FrequencyData> {
     info!(
-        "Computing FFT for {} samples at {} Hz",
+        "Computing FFT for {} samples at {} Hz (size {}, {:?} window)",
         samples.len(),
-        sample_rate
+        sample_rate,
+        fft_size,
+        window
     );
 
     // We need a power of 2 for the FFT size
-    let mut fft_size = 1;
-    while fft_size < samples.len() {
-        fft_size *= 2;
-    }
-
-    // Prepare input data for FFT
-    let mut fft_input: Vec<Complex<f32>> = Vec::with_capacity(fft_size);
-
-    // Apply Hann window function to reduce spectral leakage
-    for (i, &sample) in samples.iter().take(fft_size).enumerate() {
-        let window_val = 0.5 * (1.0 - (2.0 * PI * i as f32 / (fft_size as f32 - 1.0)).cos());
-        fft_input.push(Complex {
-            re: sample * window_val,
+    let used = samples.len().min(fft_size);
+    let fft_size = fft_size.max(1).next_power_of_two();
+
+    // Apply the window function to reduce spectral leakage
+    let coefficients = window.coefficients(used);
+    let mut fft_input: Vec<Complex<f32>> = samples
+        .iter()
+        .zip(&coefficients)
+        .map(|(&sample, &w)| Complex {
+            re: sample * w,
             im: 0.0,
-        });
-    }
+        })
+        .collect();
 
     // Pad with zeros if needed
     fft_input.resize(fft_size, Complex { re: 0.0, im: 0.0 });
@@ -81,6 +96,7 @@ pub fn compute_fft(samples: &[f32], sample_rate: u32) -> Vec<FrequencyData> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f32::consts::PI;
 
     #[test]
     fn test_compute_fft_sine_wave() {
@@ -107,4 +123,24 @@ mod tests {
         // The peak frequency should be close to 440 Hz
         assert!((peak.frequency - 440.0).abs() < 10.0);
     }
+
+    #[test]
+    fn test_larger_fft_size_gives_finer_bins() {
+        let samples = vec![0.5; 8192];
+        let coarse = compute_fft_with(&samples, 44100, 1024, WindowType::Hann);
+        let fine = compute_fft_with(&samples, 44100, 4096, WindowType::Hann);
+
+        assert_eq!(coarse.len(), 512);
+        assert_eq!(fine.len(), 2048);
+        let bin_width = |bins: &[FrequencyData]| bins[1].frequency - bins[0].frequency;
+        assert!((bin_width(&coarse) - 44100.0 / 1024.0).abs() < 1e-3);
+        assert!((bin_width(&fine) - 44100.0 / 4096.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_non_power_of_two_size_is_padded() {
+        let samples = vec![0.5; 1000];
+        let frequencies = compute_fft_with(&samples, 44100, 1000, WindowType::Rectangular);
+        assert_eq!(frequencies.len(), 512);
+    }
 }
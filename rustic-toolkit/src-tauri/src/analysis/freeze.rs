@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use rustfft::{Fft, FftPlanner, num_complex::Complex};
+use std::f32::consts::PI;
+
+use super::window::{apply_window, hann_window};
+
+/// STFT frame size used by [`SpectralFreeze::new`]
+pub const DEFAULT_FREEZE_FRAME_SIZE: usize = 2048;
+
+/// Captures one STFT frame of a stream and resynthesizes it for as long as
+/// the freeze is held, for sustained ambient textures.
+///
+/// Until [`freeze`](Self::freeze) is enabled the input passes through
+/// unchanged. Freezing captures the magnitudes of the latest frame; every
+/// hop, a frame with those magnitudes and random phases is then
+/// overlap-added to the output. Randomizing the phases avoids the static
+/// buzz a repeated frame would produce.
+pub struct SpectralFreeze {
+    frame_size: usize,
+    hop_size: usize,
+    window: Vec<f32>,
+    /// Overlap-add gain of the squared window at this hop size
+    window_gain: f32,
+    fft: Arc<dyn Fft<f32>>,
+    ifft: Arc<dyn Fft<f32>>,
+    /// Latest `frame_size` input samples; `write` is where the next one goes
+    history: Vec<f32>,
+    write: usize,
+    /// Frozen magnitudes of bins `0..=frame_size / 2`, when frozen
+    magnitudes: Option<Vec<f32>>,
+    /// Latest synthesized spectrum
+    spectrum: Vec<Complex<f32>>,
+    /// Overlap-add accumulator; its first `hop_size` samples are being output
+    overlap: Vec<f32>,
+    position: usize,
+    rng: u32,
+}
+
+impl SpectralFreeze {
+    pub fn new() -> Self {
+        Self::with_frame_size(DEFAULT_FREEZE_FRAME_SIZE)
+    }
+
+    /// A freeze working on frames of `frame_size` samples (a power of two
+    /// is fastest), resynthesized every quarter frame.
+    pub fn with_frame_size(frame_size: usize) -> Self {
+        let frame_size = frame_size.max(4);
+        let hop_size = frame_size / 4;
+        let window = hann_window(frame_size);
+        let window_gain = window.iter().map(|w| w * w).sum::<f32>() / hop_size as f32;
+        let mut planner = FftPlanner::new();
+        Self {
+            frame_size,
+            hop_size,
+            window,
+            window_gain,
+            fft: planner.plan_fft_forward(frame_size),
+            ifft: planner.plan_fft_inverse(frame_size),
+            history: vec![0.0; frame_size],
+            write: 0,
+            magnitudes: None,
+            spectrum: vec![Complex::default(); frame_size],
+            overlap: vec![0.0; frame_size],
+            position: 0,
+            rng: 0x9E37_79B9,
+        }
+    }
+
+    /// Freezes the latest frame of the input, or releases the freeze to let
+    /// the input through again. Freezing while frozen keeps the current frame.
+    pub fn freeze(&mut self, frozen: bool) {
+        if !frozen {
+            self.magnitudes = None;
+            return;
+        }
+        if self.magnitudes.is_some() {
+            return;
+        }
+
+        // Oldest sample first
+        let mut frame: Vec<f32> = self.history[self.write..]
+            .iter()
+            .chain(&self.history[..self.write])
+            .copied()
+            .collect();
+        apply_window(&mut frame, &self.window);
+        let mut spectrum: Vec<Complex<f32>> =
+            frame.iter().map(|&s| Complex { re: s, im: 0.0 }).collect();
+        self.fft.process(&mut spectrum);
+
+        let bins = self.frame_size / 2 + 1;
+        self.magnitudes = Some(spectrum[..bins].iter().map(|c| c.norm()).collect());
+        self.overlap.fill(0.0);
+        self.position = 0;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.magnitudes.is_some()
+    }
+
+    /// Magnitudes of the frozen frame, for bins `0..=frame_size / 2`.
+    pub fn frozen_magnitudes(&self) -> Option<&[f32]> {
+        self.magnitudes.as_deref()
+    }
+
+    /// Latest resynthesized spectrum, over all `frame_size` bins.
+    pub fn last_spectrum(&self) -> &[Complex<f32>] {
+        &self.spectrum
+    }
+
+    /// Processes a block of samples.
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        let mut output = Vec::with_capacity(input.len());
+        for &sample in input {
+            self.history[self.write] = sample;
+            self.write = (self.write + 1) % self.frame_size;
+
+            if self.magnitudes.is_none() {
+                output.push(sample);
+                continue;
+            }
+
+            if self.position == 0 {
+                self.synthesize_frame();
+            }
+            output.push(self.overlap[self.position]);
+            self.position += 1;
+            if self.position == self.hop_size {
+                self.overlap.copy_within(self.hop_size.., 0);
+                let len = self.overlap.len();
+                self.overlap[len - self.hop_size..].fill(0.0);
+                self.position = 0;
+            }
+        }
+        output
+    }
+
+    /// Overlap-adds a frame with the frozen magnitudes and random phases.
+    fn synthesize_frame(&mut self) {
+        let Some(magnitudes) = &self.magnitudes else {
+            return;
+        };
+        let bins = magnitudes.len();
+        for (k, &magnitude) in magnitudes.iter().enumerate() {
+            // DC and Nyquist stay real for the resynthesis to be real
+            let phase = if k == 0 || k == bins - 1 {
+                0.0
+            } else {
+                self.rng = xorshift(self.rng);
+                2.0 * PI * (self.rng as f32 / u32::MAX as f32)
+            };
+            self.spectrum[k] = Complex::from_polar(magnitude, phase);
+        }
+        for k in bins..self.frame_size {
+            self.spectrum[k] = self.spectrum[self.frame_size - k].conj();
+        }
+
+        let mut frame = self.spectrum.clone();
+        self.ifft.process(&mut frame);
+        let scale = 1.0 / (self.frame_size as f32 * self.window_gain);
+        for ((out, value), w) in self.overlap.iter_mut().zip(&frame).zip(&self.window) {
+            *out += value.re * w * scale;
+        }
+    }
+}
+
+impl Default for SpectralFreeze {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn xorshift(mut state: u32) -> u32 {
+    state ^= state << 13;
+    state ^= state >> 17;
+    state ^= state << 5;
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{goertzel, sine};
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_passes_through_until_frozen() {
+        let mut freeze = SpectralFreeze::new();
+        let input = sine(440.0, SAMPLE_RATE, 0.1);
+        assert_eq!(freeze.process(&input), input);
+        assert!(!freeze.is_frozen());
+    }
+
+    #[test]
+    fn test_frozen_magnitudes_stay_while_phases_vary() {
+        let mut freeze = SpectralFreeze::new();
+        freeze.process(&sine(440.0, SAMPLE_RATE, 0.1));
+        freeze.freeze(true);
+        let magnitudes = freeze.frozen_magnitudes().unwrap().to_vec();
+
+        let mut previous: Option<Vec<Complex<f32>>> = None;
+        for _ in 0..8 {
+            // The input no longer matters
+            freeze.process(&vec![0.0; DEFAULT_FREEZE_FRAME_SIZE / 4]);
+            let spectrum = freeze.last_spectrum().to_vec();
+            for (bin, magnitude) in spectrum.iter().zip(&magnitudes) {
+                assert!((bin.norm() - magnitude).abs() < 1e-3 * magnitude.max(1.0));
+            }
+            if let Some(previous) = previous {
+                let peak = (440.0 * DEFAULT_FREEZE_FRAME_SIZE as f32 / SAMPLE_RATE as f32) as usize;
+                assert!((spectrum[peak].arg() - previous[peak].arg()).abs() > 1e-3);
+            }
+            previous = Some(spectrum);
+        }
+    }
+
+    #[test]
+    fn test_frozen_output_sustains_the_captured_tone() {
+        let mut freeze = SpectralFreeze::new();
+        freeze.process(&sine(440.0, SAMPLE_RATE, 0.2));
+        freeze.freeze(true);
+
+        let output = freeze.process(&vec![0.0; SAMPLE_RATE as usize]);
+        // Random phases spread the tone over the bins around its frequency
+        let band = |block: &[f32], center: f32| {
+            (-5..=5)
+                .map(|k| goertzel(block, center + 10.0 * k as f32, SAMPLE_RATE))
+                .fold(0.0_f32, f32::max)
+        };
+        // Skip the first frame, still fading in
+        for block in output[DEFAULT_FREEZE_FRAME_SIZE..].chunks(4096) {
+            let level = rms(block);
+            assert!(level > 0.2 && level < 1.5, "rms {level}");
+            let tone = band(block, 440.0);
+            let other = band(block, 1000.0);
+            assert!(tone > 10.0 * other, "440 Hz: {tone}, 1 kHz: {other}");
+        }
+
+        freeze.freeze(false);
+        assert_eq!(freeze.process(&[0.5, 0.25]), vec![0.5, 0.25]);
+    }
+}
@@ -0,0 +1,99 @@
+use rustic::core::utils::{NOTES, TONES_FREQ};
+use std::f32::consts::PI;
+
+/// A candidate note matched by [`strongest_note`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoteMatch {
+    pub note: NOTES,
+    pub octave: usize,
+    pub frequency: f32,
+    pub magnitude: f32,
+}
+
+/// Returns the amplitude of `samples` at `target_freq` using the Goertzel algorithm.
+///
+/// This evaluates a single DFT bin in O(n) without computing a full FFT, so it
+/// is much cheaper when only a handful of frequencies are of interest. The
+/// result is normalised so that a full-scale sine at `target_freq` yields ~1.0.
+pub fn goertzel(samples: &[f32], target_freq: f32, sample_rate: u32) -> f32 {
+    if samples.is_empty() || sample_rate == 0 {
+        return 0.0;
+    }
+
+    let omega = 2.0 * PI * target_freq / sample_rate as f32;
+    let coeff = 2.0 * omega.cos();
+
+    let (mut s1, mut s2) = (0.0f32, 0.0f32);
+    for &sample in samples {
+        let s0 = sample + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+
+    let power = (s1 * s1 + s2 * s2 - coeff * s1 * s2).max(0.0);
+    2.0 * power.sqrt() / samples.len() as f32
+}
+
+/// Scans the notes of `TONES_FREQ` within `octaves` and returns the one with
+/// the strongest Goertzel response, if any has a non-zero magnitude.
+pub fn strongest_note(
+    samples: &[f32],
+    sample_rate: u32,
+    octaves: std::ops::Range<usize>,
+) -> Option<NoteMatch> {
+    let nyquist = sample_rate as f32 / 2.0;
+    TONES_FREQ
+        .iter()
+        .enumerate()
+        .flat_map(|(note, frequencies)| {
+            frequencies
+                .iter()
+                .enumerate()
+                .filter(|(octave, _)| octaves.contains(octave))
+                .map(move |(octave, &frequency)| (note, octave, frequency))
+        })
+        .filter(|&(_, _, frequency)| frequency < nyquist)
+        .map(|(note, octave, frequency)| NoteMatch {
+            note: NOTES::from(note as u8),
+            octave,
+            frequency,
+            magnitude: goertzel(samples, frequency, sample_rate),
+        })
+        .filter(|m| m.magnitude > 0.0)
+        .max_by(|a, b| a.magnitude.total_cmp(&b.magnitude))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::sine;
+
+    #[test]
+    fn test_goertzel_peaks_on_target() {
+        let samples = sine(440.0, 44100, 0.2);
+        let on_target = goertzel(&samples, 440.0, 44100);
+        assert!((on_target - 1.0).abs() < 0.05, "magnitude {on_target}");
+    }
+
+    #[test]
+    fn test_goertzel_near_zero_off_target() {
+        let samples = sine(440.0, 44100, 0.2);
+        for frequency in [220.0, 660.0, 1000.0] {
+            let off_target = goertzel(&samples, frequency, 44100);
+            assert!(off_target < 0.02, "{frequency} Hz: magnitude {off_target}");
+        }
+    }
+
+    #[test]
+    fn test_strongest_note() {
+        let samples = sine(329.63, 44100, 0.2);
+        let found = strongest_note(&samples, 44100, 2..6).unwrap();
+        assert_eq!(found.note, NOTES::E);
+        assert_eq!(found.octave, 4);
+    }
+
+    #[test]
+    fn test_strongest_note_silence() {
+        assert_eq!(strongest_note(&vec![0.0; 4410], 44100, 2..6), None);
+    }
+}
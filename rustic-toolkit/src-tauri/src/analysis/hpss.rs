@@ -0,0 +1,189 @@
+use log::info;
+use rustfft::{FftPlanner, num_complex::Complex};
+
+use super::onset::median;
+use super::window::hann_window;
+
+/// STFT frame size of the separation
+const FRAME_SIZE: usize = 1024;
+/// STFT hop size (75% overlap)
+const HOP_SIZE: usize = FRAME_SIZE / 4;
+/// Half-width of the median filters, in frames along time and in bins
+/// along frequency
+const MEDIAN_RADIUS: usize = 8;
+
+/// Splits `samples` into their harmonic and percussive parts, returned as
+/// `(harmonic, percussive)`.
+///
+/// In a magnitude spectrogram, sustained tones are horizontal lines and
+/// transients vertical ones. Median filtering each bin along time keeps the
+/// former, and each frame along frequency the latter. The two estimates give
+/// soft masks, `H² / (H² + P²)` and its complement, which are applied to the
+/// STFT before resynthesis, so the two parts add back up to the input.
+pub fn hpss(samples: &[f32], sample_rate: u32) -> (Vec<f32>, Vec<f32>) {
+    info!(
+        "Separating harmonic and percussive parts of {} samples at {} Hz",
+        samples.len(),
+        sample_rate
+    );
+
+    if samples.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let window = hann_window(FRAME_SIZE);
+    let bins = FRAME_SIZE / 2 + 1;
+
+    // Zero-pad a full frame on each side so the edges are analysed by complete frames
+    let mut padded = vec![0.0; FRAME_SIZE];
+    padded.extend_from_slice(samples);
+    padded.resize(padded.len() + FRAME_SIZE, 0.0);
+    let num_frames = (padded.len() - FRAME_SIZE) / HOP_SIZE + 1;
+
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(FRAME_SIZE);
+    let ifft = planner.plan_fft_inverse(FRAME_SIZE);
+
+    let spectra: Vec<Vec<Complex<f32>>> = (0..num_frames)
+        .map(|frame_index| {
+            let start = frame_index * HOP_SIZE;
+            let mut spectrum: Vec<Complex<f32>> = padded[start..start + FRAME_SIZE]
+                .iter()
+                .zip(&window)
+                .map(|(&s, &w)| Complex { re: s * w, im: 0.0 })
+                .collect();
+            fft.process(&mut spectrum);
+            spectrum
+        })
+        .collect();
+    let magnitudes: Vec<Vec<f32>> = spectra
+        .iter()
+        .map(|spectrum| spectrum[..bins].iter().map(|c| c.norm()).collect())
+        .collect();
+
+    // Harmonic estimate: median along time; percussive: median along frequency
+    let mut window_values = Vec::with_capacity(2 * MEDIAN_RADIUS + 1);
+    let mut harmonic_mask = vec![vec![0.0; bins]; num_frames];
+    for (n, masks) in harmonic_mask.iter_mut().enumerate() {
+        let frames = n.saturating_sub(MEDIAN_RADIUS)..(n + MEDIAN_RADIUS + 1).min(num_frames);
+        for (k, mask) in masks.iter_mut().enumerate() {
+            window_values.clear();
+            window_values.extend(magnitudes[frames.clone()].iter().map(|frame| frame[k]));
+            let harmonic = median(&mut window_values);
+
+            window_values.clear();
+            let nearby = k.saturating_sub(MEDIAN_RADIUS)..(k + MEDIAN_RADIUS + 1).min(bins);
+            window_values.extend_from_slice(&magnitudes[n][nearby]);
+            let percussive = median(&mut window_values);
+
+            let (h2, p2) = (harmonic * harmonic, percussive * percussive);
+            *mask = if h2 + p2 > 0.0 { h2 / (h2 + p2) } else { 0.5 };
+        }
+    }
+
+    // Masked resynthesis by windowed overlap-add
+    let mut harmonic = vec![0.0f32; padded.len()];
+    let mut percussive = vec![0.0f32; padded.len()];
+    let mut window_sum = vec![0.0f32; padded.len()];
+    for (n, (spectrum, masks)) in spectra.iter().zip(&harmonic_mask).enumerate() {
+        let mut harmonic_spectrum = spectrum.clone();
+        for (k, &mask) in masks.iter().enumerate() {
+            harmonic_spectrum[k] *= mask;
+            // Mirrored bins keep the spectrum conjugate-symmetric
+            if k > 0 && k < FRAME_SIZE - k {
+                harmonic_spectrum[FRAME_SIZE - k] *= mask;
+            }
+        }
+        let mut percussive_spectrum: Vec<Complex<f32>> = spectrum
+            .iter()
+            .zip(&harmonic_spectrum)
+            .map(|(full, harmonic)| full - harmonic)
+            .collect();
+        ifft.process(&mut harmonic_spectrum);
+        ifft.process(&mut percussive_spectrum);
+
+        let start = n * HOP_SIZE;
+        for (i, &w) in window.iter().enumerate() {
+            harmonic[start + i] += harmonic_spectrum[i].re / FRAME_SIZE as f32 * w;
+            percussive[start + i] += percussive_spectrum[i].re / FRAME_SIZE as f32 * w;
+            window_sum[start + i] += w * w;
+        }
+    }
+
+    let normalise = |signal: Vec<f32>| -> Vec<f32> {
+        signal
+            .iter()
+            .zip(&window_sum)
+            .skip(FRAME_SIZE)
+            .take(samples.len())
+            .map(|(&s, &weight)| if weight > 1e-3 { s / weight } else { s })
+            .collect()
+    };
+    (normalise(harmonic), normalise(percussive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: u32 = 44100;
+    const CLICK: usize = 22050;
+
+    fn tone(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| 0.3 * (2.0 * PI * 440.0 * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect()
+    }
+
+    fn energy(samples: &[f32]) -> f32 {
+        samples.iter().map(|s| s * s).sum()
+    }
+
+    #[test]
+    fn test_tone_and_click_are_separated() {
+        let tone = tone(SAMPLE_RATE as usize);
+        let mut mix = tone.clone();
+        mix[CLICK] += 1.0;
+
+        let (harmonic, percussive) = hpss(&mix, SAMPLE_RATE);
+        assert_eq!(harmonic.len(), mix.len());
+        assert_eq!(percussive.len(), mix.len());
+
+        // Away from the click, the tone is in the harmonic part only
+        let steady = 4000..16000;
+        let tone_energy = energy(&tone[steady.clone()]);
+        assert!(energy(&harmonic[steady.clone()]) > 0.9 * tone_energy);
+        assert!(energy(&percussive[steady]) < 0.01 * tone_energy);
+
+        // Around the click, the percussive part holds the click while the
+        // harmonic part stays close to the tone
+        let around = CLICK - 512..CLICK + 512;
+        let click_in_harmonic: Vec<f32> = harmonic[around.clone()]
+            .iter()
+            .zip(&tone[around.clone()])
+            .map(|(h, t)| h - t)
+            .collect();
+        let percussive_peak = percussive[around.clone()]
+            .iter()
+            .map(|s| s.abs())
+            .fold(0.0, f32::max);
+        assert!(percussive_peak > 0.5, "percussive peak {percussive_peak}");
+        assert!(energy(&percussive[around]) > 4.0 * energy(&click_in_harmonic));
+    }
+
+    #[test]
+    fn test_parts_add_back_up_to_the_input() {
+        let mut mix = tone(8192);
+        mix[4000] += 0.8;
+        let (harmonic, percussive) = hpss(&mix, SAMPLE_RATE);
+        for ((h, p), s) in harmonic.iter().zip(&percussive).zip(&mix) {
+            assert!((h + p - s).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(hpss(&[], SAMPLE_RATE), (Vec::new(), Vec::new()));
+    }
+}
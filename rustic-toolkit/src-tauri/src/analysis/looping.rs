@@ -0,0 +1,119 @@
+/// Samples compared on each side of a loop join
+const MATCH_RADIUS: usize = 64;
+/// Zero crossings kept as loop start or end candidates, evenly spread
+const MAX_CANDIDATES: usize = 128;
+
+/// Indices of the samples right after each rising zero crossing.
+fn rising_zero_crossings(samples: &[f32]) -> Vec<usize> {
+    samples
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] < 0.0 && pair[1] >= 0.0)
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+/// At most `max` of `items`, evenly spread.
+fn thin_out(items: Vec<usize>, max: usize) -> Vec<usize> {
+    let step = items.len().div_ceil(max).max(1);
+    items.into_iter().step_by(step).collect()
+}
+
+/// How much the waveform around `end` differs from the one around `start`,
+/// relative to its level: 0 when the loop joins without a trace.
+fn join_mismatch(samples: &[f32], start: usize, end: usize) -> f32 {
+    let before = MATCH_RADIUS.min(start);
+    let after = MATCH_RADIUS.min(samples.len() - end);
+    let (mut error, mut energy) = (0.0, 0.0);
+    for offset in 0..before + after {
+        let (a, b) = (
+            samples[start - before + offset],
+            samples[end - before + offset],
+        );
+        error += (a - b) * (a - b);
+        energy += a * a + b * b;
+    }
+    if energy > 0.0 { error / energy } else { 0.0 }
+}
+
+/// Finds loop points for a click-free sustain loop in `samples`.
+///
+/// Returns `(start, end)` such that playing `samples[start..end]` over and
+/// over is seamless: both points sit on rising zero crossings, and the
+/// waveform around `end` matches the one around `start` as closely as
+/// possible, so that the spectrum does not jump at the join either. The loop
+/// is at least `min_len` samples long; among equally good candidates the
+/// longest wins. Returns `None` when the signal has fewer than two zero
+/// crossings that far apart.
+pub fn find_loop_points(samples: &[f32], min_len: usize) -> Option<(usize, usize)> {
+    let min_len = min_len.max(1);
+    let crossings = thin_out(rising_zero_crossings(samples), MAX_CANDIDATES);
+
+    let mut best: Option<(f32, usize, usize)> = None;
+    for (i, &start) in crossings.iter().enumerate() {
+        for &end in &crossings[i + 1..] {
+            if end - start < min_len {
+                continue;
+            }
+            let mismatch = join_mismatch(samples, start, end);
+            let better = best.is_none_or(|(best_mismatch, best_start, best_end)| {
+                mismatch < best_mismatch
+                    || (mismatch == best_mismatch && end - start > best_end - best_start)
+            });
+            if better {
+                best = Some((mismatch, start, end));
+            }
+        }
+    }
+    best.map(|(_, start, end)| (start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const PERIOD: usize = 100;
+
+    /// A tone with a harmonic, exactly periodic over `PERIOD` samples
+    fn periodic(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| {
+                let phase = 2.0 * PI * (i % PERIOD) as f32 / PERIOD as f32;
+                0.6 * phase.sin() + 0.3 * (3.0 * phase + 0.4).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_loop_is_a_whole_number_of_periods() {
+        let samples = periodic(4000);
+        let (start, end) = find_loop_points(&samples, 1000).expect("no loop found");
+        assert!(end - start >= 1000);
+        assert_eq!((end - start) % PERIOD, 0, "loop {start}..{end}");
+    }
+
+    #[test]
+    fn test_loop_join_is_continuous() {
+        let samples = periodic(4000);
+        let (start, end) = find_loop_points(&samples, 500).expect("no loop found");
+
+        // The step across the join is no larger than anywhere in the signal
+        let largest_step = samples
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).abs())
+            .fold(0.0, f32::max);
+        let join_step = (samples[start] - samples[end - 1]).abs();
+        assert!(
+            join_step <= largest_step + 1e-4,
+            "{join_step} across the join, {largest_step} at most elsewhere"
+        );
+        assert!((samples[start] - samples[end]).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_no_loop_without_crossings() {
+        assert_eq!(find_loop_points(&[0.5; 1000], 10), None);
+        assert_eq!(find_loop_points(&periodic(400), 1000), None);
+    }
+}
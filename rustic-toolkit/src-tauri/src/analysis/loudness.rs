@@ -0,0 +1,315 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use rustic::core::audio::true_peak;
+
+/// Loudness reported for silence, or signals too short to hold one gating block
+pub const SILENCE_LUFS: f32 = -70.0;
+
+/// Blocks quieter than this are left out of the integrated loudness
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+/// Blocks this much quieter than the ungated loudness are left out as well
+const RELATIVE_GATE_LU: f64 = -10.0;
+/// Gating blocks of 400 ms, starting every 100 ms
+const BLOCK_SECONDS: f64 = 0.4;
+const STEP_SECONDS: f64 = 0.1;
+
+/// True peak the normalisation limiter keeps the signal under, in dBTP
+pub const TRUE_PEAK_CEILING: f32 = -1.0;
+/// Look-ahead of the limiter: gain reduction fades in and out over this time
+const LIMITER_SECONDS: f64 = 0.005;
+/// Attempts of the limiter before giving up on a stubborn inter-sample peak
+const LIMITER_PASSES: usize = 4;
+/// Measure-and-scale rounds of the normalisation, and the error it settles for
+const NORMALIZE_PASSES: usize = 4;
+const NORMALIZE_TOLERANCE: f32 = 0.05;
+
+/// Biquad in direct form I, coefficients normalised by `a0`.
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&self, samples: impl IntoIterator<Item = f64>) -> Vec<f64> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        samples
+            .into_iter()
+            .map(|x| {
+                let y = self.b[0] * x + self.b[1] * x1 + self.b[2] * x2
+                    - self.a[0] * y1
+                    - self.a[1] * y2;
+                (x2, x1, y2, y1) = (x1, x, y1, y);
+                y
+            })
+            .collect()
+    }
+}
+
+/// The two stages of the BS.1770 K-weighting filter, designed for
+/// `sample_rate` so that they match the reference coefficients at 48 kHz:
+/// a high shelf modelling the head, then a high-pass (RLB weighting).
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let sample_rate = sample_rate as f64;
+
+    let (f0, gain_db, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+    let k = (PI * f0 / sample_rate).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    let (f0, q) = (38.13547087602444, 0.5003270373238773);
+    let k = (PI * f0 / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let highpass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    [shelf, highpass]
+}
+
+fn block_loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+/// Integrated loudness of a mono signal in LUFS, per ITU-R BS.1770.
+///
+/// See [`measure_lufs_channels`] for the details of the measurement.
+pub fn measure_lufs(samples: &[f32], sample_rate: u32) -> f32 {
+    measure_lufs_channels(&[samples], sample_rate)
+}
+
+/// Integrated loudness of a multichannel signal in LUFS, per ITU-R BS.1770.
+///
+/// Each channel is K-weighted, and its mean square is measured over 400 ms
+/// blocks overlapping by 75%. Blocks below -70 LUFS, then blocks more than
+/// 10 LU below the loudness of the remaining ones, are discarded, and the
+/// integrated loudness is that of the blocks left. Channels are in the
+/// BS.1770 order (L, R, C, LFE, Ls, Rs): the surround channels weigh 1.41
+/// and the LFE is ignored. Silent or shorter than 400 ms signals measure
+/// [`SILENCE_LUFS`].
+pub fn measure_lufs_channels(channels: &[&[f32]], sample_rate: u32) -> f32 {
+    let block = (BLOCK_SECONDS * sample_rate as f64).round() as usize;
+    let step = (STEP_SECONDS * sample_rate as f64).round() as usize;
+    let len = channels.iter().map(|c| c.len()).min().unwrap_or(0);
+    if sample_rate == 0 || block == 0 || len < block {
+        return SILENCE_LUFS;
+    }
+
+    // Weighted mean square of every gating block, summed over the channels
+    let block_count = (len - block) / step + 1;
+    let mut powers = vec![0.0_f64; block_count];
+    for (index, samples) in channels.iter().enumerate() {
+        let weight = match index {
+            3 => continue,
+            4 | 5 => 1.41,
+            _ => 1.0,
+        };
+        let [shelf, highpass] = k_weighting(sample_rate);
+        let weighted = highpass.process(shelf.process(samples[..len].iter().map(|&s| s as f64)));
+
+        // Prefix sums of the squares give each block's energy in O(1)
+        let mut energy = Vec::with_capacity(len + 1);
+        energy.push(0.0);
+        for sample in &weighted {
+            energy.push(energy.last().copied().unwrap_or(0.0) + sample * sample);
+        }
+        for (j, power) in powers.iter_mut().enumerate() {
+            let start = j * step;
+            *power += weight * (energy[start + block] - energy[start]) / block as f64;
+        }
+    }
+
+    let gated_mean = |threshold: f64| {
+        let kept: Vec<f64> = powers
+            .iter()
+            .copied()
+            .filter(|&power| power > 0.0 && block_loudness(power) > threshold)
+            .collect();
+        (!kept.is_empty()).then(|| kept.iter().sum::<f64>() / kept.len() as f64)
+    };
+
+    let Some(ungated) = gated_mean(ABSOLUTE_GATE_LUFS) else {
+        return SILENCE_LUFS;
+    };
+    let relative_gate = block_loudness(ungated) + RELATIVE_GATE_LU;
+    let threshold = relative_gate.max(ABSOLUTE_GATE_LUFS);
+    gated_mean(threshold)
+        .map(|power| block_loudness(power) as f32)
+        .unwrap_or(SILENCE_LUFS)
+}
+
+/// Scales `samples` so that their integrated loudness becomes `target_lufs`.
+///
+/// The gain is applied uniformly, then peaks that would go over
+/// [`TRUE_PEAK_CEILING`] are brought down by a look-ahead limiter, which
+/// eases the gain in and out over 5 ms around them. As limiting lowers the
+/// loudness, the signal is measured and scaled again, a few times at most,
+/// so transients carrying much of the energy can leave it a little short of
+/// the target. Silent or too short signals (see [`measure_lufs`]) are left
+/// untouched.
+pub fn normalize_to_lufs(samples: &mut [f32], sample_rate: u32, target_lufs: f32) {
+    for _ in 0..NORMALIZE_PASSES {
+        let loudness = measure_lufs(samples, sample_rate);
+        if loudness <= SILENCE_LUFS || (loudness - target_lufs).abs() < NORMALIZE_TOLERANCE {
+            return;
+        }
+        let gain = 10f32.powf((target_lufs - loudness) / 20.0);
+        samples.iter_mut().for_each(|sample| *sample *= gain);
+        limit_true_peak(samples, sample_rate);
+    }
+}
+
+/// Brings the true peak of `samples` under [`TRUE_PEAK_CEILING`].
+///
+/// The limiter works on sample peaks: when an inter-sample peak still
+/// overshoots, it aims lower by the overshoot and tries again.
+fn limit_true_peak(samples: &mut [f32], sample_rate: u32) {
+    let mut ceiling = TRUE_PEAK_CEILING;
+    for _ in 0..LIMITER_PASSES {
+        let overshoot = true_peak(samples, sample_rate) - TRUE_PEAK_CEILING;
+        if overshoot <= 0.0 {
+            return;
+        }
+        ceiling -= overshoot;
+        limit(samples, sample_rate, 10f32.powf(ceiling / 20.0));
+    }
+}
+
+/// Keeps the sample peaks of `samples` at or below `ceiling`, smoothing the
+/// gain reduction over [`LIMITER_SECONDS`] on each side of a peak.
+fn limit(samples: &mut [f32], sample_rate: u32, ceiling: f32) {
+    let radius = ((LIMITER_SECONDS * sample_rate as f64).round() as usize).max(1);
+    let len = samples.len();
+    let needed: Vec<f32> = samples
+        .iter()
+        .map(|sample| (ceiling / sample.abs()).min(1.0))
+        .collect();
+
+    // Lowest needed gain within `radius` of each sample, with a monotonic
+    // queue of candidate indices
+    let mut minimum = Vec::with_capacity(len);
+    let mut queue: VecDeque<usize> = VecDeque::new();
+    for i in 0..len + radius {
+        if i < len {
+            while queue.back().is_some_and(|&j| needed[j] >= needed[i]) {
+                queue.pop_back();
+            }
+            queue.push_back(i);
+        }
+        if i >= radius {
+            let centre = i - radius;
+            while queue.front().is_some_and(|&j| j + radius < centre) {
+                queue.pop_front();
+            }
+            minimum.push(queue.front().map_or(1.0, |&j| needed[j]));
+        }
+    }
+
+    // Averaging over the same radius smooths the gain without raising it
+    // above what any sample needs: every value averaged for a sample was
+    // already the minimum of a window containing it
+    let mut sums = Vec::with_capacity(len + 1);
+    sums.push(0.0_f64);
+    for &gain in &minimum {
+        sums.push(sums.last().copied().unwrap_or(0.0) + gain as f64);
+    }
+    for (i, sample) in samples.iter_mut().enumerate() {
+        let (start, end) = (i.saturating_sub(radius), (i + radius + 1).min(len));
+        let gain = (sums[end] - sums[start]) / (end - start) as f64;
+        *sample *= gain as f32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::sine;
+
+    fn scaled(samples: Vec<f32>, amplitude: f32) -> Vec<f32> {
+        samples.into_iter().map(|s| amplitude * s).collect()
+    }
+
+    #[test]
+    fn test_calibrated_stereo_sine_is_minus_23_lufs() {
+        // EBU Tech 3341, case 1: a 1 kHz sine at -23 dBFS on both channels
+        let amplitude = 10f32.powf(-23.0 / 20.0);
+        for sample_rate in [44100, 48000] {
+            let channel = scaled(sine(1000.0, sample_rate, 20.0), amplitude);
+            let lufs = measure_lufs_channels(&[&channel, &channel], sample_rate);
+            assert!((lufs + 23.0).abs() < 0.1, "{sample_rate} Hz: {lufs} LUFS");
+        }
+    }
+
+    #[test]
+    fn test_mono_measures_one_channel() {
+        // Half the power of the stereo case
+        let channel = scaled(sine(1000.0, 48000, 20.0), 10f32.powf(-23.0 / 20.0));
+        let lufs = measure_lufs(&channel, 48000);
+        assert!((lufs + 26.01).abs() < 0.1, "{lufs} LUFS");
+    }
+
+    #[test]
+    fn test_gating_ignores_silence() {
+        // The pauses would lower an ungated measure by 4.8 LU; only the
+        // blocks straddling the edges of the tone still count
+        let tone = scaled(sine(1000.0, 48000, 10.0), 10f32.powf(-23.0 / 20.0));
+        let mut with_pauses = vec![0.0; 48000 * 10];
+        with_pauses.extend(&tone);
+        with_pauses.extend(vec![0.0; 48000 * 10]);
+        let lufs = measure_lufs(&with_pauses, 48000);
+        assert!(
+            (lufs - measure_lufs(&tone, 48000)).abs() < 0.25,
+            "{lufs} LUFS"
+        );
+    }
+
+    #[test]
+    fn test_normalize_reaches_target() {
+        let mut samples = scaled(sine(1000.0, 48000, 10.0), 0.05);
+        normalize_to_lufs(&mut samples, 48000, -16.0);
+        let lufs = measure_lufs(&samples, 48000);
+        assert!((lufs + 16.0).abs() < 0.1, "{lufs} LUFS");
+    }
+
+    #[test]
+    fn test_normalize_limits_true_peaks() {
+        // A quiet tone with sharp clicks: matching -16 LUFS pushes the
+        // clicks far above full scale, so the limiter has to catch them
+        let mut samples = scaled(sine(440.0, 48000, 10.0), 0.02);
+        for (i, sample) in samples.iter_mut().enumerate() {
+            if i % 12000 == 5 {
+                *sample = 0.8;
+            }
+        }
+        normalize_to_lufs(&mut samples, 48000, -16.0);
+
+        let lufs = measure_lufs(&samples, 48000);
+        assert!((lufs + 16.0).abs() < 0.5, "{lufs} LUFS");
+        let peak = true_peak(&samples, 48000);
+        assert!(peak <= 0.0, "{peak} dBTP");
+    }
+
+    #[test]
+    fn test_normalize_leaves_silence() {
+        let mut samples = vec![0.0; 48000];
+        normalize_to_lufs(&mut samples, 48000, -16.0);
+        assert!(samples.iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_silence_and_short_signals() {
+        assert_eq!(measure_lufs(&[], 48000), SILENCE_LUFS);
+        assert_eq!(measure_lufs(&vec![0.0; 48000], 48000), SILENCE_LUFS);
+        assert_eq!(measure_lufs(&vec![0.5; 100], 48000), SILENCE_LUFS);
+    }
+}
@@ -0,0 +1,135 @@
+use log::info;
+
+use super::spectrum::{DEFAULT_FFT_SIZE, DEFAULT_HOP_SIZE, compute_spectrum_with};
+use super::window::WindowType;
+
+/// Converts a frequency in Hz to the (HTK) mel scale
+pub fn hz_to_mel(frequency: f32) -> f32 {
+    2595.0 * (1.0 + frequency / 700.0).log10()
+}
+
+/// Converts a mel value back to a frequency in Hz
+pub fn mel_to_hz(mel: f32) -> f32 {
+    700.0 * (10f32.powf(mel / 2595.0) - 1.0)
+}
+
+/// Returns the `n_mels + 2` band edges in Hz, evenly spaced on the mel scale
+/// between 0 Hz and Nyquist. Band `i` spans `edges[i]..edges[i + 2]` and
+/// peaks at `edges[i + 1]`.
+pub fn mel_band_edges(n_mels: usize, sample_rate: u32) -> Vec<f32> {
+    let max_mel = hz_to_mel(sample_rate as f32 / 2.0);
+    (0..n_mels + 2)
+        .map(|i| mel_to_hz(max_mel * i as f32 / (n_mels + 1) as f32))
+        .collect()
+}
+
+/// Builds a triangular mel filterbank of `n_mels` filters over the
+/// `fft_size / 2` magnitude bins of an FFT.
+pub fn mel_filterbank(n_mels: usize, fft_size: usize, sample_rate: u32) -> Vec<Vec<f32>> {
+    let edges = mel_band_edges(n_mels, sample_rate);
+    let bin_width = sample_rate as f32 / fft_size as f32;
+
+    (0..n_mels)
+        .map(|band| {
+            let (low, centre, high) = (edges[band], edges[band + 1], edges[band + 2]);
+            (0..fft_size / 2)
+                .map(|bin| {
+                    let frequency = bin as f32 * bin_width;
+                    if frequency <= low || frequency >= high {
+                        0.0
+                    } else if frequency <= centre {
+                        (frequency - low) / (centre - low)
+                    } else {
+                        (high - frequency) / (high - centre)
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Computes a mel spectrogram: the power spectrum of each STFT frame
+/// (1024-sample Hann frames, 75% overlap) projected onto `n_mels` triangular
+/// mel bands. Returns one `n_mels`-long energy vector per frame.
+pub fn compute_mel_spectrogram(samples: &[f32], sample_rate: u32, n_mels: usize) -> Vec<Vec<f32>> {
+    info!(
+        "Computing {}-band mel spectrogram for {} samples at {} Hz",
+        n_mels,
+        samples.len(),
+        sample_rate
+    );
+
+    let spectrogram = compute_spectrum_with(
+        samples,
+        sample_rate,
+        DEFAULT_FFT_SIZE,
+        DEFAULT_HOP_SIZE,
+        WindowType::Hann,
+    );
+    let filterbank = mel_filterbank(n_mels, DEFAULT_FFT_SIZE, sample_rate);
+
+    spectrogram
+        .iter()
+        .map(|frame| {
+            filterbank
+                .iter()
+                .map(|filter| {
+                    filter
+                        .iter()
+                        .zip(frame)
+                        .map(|(weight, magnitude)| weight * magnitude * magnitude)
+                        .sum()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_mel_round_trip() {
+        for frequency in [0.0, 100.0, 1000.0, 8000.0] {
+            assert!((mel_to_hz(hz_to_mel(frequency)) - frequency).abs() < 0.5);
+        }
+        assert!((hz_to_mel(1000.0) - 1000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_mel_band_count() {
+        let samples = vec![0.1; 8192];
+        let mel = compute_mel_spectrogram(&samples, 44100, 40);
+        assert!(!mel.is_empty());
+        assert!(mel.iter().all(|frame| frame.len() == 40));
+    }
+
+    #[test]
+    fn test_mel_energy_concentrates_on_tone() {
+        let sample_rate = 44100;
+        let samples: Vec<f32> = (0..8192)
+            .map(|i| (2.0 * PI * 1000.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+
+        let mel = compute_mel_spectrogram(&samples, sample_rate, 40);
+        let edges = mel_band_edges(40, sample_rate);
+        let frame = &mel[mel.len() / 2];
+
+        let strongest = (0..frame.len())
+            .max_by(|&a, &b| frame[a].total_cmp(&frame[b]))
+            .unwrap();
+        assert!(
+            edges[strongest] < 1000.0 && 1000.0 < edges[strongest + 2],
+            "band {strongest} spans {}..{} Hz",
+            edges[strongest],
+            edges[strongest + 2]
+        );
+
+        // Bands far from the tone hold almost nothing
+        let total: f32 = frame.iter().sum();
+        let far: f32 = frame[strongest + 5..].iter().sum();
+        assert!(far < total * 0.01);
+    }
+}
@@ -1,17 +1,64 @@
 //! Audio analysis module
 //!
 //! This module contains functionality for analyzing audio samples,
-//! including FFT, spectrum analysis, pitch detection, and waveform downsampling.
+//! including FFT, spectrum, mel-spectrum and chroma analysis, pitch and chord
+//! detection, single-frequency (Goertzel) detection, onset and tempo
+//! detection, loudness (LUFS) measurement and normalisation, loop-point
+//! detection, time stretching, harmonic/percussive separation, spectral
+//! freeze, and waveform downsampling.
 
+mod chord;
+mod chroma;
 mod downsample;
 mod fft;
+mod freeze;
+mod goertzel;
+mod hpss;
+mod looping;
+mod loudness;
+mod mel;
+mod onset;
 mod peaks;
 mod pitch;
 mod spectrum;
+mod tempo;
+mod vocoder;
+mod window;
 
 // Re-export public items
+pub use chord::{ChordName, ChordQuality, MIN_CHORD_CONFIDENCE, detect_chord};
+pub use chroma::{
+    DEFAULT_CHROMA_FFT_SIZE, DEFAULT_CHROMA_HOP_SIZE, compute_chromagram, compute_chromagram_with,
+};
 pub use downsample::downsample_waveform;
-pub use fft::{FrequencyData, compute_fft};
+pub use fft::{FrequencyData, compute_fft, compute_fft_with};
+pub use freeze::{DEFAULT_FREEZE_FRAME_SIZE, SpectralFreeze};
+pub use goertzel::{NoteMatch, goertzel, strongest_note};
+pub use hpss::hpss;
+pub use looping::find_loop_points;
+pub use loudness::{
+    SILENCE_LUFS, TRUE_PEAK_CEILING, measure_lufs, measure_lufs_channels, normalize_to_lufs,
+};
+pub use mel::{compute_mel_spectrogram, hz_to_mel, mel_band_edges, mel_filterbank, mel_to_hz};
+pub use onset::detect_onsets;
 pub use peaks::pick_top_frequencies;
-pub use pitch::{estimate_pitch, frequency_to_note};
-pub use spectrum::{compute_spectrum, downsample_spectrogram};
+pub use pitch::{
+    PitchSearch, estimate_pitch, estimate_pitch_autocorrelation,
+    estimate_pitch_autocorrelation_with, frequency_to_note,
+};
+pub use spectrum::{
+    DEFAULT_FFT_SIZE, DEFAULT_HOP_SIZE, compute_spectrum, compute_spectrum_with,
+    downsample_spectrogram,
+};
+pub use tempo::estimate_tempo;
+pub use vocoder::{pitch_shift, time_stretch};
+pub use window::{WindowType, apply_window, hann_window};
+
+/// `seconds` of a full-scale sine at `frequency`, shared by the analysis tests.
+#[cfg(test)]
+pub(crate) fn sine(frequency: f32, sample_rate: u32, seconds: f32) -> Vec<f32> {
+    let len = (seconds * sample_rate as f32).round() as usize;
+    (0..len)
+        .map(|i| (2.0 * std::f32::consts::PI * frequency * i as f32 / sample_rate as f32).sin())
+        .collect()
+}
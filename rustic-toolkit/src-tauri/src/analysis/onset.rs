@@ -0,0 +1,148 @@
+use super::spectrum::compute_spectrum_with;
+use super::window::WindowType;
+
+/// STFT frame of the novelty function, about 12 ms at 44.1 kHz
+const ONSET_FRAME_SIZE: usize = 512;
+/// Hop between novelty values, about 3 ms at 44.1 kHz
+pub(super) const ONSET_HOP_SIZE: usize = 128;
+/// Magnitudes are compressed as `ln(1 + COMPRESSION * |X|)` before the flux
+const COMPRESSION: f32 = 10.0;
+/// Half-width of the moving median the novelty must rise above, in seconds
+const THRESHOLD_SECONDS: f32 = 0.1;
+/// Offset added to the moving median, relative to the novelty peak
+const THRESHOLD_DELTA: f32 = 0.1;
+/// Shortest time between two onsets, in seconds
+const MIN_ONSET_GAP_SECONDS: f32 = 0.03;
+
+/// Spectral-flux novelty of `samples`: for each frame, how much the
+/// log-compressed magnitudes rose since the previous one, summed over the
+/// bins. Value `n` belongs to the frame centred on sample `n * ONSET_HOP_SIZE`,
+/// and the result is normalised so that its peak is 1. Silence gives zeros.
+pub(super) fn spectral_flux(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+    // Padding by half a frame centres frame `n` on `n * ONSET_HOP_SIZE`
+    let mut padded = vec![0.0; ONSET_FRAME_SIZE / 2];
+    padded.extend_from_slice(samples);
+    let spectrogram = compute_spectrum_with(
+        &padded,
+        sample_rate,
+        ONSET_FRAME_SIZE,
+        ONSET_HOP_SIZE,
+        WindowType::Hann,
+    );
+
+    let compressed: Vec<Vec<f32>> = spectrogram
+        .into_iter()
+        .map(|frame| {
+            frame
+                .into_iter()
+                .map(|m| (1.0 + COMPRESSION * m).ln())
+                .collect()
+        })
+        .collect();
+    let mut flux: Vec<f32> = std::iter::once(0.0)
+        .chain(compressed.windows(2).map(|pair| {
+            pair[1]
+                .iter()
+                .zip(&pair[0])
+                .map(|(current, previous)| (current - previous).max(0.0))
+                .sum()
+        }))
+        .take(compressed.len())
+        .collect();
+
+    let peak = flux.iter().copied().fold(0.0, f32::max);
+    if peak > 0.0 {
+        flux.iter_mut().for_each(|value| *value /= peak);
+    }
+    flux
+}
+
+/// Median of `values`, which it reorders.
+pub(crate) fn median(values: &mut [f32]) -> f32 {
+    let middle = values.len() / 2;
+    *values.select_nth_unstable_by(middle, f32::total_cmp).1
+}
+
+/// Detects note onsets and transients in `samples`, returning their
+/// positions in samples, in ascending order.
+///
+/// The novelty function is the spectral flux over 512-sample frames hopping
+/// by 128. A frame is an onset when its novelty is the largest within 30 ms,
+/// and rises above the median of the surrounding 200 ms by a tenth of the
+/// strongest novelty; this adaptive threshold ignores the steady fluctuations
+/// of sustained sounds. Onsets are accurate to a few milliseconds.
+pub fn detect_onsets(samples: &[f32], sample_rate: u32) -> Vec<usize> {
+    let flux = spectral_flux(samples, sample_rate);
+    let frames_per_second = sample_rate as f32 / ONSET_HOP_SIZE as f32;
+    let median_radius = (THRESHOLD_SECONDS * frames_per_second).round() as usize;
+    let gap = ((MIN_ONSET_GAP_SECONDS * frames_per_second).round() as usize).max(1);
+
+    let mut onsets = Vec::new();
+    for (n, &value) in flux.iter().enumerate() {
+        let neighbours = n.saturating_sub(gap)..(n + gap + 1).min(flux.len());
+        // Strict on the left so that a flat peak only counts once
+        let is_peak = flux[neighbours.start..n].iter().all(|&v| v < value)
+            && flux[n + 1..neighbours.end].iter().all(|&v| v <= value);
+        if !is_peak {
+            continue;
+        }
+        let mut window =
+            flux[n.saturating_sub(median_radius)..(n + median_radius + 1).min(flux.len())].to_vec();
+        if value > median(&mut window) + THRESHOLD_DELTA {
+            onsets.push((n * ONSET_HOP_SIZE).min(samples.len().saturating_sub(1)));
+        }
+    }
+    onsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    #[test]
+    fn test_impulses_are_detected_in_place() {
+        // Clicks over a quiet sustained tone, which must not trigger onsets
+        let positions = [11025, 30000, 52000, 70000, 101000];
+        let mut samples: Vec<f32> = (0..SAMPLE_RATE as usize * 3)
+            .map(|i| 0.05 * (2.0 * PI * 220.0 * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect();
+        for &position in &positions {
+            samples[position] += 0.9;
+        }
+
+        let onsets = detect_onsets(&samples, SAMPLE_RATE);
+        assert_eq!(onsets.len(), positions.len(), "onsets at {onsets:?}");
+        // Within 5 ms of each click
+        for (onset, position) in onsets.iter().zip(positions) {
+            assert!(
+                onset.abs_diff(position) <= 220,
+                "onset at {onset}, click at {position}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_note_starts_are_detected() {
+        // Plucked notes decaying to silence, as a cut-off would be a transient
+        let mut samples = vec![0.0; SAMPLE_RATE as usize * 2];
+        for (start, frequency) in [(22050, 440.0), (66150, 660.0)] {
+            for (i, sample) in samples[start..start + 11025].iter_mut().enumerate() {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                *sample += 0.5 * (-t / 0.05).exp() * (2.0 * PI * frequency * t).sin();
+            }
+        }
+        let onsets = detect_onsets(&samples, SAMPLE_RATE);
+        assert_eq!(onsets.len(), 2, "onsets at {onsets:?}");
+        assert!(onsets[0].abs_diff(22050) <= 220);
+        assert!(onsets[1].abs_diff(66150) <= 220);
+    }
+
+    #[test]
+    fn test_silence_has_no_onsets() {
+        assert!(detect_onsets(&vec![0.0; 44100], SAMPLE_RATE).is_empty());
+        assert!(detect_onsets(&[], SAMPLE_RATE).is_empty());
+    }
+}
@@ -48,6 +48,97 @@ pub fn estimate_pitch(samples: &[f32], sample_rate: u32) -> Option<f32> {
     }
 }
 
+/// Search range and confidence threshold for [`estimate_pitch_autocorrelation_with`]
+#[derive(Debug, Clone, Copy)]
+pub struct PitchSearch {
+    /// Lowest frequency considered, in Hz
+    pub min_frequency: f32,
+    /// Highest frequency considered, in Hz
+    pub max_frequency: f32,
+    /// Minimum clarity in [0, 1] for a result to be accepted
+    pub clarity_threshold: f32,
+}
+
+impl Default for PitchSearch {
+    fn default() -> Self {
+        Self {
+            min_frequency: 40.0,
+            max_frequency: 2000.0,
+            clarity_threshold: 0.85,
+        }
+    }
+}
+
+/// Estimate pitch with the YIN normalized autocorrelation method, using the default search range
+pub fn estimate_pitch_autocorrelation(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    estimate_pitch_autocorrelation_with(samples, sample_rate, &PitchSearch::default())
+}
+
+/// Estimate pitch with the YIN normalized autocorrelation method.
+///
+/// Computes the cumulative mean normalized difference function over the lags
+/// matching the search range, picks the first dip whose clarity (`1 - d'`)
+/// reaches the threshold, and refines it with parabolic interpolation.
+/// Returns `None` when no lag is clear enough (noise, silence, too short input).
+pub fn estimate_pitch_autocorrelation_with(
+    samples: &[f32],
+    sample_rate: u32,
+    search: &PitchSearch,
+) -> Option<f32> {
+    if search.min_frequency <= 0.0 || search.max_frequency <= search.min_frequency {
+        return None;
+    }
+
+    let min_lag = ((sample_rate as f32 / search.max_frequency).floor() as usize).max(2);
+    let max_lag = (sample_rate as f32 / search.min_frequency).ceil() as usize;
+    if samples.len() < 2 * max_lag {
+        return None;
+    }
+    let window = samples.len() - max_lag;
+
+    // Difference function d(tau) and its cumulative mean normalized form d'(tau)
+    let mut normalized = vec![1.0f32; max_lag + 1];
+    let mut running_sum = 0.0;
+    for lag in 1..=max_lag {
+        let difference: f32 = (0..window)
+            .map(|i| {
+                let delta = samples[i] - samples[i + lag];
+                delta * delta
+            })
+            .sum();
+        running_sum += difference;
+        normalized[lag] = if running_sum > 0.0 {
+            difference * lag as f32 / running_sum
+        } else {
+            1.0
+        };
+    }
+
+    // First lag under the threshold, then walk down to the bottom of its dip
+    let threshold = 1.0 - search.clarity_threshold;
+    let mut lag = (min_lag..max_lag).find(|&lag| normalized[lag] < threshold)?;
+    while lag + 1 < max_lag && normalized[lag + 1] < normalized[lag] {
+        lag += 1;
+    }
+
+    // Parabolic interpolation around the minimum
+    let (a, b, c) = (normalized[lag - 1], normalized[lag], normalized[lag + 1]);
+    let denominator = a - 2.0 * b + c;
+    let offset = if denominator.abs() > f32::EPSILON {
+        (0.5 * (a - c) / denominator).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    };
+
+    let pitch = sample_rate as f32 / (lag as f32 + offset);
+    info!(
+        "Estimated pitch (YIN): {:.2} Hz, clarity {:.3}",
+        pitch,
+        1.0 - b
+    );
+    Some(pitch)
+}
+
 /// Converts frequency to musical note
 pub fn frequency_to_note(frequency: f32) -> String {
     // A4 = 440Hz is our reference
@@ -86,6 +177,7 @@ pub fn frequency_to_note(frequency: f32) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::sine;
     use std::f32::consts::PI;
 
     #[test]
@@ -111,6 +203,50 @@ mod tests {
         assert!((pitch - 440.0).abs() < 22.0);
     }
 
+    fn cents(detected: f32, expected: f32) -> f32 {
+        1200.0 * (detected / expected).log2()
+    }
+
+    #[test]
+    fn test_autocorrelation_low_e() {
+        let samples = sine(82.41, 44100, 0.2);
+        let pitch = estimate_pitch_autocorrelation(&samples, 44100).unwrap();
+        assert!(cents(pitch, 82.41).abs() < 5.0, "detected {pitch} Hz");
+    }
+
+    #[test]
+    fn test_autocorrelation_a4() {
+        let samples = sine(440.0, 44100, 0.2);
+        let pitch = estimate_pitch_autocorrelation(&samples, 44100).unwrap();
+        assert!(cents(pitch, 440.0).abs() < 5.0, "detected {pitch} Hz");
+    }
+
+    #[test]
+    fn test_autocorrelation_rejects_noise() {
+        // Deterministic white noise from a linear congruential generator
+        let mut state: u32 = 12345;
+        let noise: Vec<f32> = (0..8820)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0
+            })
+            .collect();
+        assert_eq!(estimate_pitch_autocorrelation(&noise, 44100), None);
+    }
+
+    #[test]
+    fn test_autocorrelation_respects_range() {
+        let samples = sine(440.0, 44100, 0.2);
+        let search = PitchSearch {
+            min_frequency: 50.0,
+            max_frequency: 300.0,
+            ..PitchSearch::default()
+        };
+        // The fundamental is out of range; the first clear dip is its sub-octave
+        let pitch = estimate_pitch_autocorrelation_with(&samples, 44100, &search).unwrap();
+        assert!(cents(pitch, 220.0).abs() < 5.0, "detected {pitch} Hz");
+    }
+
     #[test]
     fn test_frequency_to_note() {
         // Test exact frequencies
@@ -1,52 +1,83 @@
 use log::info;
 use rustfft::{FftPlanner, num_complex::Complex};
-use std::f32::consts::PI;
 
-/// Computes a time-frequency spectrogram for the given samples
+use super::window::WindowType;
+
+/// Default STFT frame size
+pub const DEFAULT_FFT_SIZE: usize = 1024;
+/// Default STFT hop size (75% overlap)
+pub const DEFAULT_HOP_SIZE: usize = DEFAULT_FFT_SIZE / 4;
+
+/// Computes a time-frequency spectrogram for the given samples, using
+/// 1024-sample Hann-windowed frames with 75% overlap
 pub fn compute_spectrum(samples: &[f32], sample_rate: u32) -> Vec<Vec<f32>> {
+    compute_spectrum_with(
+        samples,
+        sample_rate,
+        DEFAULT_FFT_SIZE,
+        DEFAULT_HOP_SIZE,
+        WindowType::Hann,
+    )
+}
+
+/// Computes a time-frequency spectrogram with an explicit Short-Time Fourier
+/// Transform configuration.
+///
+/// Each frame spans `fft_size` samples and frames start `hop_size` samples
+/// apart, so a larger `fft_size` gives finer frequency bins while a smaller
+/// `hop_size` gives more time frames. Frames are zero-padded to the next
+/// power of two after windowing.
+pub fn compute_spectrum_with(
+    samples: &[f32],
+    sample_rate: u32,
+    fft_size: usize,
+    hop_size: usize,
+    window: WindowType,
+) -> Vec<Vec<f32>> {
     info!(
-        "Computing spectrum for {} samples at {} Hz",
+        "Computing spectrum for {} samples at {} Hz (size {}, hop {}, {:?} window)",
         samples.len(),
-        sample_rate
+        sample_rate,
+        fft_size,
+        hop_size,
+        window
     );
 
     // Parameters for the Short-Time Fourier Transform (STFT)
-    let window_size = 1024;
-    let hop_size = window_size / 4; // 75% overlap
+    let window_size = fft_size.max(1);
+    let hop_size = hop_size.max(1);
+    let padded_size = window_size.next_power_of_two();
     let num_frames = samples.len().saturating_sub(window_size) / hop_size + 1;
+    let coefficients = window.coefficients(window_size);
 
     // Create the output spectrogram
     let mut spectrogram = Vec::with_capacity(num_frames);
 
     // Create FFT planner
     let mut planner = FftPlanner::new();
-    let fft = planner.plan_fft_forward(window_size);
+    let fft = planner.plan_fft_forward(padded_size);
 
     // Process each frame
     for frame_index in 0..num_frames {
         let start_index = frame_index * hop_size;
 
-        // Apply window function to the frame
-        let mut fft_input: Vec<Complex<f32>> = Vec::with_capacity(window_size);
-        for i in 0..window_size {
-            if start_index + i < samples.len() {
-                let window_val =
-                    0.5 * (1.0 - (2.0 * PI * i as f32 / (window_size as f32 - 1.0)).cos());
-                fft_input.push(Complex {
-                    re: samples[start_index + i] * window_val,
-                    im: 0.0,
-                });
-            } else {
-                fft_input.push(Complex { re: 0.0, im: 0.0 });
-            }
+        // Apply window function to the frame, zero-padding past the end of the input
+        let mut fft_input: Vec<Complex<f32>> = Vec::with_capacity(padded_size);
+        for (i, &w) in coefficients.iter().enumerate() {
+            let sample = samples.get(start_index + i).copied().unwrap_or(0.0);
+            fft_input.push(Complex {
+                re: sample * w,
+                im: 0.0,
+            });
         }
+        fft_input.resize(padded_size, Complex { re: 0.0, im: 0.0 });
 
         // Perform FFT
         let mut fft_output = fft_input;
         fft.process(&mut fft_output);
 
         // Convert FFT output to magnitudes (only using half due to Nyquist)
-        let nyquist = window_size / 2;
+        let nyquist = padded_size / 2;
         let mut frame_magnitudes = Vec::with_capacity(nyquist);
 
         for complex in fft_output.iter().take(nyquist) {
@@ -105,6 +136,7 @@ pub fn downsample_spectrogram(data: Vec<Vec<f32>>, max_time_bins: usize) -> Vec<
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f32::consts::PI;
 
     #[test]
     fn test_compute_spectrum() {
@@ -127,6 +159,26 @@ mod tests {
         assert!(!spectrogram[0].is_empty());
     }
 
+    #[test]
+    fn test_spectrum_rows_follow_hop_size() {
+        let samples = vec![0.25; 44100];
+
+        let dense = compute_spectrum_with(&samples, 44100, 2048, 256, WindowType::Hann);
+        let sparse = compute_spectrum_with(&samples, 44100, 2048, 1024, WindowType::Hann);
+
+        assert_eq!(dense.len(), (44100 - 2048) / 256 + 1);
+        assert_eq!(sparse.len(), (44100 - 2048) / 1024 + 1);
+        assert_eq!(dense[0].len(), 1024);
+    }
+
+    #[test]
+    fn test_spectrum_pads_non_power_of_two_frames() {
+        let samples = vec![0.25; 4000];
+        let spectrogram = compute_spectrum_with(&samples, 44100, 1000, 500, WindowType::Hamming);
+        assert_eq!(spectrogram.len(), (4000 - 1000) / 500 + 1);
+        assert_eq!(spectrogram[0].len(), 512);
+    }
+
     #[test]
     fn test_downsample_no_op_when_small() {
         let data = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
@@ -0,0 +1,112 @@
+use super::onset::{ONSET_HOP_SIZE, spectral_flux};
+
+/// Tempo range searched, in beats per minute
+const MIN_BPM: f32 = 60.0;
+const MAX_BPM: f32 = 200.0;
+/// Tempo favoured when several octaves fit, and the width of that preference
+/// in octaves
+const PREFERRED_BPM: f32 = 120.0;
+const PREFERENCE_OCTAVES: f32 = 1.0;
+
+/// Estimates the tempo of `samples` in beats per minute, between 60 and 200.
+///
+/// The spectral-flux novelty of the signal (see
+/// [`detect_onsets`](super::detect_onsets)) is autocorrelated, and the beat
+/// period is the lag with the strongest correlation. As a beat also
+/// correlates at twice and half its period, lags are weighted by a
+/// log-Gaussian preference centred on 120 BPM, which settles octave
+/// ambiguities the way most listeners tap along. Returns `None` for signals
+/// without onsets, or shorter than two beats at the slowest tempo.
+pub fn estimate_tempo(samples: &[f32], sample_rate: u32) -> Option<f32> {
+    let frames_per_minute = 60.0 * sample_rate as f32 / ONSET_HOP_SIZE as f32;
+    let min_lag = (frames_per_minute / MAX_BPM).floor().max(1.0) as usize;
+    let max_lag = (frames_per_minute / MIN_BPM).ceil() as usize;
+
+    let mut novelty = spectral_flux(samples, sample_rate);
+    if novelty.len() < 2 * max_lag {
+        return None;
+    }
+    let mean = novelty.iter().sum::<f32>() / novelty.len() as f32;
+    novelty.iter_mut().for_each(|value| *value -= mean);
+
+    // Unbiased autocorrelation, one lag past each end for the interpolation
+    let correlation: Vec<f32> = (min_lag - 1..=max_lag + 1)
+        .map(|lag| {
+            let terms = novelty.len() - lag;
+            novelty[..terms]
+                .iter()
+                .zip(&novelty[lag..])
+                .map(|(a, b)| a * b)
+                .sum::<f32>()
+                / terms as f32
+        })
+        .collect();
+
+    let weighted = |index: usize| {
+        let bpm = frames_per_minute / (min_lag - 1 + index) as f32;
+        let octaves = (bpm / PREFERRED_BPM).log2() / PREFERENCE_OCTAVES;
+        correlation[index] * (-0.5 * octaves * octaves).exp()
+    };
+    let best = (1..correlation.len() - 1).max_by(|&a, &b| weighted(a).total_cmp(&weighted(b)))?;
+    if correlation[best] <= 0.0 {
+        return None;
+    }
+
+    // Parabolic interpolation of the peak for a finer period than one hop
+    let (left, centre, right) = (
+        correlation[best - 1],
+        correlation[best],
+        correlation[best + 1],
+    );
+    let curvature = left - 2.0 * centre + right;
+    let offset = if curvature < 0.0 {
+        (0.5 * (left - right) / curvature).clamp(-0.5, 0.5)
+    } else {
+        0.0
+    };
+    let lag = (min_lag - 1 + best) as f32 + offset;
+    Some((frames_per_minute / lag).clamp(MIN_BPM, MAX_BPM))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    /// Short decaying clicks at `bpm` for `seconds`
+    fn click_track(bpm: f32, seconds: f32) -> Vec<f32> {
+        let mut samples = vec![0.0; (seconds * SAMPLE_RATE as f32) as usize];
+        let period = 60.0 * SAMPLE_RATE as f32 / bpm;
+        let mut beat = 0.0;
+        while (beat as usize) < samples.len() {
+            for (i, sample) in samples[beat as usize..].iter_mut().take(200).enumerate() {
+                // Alternating samples give a bright click
+                let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                *sample = sign * 0.8 * (-(i as f32) / 40.0).exp();
+            }
+            beat += period;
+        }
+        samples
+    }
+
+    #[test]
+    fn test_click_track_at_120_bpm() {
+        let bpm = estimate_tempo(&click_track(120.0, 10.0), SAMPLE_RATE).expect("no tempo");
+        assert!((bpm - 120.0).abs() < 2.0, "{bpm} BPM");
+    }
+
+    #[test]
+    fn test_other_tempos() {
+        for expected in [75.0, 96.0, 140.0, 174.0] {
+            let bpm = estimate_tempo(&click_track(expected, 10.0), SAMPLE_RATE).expect("no tempo");
+            assert!((bpm - expected).abs() < 2.0, "{bpm} BPM for {expected}");
+        }
+    }
+
+    #[test]
+    fn test_no_tempo_without_beats() {
+        assert_eq!(estimate_tempo(&vec![0.0; 44100 * 4], SAMPLE_RATE), None);
+        assert_eq!(estimate_tempo(&click_track(120.0, 1.0), SAMPLE_RATE), None);
+    }
+}
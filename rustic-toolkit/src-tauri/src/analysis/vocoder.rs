@@ -0,0 +1,232 @@
+use log::info;
+use rustfft::{FftPlanner, num_complex::Complex};
+use std::f32::consts::PI;
+
+use super::window::{apply_window, hann_window};
+
+/// STFT frame size used by the phase vocoder
+const FRAME_SIZE: usize = 2048;
+/// Analysis hop size (75% overlap)
+const HOP_SIZE: usize = FRAME_SIZE / 4;
+
+/// Shifts the pitch of `samples` by `semitones` without changing their duration.
+///
+/// The signal is first time-stretched by `2^(semitones / 12)` with a phase
+/// vocoder, then resampled back to its original length, which scales every
+/// frequency by the same ratio.
+pub fn pitch_shift(samples: &[f32], sample_rate: u32, semitones: f32) -> Vec<f32> {
+    info!(
+        "Pitch shifting {} samples at {} Hz by {} semitones",
+        samples.len(),
+        sample_rate,
+        semitones
+    );
+
+    if samples.is_empty() || semitones == 0.0 {
+        return samples.to_vec();
+    }
+
+    // Resample by the stretch actually achieved with an integer synthesis hop
+    let ratio = synthesis_hop(2f32.powf(semitones / 12.0)) as f32 / HOP_SIZE as f32;
+    let stretched = phase_vocoder(samples, ratio);
+    resample(&stretched, ratio, samples.len())
+}
+
+/// Changes the duration of `samples` by `factor` (> 1.0 is longer) without
+/// changing their pitch.
+///
+/// The stretch is done by the phase vocoder, whose synthesis hop is a whole
+/// number of samples, so the result is padded or trimmed to exactly
+/// `factor` times the input length. A factor of 1, or one that is not
+/// strictly positive, returns the samples unchanged.
+pub fn time_stretch(samples: &[f32], sample_rate: u32, factor: f32) -> Vec<f32> {
+    info!(
+        "Time stretching {} samples at {} Hz by {}",
+        samples.len(),
+        sample_rate,
+        factor
+    );
+
+    if samples.is_empty() || factor == 1.0 || !factor.is_finite() || factor <= 0.0 {
+        return samples.to_vec();
+    }
+
+    let len = (samples.len() as f32 * factor).round() as usize;
+    let mut stretched = phase_vocoder(samples, factor);
+    stretched.resize(len, 0.0);
+    stretched
+}
+
+/// Synthesis hop size for a given stretch ratio.
+fn synthesis_hop(ratio: f32) -> usize {
+    ((HOP_SIZE as f32 * ratio).round() as usize).max(1)
+}
+
+/// Wraps a phase into [-PI, PI].
+fn princarg(phase: f32) -> f32 {
+    phase - 2.0 * PI * ((phase + PI) / (2.0 * PI)).floor()
+}
+
+/// Time-stretches `samples` by `ratio` (> 1.0 is longer) using a phase vocoder:
+/// STFT analysis, phase accumulation at the synthesis hop, and windowed
+/// overlap-add resynthesis. Frames running past either edge are zero-padded.
+pub(super) fn phase_vocoder(samples: &[f32], ratio: f32) -> Vec<f32> {
+    let synthesis_hop = synthesis_hop(ratio);
+    let window = hann_window(FRAME_SIZE);
+
+    // Zero-pad a full frame on each side so the edges are analysed by complete frames.
+    let mut padded = vec![0.0; FRAME_SIZE];
+    padded.extend_from_slice(samples);
+    padded.resize(padded.len() + FRAME_SIZE, 0.0);
+
+    let num_frames = (padded.len() - FRAME_SIZE) / HOP_SIZE + 1;
+    let output_len = (num_frames - 1) * synthesis_hop + FRAME_SIZE;
+    let mut output = vec![0.0f32; output_len];
+    let mut window_sum = vec![0.0f32; output_len];
+
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(FRAME_SIZE);
+    let ifft = planner.plan_fft_inverse(FRAME_SIZE);
+
+    let bins = FRAME_SIZE / 2 + 1;
+    let mut previous_phase = vec![0.0f32; bins];
+    let mut synthesis_phase = vec![0.0f32; bins];
+
+    for frame_index in 0..num_frames {
+        let start = frame_index * HOP_SIZE;
+        let mut frame = padded[start..start + FRAME_SIZE].to_vec();
+        apply_window(&mut frame, &window);
+
+        let mut spectrum: Vec<Complex<f32>> =
+            frame.iter().map(|&s| Complex { re: s, im: 0.0 }).collect();
+        fft.process(&mut spectrum);
+
+        for k in 0..bins {
+            let magnitude = spectrum[k].norm();
+            let phase = spectrum[k].arg();
+            let bin_frequency = 2.0 * PI * k as f32 / FRAME_SIZE as f32;
+
+            // Deviation from the bin's expected phase advance gives the true frequency
+            let deviation = princarg(phase - previous_phase[k] - bin_frequency * HOP_SIZE as f32);
+            let true_frequency = bin_frequency + deviation / HOP_SIZE as f32;
+            previous_phase[k] = phase;
+
+            synthesis_phase[k] = if frame_index == 0 {
+                phase
+            } else {
+                princarg(synthesis_phase[k] + true_frequency * synthesis_hop as f32)
+            };
+            spectrum[k] = Complex::from_polar(magnitude, synthesis_phase[k]);
+        }
+        // Keep the spectrum conjugate-symmetric so the resynthesis is real
+        for k in bins..FRAME_SIZE {
+            spectrum[k] = spectrum[FRAME_SIZE - k].conj();
+        }
+
+        ifft.process(&mut spectrum);
+
+        let out_start = frame_index * synthesis_hop;
+        for (i, value) in spectrum.iter().enumerate() {
+            let w = window[i];
+            output[out_start + i] += value.re / FRAME_SIZE as f32 * w;
+            window_sum[out_start + i] += w * w;
+        }
+    }
+
+    // Normalise the overlap-add gain
+    for (sample, weight) in output.iter_mut().zip(&window_sum) {
+        if *weight > 1e-3 {
+            *sample /= weight;
+        }
+    }
+
+    // Drop the leading padding (scaled to the synthesis rate)
+    let stretch = synthesis_hop as f32 / HOP_SIZE as f32;
+    let lead = (FRAME_SIZE as f32 * stretch).round() as usize;
+    let len = (samples.len() as f32 * stretch).round() as usize;
+    output
+        .into_iter()
+        .skip(lead)
+        .chain(std::iter::repeat(0.0))
+        .take(len)
+        .collect()
+}
+
+/// Linearly resamples `samples` by reading every `step` samples, producing `len` samples.
+fn resample(samples: &[f32], step: f32, len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|i| {
+            let position = i as f32 * step;
+            let index = position.floor() as usize;
+            let fraction = position - index as f32;
+            let a = samples.get(index).copied().unwrap_or(0.0);
+            let b = samples.get(index + 1).copied().unwrap_or(0.0);
+            a + (b - a) * fraction
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{compute_fft, sine};
+
+    fn peak_frequency(samples: &[f32], sample_rate: u32) -> f32 {
+        compute_fft(samples, sample_rate)
+            .iter()
+            .max_by(|a, b| a.magnitude.partial_cmp(&b.magnitude).unwrap())
+            .map(|f| f.frequency)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pitch_shift_octave_up_doubles_frequency() {
+        let sample_rate = 44100;
+        let samples = sine(440.0, sample_rate, 1.0);
+
+        let shifted = pitch_shift(&samples, sample_rate, 12.0);
+        assert_eq!(shifted.len(), samples.len());
+
+        // Analyse the steady middle section, away from the edges
+        let middle = &shifted[11025..33075];
+        let peak = peak_frequency(middle, sample_rate);
+        assert!((peak - 880.0).abs() < 880.0 * 0.03, "peak at {peak} Hz");
+    }
+
+    #[test]
+    fn test_time_stretch_doubles_duration_at_same_pitch() {
+        let sample_rate = 44100;
+        let samples = sine(440.0, sample_rate, 1.0);
+
+        let stretched = time_stretch(&samples, sample_rate, 2.0);
+        assert_eq!(stretched.len(), 2 * samples.len());
+
+        let middle = &stretched[22050..66150];
+        let peak = peak_frequency(middle, sample_rate);
+        assert!((peak - 440.0).abs() < 440.0 * 0.03, "peak at {peak} Hz");
+    }
+
+    #[test]
+    fn test_time_stretch_shortens_at_same_pitch() {
+        let sample_rate = 44100;
+        let samples = sine(660.0, sample_rate, 1.0);
+
+        let stretched = time_stretch(&samples, sample_rate, 0.5);
+        assert_eq!(stretched.len(), samples.len() / 2);
+        let peak = peak_frequency(&stretched[5512..16538], sample_rate);
+        assert!((peak - 660.0).abs() < 660.0 * 0.03, "peak at {peak} Hz");
+    }
+
+    #[test]
+    fn test_time_stretch_one_is_identity() {
+        let samples = sine(440.0, 44100, 0.1);
+        assert_eq!(time_stretch(&samples, 44100, 1.0), samples);
+        assert_eq!(time_stretch(&samples, 44100, 0.0), samples);
+    }
+
+    #[test]
+    fn test_pitch_shift_zero_is_identity() {
+        let samples = sine(440.0, 44100, 0.1);
+        assert_eq!(pitch_shift(&samples, 44100, 0.0), samples);
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+use ts_rs::TS;
+
+/// Window functions available for spectral analysis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/types/")]
+pub enum WindowType {
+    Rectangular,
+    #[default]
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl WindowType {
+    /// Returns the window coefficients for `size` points.
+    pub fn coefficients(&self, size: usize) -> Vec<f32> {
+        if size < 2 {
+            return vec![1.0; size];
+        }
+        let last = size as f32 - 1.0;
+        (0..size)
+            .map(|i| {
+                let x = 2.0 * PI * i as f32 / last;
+                match self {
+                    Self::Rectangular => 1.0,
+                    Self::Hann => 0.5 * (1.0 - x.cos()),
+                    Self::Hamming => 0.54 - 0.46 * x.cos(),
+                    Self::Blackman => 0.42 - 0.5 * x.cos() + 0.08 * (2.0 * x).cos(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns a Hann window of `size` points.
+pub fn hann_window(size: usize) -> Vec<f32> {
+    WindowType::Hann.coefficients(size)
+}
+
+/// Multiplies `frame` in place by `window`, element-wise.
+pub fn apply_window(frame: &mut [f32], window: &[f32]) {
+    for (sample, w) in frame.iter_mut().zip(window) {
+        *sample *= w;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hann_window_shape() {
+        let window = hann_window(9);
+        assert!(window[0].abs() < 1e-6);
+        assert!(window[8].abs() < 1e-6);
+        assert!((window[4] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_window_types_peak_at_centre() {
+        for window in [WindowType::Hann, WindowType::Hamming, WindowType::Blackman] {
+            let coefficients = window.coefficients(9);
+            assert!((coefficients[4] - 1.0).abs() < 1e-6, "{window:?}");
+            assert!(coefficients[0] < 0.1, "{window:?}");
+        }
+        assert_eq!(WindowType::Rectangular.coefficients(4), vec![1.0; 4]);
+    }
+
+    #[test]
+    fn test_apply_window() {
+        let mut frame = vec![2.0; 4];
+        apply_window(&mut frame, &[0.0, 0.5, 1.0, 0.25]);
+        assert_eq!(frame, vec![0.0, 1.0, 2.0, 0.5]);
+    }
+}
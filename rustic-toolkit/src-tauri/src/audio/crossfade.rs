@@ -0,0 +1,72 @@
+use std::f32::consts::FRAC_PI_2;
+
+/// Concatenates `a` and `b`, overlapping the last `overlap` samples of `a`
+/// with the first ones of `b` through an equal-power crossfade.
+///
+/// Across the overlap `a` fades out as `cos(t * PI / 2)` while `b` fades in
+/// as `sin(t * PI / 2)`, so the squared gains always sum to one and
+/// uncorrelated material keeps its loudness through the join. The overlap is
+/// clamped to the length of the shorter buffer; with no overlap the buffers
+/// are simply concatenated. Crossfading the end of a sample into its own
+/// start gives a loop without a click.
+pub fn crossfade(a: &[f32], b: &[f32], overlap: usize) -> Vec<f32> {
+    let overlap = overlap.min(a.len()).min(b.len());
+    let (head, tail) = a.split_at(a.len() - overlap);
+    let (fade_in, rest) = b.split_at(overlap);
+
+    let mut output = Vec::with_capacity(a.len() + b.len() - overlap);
+    output.extend_from_slice(head);
+    output.extend(
+        tail.iter()
+            .zip(fade_in)
+            .enumerate()
+            .map(|(i, (&out, &inc))| {
+                // Centred on each sample so the fade is symmetric around the middle
+                let t = (i as f32 + 0.5) / overlap as f32;
+                out * (t * FRAC_PI_2).cos() + inc * (t * FRAC_PI_2).sin()
+            }),
+    );
+    output.extend_from_slice(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_overlap_concatenates() {
+        let a = [0.1, 0.2, 0.3];
+        let b = [0.4, 0.5];
+        assert_eq!(crossfade(&a, &b, 0), vec![0.1, 0.2, 0.3, 0.4, 0.5]);
+    }
+
+    #[test]
+    fn test_crossfade_keeps_constant_power() {
+        // Unit-power fade-out and fade-in curves, measured separately: their
+        // squared gains must add up to one on every overlapping sample
+        let overlap = 64;
+        let ones = vec![1.0; 128];
+        let zeros = vec![0.0; 128];
+        let fade_out = crossfade(&ones, &zeros, overlap);
+        let fade_in = crossfade(&zeros, &ones, overlap);
+        assert_eq!(fade_out.len(), 256 - overlap);
+
+        for i in 128 - overlap..128 {
+            let power = fade_out[i].powi(2) + fade_in[i].powi(2);
+            assert!((power - 1.0).abs() < 1e-5, "sample {i}: power {power}");
+        }
+        // Untouched outside of the overlap
+        assert!(fade_out[..128 - overlap].iter().all(|&s| s == 1.0));
+        assert!(fade_in[128..].iter().all(|&s| s == 1.0));
+    }
+
+    #[test]
+    fn test_overlap_is_clamped() {
+        let a = [1.0; 4];
+        let b = [1.0; 10];
+        let output = crossfade(&a, &b, 100);
+        assert_eq!(output.len(), 10);
+        assert!(crossfade(&[], &b, 5) == b);
+    }
+}
@@ -1,7 +1,10 @@
 //! Audio processing and loading module
 //!
-//! This module handles loading audio files in various formats.
+//! This module handles loading audio files in various formats, and joining
+//! sample buffers with crossfades.
 
+mod crossfade;
 mod loader;
 
+pub use crossfade::crossfade;
 pub use loader::{AudioBuffer, AudioLoader};
@@ -2,7 +2,7 @@ use std::sync::Mutex;
 use std::sync::atomic::Ordering;
 
 use crate::{RusticState, error::AppError};
-use rustic::app::commands::{GraphCommand, NodeKind};
+use rustic::app::commands::{GraphCommand, GraphTopology, NodeKind};
 use rustic::prelude::Command;
 use tauri::State;
 
@@ -75,7 +75,9 @@ pub fn graph_connect(
 #[tauri::command]
 pub fn graph_disconnect(
     from: u64,
+    from_port: usize,
     to: u64,
+    to_port: usize,
     rustic_state: State<'_, Mutex<RusticState>>,
 ) -> Result<(), AppError> {
     let state = rustic_state
@@ -83,7 +85,12 @@ pub fn graph_disconnect(
         .map_err(|_| AppError::LockPoisoned)?;
     state
         .app
-        .send(Command::Graph(GraphCommand::Disconnect { from, to }))
+        .send(Command::Graph(GraphCommand::Disconnect {
+            from,
+            from_port,
+            to,
+            to_port,
+        }))
         .map_err(rustic_err)
 }
 
@@ -153,6 +160,42 @@ pub fn graph_set_parameter(
         .map_err(rustic_err)
 }
 
+/// Revert the latest structural graph edit (add/remove node, connect, disconnect).
+#[tauri::command]
+pub fn graph_undo(rustic_state: State<'_, Mutex<RusticState>>) -> Result<(), AppError> {
+    let state = rustic_state
+        .try_lock()
+        .map_err(|_| AppError::LockPoisoned)?;
+    state
+        .app
+        .send(Command::Graph(GraphCommand::Undo))
+        .map_err(rustic_err)
+}
+
+/// Apply again the latest graph edit reverted by `graph_undo`.
+#[tauri::command]
+pub fn graph_redo(rustic_state: State<'_, Mutex<RusticState>>) -> Result<(), AppError> {
+    let state = rustic_state
+        .try_lock()
+        .map_err(|_| AppError::LockPoisoned)?;
+    state
+        .app
+        .send(Command::Graph(GraphCommand::Redo))
+        .map_err(rustic_err)
+}
+
+/// Describe the current graph (nodes with their parameter values, and
+/// connections) so the editor can render the patch.
+#[tauri::command]
+pub fn get_graph_topology(
+    rustic_state: State<'_, Mutex<RusticState>>,
+) -> Result<GraphTopology, AppError> {
+    let state = rustic_state
+        .try_lock()
+        .map_err(|_| AppError::LockPoisoned)?;
+    Ok(state.app.graph_topology())
+}
+
 /// Connect a source as a modulator for a named parameter on another node.
 #[tauri::command]
 pub fn graph_modulate(
@@ -4,8 +4,8 @@ use log::info;
 use tauri::State;
 
 use crate::analysis::{
-    FrequencyData, compute_fft, compute_spectrum, downsample_spectrogram, downsample_waveform,
-    pick_top_frequencies,
+    DEFAULT_FFT_SIZE, DEFAULT_HOP_SIZE, FrequencyData, WindowType, compute_fft,
+    compute_spectrum_with, downsample_spectrogram, downsample_waveform, pick_top_frequencies,
 };
 use crate::error::AppError;
 use crate::state::AudioState;
@@ -118,13 +118,25 @@ pub async fn get_top_frequencies(
 }
 
 /// Phase 2: Return spectrogram (STFT) data for a time window.
+///
+/// `fft_size`, `hop_size` and `window` default to 1024 samples, 256 samples
+/// and a Hann window when omitted.
 #[tauri::command]
 pub async fn get_spectrogram(
     start: f64,
     end: f64,
+    fft_size: Option<usize>,
+    hop_size: Option<usize>,
+    window: Option<WindowType>,
     state: State<'_, RwLock<AudioState>>,
 ) -> Result<SpectrogramData, AppError> {
-    info!("get_spectrogram [{:.3}s, {:.3}s]", start, end);
+    let fft_size = fft_size.unwrap_or(DEFAULT_FFT_SIZE);
+    let hop_size = hop_size.unwrap_or(DEFAULT_HOP_SIZE);
+    let window = window.unwrap_or_default();
+    info!(
+        "get_spectrogram [{:.3}s, {:.3}s] fft_size={} hop_size={} window={:?}",
+        start, end, fft_size, hop_size, window
+    );
 
     let (slice, sample_rate) = {
         let st = state.read()?;
@@ -135,7 +147,7 @@ pub async fn get_spectrogram(
         (buf.samples()[s..e].to_vec(), buf.sample_rate())
     };
 
-    let raw = compute_spectrum(&slice, sample_rate);
+    let raw = compute_spectrum_with(&slice, sample_rate, fft_size, hop_size, window);
     let data = downsample_spectrogram(raw, 500);
     let time_bins = data.len() as u32;
     let freq_bins = data.first().map_or(0, |v| v.len()) as u32;
@@ -91,6 +91,9 @@ pub fn run() {
             commands::graph::graph_kill_node,
             commands::graph::graph_set_parameter,
             commands::graph::graph_compile,
+            commands::graph::get_graph_topology,
+            commands::graph::graph_undo,
+            commands::graph::graph_redo,
             commands::graph::graph_modulate,
             commands::graph::graph_demodulate,
             commands::graph::graph_trigger_play,
@@ -17,23 +17,35 @@ function sanitizeType(name: string): string {
     return name.replace(/\s+/g, "");
 }
 
+function withUnit(title: string, unit: string | null | undefined): string {
+    return unit ? `${title} (${unit})` : title;
+}
+
 function createParameterInterface(param: ParamStr): () => NodeInterface<any> {
     if ("Range" in param) {
-        const { title, default: def, min, max } = param.Range;
-        return () => new SliderInterface(title, def, min, max).setPort(false);
+        const { title, default: def, min, max, unit } = param.Range;
+        return () => new SliderInterface(withUnit(title, unit), def, min, max).setPort(false);
     }
     if ("Float" in param) {
-        const { title, default: def } = param.Float;
-        return () => new NumberInterface(title, def).setPort(false);
+        const { title, default: def, unit } = param.Float;
+        return () => new NumberInterface(withUnit(title, unit), def).setPort(false);
     }
     if ("Toggle" in param) {
         const { title, default: def } = param.Toggle;
         return () => new CheckboxInterface(title, def).setPort(false);
     }
     if ("Int" in param) {
-        const { title, default: def, min, max } = param.Int;
+        const { title, default: def, min, max, unit } = param.Int;
         return () =>
-            new IntegerInterface(title, def, min ?? undefined, max ?? undefined).setPort(false);
+            new IntegerInterface(withUnit(title, unit), def, min ?? undefined, max ?? undefined).setPort(
+                false,
+            );
+    }
+    if ("Choice" in param) {
+        const { title, default: def, options } = param.Choice;
+        // Items carry their index so the graph bridge forwards a number to set_parameter
+        const items = options.map((text, value) => ({ text, value }));
+        return () => new SelectInterface<number>(title, def, items).setPort(false);
     }
     // List parameters are skipped for now
     return () => new NumberInterface("Unknown", 0).setPort(false);
@@ -210,8 +222,8 @@ export function registerNodesFromMetadata(editor: Editor, metadata: GraphMetadat
             } else {
                 const fieldName = getFieldName(input.parameter);
                 inputs[fieldName] = createParameterInterface(input.parameter);
-                // CV modulation input port (skip mix_mode — it's an enum selector, not a number)
-                if (fieldName !== "mix_mode") {
+                // CV modulation input port (skip selectors — they are not numbers)
+                if (fieldName !== "mix_mode" && !("Choice" in input.parameter)) {
                     inputs[`mod_${fieldName}`] = () =>
                         new NodeInterface<number>(`↗ ${fieldName}`, 0);
                 }
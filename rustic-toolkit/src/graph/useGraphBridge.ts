@@ -224,6 +224,7 @@ export function useGraphBridge(baklava: ReturnType<typeof useBaklava>) {
             const toBackendId = Number((toNode.inputs as any).backendNodeId?.value);
             if (!fromBackendId || !toBackendId) return;
 
+            const fromKey = findInterfaceKey(fromNode, conn.from);
             const toKey = findInterfaceKey(toNode, conn.to);
             isDirty.value = true;
             if (toKey?.startsWith("mod_")) {
@@ -231,10 +232,13 @@ export function useGraphBridge(baklava: ReturnType<typeof useBaklava>) {
                 graphDemodulateParameter(fromBackendId, toBackendId, paramName).catch((e) =>
                     notifications.error(`Failed to remove modulation: ${e}`)
                 );
-            } else {
-                graphDisconnect(fromBackendId, toBackendId).catch((e) =>
-                    notifications.error(`Failed to disconnect nodes: ${e}`)
-                );
+            } else if (fromKey && toKey) {
+                graphDisconnect(
+                    fromBackendId,
+                    getPortIndex(fromKey),
+                    toBackendId,
+                    getPortIndex(toKey)
+                ).catch((e) => notifications.error(`Failed to disconnect nodes: ${e}`));
             }
         });
     });
@@ -7,6 +7,7 @@ export type { FrequencyData } from "./FrequencyData";
 export type { SpectrogramData } from "./SpectrogramData";
 export type { SpectrumData } from "./SpectrumData";
 export type { WaveformData } from "./WaveformData";
+export type { WindowType } from "./WindowType";
 export type { GraphMetadata } from "./GraphMetadata";
 export type { EngineConfig, SystemConfig, AudioConfig, LogConfig } from "./EngineConfig";
 
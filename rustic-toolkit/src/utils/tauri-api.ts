@@ -13,6 +13,7 @@ import type {
   WaveformData,
   SpectrumData,
   SpectrogramData,
+  WindowType,
   GraphMetadata,
   EngineConfig,
 } from "@/types";
@@ -64,12 +65,20 @@ export async function getTopFrequencies(
   });
 }
 
+/** STFT resolution options; omitted fields use the backend defaults. */
+export interface SpectrogramOptions {
+  fftSize?: number;
+  hopSize?: number;
+  window?: WindowType;
+}
+
 /** Get spectrogram (STFT) data for a time window. */
 export async function getSpectrogram(
   start: number,
   end: number,
+  options: SpectrogramOptions = {},
 ): Promise<SpectrogramData> {
-  return invoke<SpectrogramData>("get_spectrogram", { start, end });
+  return invoke<SpectrogramData>("get_spectrogram", { start, end, ...options });
 }
 
 export async function getGraphMetadata(): Promise<GraphMetadata> {
@@ -123,9 +132,14 @@ export async function graphConnect(
   return invoke<void>("graph_connect", { from, fromPort, to, toPort });
 }
 
-/** Disconnect two nodes in the backend audio graph. */
-export async function graphDisconnect(from: number, to: number): Promise<void> {
-  return invoke<void>("graph_disconnect", { from, to });
+/** Remove the connection between two node ports in the backend audio graph. */
+export async function graphDisconnect(
+  from: number,
+  fromPort: number,
+  to: number,
+  toPort: number,
+): Promise<void> {
+  return invoke<void>("graph_disconnect", { from, fromPort, to, toPort });
 }
 
 /** Start a specific generator node in the audio graph. */
@@ -163,6 +177,16 @@ export async function graphCompile(): Promise<void> {
   return invoke<void>("graph_compile");
 }
 
+/** Revert the latest structural graph edit. */
+export async function graphUndo(): Promise<void> {
+  return invoke<void>("graph_undo");
+}
+
+/** Apply again the latest graph edit reverted by `graphUndo`. */
+export async function graphRedo(): Promise<void> {
+  return invoke<void>("graph_redo");
+}
+
 /** Connect a source as a CV modulator for a named parameter on another node. */
 export async function graphModulateParameter(
   from: number,
@@ -0,0 +1,79 @@
+use rustic::audio::EventFilter;
+use rustic::core::envelope::prelude::{ADSREnvelopeBuilder, LinearSegment};
+use rustic::instruments::Instrument;
+use rustic::instruments::prelude::{HiHat, KeyboardBuilder, Kick, Snare};
+use rustic::prelude::{App, AudioCommand, Command};
+
+/// Where the [`Player`](super::Player) sends the notes of the session.
+///
+/// Instruments are addressed by their position in the list given to
+/// [`start`](Self::start).
+pub trait AudioBackend: Send {
+    /// Starts the engine with one instrument per name and returns its sample
+    /// rate.
+    fn start(&mut self, instruments: &[String]) -> Result<u32, String>;
+
+    fn send(&mut self, command: AudioCommand) -> Result<(), String>;
+
+    fn stop(&mut self) -> Result<(), String>;
+}
+
+/// Backend playing through the rustic audio engine.
+#[derive(Default)]
+pub struct RusticBackend {
+    app: Option<App>,
+}
+
+impl RusticBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AudioBackend for RusticBackend {
+    fn start(&mut self, instruments: &[String]) -> Result<u32, String> {
+        let mut app = App::init().map_err(|e| e.to_string())?;
+        for name in instruments {
+            app.add_instrument(instrument(name)?);
+        }
+        // Backend events are not displayed yet
+        app.start(EventFilter::default())
+            .map_err(|e| e.to_string())?;
+        let sample_rate = app.config.system.sample_rate;
+        self.app = Some(app);
+        Ok(sample_rate)
+    }
+
+    fn send(&mut self, command: AudioCommand) -> Result<(), String> {
+        let app = self.app.as_ref().ok_or("Audio engine not started")?;
+        app.send(Command::Audio(command)).map_err(|e| e.to_string())
+    }
+
+    fn stop(&mut self) -> Result<(), String> {
+        match self.app.take() {
+            Some(mut app) => app.stop().map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builds the instrument a pattern names: drums by name, a keyboard otherwise.
+fn instrument(name: &str) -> Result<Box<dyn Instrument>, String> {
+    Ok(match name {
+        "kick" => Box::new(Kick::new()),
+        "snare" => Box::new(Snare::new()),
+        "hihat" | "hats" => Box::new(HiHat::new()?),
+        _ => Box::new(
+            KeyboardBuilder::new()
+                .with_voices(8)
+                .with_note_envelope(
+                    ADSREnvelopeBuilder::new()
+                        .attack(Box::new(LinearSegment::new(0.0, 1.0, 0.005)))
+                        .decay(Box::new(LinearSegment::new(1.0, 1.0, 0.001)))
+                        .release(Box::new(LinearSegment::new(1.0, 0.0, 0.2)))
+                        .build(),
+                )
+                .build(),
+        ),
+    })
+}
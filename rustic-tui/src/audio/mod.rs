@@ -0,0 +1,5 @@
+mod backend;
+mod player;
+
+pub use backend::{AudioBackend, RusticBackend};
+pub use player::Player;
@@ -0,0 +1,394 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use rustic::Note;
+use rustic::prelude::AudioCommand;
+use rustic_lang::{EventValue, Session, Transport, TransportEvent};
+
+use super::AudioBackend;
+
+/// Note played by drum triggers; drums ignore the pitch.
+const TRIGGER_NOTE: u8 = 60;
+
+/// How far ahead of the clock commands are handed to the dispatch thread.
+/// Longer than a UI tick, so that a slow frame does not delay a note.
+const LOOKAHEAD: Duration = Duration::from_millis(100);
+
+/// A command and the instant it should reach the engine.
+type Timed = (Instant, AudioCommand);
+
+/// Streams the patterns of a [`Session`] to an [`AudioBackend`].
+///
+/// A [`Transport`] converts cycles to samples: at each bar the events of that
+/// cycle are scheduled, then [`advance`](Self::advance) stamps the commands
+/// that fall in the frames it advances by with the instant they are due.  A
+/// dispatch thread sends each one at that instant, so note timing does not
+/// depend on how often the UI loop calls [`update`](Self::update).  Scale
+/// degrees no scale resolved are not played.
+pub struct Player {
+    backend: Arc<Mutex<Box<dyn AudioBackend>>>,
+    /// Instrument index of each instrument name, fixed when playback starts.
+    instruments: BTreeMap<String, usize>,
+    /// Present while playing.
+    transport: Option<Transport>,
+    /// Present while playing.
+    dispatcher: Option<Dispatcher>,
+    sample_rate: u32,
+    /// Instant of the first frame.
+    started: Instant,
+    /// Commands waiting for their sample, in order.
+    scheduled: Vec<(u64, AudioCommand)>,
+}
+
+/// Thread sending the commands to the backend at their deadline.
+struct Dispatcher {
+    commands: Sender<Timed>,
+    errors: Receiver<String>,
+    handle: JoinHandle<()>,
+}
+
+impl Player {
+    pub fn new(backend: Box<dyn AudioBackend>) -> Self {
+        Self {
+            backend: Arc::new(Mutex::new(backend)),
+            instruments: BTreeMap::new(),
+            transport: None,
+            dispatcher: None,
+            sample_rate: 0,
+            started: Instant::now(),
+            scheduled: Vec::new(),
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.transport.is_some()
+    }
+
+    /// Starts the backend with the instruments of `session` and plays from
+    /// the first bar.
+    pub fn play(&mut self, session: &Session) -> Result<(), String> {
+        self.play_from(session, Instant::now())
+    }
+
+    /// Like [`play`](Self::play), with the first bar due at `started`.
+    pub fn play_from(&mut self, session: &Session, started: Instant) -> Result<(), String> {
+        if self.is_playing() {
+            return Err("Already playing".to_string());
+        }
+
+        let names: Vec<String> = session
+            .all_patterns()
+            .values()
+            .map(|pattern| pattern.instrument.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let sample_rate = self.backend.lock().unwrap().start(&names)?;
+        self.sample_rate = sample_rate;
+        self.started = started;
+        self.instruments = names
+            .into_iter()
+            .enumerate()
+            .map(|(idx, name)| (name, idx))
+            .collect();
+        self.transport = Some(Transport::new(sample_rate, session));
+        self.dispatcher = Some(Dispatcher::spawn(self.backend.clone()));
+        Ok(())
+    }
+
+    /// Stops the backend, dropping the notes not played yet.
+    pub fn stop(&mut self) -> Result<(), String> {
+        if self.transport.take().is_none() {
+            return Ok(());
+        }
+        self.scheduled.clear();
+        if let Some(dispatcher) = self.dispatcher.take() {
+            dispatcher.stop();
+        }
+        self.backend.lock().unwrap().stop()
+    }
+
+    /// Schedules the commands due up to [`LOOKAHEAD`] from now.  Does nothing
+    /// when stopped.
+    pub fn update(&mut self, session: &Session) -> Result<(), String> {
+        let Some(transport) = self.transport.as_ref() else {
+            return Ok(());
+        };
+        let horizon = self.started.elapsed() + LOOKAHEAD;
+        let due = (horizon.as_secs_f64() * self.sample_rate as f64) as u64;
+        let frames = due.saturating_sub(transport.sample());
+        self.advance(session, frames as usize)
+    }
+
+    /// Advances playback by `frames` samples, handing the commands that fall
+    /// in them to the dispatch thread.  Does nothing when stopped.
+    pub fn advance(&mut self, session: &Session, frames: usize) -> Result<(), String> {
+        if let Some(error) = self
+            .dispatcher
+            .as_ref()
+            .and_then(|dispatcher| dispatcher.errors.try_recv().ok())
+        {
+            return Err(error);
+        }
+        let Some(transport) = self.transport.as_mut() else {
+            return Ok(());
+        };
+        transport.sync(session);
+        let events = transport.advance(frames);
+        let end = transport.sample();
+        let samples_per_bar = transport.samples_per_beat() * transport.sig().0 as f64;
+        for event in events {
+            if let TransportEvent::Bar { bar, sample } = event {
+                self.schedule(session, bar, sample, samples_per_bar);
+            }
+        }
+
+        let Some(dispatcher) = self.dispatcher.as_ref() else {
+            return Ok(());
+        };
+        let due = self.scheduled.partition_point(|(sample, _)| *sample < end);
+        for (sample, command) in self.scheduled.drain(..due) {
+            let offset = Duration::from_secs_f64(sample as f64 / self.sample_rate as f64);
+            dispatcher
+                .commands
+                .send((self.started + offset, command))
+                .map_err(|_| "Playback thread stopped".to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Schedules the events of cycle `bar`, which starts at `start`.
+    fn schedule(&mut self, session: &Session, bar: u64, start: u64, samples_per_bar: f64) {
+        let at = |cycles: f64| start + ((cycles - bar as f64) * samples_per_bar).round() as u64;
+        for pattern in session.active_patterns() {
+            let Some(&instrument_idx) = self.instruments.get(&pattern.instrument) else {
+                continue;
+            };
+            for event in session.events(&pattern.name, bar) {
+                let note = match event.value {
+                    EventValue::Pitch(pitch) => Note::from_midi(pitch.clamp(0, 127) as u8),
+                    EventValue::Trigger => Note::from_midi(TRIGGER_NOTE),
+                    EventValue::Degree(_) => continue,
+                };
+                let onset = at(event.onset);
+                self.scheduled.push((
+                    onset,
+                    AudioCommand::NoteStart {
+                        instrument_idx,
+                        note,
+                        velocity: event.velocity.clamp(0.0, 1.0) as f32,
+                    },
+                ));
+                // One sample early, so a note restarting right away is not
+                // cut by the end of the previous one
+                let end = at(event.onset + event.duration).saturating_sub(1);
+                self.scheduled.push((
+                    end.max(onset),
+                    AudioCommand::NoteStop {
+                        instrument_idx,
+                        note,
+                    },
+                ));
+            }
+        }
+        self.scheduled.sort_by_key(|(sample, _)| *sample);
+    }
+}
+
+impl Dispatcher {
+    fn spawn(backend: Arc<Mutex<Box<dyn AudioBackend>>>) -> Self {
+        let (commands, queue) = mpsc::channel();
+        let (error_tx, errors) = mpsc::channel();
+        let handle = thread::Builder::new()
+            .name("rustic-tui-player".to_string())
+            .spawn(move || dispatch(backend, queue, error_tx))
+            .expect("failed to spawn the playback thread");
+        Self {
+            commands,
+            errors,
+            handle,
+        }
+    }
+
+    /// Drops the commands not sent yet and joins the thread.
+    fn stop(self) {
+        drop(self.commands);
+        let _ = self.handle.join();
+    }
+}
+
+/// Sends each command at its deadline, until the queue is disconnected or
+/// the backend fails.  Commands arrive in deadline order.
+fn dispatch(
+    backend: Arc<Mutex<Box<dyn AudioBackend>>>,
+    queue: Receiver<Timed>,
+    errors: Sender<String>,
+) {
+    let mut pending: VecDeque<Timed> = VecDeque::new();
+    loop {
+        let received = match pending.front() {
+            Some((at, _)) => queue.recv_timeout(at.saturating_duration_since(Instant::now())),
+            None => queue.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(timed) => pending.push_back(timed),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let now = Instant::now();
+        while pending.front().is_some_and(|(at, _)| *at <= now) {
+            let Some((_, command)) = pending.pop_front() else {
+                break;
+            };
+            if let Err(e) = backend.lock().unwrap().send(command) {
+                let _ = errors.send(e);
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use rustic::NOTES;
+
+    use super::*;
+
+    /// `(is_start, instrument_idx, note)` of a sent command.
+    type Sent = (bool, usize, Note);
+
+    /// Backend recording what it is asked to do.
+    #[derive(Default)]
+    struct MockBackend {
+        instruments: Arc<Mutex<Vec<String>>>,
+        sent: Arc<Mutex<Vec<Sent>>>,
+        stopped: Arc<Mutex<bool>>,
+    }
+
+    impl AudioBackend for MockBackend {
+        fn start(&mut self, instruments: &[String]) -> Result<u32, String> {
+            *self.instruments.lock().unwrap() = instruments.to_vec();
+            Ok(48000)
+        }
+
+        fn send(&mut self, command: AudioCommand) -> Result<(), String> {
+            let sent = match command {
+                AudioCommand::NoteStart {
+                    instrument_idx,
+                    note,
+                    ..
+                } => (true, instrument_idx, note),
+                AudioCommand::NoteStop {
+                    instrument_idx,
+                    note,
+                } => (false, instrument_idx, note),
+                AudioCommand::Shutdown => return Err("unexpected shutdown".to_string()),
+            };
+            self.sent.lock().unwrap().push(sent);
+            Ok(())
+        }
+
+        fn stop(&mut self) -> Result<(), String> {
+            *self.stopped.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    fn session(source: &str) -> Session {
+        let mut session = Session::new();
+        session.evaluate(source);
+        session
+    }
+
+    /// Waits for the dispatch thread to send `count` commands.
+    fn wait_for(sent: &Mutex<Vec<Sent>>, count: usize) {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while sent.lock().unwrap().len() < count {
+            assert!(Instant::now() < deadline, "{count} commands never sent");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_play_sends_compiled_notes() {
+        let backend = MockBackend::default();
+        let (instruments, sent) = (backend.instruments.clone(), backend.sent.clone());
+        let session = session("bpm 120\nkick kick \"x ~ x ~\"\nlead saw \"c4 e4\"");
+        let mut player = Player::new(Box::new(backend));
+        // Started long ago, so that every command is already due
+        player
+            .play_from(&session, Instant::now() - Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(*instruments.lock().unwrap(), vec!["kick", "saw"]);
+
+        // A bar of 4/4 at 120 BPM lasts 96000 samples; the first half holds
+        // both first notes and the end of the first kick
+        player.advance(&session, 48000).unwrap();
+        wait_for(&sent, 4);
+        let of = |idx: usize| -> Vec<Sent> {
+            sent.lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, i, _)| *i == idx)
+                .copied()
+                .collect()
+        };
+        let (kick, c4, e4) = (Note(NOTES::C, 4), Note(NOTES::C, 4), Note(NOTES::E, 4));
+        assert_eq!(of(0), vec![(true, 0, kick), (false, 0, kick)]);
+        assert_eq!(of(1), vec![(true, 1, c4), (false, 1, c4)]);
+
+        player.advance(&session, 48000).unwrap();
+        wait_for(&sent, 8);
+        assert_eq!(of(0).len(), 4);
+        assert_eq!(of(1)[2..], [(true, 1, e4), (false, 1, e4)]);
+    }
+
+    #[test]
+    fn test_commands_are_sent_at_their_deadline() {
+        let backend = MockBackend::default();
+        let sent = backend.sent.clone();
+        // A bar lasts 400ms, the second kick starts 200ms in
+        let session = session("bpm 600\nkick kick \"x x\"");
+        let mut player = Player::new(Box::new(backend));
+        let started = Instant::now();
+        player.play_from(&session, started).unwrap();
+
+        // The whole bar is handed over at once
+        player.advance(&session, 19200).unwrap();
+        wait_for(&sent, 3);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert_eq!(sent.lock().unwrap()[2], (true, 0, Note(NOTES::C, 4)));
+    }
+
+    #[test]
+    fn test_play_twice_and_stop() {
+        let backend = MockBackend::default();
+        let (sent, stopped) = (backend.sent.clone(), backend.stopped.clone());
+        let session = session("kick kick \"x*4\"");
+        let mut player = Player::new(Box::new(backend));
+        assert!(!player.is_playing());
+
+        player.play(&session).unwrap();
+        assert!(player.play(&session).is_err());
+        assert!(player.is_playing());
+
+        player.stop().unwrap();
+        assert!(*stopped.lock().unwrap());
+        assert!(!player.is_playing());
+        player.advance(&session, 48000).unwrap();
+        player.update(&session).unwrap();
+        assert!(sent.lock().unwrap().is_empty());
+    }
+}
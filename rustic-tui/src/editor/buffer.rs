@@ -262,6 +262,18 @@ impl Buffer {
         }
     }
 
+    /// Replace the character under the cursor, like vim `r`. The cursor
+    /// stays on the replaced character; past the end of the line this is a
+    /// no-op.
+    pub fn replace_char(&mut self, ch: char) {
+        let line = &mut self.lines[self.cursor_row];
+        if let Some(current) = line[self.cursor_col.min(line.len())..].chars().next() {
+            let end = self.cursor_col + current.len_utf8();
+            line.replace_range(self.cursor_col..end, ch.encode_utf8(&mut [0; 4]));
+            self.dirty = true;
+        }
+    }
+
     /// Open a new line below the cursor and position cursor there.
     pub fn open_line_below(&mut self) {
         self.cursor_row += 1;
@@ -299,6 +311,55 @@ impl Buffer {
         self.dirty = true;
     }
 
+    /// Delete from the cursor to the start of the next word, like vim `dw`.
+    ///
+    /// On a word, the word and the whitespace after it are removed; on
+    /// whitespace, only the whitespace is. The deletion stops at the end of
+    /// the line rather than joining the next one.
+    pub fn delete_word(&mut self) {
+        let end = self.word_boundary(true);
+        self.delete_to(end);
+    }
+
+    /// Delete the word under the cursor, like vim `cw`: the same boundary as
+    /// [`delete_word`](Self::delete_word), but the whitespace after the word
+    /// is kept so the replacement stays separated from the next word.
+    pub fn change_word(&mut self) {
+        let end = self.word_boundary(false);
+        self.delete_to(end);
+    }
+
+    /// Column where a word deletion from the cursor ends on the current line.
+    fn word_boundary(&self, include_trailing_space: bool) -> usize {
+        let bytes = self.current_line().as_bytes();
+        let len = bytes.len();
+        let mut col = self.cursor_col.min(len);
+
+        if col < len && bytes[col].is_ascii_whitespace() {
+            while col < len && bytes[col].is_ascii_whitespace() {
+                col += 1;
+            }
+            return col;
+        }
+        while col < len && !bytes[col].is_ascii_whitespace() {
+            col += 1;
+        }
+        if include_trailing_space {
+            while col < len && bytes[col].is_ascii_whitespace() {
+                col += 1;
+            }
+        }
+        col
+    }
+
+    fn delete_to(&mut self, end: usize) {
+        let start = self.cursor_col.min(end);
+        if start < end {
+            self.lines[self.cursor_row].replace_range(start..end, "");
+            self.dirty = true;
+        }
+    }
+
     // --- Visual mode ---
 
     pub fn start_visual(&mut self) {
@@ -421,6 +482,44 @@ impl Buffer {
         false
     }
 
+    /// Find the previous occurrence of `needle` before the cursor position,
+    /// wrapping around to the bottom of the buffer.
+    pub fn search_backward(&mut self, needle: &str) -> bool {
+        if needle.is_empty() {
+            return false;
+        }
+        let last_match = |line: &str, accept: &dyn Fn(usize) -> bool| {
+            line.match_indices(needle)
+                .map(|(pos, _)| pos)
+                .filter(|&pos| accept(pos))
+                .last()
+        };
+        let cursor_col = self.cursor_col;
+        // Search in current line before cursor
+        if let Some(pos) = last_match(&self.lines[self.cursor_row], &|pos| pos < cursor_col) {
+            self.cursor_col = pos;
+            return true;
+        }
+        // Search in preceding lines
+        for row in (0..self.cursor_row).rev() {
+            if let Some(pos) = last_match(&self.lines[row], &|_| true) {
+                self.cursor_row = row;
+                self.cursor_col = pos;
+                return true;
+            }
+        }
+        // Wrap around
+        for row in (self.cursor_row..self.lines.len()).rev() {
+            let accept = |pos| row != self.cursor_row || pos > cursor_col;
+            if let Some(pos) = last_match(&self.lines[row], &accept) {
+                self.cursor_row = row;
+                self.cursor_col = pos;
+                return true;
+            }
+        }
+        false
+    }
+
     // --- Internal helpers ---
 
     fn clamp_cursor_col(&mut self) {
@@ -496,6 +595,67 @@ mod tests {
         assert_eq!(buf.cursor_col, 6); // back to 'world'
     }
 
+    #[test]
+    fn test_delete_word() {
+        let mut buf = Buffer::from_text("test", "kick sine \"x ~ x\"");
+        buf.delete_word();
+        assert_eq!(buf.line(0), "sine \"x ~ x\"");
+        assert_eq!(buf.cursor_col, 0);
+
+        // From the middle of a word, only its tail goes
+        buf.cursor_col = 2;
+        buf.delete_word();
+        assert_eq!(buf.line(0), "si\"x ~ x\"");
+
+        // On the last word, stop at the end of the line
+        let mut buf = Buffer::from_text("test", "a  b\nc");
+        buf.cursor_col = 3;
+        buf.delete_word();
+        assert_eq!(buf.line(0), "a  ");
+        assert_eq!(buf.line_count(), 2);
+    }
+
+    #[test]
+    fn test_delete_word_across_whitespace() {
+        let mut buf = Buffer::from_text("test", "one   two three");
+        buf.cursor_col = 3;
+        buf.delete_word();
+        assert_eq!(buf.line(0), "onetwo three");
+    }
+
+    #[test]
+    fn test_change_word_keeps_trailing_space() {
+        let mut buf = Buffer::from_text("test", "one two three");
+        buf.cursor_col = 4;
+        buf.change_word();
+        assert_eq!(buf.line(0), "one  three");
+        assert_eq!(buf.cursor_col, 4);
+    }
+
+    #[test]
+    fn test_replace_char() {
+        let mut buf = Buffer::from_text("test", "kick");
+        buf.cursor_col = 1;
+        buf.replace_char('x');
+        assert_eq!(buf.line(0), "kxck");
+        assert_eq!(buf.cursor_col, 1);
+        assert!(buf.dirty);
+    }
+
+    #[test]
+    fn test_replace_char_at_end_or_empty_line() {
+        let mut buf = Buffer::from_text("test", "ab\n\ncd");
+        buf.cursor_col = 2;
+        buf.replace_char('x');
+        assert_eq!(buf.line(0), "ab");
+
+        buf.cursor_row = 1;
+        buf.cursor_col = 0;
+        buf.replace_char('x');
+        assert_eq!(buf.line(1), "");
+        assert!(!buf.dirty);
+    }
+
     #[test]
     fn test_search_forward() {
         let mut buf = Buffer::from_text("test", "foo bar baz\nqux foo");
@@ -507,4 +667,30 @@ mod tests {
         assert_eq!(buf.cursor_row, 1);
         assert_eq!(buf.cursor_col, 4);
     }
+
+    #[test]
+    fn test_search_backward() {
+        let mut buf = Buffer::from_text("test", "foo bar foo\nbaz foo bar");
+        buf.cursor_row = 1;
+        buf.cursor_col = 8;
+        assert!(buf.search_backward("foo"));
+        assert_eq!((buf.cursor_row, buf.cursor_col), (1, 4));
+        assert!(buf.search_backward("foo"));
+        assert_eq!((buf.cursor_row, buf.cursor_col), (0, 8));
+        assert!(buf.search_backward("bar"));
+        assert_eq!((buf.cursor_row, buf.cursor_col), (0, 4));
+    }
+
+    #[test]
+    fn test_search_backward_wraps() {
+        let mut buf = Buffer::from_text("test", "foo bar\nbaz\nqux bar");
+        buf.cursor_col = 2;
+        // No `bar` before the cursor: wrap to the last one in the buffer
+        assert!(buf.search_backward("bar"));
+        assert_eq!((buf.cursor_row, buf.cursor_col), (2, 4));
+        assert!(buf.search_backward("bar"));
+        assert_eq!((buf.cursor_row, buf.cursor_col), (0, 4));
+        assert!(!buf.search_backward("nope"));
+        assert_eq!((buf.cursor_row, buf.cursor_col), (0, 4));
+    }
 }
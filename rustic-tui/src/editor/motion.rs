@@ -10,4 +10,6 @@ pub enum Motion {
     Change,
     /// Waiting for second key in `g` sequences (gg, etc.)
     G,
+    /// Waiting for the character to put under the cursor after `r`.
+    Replace,
 }
@@ -1,4 +1,6 @@
-use crate::panels::{EvalEntry, EvalEntryKind};
+use std::collections::BTreeMap;
+
+use crate::panels::{EvalEntry, EvalEntryKind, InstrumentInfo};
 use rustic_lang::session::{Delta, Session};
 
 /// Evaluation engine backed by rustic-lang's Session.
@@ -46,23 +48,37 @@ impl EvalEngine {
             entries.push(EvalEntry {
                 timestamp: timestamp.clone(),
                 kind: EvalEntryKind::Error,
-                message: format!("Line {}: {}", err.location.line, err.message),
+                message: match &err.suggestion {
+                    Some(hint) => format!(
+                        "Line {}, col {}: {} ({})",
+                        err.location.line, err.location.column, err.message, hint
+                    ),
+                    None => format!(
+                        "Line {}, col {}: {}",
+                        err.location.line, err.location.column, err.message
+                    ),
+                },
             });
         }
 
         // Report deltas
         for delta in &result.deltas {
-            let (action, name) = match delta {
-                Delta::Add(n) => ("Added", n.as_str()),
-                Delta::Modify(n) => ("Modified", n.as_str()),
-                Delta::Remove(n) => ("Removed", n.as_str()),
-                Delta::Mute(n) => ("Muted", n.as_str()),
-                Delta::Unmute(n) => ("Unmuted", n.as_str()),
+            let message = match delta {
+                Delta::Add(n) => format!("+ added {}", n),
+                Delta::Modify(n) => format!("~ modified {}", n),
+                Delta::Remove(n) => format!("- removed {}", n),
+                Delta::Mute(n) => format!("; muted {}", n),
+                Delta::Unmute(n) => format!("> unmuted {}", n),
+                Delta::SetParameter {
+                    pattern,
+                    param,
+                    value,
+                } => format!("= set {} {} {}", pattern, param, value),
             };
             entries.push(EvalEntry {
                 timestamp: timestamp.clone(),
                 kind: EvalEntryKind::Info,
-                message: format!("{} pattern: {}", action, name),
+                message,
             });
         }
 
@@ -105,9 +121,28 @@ impl EvalEngine {
     }
 
     /// Get the current session (for context panel).
-    pub fn _session(&self) -> &Session {
+    pub fn session(&self) -> &Session {
         &self.session
     }
+
+    /// Instruments used by the session's patterns, by name.  An instrument
+    /// is active when one of its patterns is unmuted; its voice count is the
+    /// number of patterns playing it.
+    pub fn instruments(&self) -> Vec<InstrumentInfo> {
+        let mut instruments: BTreeMap<&str, InstrumentInfo> = BTreeMap::new();
+        for pattern in self.session.all_patterns().values() {
+            let info = instruments
+                .entry(pattern.instrument.as_str())
+                .or_insert_with(|| InstrumentInfo {
+                    name: pattern.instrument.clone(),
+                    active: false,
+                    voice_count: 0,
+                });
+            info.active |= !pattern.muted;
+            info.voice_count += 1;
+        }
+        instruments.into_values().collect()
+    }
 }
 
 #[cfg(test)]
@@ -127,7 +162,52 @@ mod tests {
         let mut engine = EvalEngine::new();
         let entries = engine.evaluate("kick kick \"x ~ x ~\"");
         assert!(entries.iter().any(|e| e.kind == EvalEntryKind::Success));
-        assert!(entries.iter().any(|e| e.message.contains("Added")));
+        assert!(entries.iter().any(|e| e.message == "+ added kick"));
+    }
+
+    #[test]
+    fn test_eval_valid_buffer_summarises_deltas() {
+        let mut engine = EvalEngine::new();
+        engine.evaluate("kick kick \"x ~ x ~\"\nbass saw \"c2 eb2\"");
+        let entries =
+            engine.evaluate("kick kick \"x ~ x ~\"\nbass saw \"c2 g2\"\n; hats hihat \"x*8\"");
+
+        let messages: Vec<&str> = entries
+            .iter()
+            .filter(|e| e.kind == EvalEntryKind::Info)
+            .map(|e| e.message.as_str())
+            .collect();
+        assert_eq!(messages[..2], ["~ modified bass", "+ added hats"]);
+        let summary = entries
+            .iter()
+            .find(|e| e.kind == EvalEntryKind::Success)
+            .expect("missing success entry");
+        assert_eq!(summary.message, "OK — 2 active, 1 muted (2 changes queued)");
+        assert!(!entries.iter().any(|e| e.kind == EvalEntryKind::Error));
+
+        let instruments = engine.instruments();
+        let names: Vec<&str> = instruments.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["hihat", "kick", "saw"]);
+        assert!(!instruments[0].active);
+    }
+
+    #[test]
+    fn test_eval_syntax_error_reports_its_line() {
+        let mut engine = EvalEngine::new();
+        let entries = engine.evaluate("bpm 120\nkick kick \"x ~ x ~\"\nlead saw \"c4 q4\"");
+        let errors: Vec<&EvalEntry> = entries
+            .iter()
+            .filter(|e| e.kind == EvalEntryKind::Error)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].message.starts_with("Line 3, col 14:"),
+            "{}",
+            errors[0].message
+        );
+        // The valid pattern is still applied, but the evaluation is not a success
+        assert!(entries.iter().any(|e| e.message == "+ added kick"));
+        assert!(!entries.iter().any(|e| e.kind == EvalEntryKind::Success));
     }
 
     #[test]
@@ -142,7 +222,7 @@ mod tests {
         let mut engine = EvalEngine::new();
         engine.evaluate("kick kick \"x ~ x ~\"");
         let entries = engine.evaluate("kick kick \"x x x x\"");
-        assert!(entries.iter().any(|e| e.message.contains("Modified")));
+        assert!(entries.iter().any(|e| e.message == "~ modified kick"));
     }
 
     #[test]
@@ -1,3 +1,4 @@
+pub mod audio;
 pub mod editor;
 pub mod eval;
 pub mod layout;
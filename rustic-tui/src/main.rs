@@ -1,3 +1,4 @@
+mod audio;
 mod editor;
 mod eval;
 mod layout;
@@ -20,17 +21,21 @@ use ratatui::{
     widgets::Paragraph,
 };
 
+use audio::{Player, RusticBackend};
 use editor::{Buffer, CommandLine, Mode, Motion};
 use eval::EvalEngine;
 use layout::ColumnLayout;
 use panels::{
     CodeEditorPanel, ContextInfo, ContextPanel, EvalEntry, EvalEntryKind, EvalOutputPanel,
-    InstrumentInfo,
 };
 
 /// Target frame rate (ticks per second).
 const TPS: u64 = 30;
 
+/// Largest count accepted before a command (`9999j`); longer counts are
+/// capped so that a stray run of digits cannot stall the editor.
+const MAX_COUNT: usize = 9999;
+
 /// Application state.
 struct App {
     /// The code editor buffer (Column 1).
@@ -41,17 +46,23 @@ struct App {
     eval_scroll: usize,
     /// Context information for the reference panel.
     context: ContextInfo,
-    /// The eval engine (stub).
+    /// Evaluation engine driving the rustic-lang session.
     eval_engine: EvalEngine,
+    /// Streams the session to the audio engine.
+    player: Player,
 
     /// Current editor mode.
     mode: Mode,
     /// Command-line input state.
     command_line: CommandLine,
-    /// Last search query for `/` and `n` repeat.
+    /// Last search query for `/`, `?`, `n` and `N`.
     last_search: String,
+    /// Whether the last search (or the one being typed) runs backward (`?`).
+    search_backward: bool,
     /// Pending operator motion (d, y, c).
     pending_motion: Option<Motion>,
+    /// Count typed before a motion or operator (the `3` of `3j`).
+    pending_count: Option<usize>,
     /// Yank register (clipboard).
     yank_register: String,
 
@@ -87,16 +98,20 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
             eval_scroll: 0,
             context: ContextInfo::default(),
             eval_engine: EvalEngine::new(),
+            player: Player::new(Box::new(RusticBackend::new())),
             mode: Mode::Normal,
             command_line: CommandLine::new(),
             last_search: String::new(),
+            search_backward: false,
             pending_motion: None,
+            pending_count: None,
             yank_register: String::new(),
             columns: ColumnLayout::new(),
             status_message: None,
             should_quit: false,
         };
         app.update_context_keybindings();
+        app.update_context_session();
         app
     }
 
@@ -115,6 +130,7 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
             self.eval_scroll = self.eval_entries.len() - viewport_guess;
         }
         self.code_buffer.dirty = false;
+        self.update_context_session();
         if success {
             self.set_status("Evaluation complete.");
         } else {
@@ -169,16 +185,22 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
                 ("v".into(), "Enter visual mode".into()),
                 (":".into(), "Command mode".into()),
                 ("/".into(), "Search forward".into()),
+                ("?".into(), "Search backward".into()),
+                ("n/N".into(), "Next/previous match".into()),
                 ("h/j/k/l".into(), "Move cursor".into()),
+                ("3j/2dd".into(), "Repeat with a count".into()),
                 ("w/b/e".into(), "Word motions".into()),
                 ("0/$".into(), "Line start/end".into()),
                 ("gg/G".into(), "Top/bottom".into()),
                 ("dd".into(), "Delete line".into()),
                 ("D".into(), "Delete to EOL".into()),
+                ("r".into(), "Replace char".into()),
                 ("yy".into(), "Yank line".into()),
                 ("p".into(), "Paste below".into()),
                 ("o/O".into(), "Open line below/above".into()),
                 ("Ctrl+S".into(), "Evaluate (save)".into()),
+                ("Ctrl+P".into(), "Play".into()),
+                ("Ctrl+K".into(), "Stop".into()),
                 ("Tab".into(), "Next column".into()),
                 ("Shift+Tab".into(), "Prev column".into()),
                 ("Ctrl+>".into(), "Grow column".into()),
@@ -209,23 +231,57 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
             Mode::Search => vec![
                 ("Enter".into(), "Search".into()),
                 ("Esc".into(), "Cancel".into()),
-                ("n".into(), "(after) Next match".into()),
+                ("n/N".into(), "(after) Next/previous match".into()),
             ],
         };
         self.context.keybindings = bindings;
-        self.context.engine_status = "Idle (no audio backend)".to_string();
-        self.context.instruments = vec![
-            InstrumentInfo {
-                name: "piano".into(),
-                active: false,
-                voice_count: 8,
-            },
-            InstrumentInfo {
-                name: "drums".into(),
-                active: false,
-                voice_count: 4,
-            },
-        ];
+    }
+
+    /// Refreshes the instruments and engine status from the session.
+    fn update_context_session(&mut self) {
+        let session = self.eval_engine.session();
+        self.context.engine_status = format!(
+            "{} BPM, {}/{} — {}",
+            session.bpm,
+            session.sig.0,
+            session.sig.1,
+            if self.player.is_playing() {
+                "Playing"
+            } else {
+                "Stopped"
+            }
+        );
+        self.context.instruments = self.eval_engine.instruments();
+    }
+
+    /// Evaluates the buffer and starts streaming it to the audio engine.
+    fn play(&mut self) {
+        if self.player.is_playing() {
+            self.set_status("Already playing.");
+            return;
+        }
+        self.evaluate_buffer();
+        match self.player.play(self.eval_engine.session()) {
+            Ok(()) => self.set_status("Playing."),
+            Err(e) => self.set_status(format!("Cannot play: {e}")),
+        }
+        self.update_context_session();
+    }
+
+    fn stop(&mut self) {
+        match self.player.stop() {
+            Ok(()) => self.set_status("Stopped."),
+            Err(e) => self.set_status(format!("Error while stopping: {e}")),
+        }
+        self.update_context_session();
+    }
+
+    /// Schedules the audio commands due before the next ticks.
+    fn update_playback(&mut self) {
+        if let Err(e) = self.player.update(self.eval_engine.session()) {
+            self.set_status(format!("Playback error: {e}"));
+            self.stop();
+        }
     }
 
     /// Handle a keyboard event based on the current mode.
@@ -247,6 +303,14 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
                     self.update_context_keybindings();
                     return;
                 }
+                KeyCode::Char('p') => {
+                    self.play();
+                    return;
+                }
+                KeyCode::Char('k') => {
+                    self.stop();
+                    return;
+                }
                 _ => {}
             }
         }
@@ -262,54 +326,100 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
     }
 
     fn handle_normal_mode(&mut self, key: KeyEvent) {
+        // Digits accumulate a count, except a leading `0` (line start) and the
+        // character awaited by an operator such as `r`
+        if let KeyCode::Char(digit @ '0'..='9') = key.code
+            && key.modifiers.is_empty()
+            && self.pending_motion.is_none()
+            && (digit != '0' || self.pending_count.is_some())
+        {
+            let count = self.pending_count.unwrap_or(0);
+            let digit = digit.to_digit(10).unwrap_or(0) as usize;
+            self.pending_count = Some((count * 10 + digit).min(MAX_COUNT));
+            return;
+        }
+
         // Handle pending motions first (dd, yy, gg, etc.)
         if let Some(motion) = self.pending_motion.take() {
-            match motion {
-                Motion::Delete => match key.code {
-                    KeyCode::Char('d') => {
-                        self.yank_register = self.code_buffer.current_line().to_string();
-                        self.code_buffer.delete_line();
-                    }
-                    KeyCode::Char('w') => {
-                        // delete word — simplified: delete to next word boundary
-                        self.code_buffer.delete_to_end_of_line();
-                    }
-                    _ => {
-                        self.set_status("Unknown motion for d");
-                    }
-                },
-                Motion::Yank => match key.code {
-                    KeyCode::Char('y') => {
-                        self.yank_register = self.code_buffer.current_line().to_string();
-                        self.set_status("Line yanked.");
-                    }
-                    _ => {
-                        self.set_status("Unknown motion for y");
-                    }
-                },
-                Motion::Change => match key.code {
-                    KeyCode::Char('c') => {
-                        self.yank_register = self.code_buffer.current_line().to_string();
-                        self.code_buffer.delete_line();
-                        self.code_buffer.open_line_above();
-                        self.mode = Mode::Insert;
-                    }
-                    _ => {
-                        self.set_status("Unknown motion for c");
-                    }
-                },
-                Motion::G => match key.code {
-                    KeyCode::Char('g') => {
-                        self.code_buffer.move_to_top();
-                    }
-                    _ => {
-                        self.set_status("Unknown g motion");
-                    }
-                },
+            let count = self.pending_count.take().unwrap_or(1);
+            for _ in 0..count {
+                self.apply_motion(&motion, key);
+                if self.mode != Mode::Normal {
+                    break;
+                }
             }
             return;
         }
 
+        // Repeat the command, but keep the count for the motion an operator
+        // waits for (`2dd`)
+        let count = self.pending_count.take().unwrap_or(1);
+        for _ in 0..count {
+            self.normal_command(key);
+            if self.mode != Mode::Normal || self.pending_motion.is_some() {
+                break;
+            }
+        }
+        if self.pending_motion.is_some() && count > 1 {
+            self.pending_count = Some(count);
+        }
+    }
+
+    /// Completes the operator `motion` with `key`.
+    fn apply_motion(&mut self, motion: &Motion, key: KeyEvent) {
+        match motion {
+            Motion::Delete => match key.code {
+                KeyCode::Char('d') => {
+                    self.yank_register = self.code_buffer.current_line().to_string();
+                    self.code_buffer.delete_line();
+                }
+                KeyCode::Char('w') => self.code_buffer.delete_word(),
+                _ => {
+                    self.set_status("Unknown motion for d");
+                }
+            },
+            Motion::Yank => match key.code {
+                KeyCode::Char('y') => {
+                    self.yank_register = self.code_buffer.current_line().to_string();
+                    self.set_status("Line yanked.");
+                }
+                _ => {
+                    self.set_status("Unknown motion for y");
+                }
+            },
+            Motion::Change => match key.code {
+                KeyCode::Char('c') => {
+                    self.yank_register = self.code_buffer.current_line().to_string();
+                    self.code_buffer.delete_line();
+                    self.code_buffer.open_line_above();
+                    self.mode = Mode::Insert;
+                }
+                KeyCode::Char('w') => {
+                    self.code_buffer.change_word();
+                    self.mode = Mode::Insert;
+                }
+                _ => {
+                    self.set_status("Unknown motion for c");
+                }
+            },
+            Motion::G => match key.code {
+                KeyCode::Char('g') => {
+                    self.code_buffer.move_to_top();
+                }
+                _ => {
+                    self.set_status("Unknown g motion");
+                }
+            },
+            Motion::Replace => {
+                if let KeyCode::Char(ch) = key.code {
+                    self.code_buffer.replace_char(ch);
+                }
+            }
+        }
+    }
+
+    /// Runs a single normal-mode command.
+    fn normal_command(&mut self, key: KeyEvent) {
         // Column switching with Tab / Shift+Tab
         if key.code == KeyCode::Tab {
             if key.modifiers.contains(KeyModifiers::SHIFT) {
@@ -427,8 +537,9 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
                 self.mode = Mode::Command;
                 self.command_line.clear();
             }
-            KeyCode::Char('/') => {
+            KeyCode::Char('/') | KeyCode::Char('?') => {
                 self.mode = Mode::Search;
+                self.search_backward = key.code == KeyCode::Char('?');
                 self.command_line.clear();
             }
 
@@ -451,12 +562,7 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
 
             // Editing
             KeyCode::Char('x') => self.code_buffer.delete_char(),
-            KeyCode::Char('r') => {
-                // Replace mode: next char typed replaces current
-                // Simplified: just delete and enter insert
-                self.code_buffer.delete_char();
-                self.mode = Mode::Insert;
-            }
+            KeyCode::Char('r') => self.pending_motion = Some(Motion::Replace),
 
             KeyCode::Char('o') => {
                 self.code_buffer.open_line_below();
@@ -474,33 +580,28 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
             KeyCode::Char('y') => self.pending_motion = Some(Motion::Yank),
             KeyCode::Char('c') => self.pending_motion = Some(Motion::Change),
 
-            KeyCode::Char('p') => {
+            KeyCode::Char('p') if !self.yank_register.is_empty() => {
                 // Paste below
-                if !self.yank_register.is_empty() {
-                    self.code_buffer.open_line_below();
-                    for ch in self.yank_register.clone().chars() {
-                        self.code_buffer.insert_char(ch);
-                    }
+                self.code_buffer.open_line_below();
+                for ch in self.yank_register.clone().chars() {
+                    self.code_buffer.insert_char(ch);
                 }
             }
-            KeyCode::Char('P') => {
+            KeyCode::Char('P') if !self.yank_register.is_empty() => {
                 // Paste above
-                if !self.yank_register.is_empty() {
-                    self.code_buffer.open_line_above();
-                    for ch in self.yank_register.clone().chars() {
-                        self.code_buffer.insert_char(ch);
-                    }
+                self.code_buffer.open_line_above();
+                for ch in self.yank_register.clone().chars() {
+                    self.code_buffer.insert_char(ch);
                 }
             }
 
-            KeyCode::Char('n') => {
+            KeyCode::Char('n') if !self.last_search.is_empty() => {
                 // Repeat last search
-                if !self.last_search.is_empty() {
-                    let query = self.last_search.clone();
-                    if !self.code_buffer.search_forward(&query) {
-                        self.set_status(format!("Pattern not found: {}", query));
-                    }
-                }
+                self.run_search(self.search_backward);
+            }
+            KeyCode::Char('N') if !self.last_search.is_empty() => {
+                // Repeat last search in the opposite direction
+                self.run_search(!self.search_backward);
             }
 
             _ => {}
@@ -612,6 +713,18 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
         }
     }
 
+    /// Searches for the last query, backward or forward.
+    fn run_search(&mut self, backward: bool) {
+        let found = if backward {
+            self.code_buffer.search_backward(&self.last_search)
+        } else {
+            self.code_buffer.search_forward(&self.last_search)
+        };
+        if !found {
+            self.set_status(format!("Pattern not found: {}", self.last_search));
+        }
+    }
+
     fn handle_search_mode(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
@@ -622,10 +735,8 @@ lead  piano  \"c4 eb4 g4 bb4\" | slow 2
                 let query = self.command_line.take();
                 self.mode = Mode::Normal;
                 if !query.is_empty() {
-                    self.last_search = query.clone();
-                    if !self.code_buffer.search_forward(&query) {
-                        self.set_status(format!("Pattern not found: {}", query));
-                    }
+                    self.last_search = query;
+                    self.run_search(self.search_backward);
                 }
             }
             KeyCode::Backspace => {
@@ -745,7 +856,11 @@ fn render(app: &mut App, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>)
         // --- Command / mode line ---
         let cmdline_content = match app.mode {
             Mode::Command => format!(":{}", app.command_line.input),
-            Mode::Search => format!("/{}", app.command_line.input),
+            Mode::Search => format!(
+                "{}{}",
+                if app.search_backward { '?' } else { '/' },
+                app.command_line.input
+            ),
             _ => String::new(),
         };
         let cmdline_widget = Paragraph::new(cmdline_content)
@@ -787,6 +902,7 @@ fn main() -> io::Result<()> {
         if app.should_quit {
             break;
         }
+        app.update_playback();
 
         // Poll for events with timeout
         if event::poll(tick_rate)?
@@ -803,3 +919,76 @@ fn main() -> io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with(text: &str) -> App {
+        let mut app = App::new();
+        app.code_buffer = Buffer::from_text("test", text);
+        app
+    }
+
+    fn type_keys(app: &mut App, keys: &str) {
+        for ch in keys.chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn test_count_repeats_motion() {
+        let mut app = app_with("a\nb\nc\nd\ne");
+        type_keys(&mut app, "3j");
+        assert_eq!(app.code_buffer.cursor_row, 3);
+        assert_eq!(app.pending_count, None);
+
+        type_keys(&mut app, "j");
+        assert_eq!(app.code_buffer.cursor_row, 4);
+    }
+
+    #[test]
+    fn test_count_repeats_operator() {
+        let mut app = app_with("one\ntwo\nthree\nfour");
+        type_keys(&mut app, "2dd");
+        assert_eq!(app.code_buffer.content(), "three\nfour");
+        assert_eq!(app.pending_count, None);
+    }
+
+    #[test]
+    fn test_count_repeats_edit() {
+        let mut app = app_with("kick snare");
+        type_keys(&mut app, "4x");
+        assert_eq!(app.code_buffer.line(0), " snare");
+    }
+
+    #[test]
+    fn test_count_is_capped() {
+        let mut app = app_with("a\nb\nc");
+        type_keys(&mut app, "99999999999999999999");
+        assert_eq!(app.pending_count, Some(MAX_COUNT));
+
+        type_keys(&mut app, "j");
+        assert_eq!(app.code_buffer.cursor_row, 2);
+        assert_eq!(app.pending_count, None);
+    }
+
+    #[test]
+    fn test_zero_without_count_is_line_start() {
+        let mut app = app_with("a line of text");
+        app.code_buffer.cursor_col = 5;
+        type_keys(&mut app, "0");
+        assert_eq!(app.code_buffer.cursor_col, 0);
+        assert_eq!(app.pending_count, None);
+
+        type_keys(&mut app, "10l");
+        assert_eq!(app.code_buffer.cursor_col, 10);
+    }
+
+    #[test]
+    fn test_digit_after_replace_is_replacement() {
+        let mut app = app_with("x ~ x");
+        type_keys(&mut app, "r3");
+        assert_eq!(app.code_buffer.line(0), "3 ~ x");
+    }
+}
@@ -6,10 +6,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
+use rustic_lang::{TokenKind, tokenize};
+
 use crate::editor::{Buffer, Mode};
 
 /// The main code editor panel (Column 1).
-/// Displays the Buffer contents with line numbers, cursor, and visual selection.
+/// Displays the Buffer contents with line numbers, cursor, visual selection
+/// and syntax highlighting.
 pub struct CodeEditorPanel<'a> {
     buffer: &'a Buffer,
     mode: &'a Mode,
@@ -91,8 +94,9 @@ impl Widget for CodeEditorPanel<'_> {
             let line_num_str = format!("{:>width$} ", line_idx + 1, width = line_num_width);
 
             // Build spans for the line content, handling cursor and visual selection
+            let colors = highlight(line_text, self.buffer.scroll_x, &display_text);
             let content_spans =
-                self.build_line_spans(&display_text, line_idx, visual_range, text_width);
+                self.build_line_spans(&display_text, &colors, line_idx, visual_range, text_width);
 
             let mut spans = vec![Span::styled(line_num_str, line_num_style)];
             spans.extend(content_spans);
@@ -104,10 +108,40 @@ impl Widget for CodeEditorPanel<'_> {
     }
 }
 
+/// Colour of each token kind.
+fn token_color(kind: TokenKind) -> Color {
+    match kind {
+        TokenKind::Directive => Color::Magenta,
+        TokenKind::PatternName => Color::Yellow,
+        TokenKind::Instrument => Color::Cyan,
+        TokenKind::Pattern => Color::Green,
+        TokenKind::Transform => Color::LightBlue,
+        TokenKind::Number => Color::LightRed,
+        TokenKind::Pipe | TokenKind::Mute | TokenKind::Comment => Color::DarkGray,
+        TokenKind::Identifier => Color::White,
+    }
+}
+
+/// Foreground colour of each character of `display_text`, the part of
+/// `line` shown from byte `scroll_x` on.
+fn highlight(line: &str, scroll_x: usize, display_text: &str) -> Vec<Color> {
+    let tokens = tokenize(line);
+    display_text
+        .char_indices()
+        .map(|(offset, _)| {
+            tokens
+                .iter()
+                .find(|(_, span)| span.contains(scroll_x + offset))
+                .map_or(Color::White, |(kind, _)| token_color(*kind))
+        })
+        .collect()
+}
+
 impl CodeEditorPanel<'_> {
     fn build_line_spans(
         &self,
         display_text: &str,
+        colors: &[Color],
         line_idx: usize,
         visual_range: Option<(usize, usize, usize, usize)>,
         text_width: usize,
@@ -159,7 +193,7 @@ impl CodeEditorPanel<'_> {
             } else if in_visual {
                 Style::default().fg(Color::Black).bg(Color::Blue)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(colors.get(i).copied().unwrap_or(Color::White))
             };
 
             // Batch consecutive characters with the same style
@@ -171,7 +205,7 @@ impl CodeEditorPanel<'_> {
                 if next_is_cursor || (is_cursor && j == i + 1) {
                     break;
                 }
-                if next_in_visual != in_visual {
+                if next_in_visual != in_visual || colors.get(j) != colors.get(i) {
                     break;
                 }
                 j += 1;
@@ -0,0 +1,86 @@
+//! Block kernels (gain and mixing), comparing the default implementation with
+//! the scalar reference. Run with `--features simd` to measure the speedup of
+//! the vectorized path.
+//!
+//! Also compares `Filter::transform`, which allocates its output blocks, with
+//! `Filter::transform_into` writing into reused buffers.
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+
+use rustic::core::Block;
+use std::sync::Arc;
+
+use rustic::core::audio::{add_block, add_block_scalar, scale_block, scale_block_scalar};
+use rustic::core::filters::prelude::{GainFilter, LowPassFilter};
+use rustic::core::graph::Filter;
+
+const BLOCK_SIZES: [usize; 3] = [64, 512, 4096];
+
+fn ramp_block(len: usize) -> Block {
+    (0..len)
+        .map(|i| {
+            let s = (i as f32 * 0.001).sin();
+            [s, -s]
+        })
+        .collect()
+}
+
+fn bench_scale(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scale_block");
+    for size in BLOCK_SIZES {
+        let block = ramp_block(size);
+        group.bench_with_input(BenchmarkId::new("default", size), &block, |b, block| {
+            b.iter(|| scale_block(black_box(block), black_box(0.8)))
+        });
+        group.bench_with_input(BenchmarkId::new("scalar", size), &block, |b, block| {
+            b.iter(|| scale_block_scalar(black_box(block), black_box(0.8)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_add(c: &mut Criterion) {
+    let mut group = c.benchmark_group("add_block");
+    for size in BLOCK_SIZES {
+        let block = ramp_block(size);
+        let mut acc = ramp_block(size);
+        group.bench_with_input(BenchmarkId::new("default", size), &block, |b, block| {
+            b.iter(|| add_block(black_box(&mut acc), black_box(block)))
+        });
+        let mut acc = ramp_block(size);
+        group.bench_with_input(BenchmarkId::new("scalar", size), &block, |b, block| {
+            b.iter(|| add_block_scalar(black_box(&mut acc), black_box(block)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_transform(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transform");
+    for size in BLOCK_SIZES {
+        let block = Arc::new(ramp_block(size));
+        let filters: [(&str, Box<dyn Filter>); 2] = [
+            ("gain", Box::new(GainFilter::new(0.8))),
+            ("lowpass", Box::new(LowPassFilter::new(1000.0, 44100.0))),
+        ];
+        for (name, mut filter) in filters {
+            group.bench_function(BenchmarkId::new(format!("{name}/allocating"), size), |b| {
+                b.iter(|| {
+                    filter.push(block.clone(), 0);
+                    black_box(filter.transform())
+                })
+            });
+            let mut outputs = Vec::new();
+            group.bench_function(BenchmarkId::new(format!("{name}/in_place"), size), |b| {
+                b.iter(|| {
+                    filter.push(block.clone(), 0);
+                    filter.transform_into(black_box(&mut outputs));
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_scale, bench_add, bench_transform);
+criterion_main!(benches);
@@ -43,8 +43,8 @@ fn build_simple_system() -> System {
     let lp2 = system.add_filter(Box::new(LowPassFilter::new(1000.0, SAMPLE_RATE)));
 
     system.connect_source(src, lp1, 0);
-    system.connect(lp1, gain, 0, 0);
-    system.connect(gain, lp2, 0, 0);
+    system.connect(lp1, gain, 0, 0).unwrap();
+    system.connect(gain, lp2, 0, 0).unwrap();
 
     let sink = system.add_sink(Box::new(SimpleSink::new()));
     system.connect_sink(lp2, sink, 0);
@@ -87,10 +87,10 @@ fn build_complex_system() -> System {
     let att = system.add_filter(Box::new(GainFilter::new(0.4)));
 
     system.connect_source(src1, lp1, 0);
-    system.connect(lp1, sum_node, 0, 0); // forward path
-    system.connect(sum_node, delay, 0, 0); // into delay
-    system.connect(delay, att, 0, 0); // attenuate
-    system.connect(att, sum_node, 0, 0); // feedback → same port 0, summed
+    system.connect(lp1, sum_node, 0, 0).unwrap(); // forward path
+    system.connect(sum_node, delay, 0, 0).unwrap(); // into delay
+    system.connect(delay, att, 0, 0).unwrap(); // attenuate
+    system.connect(att, sum_node, 0, 0).unwrap(); // feedback → same port 0, summed
 
     // --- source 2: square 880 Hz → HighPass → Tremolo ---
     let src2 = system.add_source(simple_source(
@@ -109,7 +109,7 @@ fn build_complex_system() -> System {
     let tremolo = system.add_filter(Box::new(Tremolo::new(6.0, 0.5, SAMPLE_RATE)));
 
     system.connect_source(src2, hp, 0);
-    system.connect(hp, tremolo, 0, 0);
+    system.connect(hp, tremolo, 0, 0).unwrap();
 
     // --- source 3: sawtooth 220 Hz → 2× LowPass ---
     let src3 = system.add_source(simple_source(
@@ -128,7 +128,7 @@ fn build_complex_system() -> System {
     let lp3 = system.add_filter(Box::new(LowPassFilter::new(800.0, SAMPLE_RATE)));
 
     system.connect_source(src3, lp2, 0);
-    system.connect(lp2, lp3, 0, 0);
+    system.connect(lp2, lp3, 0, 0).unwrap();
 
     // --- source 4: white noise → 2× heavy LowPass ---
     let src4 = system.add_source(simple_source(
@@ -145,20 +145,20 @@ fn build_complex_system() -> System {
     let lp5 = system.add_filter(Box::new(LowPassFilter::new(200.0, SAMPLE_RATE)));
 
     system.connect_source(src4, lp4, 0);
-    system.connect(lp4, lp5, 0, 0);
+    system.connect(lp4, lp5, 0, 0).unwrap();
 
     // --- master: all paths fan-in on port 0 (summed), then compress + gain ---
     let master = system.add_filter(Box::new(GainFilter::new(0.25)));
     let compressor = system.add_filter(Box::new(Compressor::default()));
     let final_gain = system.add_filter(Box::new(GainFilter::new(0.8)));
 
-    system.connect(sum_node, master, 0, 0); // src1 path
-    system.connect(tremolo, master, 0, 0); // src2 path
-    system.connect(lp3, master, 0, 0); // src3 path
-    system.connect(lp5, master, 0, 0); // src4 path
+    system.connect(sum_node, master, 0, 0).unwrap(); // src1 path
+    system.connect(tremolo, master, 0, 0).unwrap(); // src2 path
+    system.connect(lp3, master, 0, 0).unwrap(); // src3 path
+    system.connect(lp5, master, 0, 0).unwrap(); // src4 path
 
-    system.connect(master, compressor, 0, 0);
-    system.connect(compressor, final_gain, 0, 0);
+    system.connect(master, compressor, 0, 0).unwrap();
+    system.connect(compressor, final_gain, 0, 0).unwrap();
 
     let sink = system.add_sink(Box::new(SimpleSink::new()));
     system.connect_sink(final_gain, sink, 0);
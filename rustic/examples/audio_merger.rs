@@ -47,11 +47,11 @@ pub fn main() {
         let filter_4 = system.add_filter(Box::new(GainFilter::new(0.4)));
         let filter_5 = system.add_filter(Box::new(GainFilter::new(0.5)));
 
-        system.connect(mixer, filter_1, 0, 0);
-        system.connect(mixer, filter_2, 0, 0);
-        system.connect(mixer, filter_3, 0, 0);
-        system.connect(mixer, filter_4, 0, 0);
-        system.connect(mixer, filter_5, 0, 0);
+        system.connect(mixer, filter_1, 0, 0).unwrap();
+        system.connect(mixer, filter_2, 0, 0).unwrap();
+        system.connect(mixer, filter_3, 0, 0).unwrap();
+        system.connect(mixer, filter_4, 0, 0).unwrap();
+        system.connect(mixer, filter_5, 0, 0).unwrap();
 
         system.connect_sink(filter_1, 0, 0);
         system.connect_sink(filter_2, 1, 0);
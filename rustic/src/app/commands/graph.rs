@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// All graph-related commands: structural mutations + playback control.
 ///
 /// Structural variants (AddNode, RemoveNode, Connect, Disconnect) mutate
-/// `GraphData` in the command thread only.
+/// `GraphData` in the command thread only, and can be reverted with Undo.
 ///
 /// Playback variants (Play, Pause, Stop, SetParameter) also send
 /// `AudioMessage`s to the render thread.
@@ -25,9 +27,12 @@ pub enum GraphCommand {
         to: u64,
         to_port: usize,
     },
+    /// Remove one connection, given by the same ports as its `Connect`.
     Disconnect {
         from: u64,
+        from_port: usize,
         to: u64,
+        to_port: usize,
     },
 
     // -- Parameter modulation (CV input) --
@@ -66,6 +71,14 @@ pub enum GraphCommand {
     /// Recompile the current graph topology and hot-swap it into the render thread.
     /// Useful after a series of edits to force a clean push.
     Compile,
+
+    // -- Edit history (command-thread only) --
+    /// Revert the latest structural edit. A removed node comes back with its
+    /// parameters and audio connections, but not its modulation wires.
+    Undo,
+    /// Apply again the latest edit reverted by `Undo`. Any new structural
+    /// edit clears the edits left to redo.
+    Redo,
 }
 
 /// The kind of node in the audio graph
@@ -75,3 +88,38 @@ pub enum NodeKind {
     Filter,
     Sink,
 }
+
+/// Snapshot of the visual graph, as returned by
+/// [`App::graph_topology`](crate::app::App::graph_topology).
+///
+/// Nodes and edges are identified by the ids given in
+/// [`GraphCommand::AddNode`], so the frontend can match them with its own
+/// nodes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphTopology {
+    /// Nodes, sorted by id.
+    pub nodes: Vec<TopologyNode>,
+    /// Audio connections, sorted by source then destination.
+    pub edges: Vec<TopologyEdge>,
+}
+
+/// A node of a [`GraphTopology`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopologyNode {
+    pub id: u64,
+    pub kind: NodeKind,
+    /// Type id the node was added with, e.g. `"sine"` or `"low_pass_filter"`.
+    pub node_type: String,
+    /// Current scalar parameter values by field name. Generators can't be
+    /// queried, so only the values set since they were added are listed.
+    pub parameters: BTreeMap<String, f32>,
+}
+
+/// An audio connection of a [`GraphTopology`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologyEdge {
+    pub from: u64,
+    pub from_port: usize,
+    pub to: u64,
+    pub to_port: usize,
+}
@@ -3,17 +3,22 @@
 //! [`GraphData`] holds the user-built audio graph (nodes, connections, ID maps).
 //! [`handle_graph_command`] mutates it in response to [`GraphCommand`]s and
 //! hot-swaps the result into the render thread via a [`AudioMessage::Graph`] swap.
+//! [`graph_topology`] describes it back to the frontend. Structural edits are
+//! recorded in an [`EditHistory`] so they can be undone and redone.
+//!
+//! Structural edits and parameter changes only need the command-thread copy,
+//! so they are accepted before the engine is started; playback control and
+//! [`GraphCommand::Compile`] need the render thread.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use petgraph::prelude::NodeIndex;
 
-use crate::app::commands::{GraphCommand, NodeKind};
+use crate::app::commands::{GraphCommand, GraphTopology, NodeKind, TopologyEdge, TopologyNode};
 use crate::app::error::AppError;
+use crate::app::graph_history::{EditHistory, GraphEdit};
 use crate::audio::{AudioMessage, GraphAudioMessage};
-use crate::core::generator::prelude::builder::*;
-use crate::core::generator::prelude::*;
-use crate::core::graph::{AudioOutputSink, Filter, ModTarget, SimpleSource, Source, System};
+use crate::core::graph::{AudioOutputSink, Filter, ModTarget, Source, System};
 
 /// State for the visual audio graph editor.
 #[derive(Default)]
@@ -22,20 +27,70 @@ pub(crate) struct GraphData {
     pub filter_map: HashMap<u64, NodeIndex<u32>>,
     pub source_map: HashMap<u64, usize>,
     pub sink_map: HashMap<u64, usize>,
+    /// Type id each node was added with
+    pub node_types: HashMap<u64, String>,
+    /// Editor position each node was added at
+    pub node_positions: HashMap<u64, (f32, f32)>,
+    /// Parameters set on generators, which can't be read back from a `Source`
+    pub source_parameters: HashMap<u64, BTreeMap<String, f32>>,
+    pub history: EditHistory,
 }
 
 pub(crate) fn handle_graph_command(
     cmd: GraphCommand,
     gs: &mut GraphData,
     sample_rate: f32,
-    message_tx: &crossbeam::channel::Sender<AudioMessage>,
+    message_tx: Option<&crossbeam::channel::Sender<AudioMessage>>,
+) -> Result<(), AppError> {
+    match cmd {
+        GraphCommand::Undo => {
+            if let Some(edit) = gs.history.pop_undo() {
+                for inverse in edit.inverse.iter().cloned() {
+                    apply_graph_command(inverse, gs, sample_rate, message_tx)?;
+                }
+                gs.history.push_redo(edit);
+            }
+            Ok(())
+        }
+        GraphCommand::Redo => {
+            if let Some(edit) = gs.history.pop_redo() {
+                apply_graph_command(edit.command.clone(), gs, sample_rate, message_tx)?;
+                gs.history.push_undo(edit);
+            }
+            Ok(())
+        }
+        GraphCommand::AddNode { .. }
+        | GraphCommand::RemoveNode { .. }
+        | GraphCommand::Connect { .. }
+        | GraphCommand::Disconnect { .. } => {
+            let inverse = inverse_of(&cmd, gs);
+            apply_graph_command(cmd.clone(), gs, sample_rate, message_tx)?;
+            // Edits that changed nothing have nothing to undo
+            if !inverse.is_empty() {
+                gs.history.record(GraphEdit {
+                    command: cmd,
+                    inverse,
+                });
+            }
+            Ok(())
+        }
+        cmd => apply_graph_command(cmd, gs, sample_rate, message_tx),
+    }
+}
+
+/// Applies a command to the graph, without recording it in the history.
+fn apply_graph_command(
+    cmd: GraphCommand,
+    gs: &mut GraphData,
+    sample_rate: f32,
+    message_tx: Option<&crossbeam::channel::Sender<AudioMessage>>,
 ) -> Result<(), AppError> {
     match cmd {
         GraphCommand::AddNode {
             id,
             node_type,
             kind,
-            ..
+            position,
         } => {
             match kind {
                 NodeKind::Generator => {
@@ -59,13 +114,24 @@ pub(crate) fn handle_graph_command(
                     gs.sink_map.insert(id, idx);
                 }
             }
+            gs.node_types.insert(id, node_type);
+            gs.node_positions.insert(id, position);
             recompute_topology(gs)
         }
 
         GraphCommand::RemoveNode { id } => {
+            gs.node_types.remove(&id);
+            gs.node_positions.remove(&id);
+            gs.source_parameters.remove(&id);
             if let Some(idx) = gs.filter_map.remove(&id) {
+                let last = NodeIndex::new(gs.system.filters_len().saturating_sub(1));
                 gs.system.remove_filter(idx);
-                // NodeIndex-based map — petgraph handles index invalidation automatically
+                // petgraph moves the last node into the freed index
+                for v in gs.filter_map.values_mut() {
+                    if *v == last {
+                        *v = idx;
+                    }
+                }
             } else if let Some(idx) = gs.source_map.remove(&id) {
                 gs.system.remove_source(idx);
                 // Vec::remove shifts elements; update all stored source indices
@@ -106,7 +172,9 @@ pub(crate) fn handle_graph_command(
             } else if !from_is_source && !to_is_sink {
                 let from_idx = gs.filter_map[&from];
                 let to_idx = gs.filter_map[&to];
-                gs.system.connect(from_idx, to_idx, from_port, to_port);
+                gs.system
+                    .connect(from_idx, to_idx, from_port, to_port)
+                    .map_err(|e| AppError::AudioError(e.to_string()))?;
             } else {
                 // from_is_source && to_is_sink: direct wire, no filter in between
                 let src_idx = gs.source_map[&from];
@@ -119,7 +187,12 @@ pub(crate) fn handle_graph_command(
             recompute_topology(gs)
         }
 
-        GraphCommand::Disconnect { from, to } => {
+        GraphCommand::Disconnect {
+            from,
+            from_port,
+            to,
+            to_port,
+        } => {
             let from_is_source = gs.source_map.contains_key(&from);
             let to_is_sink = gs.sink_map.contains_key(&to);
 
@@ -131,18 +204,24 @@ pub(crate) fn handle_graph_command(
                 if let (Some(&src_idx), Some(&filter_idx)) =
                     (gs.source_map.get(&from), gs.filter_map.get(&to))
                 {
-                    gs.system.disconnect_source(src_idx, filter_idx);
+                    gs.system
+                        .disconnect_source_port(src_idx, filter_idx, to_port);
                 }
             } else if !from_is_source
                 && !to_is_sink
                 && let (Some(&from_idx), Some(&to_idx)) =
                     (gs.filter_map.get(&from), gs.filter_map.get(&to))
             {
-                let _ = gs.system.disconnect(from_idx, to_idx);
+                let _ = gs
+                    .system
+                    .disconnect_ports(from_idx, to_idx, from_port, to_port);
+            } else if !from_is_source
+                && to_is_sink
+                && let (Some(&filter_idx), Some(&sink_idx)) =
+                    (gs.filter_map.get(&from), gs.sink_map.get(&to))
+            {
+                gs.system.disconnect_sink(filter_idx, sink_idx, from_port);
             }
-            // filter→sink: sinks track a single (from, port) connection; the new connection
-            // from connect_sink() will overwrite it on the next Connect, so no explicit
-            // disconnect method is needed.
             recompute_topology(gs)
         }
 
@@ -162,13 +241,14 @@ pub(crate) fn handle_graph_command(
                 if let Some(target) = target {
                     gs.system
                         .add_mod_wire(src_idx, target.clone(), param_name.clone());
-                    message_tx
-                        .send(AudioMessage::Graph(GraphAudioMessage::AddModulation {
+                    notify(
+                        message_tx,
+                        AudioMessage::Graph(GraphAudioMessage::AddModulation {
                             from_source: src_idx,
                             target,
                             param_name,
-                        }))
-                        .map_err(|_| AppError::ChannelClosed)?;
+                        }),
+                    )?;
                 }
             }
             Ok(())
@@ -189,13 +269,14 @@ pub(crate) fn handle_graph_command(
                 };
                 if let Some(target) = target {
                     gs.system.remove_mod_wire(src_idx, &target, &param_name);
-                    message_tx
-                        .send(AudioMessage::Graph(GraphAudioMessage::RemoveModulation {
+                    notify(
+                        message_tx,
+                        AudioMessage::Graph(GraphAudioMessage::RemoveModulation {
                             from_source: src_idx,
                             target,
                             param_name,
-                        }))
-                        .map_err(|_| AppError::ChannelClosed)?;
+                        }),
+                    )?;
                 }
             }
             Ok(())
@@ -207,28 +288,36 @@ pub(crate) fn handle_graph_command(
             value,
         } => {
             if let Some(&idx) = gs.filter_map.get(&node_id) {
-                // Keep the command-thread topology copy in sync for mix_mode
+                // Keep the command-thread copy in sync
                 if param_name == "mix_mode" {
                     let mode = rustic_meta::MixMode::from_ordinal(value as usize);
                     gs.system.set_mix_mode(idx, mode);
+                } else if let Some(filter) = gs.system.get_filter_mut(idx) {
+                    filter.set_parameter(&param_name, value);
                 }
-                message_tx
-                    .send(AudioMessage::Graph(GraphAudioMessage::SetParameter {
+                notify(
+                    message_tx,
+                    AudioMessage::Graph(GraphAudioMessage::SetParameter {
                         node_index: idx.index(),
                         param_name,
                         value,
-                    }))
-                    .map_err(|_| AppError::ChannelClosed)
+                    }),
+                )
             } else if let Some(&src_idx) = gs.source_map.get(&node_id) {
                 // Keep the command-thread copy in sync
                 gs.system.set_source_parameter(src_idx, &param_name, value);
-                message_tx
-                    .send(AudioMessage::Graph(GraphAudioMessage::SetSourceParameter {
+                gs.source_parameters
+                    .entry(node_id)
+                    .or_default()
+                    .insert(param_name.clone(), value);
+                notify(
+                    message_tx,
+                    AudioMessage::Graph(GraphAudioMessage::SetSourceParameter {
                         source_index: src_idx,
                         param_name,
                         value,
-                    }))
-                    .map_err(|_| AppError::ChannelClosed)
+                    }),
+                )
             } else {
                 Ok(())
             }
@@ -239,11 +328,10 @@ pub(crate) fn handle_graph_command(
                 log::info!(
                     "[graph] StartNode id={id} → source_index={idx}, sending StartSource to render thread"
                 );
-                message_tx
-                    .send(AudioMessage::Graph(GraphAudioMessage::StartSource {
-                        source_index: idx,
-                    }))
-                    .map_err(|_| AppError::ChannelClosed)
+                send_to_render_thread(
+                    message_tx,
+                    AudioMessage::Graph(GraphAudioMessage::StartSource { source_index: idx }),
+                )
             } else {
                 log::warn!(
                     "[graph] StartNode id={id} → NOT found in source_map (known sources: {:?})",
@@ -256,11 +344,10 @@ pub(crate) fn handle_graph_command(
         GraphCommand::StopNode { id } => {
             if let Some(&idx) = gs.source_map.get(&id) {
                 log::info!("[graph] StopNode id={id} → source_index={idx} (graceful stop)");
-                message_tx
-                    .send(AudioMessage::Graph(GraphAudioMessage::StopSource {
-                        source_index: idx,
-                    }))
-                    .map_err(|_| AppError::ChannelClosed)
+                send_to_render_thread(
+                    message_tx,
+                    AudioMessage::Graph(GraphAudioMessage::StopSource { source_index: idx }),
+                )
             } else {
                 Ok(())
             }
@@ -269,24 +356,248 @@ pub(crate) fn handle_graph_command(
         GraphCommand::KillNode { id } => {
             if let Some(&idx) = gs.source_map.get(&id) {
                 log::info!("[graph] KillNode id={id} → source_index={idx} (immediate kill)");
-                message_tx
-                    .send(AudioMessage::Graph(GraphAudioMessage::KillSource {
-                        source_index: idx,
-                    }))
-                    .map_err(|_| AppError::ChannelClosed)
+                send_to_render_thread(
+                    message_tx,
+                    AudioMessage::Graph(GraphAudioMessage::KillSource { source_index: idx }),
+                )
             } else {
                 Ok(())
             }
         }
 
         GraphCommand::Compile => rebuild_and_swap(gs, message_tx),
+
+        // The history is handled by `handle_graph_command`
+        GraphCommand::Undo | GraphCommand::Redo => Ok(()),
     }
 }
 
+/// Commands reverting a structural edit, captured before it is applied.
+/// Empty when the edit would not change the graph.
+fn inverse_of(cmd: &GraphCommand, gs: &GraphData) -> Vec<GraphCommand> {
+    match cmd {
+        GraphCommand::AddNode { id, .. } => vec![GraphCommand::RemoveNode { id: *id }],
+        GraphCommand::RemoveNode { id } => {
+            let topology = graph_topology(gs);
+            let Some(node) = topology.nodes.into_iter().find(|node| node.id == *id) else {
+                return Vec::new();
+            };
+            // Re-add the node with its parameters, then wire it back
+            let mut inverse = vec![GraphCommand::AddNode {
+                id: node.id,
+                node_type: node.node_type,
+                kind: node.kind,
+                position: gs.node_positions.get(id).copied().unwrap_or_default(),
+            }];
+            inverse.extend(node.parameters.into_iter().map(|(param_name, value)| {
+                GraphCommand::SetParameter {
+                    node_id: *id,
+                    param_name,
+                    value,
+                }
+            }));
+            inverse.extend(
+                topology
+                    .edges
+                    .into_iter()
+                    .filter(|edge| edge.from == *id || edge.to == *id)
+                    .map(connect_command),
+            );
+            inverse
+        }
+        GraphCommand::Connect {
+            from,
+            from_port,
+            to,
+            to_port,
+        } => {
+            // Direct source → sink wires are never doubled, so connecting an
+            // existing one changes nothing
+            let direct = gs.source_map.contains_key(from) && gs.sink_map.contains_key(to);
+            if direct && has_edge(gs, *from, *from_port, *to, *to_port) {
+                return Vec::new();
+            }
+            vec![GraphCommand::Disconnect {
+                from: *from,
+                from_port: *from_port,
+                to: *to,
+                to_port: *to_port,
+            }]
+        }
+        GraphCommand::Disconnect {
+            from,
+            from_port,
+            to,
+            to_port,
+        } => {
+            if !has_edge(gs, *from, *from_port, *to, *to_port) {
+                return Vec::new();
+            }
+            // Only the one connection removed is restored
+            vec![GraphCommand::Connect {
+                from: *from,
+                from_port: *from_port,
+                to: *to,
+                to_port: *to_port,
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Whether the graph holds the connection `from:from_port → to:to_port`.
+/// Sources have a single output and sinks a single input, so their port is
+/// not compared.
+fn has_edge(gs: &GraphData, from: u64, from_port: usize, to: u64, to_port: usize) -> bool {
+    let any_out = gs.source_map.contains_key(&from);
+    let any_in = gs.sink_map.contains_key(&to);
+    graph_topology(gs).edges.iter().any(|edge| {
+        edge.from == from
+            && edge.to == to
+            && (any_out || edge.from_port == from_port)
+            && (any_in || edge.to_port == to_port)
+    })
+}
+
+fn connect_command(edge: TopologyEdge) -> GraphCommand {
+    GraphCommand::Connect {
+        from: edge.from,
+        from_port: edge.from_port,
+        to: edge.to,
+        to_port: edge.to_port,
+    }
+}
+
+/// Describes the visual graph: every node with its type and parameter
+/// values, and every audio connection, by frontend node id.
+pub(crate) fn graph_topology(gs: &GraphData) -> GraphTopology {
+    let descriptor = gs.system.to_descriptor();
+    let filter_ids: HashMap<usize, u64> = gs
+        .filter_map
+        .iter()
+        .map(|(&id, idx)| (idx.index(), id))
+        .collect();
+    let source_ids: HashMap<usize, u64> = gs.source_map.iter().map(|(&id, &i)| (i, id)).collect();
+    let sink_ids: HashMap<usize, u64> = gs.sink_map.iter().map(|(&id, &i)| (i, id)).collect();
+    let node_type = |id: u64| gs.node_types.get(&id).cloned().unwrap_or_default();
+
+    let mut nodes: Vec<TopologyNode> = Vec::new();
+    for &id in gs.source_map.keys() {
+        nodes.push(TopologyNode {
+            id,
+            kind: NodeKind::Generator,
+            node_type: node_type(id),
+            parameters: gs.source_parameters.get(&id).cloned().unwrap_or_default(),
+        });
+    }
+    for (&id, idx) in &gs.filter_map {
+        let Some(filter) = descriptor.nodes.get(idx.index()) else {
+            continue;
+        };
+        nodes.push(TopologyNode {
+            id,
+            kind: NodeKind::Filter,
+            node_type: gs
+                .node_types
+                .get(&id)
+                .cloned()
+                .unwrap_or_else(|| filter.type_id.clone()),
+            parameters: filter.parameters.clone(),
+        });
+    }
+    for &id in gs.sink_map.keys() {
+        nodes.push(TopologyNode {
+            id,
+            kind: NodeKind::Sink,
+            node_type: node_type(id),
+            parameters: BTreeMap::new(),
+        });
+    }
+    nodes.sort_by_key(|node| node.id);
+
+    let mut edges: Vec<TopologyEdge> = Vec::new();
+    for edge in &descriptor.edges {
+        if let (Some(&from), Some(&to)) = (filter_ids.get(&edge.from), filter_ids.get(&edge.to)) {
+            edges.push(TopologyEdge {
+                from,
+                from_port: edge.out_port,
+                to,
+                to_port: edge.in_port,
+            });
+        }
+    }
+    for (index, source) in descriptor.sources.iter().enumerate() {
+        let Some(&from) = source_ids.get(&index) else {
+            continue;
+        };
+        for (node, port) in &source.targets {
+            if let Some(&to) = filter_ids.get(node) {
+                edges.push(TopologyEdge {
+                    from,
+                    from_port: 0,
+                    to,
+                    to_port: *port,
+                });
+            }
+        }
+    }
+    for (index, sink) in descriptor.sinks.iter().enumerate() {
+        let Some(&to) = sink_ids.get(&index) else {
+            continue;
+        };
+        for (node, port) in &sink.inputs {
+            if let Some(&from) = filter_ids.get(node) {
+                edges.push(TopologyEdge {
+                    from,
+                    from_port: *port,
+                    to,
+                    to_port: 0,
+                });
+            }
+        }
+        for source in &sink.direct_sources {
+            if let Some(&from) = source_ids.get(source) {
+                edges.push(TopologyEdge {
+                    from,
+                    from_port: 0,
+                    to,
+                    to_port: 0,
+                });
+            }
+        }
+    }
+    edges.sort_by_key(|edge| (edge.from, edge.to, edge.from_port, edge.to_port));
+
+    GraphTopology { nodes, edges }
+}
+
 // ---------------------------------------------------------------------------
 // Private helpers
 // ---------------------------------------------------------------------------
 
+/// Forwards a live update to the render thread, if the engine is running.
+/// Otherwise the command-thread copy already holds it for the next swap.
+fn notify(
+    message_tx: Option<&crossbeam::channel::Sender<AudioMessage>>,
+    message: AudioMessage,
+) -> Result<(), AppError> {
+    match message_tx {
+        Some(tx) => tx.send(message).map_err(|_| AppError::ChannelClosed),
+        None => Ok(()),
+    }
+}
+
+/// Sends a message that only makes sense with a running render thread.
+fn send_to_render_thread(
+    message_tx: Option<&crossbeam::channel::Sender<AudioMessage>>,
+    message: AudioMessage,
+) -> Result<(), AppError> {
+    message_tx
+        .ok_or(AppError::NotStarted)?
+        .send(message)
+        .map_err(|_| AppError::ChannelClosed)
+}
+
 /// Recompile the topology graph in the command-thread copy only.
 /// Does NOT push a Swap to the render thread — use `rebuild_and_swap` for that.
 fn recompute_topology(gs: &mut GraphData) -> Result<(), AppError> {
@@ -299,8 +610,9 @@ fn recompute_topology(gs: &mut GraphData) -> Result<(), AppError> {
 /// Only called by `GraphCommand::Compile`.
 fn rebuild_and_swap(
     gs: &mut GraphData,
-    message_tx: &crossbeam::channel::Sender<AudioMessage>,
+    message_tx: Option<&crossbeam::channel::Sender<AudioMessage>>,
 ) -> Result<(), AppError> {
+    let message_tx = message_tx.ok_or(AppError::NotStarted)?;
     gs.system
         .compute()
         .map_err(|e| AppError::AudioError(format!("{:?}", e)))?;
@@ -311,31 +623,16 @@ fn rebuild_and_swap(
 }
 
 fn create_source(node_type: &str, sample_rate: f32) -> Result<Box<dyn Source>, String> {
-    let waveform: Waveform = node_type.into();
-    if let Waveform::Err(name) = waveform {
-        return Err(format!("Unknown generator type: {name}"));
-    }
-    // FrequencyRelation::Identity makes update_frequency() track the base frequency in
-    // real-time (1:1 ratio), so changing the "frequency" parameter while a note plays
-    // immediately affects the pitch. Without a relation the call is a no-op.
-    let generator: MultiToneGenerator = ToneGeneratorBuilder::new()
-        .waveform(waveform)
-        .frequency_relation(FrequencyRelation::Identity)
-        .build()
-        .into();
-    Ok(SimpleSource::new(generator, sample_rate).boxed())
+    crate::meta::GeneratorRegistry::global()
+        .create(node_type, sample_rate)
+        .ok_or_else(|| format!("Unknown generator type: {node_type}"))
 }
 
 fn create_filter(node_type: &str, sample_rate: f32) -> Result<Box<dyn Filter>, String> {
-    for entry in inventory::iter::<crate::meta::FilterRegistration>() {
-        let info = (entry.info)();
-        if info.type_id == node_type {
-            let mut filter = (entry.create)();
-            // Apply sample_rate to any filter that exposes it as a parameter.
-            // Filters without a "sample_rate" field will silently ignore this (logged at debug).
-            filter.set_parameter("sample_rate", sample_rate);
-            return Ok(filter);
-        }
-    }
-    Err(format!("Unknown filter type: {}", node_type))
+    let mut filter = crate::meta::FilterRegistry::global()
+        .create(node_type)
+        .ok_or_else(|| format!("Unknown filter type: {}", node_type))?;
+    // Registry filters are built for the default rate
+    filter.set_sample_rate(sample_rate);
+    Ok(filter)
 }
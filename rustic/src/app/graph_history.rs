@@ -0,0 +1,56 @@
+//! Undo/redo history of the visual graph editor.
+//!
+//! Every structural [`GraphCommand`] applied by the graph handler is recorded
+//! along with the commands that revert it, captured from the graph state
+//! just before it was applied.
+
+use crate::app::commands::GraphCommand;
+
+/// Edits kept before the oldest ones are forgotten.
+const MAX_HISTORY: usize = 256;
+
+/// An applied edit and the commands reverting it, in the order to apply them.
+#[derive(Debug, Clone)]
+pub(crate) struct GraphEdit {
+    pub command: GraphCommand,
+    pub inverse: Vec<GraphCommand>,
+}
+
+/// Undo and redo stacks of [`GraphEdit`]s.
+#[derive(Debug, Default)]
+pub(crate) struct EditHistory {
+    undo: Vec<GraphEdit>,
+    redo: Vec<GraphEdit>,
+}
+
+impl EditHistory {
+    /// Records a new edit. The redo stack is dropped, as it no longer
+    /// follows from the current state.
+    pub fn record(&mut self, edit: GraphEdit) {
+        self.redo.clear();
+        self.push_undo(edit);
+    }
+
+    /// Takes the latest edit to revert.
+    pub fn pop_undo(&mut self) -> Option<GraphEdit> {
+        self.undo.pop()
+    }
+
+    /// Takes the latest reverted edit to apply again.
+    pub fn pop_redo(&mut self) -> Option<GraphEdit> {
+        self.redo.pop()
+    }
+
+    /// Stores an edit that can be undone, without touching the redo stack.
+    pub fn push_undo(&mut self, edit: GraphEdit) {
+        if self.undo.len() == MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.undo.push(edit);
+    }
+
+    /// Stores a reverted edit that can be redone.
+    pub fn push_redo(&mut self, edit: GraphEdit) {
+        self.redo.push(edit);
+    }
+}
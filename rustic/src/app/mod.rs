@@ -5,8 +5,7 @@ use std::sync::atomic::Ordering;
 use std::sync::mpsc::{Receiver, Sender, channel};
 use std::sync::{Arc, Mutex};
 
-use cpal::SampleRate;
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::traits::{DeviceTrait, StreamTrait};
 use log::info;
 
 pub mod audio_graph;
@@ -15,6 +14,7 @@ mod config;
 mod error;
 mod filesystem;
 pub(crate) mod graph_handler;
+mod graph_history;
 mod system;
 
 use crate::app::audio_graph::AudioGraph;
@@ -27,9 +27,9 @@ use crate::audio::{
 use crate::core::utils::Note;
 use crate::instruments::Instrument;
 
-use commands::{AppCommand, AudioCommand, SystemCommand};
+use commands::{AppCommand, AudioCommand, GraphTopology, SystemCommand};
 use config::AppConfig;
-use graph_handler::{GraphData, handle_graph_command};
+use graph_handler::{GraphData, graph_topology, handle_graph_command};
 use prelude::*;
 
 // Export essential types directly from the app module
@@ -147,25 +147,15 @@ impl App {
         let (message_tx, message_rx) = crossbeam::channel::bounded(config.message_ring_buffer_size);
 
         let host = cpal::default_host();
-        let device = host.default_output_device().ok_or(AudioError::NoDevice)?;
+        let device =
+            crate::audio::device::open_output_device(&host, config.device_name.as_deref())?;
 
-        let mut supported_configs_range = device
+        let supported: Vec<_> = device
             .supported_output_configs()
-            .map_err(|e| AudioError::StreamError(e.to_string()))?;
-
-        let supported_config = supported_configs_range
-            .find(|c| c.channels() == crate::core::audio::CHANNELS as u16)
-            .or_else(|| {
-                device
-                    .supported_output_configs()
-                    .ok()
-                    .and_then(|mut r| r.next())
-            })
-            .ok_or(AudioError::StreamError("No supported config".to_string()))?
-            .with_sample_rate(SampleRate(44100));
-
-        let mut cpal_config = supported_config.config();
-        cpal_config.buffer_size = cpal::BufferSize::Fixed(config.cpal_buffer_size as u32);
+            .map_err(|e| AudioError::StreamError(e.to_string()))?
+            .collect();
+        let cpal_config = crate::audio::resolve_stream_config(&supported, &config)
+            .ok_or(AudioError::StreamError("No supported config".to_string()))?;
 
         let sample_rate = cpal_config.sample_rate.0;
         self.config.system.sample_rate = sample_rate;
@@ -177,10 +167,17 @@ impl App {
             .store(self.config.system.master_volume, Ordering::Relaxed);
 
         info!(
-            "Audio config: sample_rate={sample_rate}, buffer_size={}, ring_buffer={}",
-            config.cpal_buffer_size, config.audio_ring_buffer_size
+            "Audio config: sample_rate={sample_rate}, buffer_size={:?}, ring_buffer={}",
+            cpal_config.buffer_size, config.audio_ring_buffer_size
         );
 
+        // The graph editor's filters may have been built for another device
+        self.graph_system
+            .get_mut()
+            .unwrap()
+            .system
+            .set_sample_rate(sample_rate as f32);
+
         let compiled = self
             .audio_graph
             .compile(sample_rate as f32)
@@ -197,24 +194,54 @@ impl App {
 
         let callback = crate::audio::create_cpal_callback(audio_queue, shared_state.clone());
 
-        let stream = device
-            .build_output_stream(
-                &cpal_config,
-                callback,
-                move |err| log::error!("Audio stream error: {}", err),
-                None,
-            )
+        // The stream lives on its own thread (cpal::Stream is not Send) and is
+        // dropped once the handle signals shutdown and unparks it.
+        let (ready_tx, ready_rx) = channel();
+        let stream_state = shared_state.clone();
+        let stream_thread = std::thread::Builder::new()
+            .name("audio-stream".to_string())
+            .spawn(move || {
+                let stream = match device
+                    .build_output_stream(
+                        &cpal_config,
+                        callback,
+                        move |err| log::error!("Audio stream error: {}", err),
+                        None,
+                    )
+                    .map_err(|e| AudioError::StreamError(e.to_string()))
+                    .and_then(|stream| {
+                        stream
+                            .play()
+                            .map_err(|e| AudioError::StreamError(e.to_string()))?;
+                        Ok(stream)
+                    }) {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+                let _ = ready_tx.send(Ok(()));
+
+                while !stream_state.shutdown.load(Ordering::Acquire) {
+                    std::thread::park();
+                }
+                drop(stream);
+                log::info!("Audio stream closed");
+            })
             .map_err(|e| AudioError::StreamError(e.to_string()))?;
 
-        stream
-            .play()
-            .map_err(|e| AudioError::StreamError(e.to_string()))?;
+        let handle = AudioHandle::new(render_thread, Some(stream_thread), shared_state);
+        if let Err(e) = ready_rx.recv().unwrap_or(Err(AudioError::ThreadPanic)) {
+            let _ = handle.shutdown();
+            return Err(e);
+        }
 
         event_tx.send(BackendEvent::Status(StatusEvent::AudioStarted {
             sample_rate,
         }));
 
-        self.handle = Some(AudioHandle::new(render_thread, stream, shared_state));
+        self.handle = Some(handle);
         self.message_tx = Some(message_tx);
 
         Ok(event_rx)
@@ -265,6 +292,8 @@ impl App {
     ///
     /// `AudioCommand`s are translated to source-index `AudioMessage`s internally.
     /// `GraphCommand`s mutate the visual graph and hot-swap the compiled result.
+    /// Graph edits are accepted before [`start()`](Self::start); playback
+    /// control and `Compile` need a running engine.
     pub fn send(&self, command: Command) -> Result<(), AppError> {
         match command {
             Command::Audio(AudioCommand::NoteStart {
@@ -281,10 +310,9 @@ impl App {
             Command::Audio(AudioCommand::Shutdown) => self.send_message(AudioMessage::Shutdown),
 
             Command::Graph(cmd) => {
-                let message_tx = self.message_tx.as_ref().ok_or(AppError::NotStarted)?;
                 let sample_rate = self.config.system.sample_rate as f32;
                 let mut gs = self.graph_system.lock().unwrap();
-                handle_graph_command(cmd, &mut gs, sample_rate, message_tx)
+                handle_graph_command(cmd, &mut gs, sample_rate, self.message_tx.as_ref())
             }
 
             Command::App(AppCommand::System(SystemCommand::SetMasterVolume(vol))) => {
@@ -299,6 +327,14 @@ impl App {
         }
     }
 
+    /// Describes the visual graph built through [`GraphCommand`]s: its nodes,
+    /// with their current parameter values, and the connections between them.
+    ///
+    /// [`GraphCommand`]: commands::GraphCommand
+    pub fn graph_topology(&self) -> GraphTopology {
+        graph_topology(&self.graph_system.lock().unwrap())
+    }
+
     /// Stop the engine: signal shutdown and join the render thread.
     pub fn stop(&mut self) -> Result<(), AppError> {
         if let Some(ref tx) = self.message_tx {
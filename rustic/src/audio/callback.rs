@@ -2,9 +2,10 @@
 
 use crossbeam::queue::ArrayQueue;
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
+use std::time::Instant;
 
-use super::shared_state::SharedAudioState;
+use super::shared_state::{CallbackDiagnostic, SharedAudioState};
+use crate::core::audio::CHANNELS;
 
 /// Creates a cpal audio callback that reads from the ring buffer
 ///
@@ -13,29 +14,49 @@ use super::shared_state::SharedAudioState;
 /// - Only reads from the ring buffer and copies to output
 /// - NO allocations, NO locks, NO complex logic
 /// - Falls back to silence on buffer underrun
+/// - Records its own load and underruns in the shared state
+///
+/// See [`fill_output`] for the callback body.
 pub fn create_cpal_callback(
     audio_queue: Arc<ArrayQueue<f32>>,
     shared_state: Arc<SharedAudioState>,
 ) -> impl FnMut(&mut [f32], &cpal::OutputCallbackInfo) + Send + 'static {
     move |data: &mut [f32], _info: &cpal::OutputCallbackInfo| {
-        let available = audio_queue.len();
-
-        if available >= data.len() {
-            // Happy path: read pre-rendered audio
-            for sample in data.iter_mut() {
-                *sample = audio_queue.pop().unwrap_or(0.0);
-            }
-        } else {
-            // Buffer underrun: fill with silence
-            data.fill(0.0);
-            shared_state
-                .buffer_underruns
-                .fetch_add(1, Ordering::Relaxed);
-
-            // Also try to read whatever is available to keep buffer clean
-            for sample in data.iter_mut().take(available) {
-                *sample = audio_queue.pop().unwrap_or(0.0);
-            }
+        fill_output(data, &audio_queue, &shared_state);
+    }
+}
+
+/// Body of the cpal callback: fills `data` from the ring buffer and records
+/// timing and underruns. Diagnostics go to the shared state's lock-free
+/// queue; nothing here allocates, locks, or performs I/O.
+pub fn fill_output(
+    data: &mut [f32],
+    audio_queue: &ArrayQueue<f32>,
+    shared_state: &SharedAudioState,
+) {
+    let started = Instant::now();
+    let available = audio_queue.len();
+    let underrun = available < data.len();
+
+    if available >= data.len() {
+        // Happy path: read pre-rendered audio
+        for sample in data.iter_mut() {
+            *sample = audio_queue.pop().unwrap_or(0.0);
         }
+    } else {
+        // Buffer underrun: fill with silence
+        data.fill(0.0);
+
+        // Also try to read whatever is available to keep buffer clean
+        for sample in data.iter_mut().take(available) {
+            *sample = audio_queue.pop().unwrap_or(0.0);
+        }
+
+        shared_state.report(CallbackDiagnostic::Underrun {
+            requested: data.len(),
+            available,
+        });
     }
+
+    shared_state.record_callback(started.elapsed(), data.len() / CHANNELS, underrun);
 }
@@ -24,6 +24,18 @@ pub struct AudioConfig {
     /// Target maximum latency in milliseconds
     #[serde(default = "default_target_latency_ms")]
     pub target_latency_ms: f32,
+
+    /// Length of the crossfade when the rendered graph is swapped, in milliseconds (0 disables it)
+    #[serde(default = "default_crossfade_ms")]
+    pub crossfade_ms: f32,
+
+    /// Name of the output device to open (host default when unset or not found)
+    #[serde(default)]
+    pub device_name: Option<String>,
+
+    /// Requested sample rate in Hz (44100 when unset, nearest supported rate if unavailable)
+    #[serde(default)]
+    pub sample_rate: Option<u32>,
 }
 
 fn default_cpal_buffer_size() -> usize {
@@ -41,6 +53,9 @@ fn default_message_ring_buffer_size() -> usize {
 fn default_target_latency_ms() -> f32 {
     50.0
 }
+fn default_crossfade_ms() -> f32 {
+    10.0
+}
 
 impl Default for AudioConfig {
     fn default() -> Self {
@@ -50,6 +65,9 @@ impl Default for AudioConfig {
             audio_ring_buffer_size: default_audio_ring_buffer_size(),
             message_ring_buffer_size: default_message_ring_buffer_size(),
             target_latency_ms: default_target_latency_ms(),
+            crossfade_ms: default_crossfade_ms(),
+            device_name: None,
+            sample_rate: None,
         }
     }
 }
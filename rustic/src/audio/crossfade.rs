@@ -0,0 +1,56 @@
+//! Equal-power crossfade used when the render thread swaps graphs
+
+use std::f32::consts::FRAC_PI_2;
+
+use crate::core::audio::{Block, CHANNELS, Frame};
+
+/// An equal-power crossfade from an outgoing to an incoming signal.
+///
+/// The outgoing signal is weighted by `cos(t * PI / 2)` and the incoming one
+/// by `sin(t * PI / 2)` as `t` goes from 0 to 1 over `length` frames, which
+/// keeps the perceived loudness constant for uncorrelated signals. Blocks are
+/// mixed in place and the position carries over between calls.
+#[derive(Debug, Clone)]
+pub struct Crossfade {
+    length: usize,
+    position: usize,
+}
+
+impl Crossfade {
+    /// Creates a crossfade lasting `length` frames.
+    pub fn new(length: usize) -> Self {
+        Self {
+            length: length.max(1),
+            position: 0,
+        }
+    }
+
+    /// Creates a crossfade lasting `milliseconds` at `sample_rate`.
+    pub fn from_millis(milliseconds: f32, sample_rate: u32) -> Self {
+        Self::new((milliseconds.max(0.0) * sample_rate as f32 / 1000.0).round() as usize)
+    }
+
+    /// Returns `true` once the incoming signal is at full gain.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.length
+    }
+
+    /// Mixes `outgoing` into `incoming` in place and advances the fade by the
+    /// length of the longer block; missing frames on either side are silence.
+    /// Never allocates unless `incoming` is the shorter block.
+    pub fn mix_into(&mut self, outgoing: &[Frame], incoming: &mut Block) {
+        if incoming.len() < outgoing.len() {
+            incoming.resize(outgoing.len(), [0.0; CHANNELS]);
+        }
+
+        for (i, frame) in incoming.iter_mut().enumerate() {
+            let t = (self.position as f32 / self.length as f32).min(1.0);
+            let (fade_out, fade_in) = ((t * FRAC_PI_2).cos(), (t * FRAC_PI_2).sin());
+            let previous = outgoing.get(i).copied().unwrap_or([0.0; CHANNELS]);
+            for (sample, old) in frame.iter_mut().zip(previous) {
+                *sample = *sample * fade_in + old * fade_out;
+            }
+            self.position += 1;
+        }
+    }
+}
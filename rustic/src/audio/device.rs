@@ -0,0 +1,90 @@
+//! Output device and stream configuration selection
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use cpal::{
+    BufferSize, Device, Host, SampleFormat, SampleRate, StreamConfig, SupportedBufferSize,
+    SupportedStreamConfigRange,
+};
+
+use super::config::AudioConfig;
+use super::error::AudioError;
+use crate::core::audio::CHANNELS;
+
+/// Sample rate used when `AudioConfig::sample_rate` is unset
+pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+/// Returns the names of the output devices of the default host.
+pub fn list_output_devices() -> Result<Vec<String>, AudioError> {
+    let devices = cpal::default_host()
+        .output_devices()
+        .map_err(|e| AudioError::StreamError(e.to_string()))?;
+    Ok(devices.filter_map(|device| device.name().ok()).collect())
+}
+
+/// Opens the output device named `name`, falling back to the host default
+/// (with a warning) when it is unset or cannot be found.
+pub(crate) fn open_output_device(host: &Host, name: Option<&str>) -> Result<Device, AudioError> {
+    if let Some(name) = name {
+        let found = host
+            .output_devices()
+            .map_err(|e| AudioError::StreamError(e.to_string()))?
+            .find(|device| device.name().is_ok_and(|n| n == name));
+        match found {
+            Some(device) => return Ok(device),
+            None => log::warn!("Output device '{name}' not found, using the default device"),
+        }
+    }
+    host.default_output_device().ok_or(AudioError::NoDevice)
+}
+
+/// Picks a stream configuration among the `supported` ranges of a device.
+///
+/// Ranges with [`CHANNELS`] channels are preferred over others, and `f32`
+/// samples over other formats. The requested sample rate (or
+/// [`DEFAULT_SAMPLE_RATE`]) is used when a range supports it; otherwise a
+/// warning is logged and the closest supported rate is used. Likewise
+/// `cpal_buffer_size` is used when the range allows it, and the host default
+/// otherwise. Returns `None` when `supported` is empty.
+pub fn resolve_stream_config(
+    supported: &[SupportedStreamConfigRange],
+    config: &AudioConfig,
+) -> Option<StreamConfig> {
+    let requested_rate = config.sample_rate.unwrap_or(DEFAULT_SAMPLE_RATE);
+    let contains_rate = |range: &SupportedStreamConfigRange| {
+        range.min_sample_rate().0 <= requested_rate && requested_rate <= range.max_sample_rate().0
+    };
+    let score = |range: &&SupportedStreamConfigRange| {
+        (
+            range.channels() == CHANNELS as u16,
+            contains_rate(range),
+            range.sample_format() == SampleFormat::F32,
+        )
+    };
+
+    let range = supported.iter().max_by_key(score)?;
+
+    let sample_rate = if contains_rate(range) {
+        requested_rate
+    } else {
+        let fallback = requested_rate.clamp(range.min_sample_rate().0, range.max_sample_rate().0);
+        log::warn!("Sample rate {requested_rate} Hz is not supported, using {fallback} Hz");
+        fallback
+    };
+
+    let requested_buffer = config.cpal_buffer_size as u32;
+    let buffer_size = match range.buffer_size() {
+        SupportedBufferSize::Range { min, max } if !(*min..=*max).contains(&requested_buffer) => {
+            log::warn!(
+                "Buffer size {requested_buffer} is outside the supported range {min}..={max}, using the host default"
+            );
+            BufferSize::Default
+        }
+        _ => BufferSize::Fixed(requested_buffer),
+    };
+
+    Some(StreamConfig {
+        channels: range.channels(),
+        sample_rate: SampleRate(sample_rate),
+        buffer_size,
+    })
+}
@@ -20,6 +20,8 @@ pub enum EventCategory {
     Diagnostics,
     /// Errors, panics, and graph compilation failures.
     Error,
+    /// Throttled output level updates (~30 Hz), intended for level meters.
+    Meter,
 }
 
 impl EventCategory {
@@ -29,6 +31,7 @@ impl EventCategory {
             Self::Audio => 1 << 1,
             Self::Diagnostics => 1 << 2,
             Self::Error => 1 << 3,
+            Self::Meter => 1 << 4,
         }
     }
 }
@@ -39,8 +42,8 @@ impl EventCategory {
 ///
 /// The default enables [`Status`](EventCategory::Status) and
 /// [`Error`](EventCategory::Error) only, skipping the high-frequency
-/// [`Audio`](EventCategory::Audio) and [`Diagnostics`](EventCategory::Diagnostics)
-/// streams.
+/// [`Audio`](EventCategory::Audio), [`Diagnostics`](EventCategory::Diagnostics)
+/// and [`Meter`](EventCategory::Meter) streams.
 ///
 /// # Example
 /// ```
@@ -115,11 +118,28 @@ pub enum AudioEvent {
     Chunk(Vec<f32>),
 }
 
+/// Output levels measured over the last reporting interval, per channel (L, R).
+/// Values are linear amplitudes; see [`crate::core::audio::amplitude_to_db`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeterEvent {
+    pub peak: [f32; 2],
+    pub rms: [f32; 2],
+}
+
+/// Periodic audio engine health snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricsEvent {
+    /// Smoothed time spent in the output callback relative to the audio it delivered.
+    pub callback_load: f32,
+    /// Total number of buffer underruns (xruns) since start.
+    pub xruns: u64,
+    /// Ring buffer fill level in [0, 1].
+    pub buffer_fill: f32,
+}
+
 /// Performance and diagnostic counters.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DiagnosticsEvent {
-    /// Periodic CPU and latency snapshot.
-    Metrics { cpu_usage: f32, latency_ms: f32 },
     /// The CPAL callback found the ring buffer empty; filled with silence.
     BufferUnderrun { count: u64 },
 }
@@ -147,6 +167,10 @@ pub enum BackendEvent {
     Audio(AudioEvent),
     Diagnostics(DiagnosticsEvent),
     Error(ErrorEvent),
+    Meter(MeterEvent),
+    /// Throttled engine metrics, filtered with the
+    /// [`Diagnostics`](EventCategory::Diagnostics) category.
+    Metrics(MetricsEvent),
 }
 
 impl BackendEvent {
@@ -157,6 +181,8 @@ impl BackendEvent {
             Self::Audio(_) => EventCategory::Audio,
             Self::Diagnostics(_) => EventCategory::Diagnostics,
             Self::Error(_) => EventCategory::Error,
+            Self::Meter(_) => EventCategory::Meter,
+            Self::Metrics(_) => EventCategory::Diagnostics,
         }
     }
 }
@@ -187,6 +213,11 @@ impl EventSender {
         }
     }
 
+    /// Returns `true` if events of `category` are currently forwarded.
+    pub fn allows(&self, category: EventCategory) -> bool {
+        self.filter.load(Ordering::Relaxed) & category.bit() != 0
+    }
+
     /// Update the enabled categories at runtime.
     #[allow(dead_code)]
     pub fn set_filter(&self, filter: EventFilter) {
@@ -1,44 +1,96 @@
+use super::config::AudioConfig;
+use super::events::{BackendEvent, EventFilter, EventSender};
+use super::messages::AudioMessage;
 use super::{AudioError, SharedAudioState};
+use crate::core::graph::System;
+use crossbeam::queue::ArrayQueue;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{Receiver, channel};
 use std::thread::JoinHandle;
 
-/// Handle to the audio render thread.
+/// Handle to the audio threads.
 ///
-/// The `cpal::Stream` is intentionally forgotten (leaked) so that this struct
-/// is `Send` and can be stored in shared state (e.g. Tauri managed state).
-/// The stream continues playing; the OS reclaims it on process exit.
+/// `cpal::Stream` is not `Send` on every platform, so the stream is built and
+/// owned by a dedicated `audio-stream` thread that parks until shutdown. This
+/// keeps the handle `Send` (it can be stored in e.g. Tauri managed state) while
+/// still dropping the stream — and closing the device — on [`shutdown`](Self::shutdown).
 pub struct AudioHandle {
     render_thread: JoinHandle<()>,
+    stream_thread: Option<JoinHandle<()>>,
     shared_state: Arc<SharedAudioState>,
 }
 
 impl AudioHandle {
+    /// Wraps the render thread and, when playing to a device, the thread owning
+    /// the `cpal::Stream`. The stream thread must exit once
+    /// `shared_state.shutdown` is set and it is unparked.
     pub fn new(
         render_thread: JoinHandle<()>,
-        stream: cpal::Stream,
+        stream_thread: Option<JoinHandle<()>>,
         shared_state: Arc<SharedAudioState>,
     ) -> Self {
-        // Forget the stream so AudioHandle is Send.
-        // The stream keeps playing; cleanup happens on process exit.
-        std::mem::forget(stream);
-
         Self {
             render_thread,
+            stream_thread,
             shared_state,
         }
     }
 
-    /// Gracefully shut down the audio system.
-    pub fn shutdown(self) -> Result<(), AudioError> {
-        use std::sync::atomic::Ordering;
+    /// Spawns a render thread without an output device.
+    ///
+    /// Rendered samples are pushed to the returned queue instead of a cpal
+    /// stream; nothing drains it unless the caller does, so the render thread
+    /// simply throttles once it is full. Useful for tests and offline tooling.
+    pub fn spawn_headless(
+        system: System,
+        config: AudioConfig,
+        filter: EventFilter,
+    ) -> (
+        Self,
+        crossbeam::channel::Sender<AudioMessage>,
+        Receiver<BackendEvent>,
+        Arc<ArrayQueue<f32>>,
+    ) {
+        let (raw_tx, event_rx) = channel();
+        let shared_state = Arc::new(SharedAudioState::new());
+        let audio_queue = Arc::new(ArrayQueue::new(config.audio_ring_buffer_size));
+        let (message_tx, message_rx) = crossbeam::channel::bounded(config.message_ring_buffer_size);
+
+        let render_thread = super::render_thread::spawn_audio_render_thread(
+            shared_state.clone(),
+            system,
+            message_rx,
+            audio_queue.clone(),
+            config,
+            EventSender::new(raw_tx, filter),
+        );
+
+        (
+            Self::new(render_thread, None, shared_state),
+            message_tx,
+            event_rx,
+            audio_queue,
+        )
+    }
 
+    /// Gracefully shut down the audio system: stops the render thread, drops
+    /// the output stream and joins both threads.
+    pub fn shutdown(self) -> Result<(), AudioError> {
         self.shared_state.shutdown.store(true, Ordering::Release);
 
-        self.render_thread
-            .join()
-            .map_err(|_| AudioError::ThreadPanic)?;
+        let render = self.render_thread.join();
+        if let Some(stream_thread) = self.stream_thread {
+            stream_thread.thread().unpark();
+            stream_thread.join().map_err(|_| AudioError::ThreadPanic)?;
+        }
+        render.map_err(|_| AudioError::ThreadPanic)
+    }
 
-        Ok(())
+    /// Returns `true` once the render thread has exited, e.g. after it
+    /// received [`AudioMessage::Shutdown`] or its message channel closed.
+    pub fn is_finished(&self) -> bool {
+        self.render_thread.is_finished()
     }
 
     /// Access the shared audio state (e.g. to update master_volume).
@@ -48,11 +100,10 @@ impl AudioHandle {
 
     /// Get audio metrics.
     pub fn get_metrics(&self) -> AudioMetrics {
-        use std::sync::atomic::Ordering;
-
         AudioMetrics {
             buffer_underruns: self.shared_state.buffer_underruns.load(Ordering::Relaxed),
             sample_rate: self.shared_state.sample_rate.load(Ordering::Relaxed),
+            callback_load: self.shared_state.callback_load.load(Ordering::Relaxed),
         }
     }
 }
@@ -61,4 +112,5 @@ impl AudioHandle {
 pub struct AudioMetrics {
     pub buffer_underruns: u64,
     pub sample_rate: u32,
+    pub callback_load: f32,
 }
@@ -0,0 +1,50 @@
+//! Throttled output level reporting for the render thread.
+
+use crate::core::audio::{Frame, LevelMeter};
+
+use super::events::MeterEvent;
+
+/// Default rate at which the render thread reports output levels.
+pub const METER_RATE_HZ: f32 = 30.0;
+
+/// Feeds rendered blocks to a [`LevelMeter`] whose RMS window spans one
+/// reporting interval, and yields a [`MeterEvent`] once per interval.
+///
+/// The meter never allocates after construction, so observing a block adds
+/// negligible cost to the render loop.
+#[derive(Debug, Clone)]
+pub struct MeterReporter {
+    meter: LevelMeter,
+    interval_frames: usize,
+    frames: usize,
+}
+
+impl MeterReporter {
+    /// Creates a reporter emitting `rate_hz` events per second of audio.
+    pub fn new(sample_rate: u32, rate_hz: f32) -> Self {
+        let sample_rate = sample_rate.max(1) as f32;
+        let interval_frames = ((sample_rate / rate_hz.max(f32::EPSILON)) as usize).max(1);
+        Self {
+            meter: LevelMeter::new(sample_rate, interval_frames as f32 / sample_rate),
+            interval_frames,
+            frames: 0,
+        }
+    }
+
+    /// Feeds a rendered block; returns the current levels once a full
+    /// interval has been observed since the last report.
+    pub fn observe(&mut self, block: &[Frame]) -> Option<MeterEvent> {
+        self.meter.process(block);
+        self.frames += block.len();
+
+        if self.frames < self.interval_frames {
+            return None;
+        }
+
+        self.frames = 0;
+        Some(MeterEvent {
+            peak: self.meter.peak(),
+            rms: self.meter.rms(),
+        })
+    }
+}
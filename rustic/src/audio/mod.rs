@@ -7,21 +7,28 @@
 
 pub mod callback;
 pub mod config;
+pub mod crossfade;
+pub mod device;
 pub mod error;
 pub mod events;
 mod handle;
 pub mod messages;
+pub mod meter;
 pub(crate) mod render_thread;
 pub mod shared_state;
 
 // Re-export commonly used types
-pub use callback::create_cpal_callback;
+pub use callback::{create_cpal_callback, fill_output};
 pub use config::{AudioConfig, LogConfig};
+pub use crossfade::Crossfade;
+pub use device::{list_output_devices, resolve_stream_config};
 pub use error::{AudioError, CommandError};
 pub(crate) use events::EventSender;
 pub use events::{
-    AudioEvent, BackendEvent, DiagnosticsEvent, ErrorEvent, EventCategory, EventFilter, StatusEvent,
+    AudioEvent, BackendEvent, DiagnosticsEvent, ErrorEvent, EventCategory, EventFilter, MeterEvent,
+    MetricsEvent, StatusEvent,
 };
 pub use handle::{AudioHandle, AudioMetrics};
 pub use messages::{AudioMessage, GraphAudioMessage, InstrumentAudioMessage};
-pub use shared_state::SharedAudioState;
+pub use meter::MeterReporter;
+pub use shared_state::{CallbackDiagnostic, SharedAudioState};
@@ -6,19 +6,30 @@ use std::sync::atomic::Ordering;
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
+use crossbeam::channel::{RecvTimeoutError, TryRecvError};
 use crossbeam::queue::ArrayQueue;
 use petgraph::graph::NodeIndex;
 
 use super::config::AudioConfig;
-use super::events::{AudioEvent, BackendEvent, ErrorEvent, EventSender};
+use super::crossfade::Crossfade;
+use super::events::{
+    AudioEvent, BackendEvent, ErrorEvent, EventCategory, EventSender, MetricsEvent,
+};
 use super::messages::{AudioMessage, GraphAudioMessage, InstrumentAudioMessage};
-use super::shared_state::SharedAudioState;
+use super::meter::{METER_RATE_HZ, MeterReporter};
+use super::shared_state::{CallbackDiagnostic, SharedAudioState};
+use crate::core::audio::Block;
 use crate::core::graph::System;
 
+/// Rate at which engine metrics are reported, in events per second of audio.
+const METRICS_RATE_HZ: u32 = 4;
+
 /// Spawns the audio render thread.
 ///
 /// The thread owns a single [`System`] graph (always valid — never `Option`).
-/// It runs the system block-by-block, processing control messages between blocks.
+/// It runs the system block-by-block, processing control messages between blocks,
+/// until `shared_state.shutdown` is set, an [`AudioMessage::Shutdown`] arrives,
+/// or every message sender has been dropped.
 ///
 /// If the render loop panics (e.g. a DSP node hits an unrecoverable state), the
 /// panic is caught, an [`ErrorEvent::ThreadPanic`] event is emitted, and the
@@ -80,16 +91,54 @@ fn render_loop(
         config.calculate_ring_buffer_size(sample_rate) * crate::core::audio::CHANNELS;
 
     let mut block_count: u64 = 0;
+    let mut meter = MeterReporter::new(sample_rate, METER_RATE_HZ);
+    let metrics_interval = (sample_rate / METRICS_RATE_HZ).max(1) as usize;
+    let mut frames_since_metrics = 0usize;
+    // Half a render chunk: short enough to refill the queue before it drains
+    let idle_wait = Duration::from_secs_f64(
+        config.render_chunk_size as f64 / (2.0 * sample_rate.max(1) as f64),
+    );
+
+    let mut fade = Fade::new(config.crossfade_ms, sample_rate);
 
     while !shared_state.shutdown.load(Ordering::Relaxed) {
-        // Process all pending control messages
-        while let Ok(msg) = message_rx.try_recv() {
-            process_audio_message(system, msg, event_tx);
+        // Process every message queued before this block, so bursts of notes
+        // are never split across blocks while a producer flooding the channel
+        // cannot starve rendering. A Shutdown message or a closed channel
+        // (App dropped its sender) ends the loop.
+        for _ in 0..message_rx.len().max(1) {
+            match message_rx.try_recv() {
+                Ok(AudioMessage::Shutdown) | Err(TryRecvError::Disconnected) => {
+                    shared_state.shutdown.store(true, Ordering::Release);
+                    return;
+                }
+                Ok(msg) => process_audio_message(system, &mut fade, msg, event_tx),
+                Err(TryRecvError::Empty) => break,
+            }
         }
 
-        // Throttle to target latency
+        // Log what the callback reported since the last block. Underruns are
+        // routine while nothing renders (e.g. a silent system), so keep them quiet.
+        while let Some(diagnostic) = shared_state.diagnostics.pop() {
+            match diagnostic {
+                CallbackDiagnostic::Underrun { .. } => log::trace!("[callback] {diagnostic}"),
+                CallbackDiagnostic::Overrun { .. } => log::warn!("[callback] {diagnostic}"),
+            }
+        }
+
+        // Throttle to target latency. Block on the message channel rather
+        // than sleeping so a new message (or Shutdown) wakes us immediately.
         if audio_queue.len() >= target_samples {
-            thread::sleep(Duration::from_micros(100));
+            if !wait_for_message(
+                shared_state,
+                system,
+                &mut fade,
+                message_rx,
+                idle_wait,
+                event_tx,
+            ) {
+                return;
+            }
             continue;
         }
 
@@ -105,13 +154,43 @@ fn render_loop(
         // Run the graph for one block
         system.run();
         chunk_buffer.clear();
-        if let Ok(sink) = system.get_sink(0) {
-            let frames = sink.consume();
-            log::trace!("[render] consumed {} frames from sink", frames.len());
-            for frame in &frames {
-                chunk_buffer.push(frame[0]); // L — master volume + limiting applied inside sink
-                chunk_buffer.push(frame[1]); // R
+        let mut frames = system
+            .get_sink(0)
+            .map(|sink| sink.consume())
+            .unwrap_or_default();
+        log::trace!("[render] consumed {} frames from sink", frames.len());
+
+        // Blend in the previous graph while it fades out after a swap
+        fade.mix_outgoing(
+            &mut frames,
+            shared_state.master_volume.load(Ordering::Relaxed),
+        );
+
+        for frame in &frames {
+            chunk_buffer.push(frame[0]); // L — master volume + limiting applied inside sink
+            chunk_buffer.push(frame[1]); // R
+        }
+        // Skip metering entirely when nobody listens for it
+        if !frames.is_empty()
+            && event_tx.allows(EventCategory::Meter)
+            && let Some(levels) = meter.observe(&frames)
+        {
+            event_tx.send(BackendEvent::Meter(levels));
+        }
+
+        // Nothing to render (e.g. a silent system): idle instead of spinning
+        if chunk_buffer.is_empty() {
+            if !wait_for_message(
+                shared_state,
+                system,
+                &mut fade,
+                message_rx,
+                idle_wait,
+                event_tx,
+            ) {
+                return;
             }
+            continue;
         }
 
         // Write to ring buffer
@@ -131,6 +210,18 @@ fn render_loop(
             );
         }
 
+        frames_since_metrics += chunk_buffer.len() / crate::core::audio::CHANNELS;
+        if frames_since_metrics >= metrics_interval {
+            frames_since_metrics = 0;
+            if event_tx.allows(EventCategory::Diagnostics) {
+                event_tx.send(BackendEvent::Metrics(MetricsEvent {
+                    callback_load: shared_state.callback_load.load(Ordering::Relaxed),
+                    xruns: shared_state.buffer_underruns.load(Ordering::Relaxed),
+                    buffer_fill: audio_queue.len() as f32 / audio_queue.capacity() as f32,
+                }));
+            }
+        }
+
         block_count += 1;
         // Every ~1 second (86 blocks @ 512 frames / 44100 Hz), log a status line
         if block_count.is_multiple_of(86) {
@@ -156,6 +247,93 @@ fn render_loop(
     }
 }
 
+/// The graphs being faded out after a [`GraphAudioMessage::Swap`] or `Clear`.
+///
+/// Each outgoing graph carries the crossfade into the graph that replaced it,
+/// oldest first. A swap during a running fade chains a new fade after it: the
+/// older graphs keep fading into the one being replaced, and that blend fades
+/// into the new graph, so the output stays continuous however close the swaps.
+struct Fade {
+    milliseconds: f32,
+    sample_rate: u32,
+    outgoing: Vec<(System, Crossfade)>,
+}
+
+impl Fade {
+    fn new(milliseconds: f32, sample_rate: u32) -> Self {
+        Self {
+            milliseconds,
+            sample_rate,
+            outgoing: Vec::new(),
+        }
+    }
+
+    /// Starts fading `previous` out; a zero-length fade drops it right away.
+    fn start(&mut self, previous: System) {
+        if self.milliseconds > 0.0 {
+            self.outgoing.push((
+                previous,
+                Crossfade::from_millis(self.milliseconds, self.sample_rate),
+            ));
+        }
+    }
+
+    /// Renders one block of the outgoing graphs and crossfades them into
+    /// `frames`.
+    fn mix_outgoing(&mut self, frames: &mut Block, master_volume: f32) {
+        // Blend of the graphs older than the one being rendered, and the
+        // crossfade from that blend into it
+        let mut older: Option<(Block, &mut Crossfade)> = None;
+        for (previous, crossfade) in &mut self.outgoing {
+            previous.set_sink_parameter(0, "master_volume", master_volume);
+            previous.run();
+            let mut block = previous
+                .get_sink(0)
+                .map(|sink| sink.consume())
+                .unwrap_or_default();
+            if let Some((blend, fade)) = older.take() {
+                fade.mix_into(&blend, &mut block);
+            }
+            older = Some((block, crossfade));
+        }
+        if let Some((blend, fade)) = older {
+            fade.mix_into(&blend, frames);
+        }
+
+        // Once a graph has fully faded in, everything before it is silent
+        if let Some(done) = self
+            .outgoing
+            .iter()
+            .rposition(|(_, crossfade)| crossfade.is_finished())
+        {
+            self.outgoing.drain(..=done);
+        }
+    }
+}
+
+/// Blocks on the message channel for up to `timeout` and processes the message
+/// that arrives, if any. Returns `false` when the render loop should stop.
+fn wait_for_message(
+    shared_state: &SharedAudioState,
+    system: &mut System,
+    fade: &mut Fade,
+    message_rx: &crossbeam::channel::Receiver<AudioMessage>,
+    timeout: Duration,
+    event_tx: &EventSender,
+) -> bool {
+    match message_rx.recv_timeout(timeout) {
+        Ok(AudioMessage::Shutdown) | Err(RecvTimeoutError::Disconnected) => {
+            shared_state.shutdown.store(true, Ordering::Release);
+            false
+        }
+        Ok(msg) => {
+            process_audio_message(system, fade, msg, event_tx);
+            true
+        }
+        Err(RecvTimeoutError::Timeout) => true,
+    }
+}
+
 fn process_instrument_message(system: &mut System, cmd: InstrumentAudioMessage) {
     match cmd {
         InstrumentAudioMessage::NoteStart {
@@ -171,13 +349,18 @@ fn process_instrument_message(system: &mut System, cmd: InstrumentAudioMessage)
     }
 }
 
-fn process_graph_message(system: &mut System, cmd: GraphAudioMessage, event_tx: &EventSender) {
+fn process_graph_message(
+    system: &mut System,
+    fade: &mut Fade,
+    cmd: GraphAudioMessage,
+    event_tx: &EventSender,
+) {
     match cmd {
         GraphAudioMessage::Swap(new_system) => {
-            *system = new_system;
+            fade.start(std::mem::replace(system, new_system));
         }
         GraphAudioMessage::Clear => {
-            *system = System::silent();
+            fade.start(std::mem::replace(system, System::silent()));
         }
         GraphAudioMessage::StartSource { source_index } => {
             log::info!(
@@ -236,12 +419,17 @@ fn process_graph_message(system: &mut System, cmd: GraphAudioMessage, event_tx:
     let _ = event_tx;
 }
 
-fn process_audio_message(system: &mut System, msg: AudioMessage, event_tx: &EventSender) {
+fn process_audio_message(
+    system: &mut System,
+    fade: &mut Fade,
+    msg: AudioMessage,
+    event_tx: &EventSender,
+) {
     match msg {
         AudioMessage::Instrument(cmd) => process_instrument_message(system, cmd),
-        AudioMessage::Graph(cmd) => process_graph_message(system, cmd, event_tx),
+        AudioMessage::Graph(cmd) => process_graph_message(system, fade, cmd, event_tx),
         AudioMessage::Shutdown => {
-            // Handled via shutdown flag in shared_state
+            // Intercepted by the render loop before dispatch
         }
     }
 }
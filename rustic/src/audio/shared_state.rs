@@ -1,7 +1,48 @@
 //! Shared state between audio threads using atomic types
 
 use atomic_float::AtomicF32;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
+use crossbeam::queue::ArrayQueue;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Smoothing factor of the callback load moving average
+const LOAD_SMOOTHING: f32 = 0.1;
+/// Number of callback diagnostics buffered until the render thread logs them
+const DIAGNOSTICS_CAPACITY: usize = 256;
+
+/// An event observed by the output callback.
+///
+/// The callback cannot log (formatting allocates, and the logger may lock or
+/// write), so it pushes these plain values to a lock-free queue instead; the
+/// render thread drains and logs them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CallbackDiagnostic {
+    /// The ring buffer held fewer samples than the device requested
+    Underrun { requested: usize, available: usize },
+    /// The callback took longer than the duration of the audio it delivered
+    Overrun { elapsed: Duration, budget: Duration },
+}
+
+impl fmt::Display for CallbackDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallbackDiagnostic::Underrun {
+                requested,
+                available,
+            } => write!(
+                f,
+                "buffer underrun: {available} / {requested} samples available"
+            ),
+            CallbackDiagnostic::Overrun { elapsed, budget } => {
+                write!(
+                    f,
+                    "callback overrun: took {elapsed:?} for {budget:?} of audio"
+                )
+            }
+        }
+    }
+}
 
 /// State shared between threads using lock-free atomics
 pub struct SharedAudioState {
@@ -9,6 +50,13 @@ pub struct SharedAudioState {
     pub buffer_underruns: AtomicU64,
     pub sample_rate: AtomicU32,
     pub master_volume: AtomicF32,
+    /// Smoothed ratio of time spent in the output callback to the duration
+    /// of the audio it delivered (1.0 means the callback used its whole budget)
+    pub callback_load: AtomicF32,
+    /// Diagnostics pushed by the callback, drained by the render thread
+    pub diagnostics: ArrayQueue<CallbackDiagnostic>,
+    /// Diagnostics lost because the queue was full
+    pub dropped_diagnostics: AtomicU64,
 }
 
 impl SharedAudioState {
@@ -18,7 +66,47 @@ impl SharedAudioState {
             buffer_underruns: AtomicU64::new(0),
             sample_rate: AtomicU32::new(44100),
             master_volume: AtomicF32::new(1.0),
+            callback_load: AtomicF32::new(0.0),
+            diagnostics: ArrayQueue::new(DIAGNOSTICS_CAPACITY),
+            dropped_diagnostics: AtomicU64::new(0),
+        }
+    }
+
+    /// Queues a diagnostic without blocking or allocating; counts it as
+    /// dropped when the queue is full.
+    pub fn report(&self, diagnostic: CallbackDiagnostic) {
+        if self.diagnostics.push(diagnostic).is_err() {
+            self.dropped_diagnostics.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records one output callback that took `elapsed` to deliver `frames`
+    /// frames, counting an underrun when the ring buffer ran dry.
+    ///
+    /// Only called from the callback thread, so the load/store pair on the
+    /// moving average never races with another writer.
+    pub fn record_callback(&self, elapsed: Duration, frames: usize, underrun: bool) {
+        if underrun {
+            self.buffer_underruns.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let sample_rate = self.sample_rate.load(Ordering::Relaxed);
+        if frames == 0 || sample_rate == 0 {
+            return;
+        }
+        let budget = frames as f32 / sample_rate as f32;
+        let load = elapsed.as_secs_f32() / budget;
+        if load > 1.0 {
+            self.report(CallbackDiagnostic::Overrun {
+                elapsed,
+                budget: Duration::from_secs_f32(budget),
+            });
         }
+        let previous = self.callback_load.load(Ordering::Relaxed);
+        self.callback_load.store(
+            previous + LOAD_SMOOTHING * (load - previous),
+            Ordering::Relaxed,
+        );
     }
 }
 
@@ -16,3 +16,271 @@ pub fn silent_block(n: usize) -> Block {
 pub fn mono_to_frame(s: f32) -> Frame {
     [s; CHANNELS]
 }
+
+/// Mean of all the samples of `block`, over frames and channels; 0 when empty.
+pub fn block_mean(block: &[Frame]) -> f32 {
+    if block.is_empty() {
+        return 0.0;
+    }
+    block.iter().map(|f| f.iter().sum::<f32>()).sum::<f32>() / (block.len() * CHANNELS) as f32
+}
+
+/// Multiplies every sample of `block` by `factor`.
+///
+/// With the `simd` feature, samples are processed 8 at a time (4 stereo
+/// frames); otherwise this is [`scale_block_scalar`].
+pub fn scale_block(block: &[Frame], factor: f32) -> Block {
+    let mut output = Vec::with_capacity(block.len());
+    scale_block_into(block, factor, &mut output);
+    output
+}
+
+/// Same as [`scale_block`], writing into `output` and reusing its capacity.
+pub fn scale_block_into(block: &[Frame], factor: f32, output: &mut Block) {
+    output.clear();
+    output.extend_from_slice(block);
+    #[cfg(feature = "simd")]
+    simd::scale(output.as_flattened_mut(), factor);
+    #[cfg(not(feature = "simd"))]
+    output
+        .iter_mut()
+        .for_each(|frame| frame.iter_mut().for_each(|sample| *sample *= factor));
+}
+
+/// Scalar implementation of [`scale_block`], always available as a reference.
+pub fn scale_block_scalar(block: &[Frame], factor: f32) -> Block {
+    block
+        .iter()
+        .map(|frame| frame.map(|sample| sample * factor))
+        .collect()
+}
+
+/// Adds `block` to `acc`, frame by frame, up to the shortest of both.
+///
+/// With the `simd` feature, samples are processed 8 at a time (4 stereo
+/// frames); otherwise this is [`add_block_scalar`].
+pub fn add_block(acc: &mut [Frame], block: &[Frame]) {
+    #[cfg(feature = "simd")]
+    {
+        let len = acc.len().min(block.len());
+        simd::add(acc[..len].as_flattened_mut(), block[..len].as_flattened());
+    }
+    #[cfg(not(feature = "simd"))]
+    add_block_scalar(acc, block)
+}
+
+/// Scalar implementation of [`add_block`], always available as a reference.
+pub fn add_block_scalar(acc: &mut [Frame], block: &[Frame]) {
+    for (af, bf) in acc.iter_mut().zip(block) {
+        for ch in 0..CHANNELS {
+            af[ch] += bf[ch];
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+mod simd {
+    use wide::f32x8;
+
+    const LANES: usize = 8;
+
+    pub(super) fn scale(samples: &mut [f32], factor: f32) {
+        let factor_x8 = f32x8::splat(factor);
+        let mut chunks = samples.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            let lanes = f32x8::from(<[f32; LANES]>::try_from(&*chunk).unwrap()) * factor_x8;
+            chunk.copy_from_slice(&lanes.to_array());
+        }
+        for sample in chunks.into_remainder() {
+            *sample *= factor;
+        }
+    }
+
+    pub(super) fn add(acc: &mut [f32], samples: &[f32]) {
+        let mut acc_chunks = acc.chunks_exact_mut(LANES);
+        let mut chunks = samples.chunks_exact(LANES);
+        for (acc_chunk, chunk) in (&mut acc_chunks).zip(&mut chunks) {
+            let sum = f32x8::from(<[f32; LANES]>::try_from(&*acc_chunk).unwrap())
+                + f32x8::from(<[f32; LANES]>::try_from(chunk).unwrap());
+            acc_chunk.copy_from_slice(&sum.to_array());
+        }
+        for (a, b) in acc_chunks
+            .into_remainder()
+            .iter_mut()
+            .zip(chunks.remainder())
+        {
+            *a += b;
+        }
+    }
+}
+
+/// Floor used when converting silent levels to decibels
+pub const MIN_DB: f32 = -120.0;
+
+/// Converts a linear amplitude to decibels full scale, floored at [`MIN_DB`]
+pub fn amplitude_to_db(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        MIN_DB
+    } else {
+        (20.0 * amplitude.log10()).max(MIN_DB)
+    }
+}
+
+/// Taps of each phase of the [`true_peak`] interpolation filter
+const TRUE_PEAK_TAPS_PER_PHASE: usize = 12;
+
+/// Highest peak of `samples` between as well as on the samples, in dB true
+/// peak (dBTP), as used by loudness standards (ITU-R BS.1770).
+///
+/// A signal whose samples stay below full scale can still exceed it once
+/// reconstructed, when its peaks fall between two samples. The samples are
+/// oversampled through a polyphase windowed-sinc filter, by 4 at 44.1 or
+/// 48 kHz and less at higher rates, to reach about 192 kHz before taking the
+/// peak. The result is never below the sample peak.
+pub fn true_peak(samples: &[f32], sample_rate: u32) -> f32 {
+    let sample_peak = samples.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+    let factor = (192_000 / sample_rate.max(1)).clamp(1, 4) as usize;
+    if factor == 1 {
+        return amplitude_to_db(sample_peak);
+    }
+
+    let len = TRUE_PEAK_TAPS_PER_PHASE * factor;
+    let mid = (len - 1) as f32 / 2.0;
+    let taps: Vec<f32> = (0..len)
+        .map(|i| {
+            let t = (i as f32 - mid) / factor as f32;
+            let sinc = if t == 0.0 {
+                1.0
+            } else {
+                (std::f32::consts::PI * t).sin() / (std::f32::consts::PI * t)
+            };
+            let phase = 2.0 * std::f32::consts::PI * i as f32 / (len - 1) as f32;
+            sinc * (0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+        })
+        .collect();
+
+    let mut peak = sample_peak;
+    for n in 0..samples.len() + TRUE_PEAK_TAPS_PER_PHASE {
+        for phase in 0..factor {
+            // Oversampled value between sample `n - 1` and `n`
+            let value: f32 = taps
+                .iter()
+                .skip(phase)
+                .step_by(factor)
+                .enumerate()
+                .filter_map(|(k, tap)| {
+                    n.checked_sub(k)
+                        .and_then(|i| samples.get(i))
+                        .map(|s| tap * s)
+                })
+                .sum();
+            peak = peak.max(value.abs());
+        }
+    }
+    amplitude_to_db(peak)
+}
+
+/// A streaming per-channel level meter.
+///
+/// Blocks are fed incrementally with [`LevelMeter::process`]. The meter keeps
+/// a sliding-window RMS over the last `window` seconds and a peak-hold value
+/// that stays put for `hold` seconds after a new peak before decaying at a
+/// fixed rate in dB per second (classic PPM-style ballistics).
+///
+/// All buffers are allocated at construction, so processing never allocates.
+#[derive(Debug, Clone)]
+pub struct LevelMeter {
+    /// Ring buffer of squared samples covering the RMS window
+    squares: Vec<Frame>,
+    position: usize,
+    sum_squares: [f64; CHANNELS],
+    peak: Frame,
+    hold_remaining: [usize; CHANNELS],
+    hold_frames: usize,
+    /// Linear gain applied to the held peak per frame once the hold elapsed
+    decay_per_frame: f32,
+}
+
+impl LevelMeter {
+    /// Creates a meter with a `window_seconds` RMS window, a 1.5 s peak hold
+    /// and a 20 dB/s peak decay.
+    pub fn new(sample_rate: f32, window_seconds: f32) -> Self {
+        let window = ((sample_rate * window_seconds).round() as usize).max(1);
+        Self {
+            squares: vec![[0.0; CHANNELS]; window],
+            position: 0,
+            sum_squares: [0.0; CHANNELS],
+            peak: [0.0; CHANNELS],
+            hold_remaining: [0; CHANNELS],
+            hold_frames: 0,
+            decay_per_frame: 1.0,
+        }
+        .with_ballistics(sample_rate, 1.5, 20.0)
+    }
+
+    /// Builder-style setter for the peak hold time and decay rate.
+    pub fn with_ballistics(
+        mut self,
+        sample_rate: f32,
+        hold_seconds: f32,
+        decay_db_per_second: f32,
+    ) -> Self {
+        self.hold_frames = (sample_rate * hold_seconds).round() as usize;
+        self.decay_per_frame = 10f32.powf(-decay_db_per_second / (20.0 * sample_rate));
+        self
+    }
+
+    /// Feeds a block of frames to the meter.
+    pub fn process(&mut self, block: &[Frame]) {
+        for frame in block {
+            let old = self.squares[self.position];
+            for ch in 0..CHANNELS {
+                let sample = frame[ch];
+                let square = sample * sample;
+                self.sum_squares[ch] += square as f64 - old[ch] as f64;
+                self.squares[self.position][ch] = square;
+
+                let level = sample.abs();
+                if level >= self.peak[ch] {
+                    self.peak[ch] = level;
+                    self.hold_remaining[ch] = self.hold_frames;
+                } else if self.hold_remaining[ch] > 0 {
+                    self.hold_remaining[ch] -= 1;
+                } else {
+                    self.peak[ch] *= self.decay_per_frame;
+                }
+            }
+            self.position = (self.position + 1) % self.squares.len();
+        }
+    }
+
+    /// Per-channel RMS amplitude over the sliding window
+    pub fn rms(&self) -> Frame {
+        let window = self.squares.len() as f64;
+        std::array::from_fn(|ch| (self.sum_squares[ch].max(0.0) / window).sqrt() as f32)
+    }
+
+    /// Per-channel held peak amplitude
+    pub fn peak(&self) -> Frame {
+        self.peak
+    }
+
+    /// Per-channel RMS level in dBFS
+    pub fn rms_db(&self) -> Frame {
+        self.rms().map(amplitude_to_db)
+    }
+
+    /// Per-channel held peak level in dBFS
+    pub fn peak_db(&self) -> Frame {
+        self.peak.map(amplitude_to_db)
+    }
+
+    /// Clears the RMS window and the held peaks.
+    pub fn reset(&mut self) {
+        self.squares.iter_mut().for_each(|f| *f = [0.0; CHANNELS]);
+        self.position = 0;
+        self.sum_squares = [0.0; CHANNELS];
+        self.peak = [0.0; CHANNELS];
+        self.hold_remaining = [0; CHANNELS];
+    }
+}
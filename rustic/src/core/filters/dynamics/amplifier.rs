@@ -1,10 +1,10 @@
 use std::fmt;
 use std::sync::Arc;
 
-use rayon::prelude::*;
 use rustic_derive::FilterMetaData;
 
 use crate::core::Block;
+use crate::core::audio::scale_block_into;
 use crate::core::graph::{Entry, Filter};
 
 /// A filter that returns the input value multiplied by a constant factor.
@@ -41,13 +41,14 @@ impl fmt::Display for GainFilter {
 
 impl Filter for GainFilter {
     fn transform(&mut self) -> Vec<Block> {
-        let factor = self.factor;
-        let output: Block = self
-            .source
-            .par_iter()
-            .map(|frame| std::array::from_fn(|ch| frame[ch] * factor))
-            .collect();
-        vec![output]
+        let mut outputs = Vec::with_capacity(1);
+        self.transform_into(&mut outputs);
+        outputs
+    }
+
+    fn transform_into(&mut self, outputs: &mut Vec<Block>) {
+        outputs.resize_with(1, Vec::new);
+        scale_block_into(&self.source, self.factor, &mut outputs[0]);
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS, Frame};
+
+/// Lo-fi bit-depth and sample-rate reducer. Each sample is quantized to
+/// `2^bits` levels in [-1, 1], and every `downsample` frames a new sample is
+/// taken and held (sample-and-hold decimation).
+#[derive(FilterMetaData, Debug, Clone)]
+pub struct BitCrusher {
+    #[filter_source]
+    source: Arc<Block>,
+    #[filter_parameter(val, 8, 1, 24)]
+    pub bits: u32,
+    #[filter_parameter(val, 1, 1, 64)]
+    pub downsample: usize,
+    /// Last sampled (quantized) frame, held until the next decimation point.
+    held: Frame,
+    /// Frames elapsed since the last sample was taken, kept across blocks.
+    counter: usize,
+}
+
+impl BitCrusher {
+    pub fn new(bits: u32, downsample: usize) -> Self {
+        Self {
+            source: Arc::new(Vec::new()),
+            bits,
+            downsample,
+            held: [0.0; CHANNELS],
+            counter: 0,
+        }
+    }
+
+    /// Rounds `sample` to the closest of the `2^bits` levels spanning [-1, 1].
+    fn quantize(&self, sample: f32) -> f32 {
+        let levels = 2f32.powi(self.bits.clamp(1, 24) as i32);
+        let step = 2.0 / (levels - 1.0);
+        let index = ((sample.clamp(-1.0, 1.0) + 1.0) / step).round();
+        index * step - 1.0
+    }
+}
+
+impl Default for BitCrusher {
+    fn default() -> Self {
+        Self::new(8, 1)
+    }
+}
+
+impl fmt::Display for BitCrusher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BitCrusher: {} bits, downsample x{}",
+            self.bits, self.downsample
+        )
+    }
+}
+
+impl Entry for BitCrusher {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl Filter for BitCrusher {
+    fn transform(&mut self) -> Vec<Block> {
+        let downsample = self.downsample.max(1);
+        let source = Arc::clone(&self.source);
+        let output: Block = source
+            .iter()
+            .map(|frame| {
+                if self.counter == 0 {
+                    self.held = std::array::from_fn(|ch| self.quantize(frame[ch]));
+                }
+                self.counter = (self.counter + 1) % downsample;
+                self.held
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -7,12 +7,39 @@ use rustic_derive::FilterMetaData;
 use crate::core::Block;
 use crate::core::graph::{Entry, Filter};
 
+/// How the clipper limits samples above its threshold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClipMode {
+    /// Clamp samples to the threshold.
+    #[default]
+    Hard,
+    /// Saturate smoothly towards the threshold with `tanh`.
+    Soft,
+}
+
+impl From<usize> for ClipMode {
+    fn from(index: usize) -> Self {
+        match index {
+            1 => ClipMode::Soft,
+            _ => ClipMode::Hard,
+        }
+    }
+}
+
+impl From<ClipMode> for usize {
+    fn from(mode: ClipMode) -> Self {
+        mode as usize
+    }
+}
+
 #[derive(FilterMetaData, Debug, Clone, Default)]
 pub struct Clipper {
     #[filter_source]
     source: Arc<Block>,
     #[filter_parameter(range, 0.0, 1.0, 0.5)]
     pub max_ampl: f32,
+    #[filter_parameter(choices = ["Hard", "Soft"])]
+    pub mode: ClipMode,
 }
 
 impl Clipper {
@@ -20,13 +47,19 @@ impl Clipper {
         Self {
             source: Arc::new(Vec::new()),
             max_ampl: max,
+            mode: ClipMode::Hard,
         }
     }
+
+    pub fn with_mode(mut self, mode: ClipMode) -> Self {
+        self.mode = mode;
+        self
+    }
 }
 
 impl fmt::Display for Clipper {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Clipper: max ampl: {}", self.max_ampl)
+        write!(f, "Clipper: max ampl: {} ({:?})", self.max_ampl, self.mode)
     }
 }
 
@@ -39,10 +72,20 @@ impl Entry for Clipper {
 impl Filter for Clipper {
     fn transform(&mut self) -> Vec<Block> {
         let max = self.max_ampl;
+        let clip = match self.mode {
+            ClipMode::Hard => |sample: f32, max: f32| sample.clamp(-max, max),
+            ClipMode::Soft => |sample: f32, max: f32| {
+                if max > 0.0 {
+                    max * (sample / max).tanh()
+                } else {
+                    0.0
+                }
+            },
+        };
         let output: Block = self
             .source
             .par_iter()
-            .map(|frame| std::array::from_fn(|ch| frame[ch].clamp(-max, max)))
+            .map(|frame| std::array::from_fn(|ch| clip(frame[ch], max)))
             .collect();
         vec![output]
     }
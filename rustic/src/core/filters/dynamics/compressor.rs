@@ -15,13 +15,13 @@ pub struct Compressor {
     #[filter_parameter(range, 0.0, 1.0, 0.5)]
     threshold: f32,
     /// Compression ratio (1.0 = no compression, higher = more compression)
-    #[filter_parameter(range, 1.0, 20.0, 4.0)]
+    #[filter_parameter(range, 1.0, 20.0, 4.0, scale = log)]
     ratio: f32,
     /// Attack time in seconds
-    #[filter_parameter(range, 0.0001, 0.1, 0.01)]
+    #[filter_parameter(range, 0.0001, 0.1, 0.01, unit = "s")]
     attack: f32,
     /// Release time in seconds
-    #[filter_parameter(range, 0.01, 1.0, 0.1)]
+    #[filter_parameter(range, 0.01, 1.0, 0.1, unit = "s")]
     release: f32,
     /// Per-channel envelope follower values
     envelope: [f32; CHANNELS],
@@ -29,20 +29,26 @@ pub struct Compressor {
     sample_rate: f32,
 }
 
-impl Default for Compressor {
-    fn default() -> Self {
+impl Compressor {
+    pub fn new(threshold: f32, ratio: f32, attack: f32, release: f32, sample_rate: f32) -> Self {
         Self {
             source: Arc::new(Vec::new()),
-            threshold: 0.5,
-            ratio: 4.0,
-            attack: 0.01,
-            release: 0.1,
+            threshold,
+            ratio,
+            attack,
+            release,
             envelope: [0.0; CHANNELS],
-            sample_rate: 44100.0,
+            sample_rate,
         }
     }
 }
 
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new(0.5, 4.0, 0.01, 0.1, 44100.0)
+    }
+}
+
 impl Entry for Compressor {
     fn push(&mut self, block: Arc<Block>, _port: usize) {
         self.source = block;
@@ -74,8 +80,9 @@ impl Filter for Compressor {
                             release_coeff * (self.envelope[ch] - input_abs) + input_abs;
                     }
                     let gain = if self.envelope[ch] > self.threshold {
+                        // Above the threshold, the level rises 1/ratio as fast
                         let excess = self.envelope[ch] / self.threshold;
-                        let compressed = excess.powf(1.0 - 1.0 / self.ratio);
+                        let compressed = excess.powf(1.0 / self.ratio);
                         (compressed * self.threshold) / self.envelope[ch]
                     } else {
                         1.0
@@ -88,6 +95,10 @@ impl Filter for Compressor {
         vec![output]
     }
 
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -0,0 +1,100 @@
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+
+/// Follows the amplitude envelope of its input, for sidechaining or
+/// envelope-controlled effects such as an auto-wah.
+/// Output port 0 passes the input through unchanged, output port 1 carries
+/// the smoothed amplitude of each channel. Connected to a parameter port,
+/// the envelope drives that parameter.
+#[derive(FilterMetaData, Clone, Debug)]
+#[filter(outputs = 2)]
+pub struct EnvelopeFollower {
+    #[filter_source]
+    source: Arc<Block>,
+    /// Time for the envelope to cover 63% of a rise, in seconds
+    #[filter_parameter(range, 0.0001, 1.0, 0.01, unit = "s", scale = log)]
+    attack: f32,
+    /// Time for the envelope to cover 63% of a fall, in seconds
+    #[filter_parameter(range, 0.001, 5.0, 0.1, unit = "s", scale = log)]
+    release: f32,
+    envelope: [f32; CHANNELS],
+    sample_rate: f32,
+}
+
+impl EnvelopeFollower {
+    pub fn new(attack: f32, release: f32, sample_rate: f32) -> Self {
+        Self {
+            source: Arc::new(Vec::new()),
+            attack,
+            release,
+            envelope: [0.0; CHANNELS],
+            sample_rate,
+        }
+    }
+
+    /// Latest value of the envelope, per channel.
+    pub fn envelope(&self) -> [f32; CHANNELS] {
+        self.envelope
+    }
+}
+
+impl Default for EnvelopeFollower {
+    fn default() -> Self {
+        Self::new(0.01, 0.1, 44100.0)
+    }
+}
+
+impl Entry for EnvelopeFollower {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl fmt::Display for EnvelopeFollower {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Envelope Follower - attack: {}s, release: {}s",
+            self.attack, self.release
+        )
+    }
+}
+
+impl Filter for EnvelopeFollower {
+    fn transform(&mut self) -> Vec<Block> {
+        let attack_coeff = (-1.0 / (self.attack * self.sample_rate)).exp();
+        let release_coeff = (-1.0 / (self.release * self.sample_rate)).exp();
+
+        let envelope: Block = self
+            .source
+            .iter()
+            .map(|frame| {
+                std::array::from_fn(|ch| {
+                    let input_abs = frame[ch].abs();
+                    let coeff = if input_abs > self.envelope[ch] {
+                        attack_coeff
+                    } else {
+                        release_coeff
+                    };
+                    self.envelope[ch] = coeff * (self.envelope[ch] - input_abs) + input_abs;
+                    self.envelope[ch]
+                })
+            })
+            .collect();
+
+        vec![self.source.to_vec(), envelope]
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -20,10 +20,10 @@ pub struct Limiter {
     #[filter_parameter(range, 0.0, 1.0, 0.95)]
     threshold: f32,
     /// Attack time in seconds — how fast the limiter engages on a peak
-    #[filter_parameter(range, 0.0001, 0.1, 0.001)]
+    #[filter_parameter(range, 0.0001, 0.1, 0.001, unit = "s")]
     attack: f32,
     /// Release time in seconds — how fast the limiter recovers after a peak
-    #[filter_parameter(range, 0.01, 1.0, 0.2)]
+    #[filter_parameter(range, 0.01, 1.0, 0.2, unit = "s")]
     release: f32,
     /// Per-channel peak envelope state
     envelope: [f32; CHANNELS],
@@ -90,6 +90,10 @@ impl Filter for Limiter {
         vec![output]
     }
 
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
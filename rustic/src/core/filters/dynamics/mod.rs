@@ -1,9 +1,15 @@
 pub mod amplifier;
+pub mod bitcrusher;
 pub mod clipper;
 pub mod compressor;
+pub mod envelope_follower;
 pub mod limiter;
+pub mod multiband_compressor;
 
 pub use amplifier::*;
+pub use bitcrusher::*;
 pub use clipper::*;
 pub use compressor::*;
+pub use envelope_follower::*;
 pub use limiter::*;
+pub use multiband_compressor::*;
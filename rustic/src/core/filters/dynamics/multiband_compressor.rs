@@ -0,0 +1,162 @@
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+use rustic_meta::MetaFilter;
+
+use crate::core::audio::add_block;
+use crate::core::filters::dynamics::Compressor;
+use crate::core::filters::frequency::Crossover;
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+
+/// Three-band compressor: the input is split into low, mid and high bands
+/// by a [`Crossover`], each band goes through its own [`Compressor`], and
+/// the bands are summed back.
+///
+/// Each band has its own threshold and ratio, while attack and release are
+/// shared. A band whose level stays below its threshold comes out as it went
+/// in, so the compressor only acts where the energy is.
+#[derive(FilterMetaData, Clone, Debug)]
+pub struct MultibandCompressor {
+    #[filter_source]
+    source: Arc<Block>,
+    /// Crossover between the low and mid bands
+    #[filter_parameter(range, 20.0, 2000.0, 200.0, unit = "Hz", scale = log)]
+    low_crossover: f32,
+    /// Crossover between the mid and high bands
+    #[filter_parameter(range, 500.0, 16000.0, 3000.0, unit = "Hz", scale = log)]
+    high_crossover: f32,
+    #[filter_parameter(range, 0.0, 1.0, 0.5)]
+    low_threshold: f32,
+    #[filter_parameter(range, 1.0, 20.0, 4.0, scale = log)]
+    low_ratio: f32,
+    #[filter_parameter(range, 0.0, 1.0, 0.5)]
+    mid_threshold: f32,
+    #[filter_parameter(range, 1.0, 20.0, 4.0, scale = log)]
+    mid_ratio: f32,
+    #[filter_parameter(range, 0.0, 1.0, 0.5)]
+    high_threshold: f32,
+    #[filter_parameter(range, 1.0, 20.0, 4.0, scale = log)]
+    high_ratio: f32,
+    /// Attack time of every band, in seconds
+    #[filter_parameter(range, 0.0001, 0.1, 0.01, unit = "s")]
+    attack: f32,
+    /// Release time of every band, in seconds
+    #[filter_parameter(range, 0.01, 1.0, 0.1, unit = "s")]
+    release: f32,
+    crossover: Crossover,
+    /// Low, mid and high band compressors
+    compressors: [Compressor; 3],
+}
+
+impl MultibandCompressor {
+    /// A compressor with bands split at `low_crossover` and `high_crossover`
+    /// Hz, all compressing above 0.5 with a 4:1 ratio until configured with
+    /// [`set_band`](Self::set_band).
+    pub fn new(low_crossover: f32, high_crossover: f32, sample_rate: f32) -> Self {
+        Self {
+            source: Arc::new(Vec::new()),
+            low_crossover,
+            high_crossover,
+            low_threshold: 0.5,
+            low_ratio: 4.0,
+            mid_threshold: 0.5,
+            mid_ratio: 4.0,
+            high_threshold: 0.5,
+            high_ratio: 4.0,
+            attack: 0.01,
+            release: 0.1,
+            crossover: Crossover::new(&[low_crossover, high_crossover], sample_rate),
+            compressors: std::array::from_fn(|_| Compressor::new(0.5, 4.0, 0.01, 0.1, sample_rate)),
+        }
+    }
+
+    /// Sets the threshold and ratio of `band`: 0 for low, 1 for mid and 2
+    /// for high. Other bands are ignored.
+    pub fn set_band(&mut self, band: usize, threshold: f32, ratio: f32) {
+        let (band_threshold, band_ratio) = match band {
+            0 => (&mut self.low_threshold, &mut self.low_ratio),
+            1 => (&mut self.mid_threshold, &mut self.mid_ratio),
+            2 => (&mut self.high_threshold, &mut self.high_ratio),
+            _ => return,
+        };
+        *band_threshold = threshold.clamp(0.0, 1.0);
+        *band_ratio = ratio.clamp(1.0, 20.0);
+    }
+
+    /// Forwards the parameters to the crossover and the band compressors.
+    fn sync_bands(&mut self) {
+        if self.crossover.frequencies() != [self.low_crossover, self.high_crossover] {
+            self.crossover
+                .set_frequencies(&[self.low_crossover, self.high_crossover]);
+        }
+        let bands = [
+            (self.low_threshold, self.low_ratio),
+            (self.mid_threshold, self.mid_ratio),
+            (self.high_threshold, self.high_ratio),
+        ];
+        for (compressor, (threshold, ratio)) in self.compressors.iter_mut().zip(bands) {
+            compressor.set_parameter("threshold", threshold);
+            compressor.set_parameter("ratio", ratio);
+            compressor.set_parameter("attack", self.attack);
+            compressor.set_parameter("release", self.release);
+        }
+    }
+}
+
+impl Default for MultibandCompressor {
+    fn default() -> Self {
+        Self::new(200.0, 3000.0, 44100.0)
+    }
+}
+
+impl Entry for MultibandCompressor {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl fmt::Display for MultibandCompressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Multiband Compressor - {}Hz / {}Hz",
+            self.low_crossover, self.high_crossover
+        )
+    }
+}
+
+impl Filter for MultibandCompressor {
+    fn transform(&mut self) -> Vec<Block> {
+        self.sync_bands();
+
+        let source = std::mem::replace(&mut self.source, Arc::new(Vec::new()));
+        let len = source.len();
+        self.crossover.push(source, 0);
+        let mut output = vec![[0.0; CHANNELS]; len];
+        for (band, compressor) in self
+            .crossover
+            .transform()
+            .into_iter()
+            .zip(&mut self.compressors)
+        {
+            compressor.push(Arc::new(band), 0);
+            if let Some(compressed) = compressor.transform().first() {
+                add_block(&mut output, compressed);
+            }
+        }
+        vec![output]
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.crossover.set_sample_rate(sample_rate);
+        for compressor in &mut self.compressors {
+            compressor.set_sample_rate(sample_rate);
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use super::comb::resize_delay_line;
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+
+/// Schroeder allpass: `y[n] = -g * x[n] + x[n - D] + g * y[n - D]`.
+///
+/// Its magnitude response is flat, only the phase changes with frequency,
+/// which smears transients without coloring the sound. Chained allpasses
+/// diffuse the echoes of a reverb and are the stages of a phaser.
+#[derive(FilterMetaData, Debug, Clone)]
+pub struct AllPassFilter {
+    #[filter_source]
+    source: Arc<Block>,
+    #[filter_parameter(val, 221, 1, 44100)]
+    pub delay: usize,
+    #[filter_parameter(range, -0.99, 0.99, 0.5)]
+    pub coefficient: f32,
+    /// Per-channel delay lines of `x[n] + g * v[n - D]`, oldest sample first
+    lines: [VecDeque<f32>; CHANNELS],
+}
+
+impl Default for AllPassFilter {
+    fn default() -> Self {
+        Self::new(221, 0.5)
+    }
+}
+
+impl AllPassFilter {
+    pub fn new(delay: usize, coefficient: f32) -> Self {
+        Self {
+            source: Arc::new(Vec::new()),
+            delay,
+            coefficient,
+            lines: std::array::from_fn(|_| VecDeque::from(vec![0.0; delay.max(1)])),
+        }
+    }
+}
+
+impl fmt::Display for AllPassFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "All-pass filter: {} samples, coefficient {}",
+            self.delay, self.coefficient
+        )
+    }
+}
+
+impl Entry for AllPassFilter {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl Filter for AllPassFilter {
+    fn transform(&mut self) -> Vec<Block> {
+        let delay = self.delay.max(1);
+        for line in &mut self.lines {
+            resize_delay_line(line, delay);
+        }
+
+        let g = self.coefficient;
+        let output: Block = self
+            .source
+            .iter()
+            .map(|frame| {
+                std::array::from_fn(|ch| {
+                    let line = &mut self.lines[ch];
+                    let delayed = line.pop_front().unwrap_or(0.0);
+                    let v = frame[ch] + g * delayed;
+                    line.push_back(v);
+                    delayed - g * v
+                })
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -10,9 +10,9 @@ use crate::core::graph::{Entry, Filter};
 #[derive(FilterMetaData, Debug, Clone, Default)]
 /// Bandpass filter using a high-pass and low-pass filter
 pub struct BandPass {
-    #[filter_parameter(range, 1.0, 20000.0, 1000.0)]
+    #[filter_parameter(range, 1.0, 20000.0, 1000.0, unit = "Hz", scale = log)]
     pub low: f32,
-    #[filter_parameter(range, 1.0, 20000.0, 1000.0)]
+    #[filter_parameter(range, 1.0, 20000.0, 1000.0, unit = "Hz", scale = log)]
     pub high: f32,
     pub sample_rate: f32,
     pub filters: (HighPassFilter, LowPassFilter),
@@ -58,6 +58,12 @@ impl Filter for BandPass {
         self.filters.1.transform()
     }
 
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.filters.0.set_sample_rate(sample_rate);
+        self.filters.1.set_sample_rate(sample_rate);
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
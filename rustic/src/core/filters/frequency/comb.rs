@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+
+/// Where the delayed signal of a [`CombFilter`] is taken from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CombMode {
+    /// Adds the delayed input: `y[n] = x[n] + g * x[n - D]`.
+    /// Notches the spectrum at odd multiples of `sample_rate / 2D` (g > 0).
+    #[default]
+    FeedForward,
+    /// Adds the delayed output: `y[n] = x[n] + g * y[n - D]`.
+    /// Resonates at multiples of `sample_rate / D`, the usual reverb comb.
+    Feedback,
+}
+
+impl From<usize> for CombMode {
+    fn from(index: usize) -> Self {
+        match index {
+            1 => CombMode::Feedback,
+            _ => CombMode::FeedForward,
+        }
+    }
+}
+
+impl From<CombMode> for usize {
+    fn from(mode: CombMode) -> Self {
+        mode as usize
+    }
+}
+
+/// Resizes a delay line to `delay` samples, keeping its most recent samples.
+pub(crate) fn resize_delay_line(line: &mut VecDeque<f32>, delay: usize) {
+    while line.len() > delay {
+        line.pop_front();
+    }
+    while line.len() < delay {
+        line.push_front(0.0);
+    }
+}
+
+/// Comb filter mixing its input with a copy delayed by `delay` samples,
+/// scaled by `gain`. Keep `|gain| < 1` in feedback mode for stability.
+#[derive(FilterMetaData, Debug, Clone)]
+pub struct CombFilter {
+    #[filter_source]
+    source: Arc<Block>,
+    #[filter_parameter(val, 441, 1, 44100)]
+    pub delay: usize,
+    #[filter_parameter(range, -0.99, 0.99, 0.5)]
+    pub gain: f32,
+    #[filter_parameter(choices = ["FeedForward", "Feedback"])]
+    pub mode: CombMode,
+    /// Per-channel delay lines, oldest sample first
+    lines: [VecDeque<f32>; CHANNELS],
+}
+
+impl Default for CombFilter {
+    fn default() -> Self {
+        Self::new(441, 0.5, CombMode::FeedForward)
+    }
+}
+
+impl CombFilter {
+    pub fn new(delay: usize, gain: f32, mode: CombMode) -> Self {
+        Self {
+            source: Arc::new(Vec::new()),
+            delay,
+            gain,
+            mode,
+            lines: std::array::from_fn(|_| VecDeque::from(vec![0.0; delay.max(1)])),
+        }
+    }
+
+    pub fn feedforward(delay: usize, gain: f32) -> Self {
+        Self::new(delay, gain, CombMode::FeedForward)
+    }
+
+    pub fn feedback(delay: usize, gain: f32) -> Self {
+        Self::new(delay, gain, CombMode::Feedback)
+    }
+}
+
+impl fmt::Display for CombFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Comb filter ({:?}): {} samples, gain {}",
+            self.mode, self.delay, self.gain
+        )
+    }
+}
+
+impl Entry for CombFilter {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl Filter for CombFilter {
+    fn transform(&mut self) -> Vec<Block> {
+        let delay = self.delay.max(1);
+        for line in &mut self.lines {
+            resize_delay_line(line, delay);
+        }
+
+        let output: Block = self
+            .source
+            .iter()
+            .map(|frame| {
+                std::array::from_fn(|ch| {
+                    let line = &mut self.lines[ch];
+                    let delayed = line.pop_front().unwrap_or(0.0);
+                    let output = frame[ch] + self.gain * delayed;
+                    line.push_back(match self.mode {
+                        CombMode::FeedForward => frame[ch],
+                        CombMode::Feedback => output,
+                    });
+                    output
+                })
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
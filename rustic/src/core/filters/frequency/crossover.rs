@@ -0,0 +1,221 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS, Frame};
+
+/// Second-order section in transposed direct form II, with one state per
+/// channel. Coefficients are the RBJ cookbook ones, normalised by `a0`.
+#[derive(Clone, Debug)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    zs: [[f64; 2]; CHANNELS],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self {
+            b: b.map(|b| b / a[0]),
+            a: [a[1] / a[0], a[2] / a[0]],
+            zs: [[0.0; 2]; CHANNELS],
+        }
+    }
+
+    /// `(cos w, alpha)` of a Butterworth (Q = 1/√2) section at `frequency`
+    fn butterworth(frequency: f32, sample_rate: f32) -> (f64, f64) {
+        let w = 2.0 * PI * frequency as f64 / sample_rate as f64;
+        (w.cos(), w.sin() / (2.0 * FRAC_1_SQRT_2))
+    }
+
+    fn lowpass(frequency: f32, sample_rate: f32) -> Self {
+        let (cos, alpha) = Self::butterworth(frequency, sample_rate);
+        let b1 = 1.0 - cos;
+        Self::new(
+            [b1 / 2.0, b1, b1 / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    fn highpass(frequency: f32, sample_rate: f32) -> Self {
+        let (cos, alpha) = Self::butterworth(frequency, sample_rate);
+        let b1 = 1.0 + cos;
+        Self::new(
+            [b1 / 2.0, -b1, b1 / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    fn allpass(frequency: f32, sample_rate: f32) -> Self {
+        let (cos, alpha) = Self::butterworth(frequency, sample_rate);
+        Self::new(
+            [1.0 - alpha, -2.0 * cos, 1.0 + alpha],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    fn process(&mut self, frame: Frame) -> Frame {
+        std::array::from_fn(|ch| {
+            let input = frame[ch] as f64;
+            let zs = &mut self.zs[ch];
+            let out = self.b[0] * input + zs[0];
+            zs[0] = self.b[1] * input - self.a[0] * out + zs[1];
+            zs[1] = self.b[2] * input - self.a[1] * out;
+            out as f32
+        })
+    }
+}
+
+/// Fourth-order Linkwitz-Riley split: two cascaded Butterworth sections per
+/// side, so that the low and high outputs sum to an all-pass.
+#[derive(Clone, Debug)]
+struct Split {
+    lowpass: [Biquad; 2],
+    highpass: [Biquad; 2],
+}
+
+impl Split {
+    fn new(frequency: f32, sample_rate: f32) -> Self {
+        Self {
+            lowpass: std::array::from_fn(|_| Biquad::lowpass(frequency, sample_rate)),
+            highpass: std::array::from_fn(|_| Biquad::highpass(frequency, sample_rate)),
+        }
+    }
+
+    /// Returns the `(low, high)` parts of `frame`.
+    fn process(&mut self, frame: Frame) -> (Frame, Frame) {
+        let [low_1, low_2] = &mut self.lowpass;
+        let [high_1, high_2] = &mut self.highpass;
+        (
+            low_2.process(low_1.process(frame)),
+            high_2.process(high_1.process(frame)),
+        )
+    }
+}
+
+/// Splits its input into frequency bands, one per output port, for
+/// multiband processing.
+///
+/// Bands are separated by fourth-order Linkwitz-Riley filters at each
+/// crossover frequency: port 0 carries the frequencies below the first
+/// crossover, the last port those above the last one. The lower bands go
+/// through all-pass filters matching the phase of the splits above them, so
+/// summing all the bands gives back the input with a flat magnitude
+/// response, only its phase being shifted.
+#[derive(FilterMetaData, Clone, Debug)]
+#[filter(outputs = 2, outputs_from = band_count)]
+pub struct Crossover {
+    #[filter_source]
+    source: Arc<Block>,
+    /// Crossover frequencies in Hz, in ascending order
+    frequencies: Vec<f32>,
+    sample_rate: f32,
+    splits: Vec<Split>,
+    /// Phase compensation of each band but the last one
+    allpasses: Vec<Vec<Biquad>>,
+}
+
+impl Crossover {
+    /// A crossover with a band below, between and above each of
+    /// `frequencies`, in Hz.
+    pub fn new(frequencies: &[f32], sample_rate: f32) -> Self {
+        let mut frequencies = frequencies.to_vec();
+        frequencies.sort_by(f32::total_cmp);
+
+        let splits = frequencies
+            .iter()
+            .map(|&frequency| Split::new(frequency, sample_rate))
+            .collect();
+        // Band `k` goes through the all-passes of the splits after its own
+        let allpasses = (0..frequencies.len())
+            .map(|band| {
+                frequencies[band + 1..]
+                    .iter()
+                    .map(|&frequency| Biquad::allpass(frequency, sample_rate))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            source: Arc::new(Vec::new()),
+            frequencies,
+            sample_rate,
+            splits,
+            allpasses,
+        }
+    }
+
+    pub fn frequencies(&self) -> &[f32] {
+        &self.frequencies
+    }
+
+    /// Moves the crossovers to `frequencies`, resetting the filters. The
+    /// number of bands follows the number of frequencies.
+    pub fn set_frequencies(&mut self, frequencies: &[f32]) {
+        let source = std::mem::take(&mut self.source);
+        *self = Self::new(frequencies, self.sample_rate);
+        self.source = source;
+    }
+
+    /// Number of bands, and of output ports.
+    pub fn band_count(&self) -> usize {
+        self.frequencies.len() + 1
+    }
+}
+
+impl Default for Crossover {
+    fn default() -> Self {
+        Self::new(&[1000.0], 44100.0)
+    }
+}
+
+impl Entry for Crossover {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl fmt::Display for Crossover {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Crossover - {:?}Hz", self.frequencies)
+    }
+}
+
+impl Filter for Crossover {
+    fn transform(&mut self) -> Vec<Block> {
+        let mut bands = vec![Vec::with_capacity(self.source.len()); self.band_count()];
+        for &frame in self.source.iter() {
+            let mut rest = frame;
+            for (band, split) in bands.iter_mut().zip(&mut self.splits) {
+                let (low, high) = split.process(rest);
+                band.push(low);
+                rest = high;
+            }
+            if let Some(last) = bands.last_mut() {
+                last.push(rest);
+            }
+        }
+
+        for (band, allpasses) in bands.iter_mut().zip(&mut self.allpasses) {
+            for allpass in allpasses {
+                for frame in band.iter_mut() {
+                    *frame = allpass.process(*frame);
+                }
+            }
+        }
+        bands
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        let frequencies = std::mem::take(&mut self.frequencies);
+        self.set_frequencies(&frequencies);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -11,7 +11,7 @@ use crate::core::{Block, CHANNELS};
 pub struct HighPassFilter {
     #[filter_source]
     source: Arc<Block>,
-    #[filter_parameter(range, 1.0, 20000.0, 1000.0)]
+    #[filter_parameter(range, 1.0, 20000.0, 1000.0, unit = "Hz", scale = log)]
     cutoff_frequency: f32,
     sample_rate: f32,
     previous_output: [f32; CHANNELS],
@@ -44,31 +44,37 @@ impl fmt::Display for HighPassFilter {
 
 impl Filter for HighPassFilter {
     fn transform(&mut self) -> Vec<Block> {
+        let mut outputs = Vec::with_capacity(1);
+        self.transform_into(&mut outputs);
+        outputs
+    }
+
+    fn transform_into(&mut self, outputs: &mut Vec<Block>) {
         let rc = 1.0 / (2.0 * std::f32::consts::PI * self.cutoff_frequency);
         let dt = 1.0 / self.sample_rate;
         let alpha = rc / (rc + dt);
 
-        let output: Block = self
-            .source
-            .iter()
-            .map(|frame| {
-                std::array::from_fn(|ch| {
-                    let y =
-                        alpha * (self.previous_output[ch] + frame[ch] - self.previous_input[ch]);
-                    self.previous_output[ch] = y;
-                    self.previous_input[ch] = frame[ch];
-                    y
-                })
+        outputs.resize_with(1, Vec::new);
+        let output = &mut outputs[0];
+        output.clear();
+        output.extend(self.source.iter().map(|frame| {
+            std::array::from_fn(|ch| {
+                let y = alpha * (self.previous_output[ch] + frame[ch] - self.previous_input[ch]);
+                self.previous_output[ch] = y;
+                self.previous_input[ch] = frame[ch];
+                y
             })
-            .collect();
-
-        vec![output]
+        }));
     }
 
     fn postponable(&self) -> bool {
         false
     }
 
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
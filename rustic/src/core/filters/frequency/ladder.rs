@@ -0,0 +1,131 @@
+use std::f32::consts::PI;
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+
+/// Feedback gain at full resonance, slightly above the 4.0 where the ladder
+/// starts to self-oscillate
+const MAX_FEEDBACK: f32 = 4.2;
+
+/// Level of the noise injected in the feedback loop, standing for the
+/// thermal noise that lets an analog ladder start oscillating on its own
+const NOISE_LEVEL: f32 = 1e-6;
+
+/// Moog-style 4-pole (24dB/octave) resonant low-pass.
+///
+/// Four one-pole stages are chained and the output of the last one is fed
+/// back, inverted and saturated by `tanh`, to the input of the first. The
+/// stages are discretized with the trapezoidal rule and the feedback loop
+/// is solved without a unit delay, so the cutoff and resonance match the
+/// analog circuit. From `resonance` ≈ 0.95 on the filter rings on its own
+/// and self-oscillates at the cutoff frequency, with the saturation keeping
+/// the oscillation bounded.
+#[derive(FilterMetaData, Debug, Clone)]
+pub struct LadderFilter {
+    #[filter_source]
+    source: Arc<Block>,
+    #[filter_parameter(range, 20.0, 20000.0, 1000.0, unit = "Hz", scale = log)]
+    pub cutoff: f32,
+    #[filter_parameter(range, 0.0, 1.0, 0.1)]
+    pub resonance: f32,
+    sample_rate: f32,
+    /// Integrator states of the four stages of each channel
+    stages: [[f32; 4]; CHANNELS],
+    /// State of the noise generator
+    seed: u32,
+}
+
+impl Default for LadderFilter {
+    fn default() -> Self {
+        Self::new(1000.0, 0.1, 44100.0)
+    }
+}
+
+impl LadderFilter {
+    pub fn new(cutoff: f32, resonance: f32, sample_rate: f32) -> Self {
+        Self {
+            source: Arc::new(Vec::new()),
+            cutoff,
+            resonance,
+            sample_rate,
+            stages: [[0.0; 4]; CHANNELS],
+            seed: 0x9E37_79B9,
+        }
+    }
+
+    /// Uniform noise in `[-NOISE_LEVEL, NOISE_LEVEL]` (xorshift)
+    fn noise(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        (self.seed as f32 / u32::MAX as f32 * 2.0 - 1.0) * NOISE_LEVEL
+    }
+}
+
+impl fmt::Display for LadderFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ladder Filter - {}Hz, resonance: {}",
+            self.cutoff, self.resonance
+        )
+    }
+}
+
+impl Entry for LadderFilter {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl Filter for LadderFilter {
+    fn transform(&mut self) -> Vec<Block> {
+        let cutoff = self.cutoff.clamp(1.0, 0.45 * self.sample_rate.max(1.0));
+        let g = (PI * cutoff / self.sample_rate.max(1.0)).tan();
+        // Gain of one stage from its input, the rest of its output comes
+        // from its state
+        let gain = g / (1.0 + g);
+        let feedback = MAX_FEEDBACK * self.resonance.max(0.0);
+
+        let source = self.source.clone();
+        let output: Block = source
+            .iter()
+            .map(|frame| {
+                std::array::from_fn(|ch| {
+                    let x = frame[ch] + self.noise();
+                    let stages = &mut self.stages[ch];
+
+                    // Linear estimate of this sample's output, which the
+                    // feedback depends on
+                    let from_states = stages
+                        .iter()
+                        .fold(0.0, |acc, state| acc * gain + state / (1.0 + g));
+                    let estimate =
+                        (gain.powi(4) * x + from_states) / (1.0 + feedback * gain.powi(4));
+
+                    let mut input = x - feedback * estimate.tanh();
+                    for state in stages.iter_mut() {
+                        let v = (input - *state) * gain;
+                        let y = v + *state;
+                        *state = y + v;
+                        input = y;
+                    }
+                    input
+                })
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -1,11 +1,19 @@
+pub mod allpass;
 pub mod bandpass;
+pub mod comb;
+pub mod crossover;
 pub mod highpass;
+pub mod ladder;
 pub mod lowpass;
 pub mod moving_average;
 pub mod resonant_bandpass;
 
+pub use allpass::*;
 pub use bandpass::*;
+pub use comb::*;
+pub use crossover::*;
 pub use highpass::*;
+pub use ladder::*;
 pub use lowpass::*;
 pub use moving_average::*;
 pub use resonant_bandpass::*;
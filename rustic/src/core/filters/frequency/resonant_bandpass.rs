@@ -13,6 +13,9 @@ use crate::core::{Block, CHANNELS};
 pub struct ResonantBandpassFilter {
     #[filter_source]
     source: Arc<Block>,
+    center_frequency: f32,
+    quality: f32,
+    sample_rate: f32,
     b: [f64; 3], // b0, b1, b2
     a: [f64; 3], // a0, a1, a2
     /// Per-channel biquad delay elements: zs[ch][0..1]
@@ -29,30 +32,26 @@ impl ResonantBandpassFilter {
     /// Resonant bandpass filter using a biquad design.
     /// Implemented from <http://musicweb.ucsd.edu/~trsmyth/filters/Bi_quadratic_Resonant_Filte.html>
     pub fn new(center_frequency: f32, quality: f32, sample_frequency: f32) -> Self {
-        let period = 1.0 / sample_frequency;
-        let bandwidth = center_frequency / quality;
-
-        let r: f64 = (-PI * bandwidth as f64 * period as f64).exp();
-
-        let gain = 1.0 - r;
-        let b: [f64; 3] = [gain, 0.0, -gain * r];
-        let a: [f64; 3] = [
-            1.0,
-            -2.0 * r * (2.0 * PI * center_frequency as f64 * period as f64).cos(),
-            r * r,
-        ];
-
-        Self {
-            source: Arc::new(Vec::new()),
-            b,
-            a,
-            zs: [[0.0; 2]; CHANNELS],
-        }
+        let mut filter = Self::default();
+        filter.set_parameters(center_frequency, quality, sample_frequency);
+        filter
     }
 
     pub fn set_parameters(&mut self, center_frequency: f32, quality: f32, sample_frequency: f32) {
-        let period = 1.0 / sample_frequency;
-        let bandwidth = center_frequency / quality;
+        self.center_frequency = center_frequency;
+        self.quality = quality;
+        self.sample_rate = sample_frequency;
+        self.update_coefficients();
+    }
+
+    /// Recomputes the biquad coefficients. A filter left unconfigured keeps
+    /// its (silent) coefficients.
+    fn update_coefficients(&mut self) {
+        if self.quality <= 0.0 || self.sample_rate <= 0.0 {
+            return;
+        }
+        let period = 1.0 / self.sample_rate;
+        let bandwidth = self.center_frequency / self.quality;
 
         let r: f64 = (-PI * bandwidth as f64 * period as f64).exp();
 
@@ -60,7 +59,7 @@ impl ResonantBandpassFilter {
         self.b = [gain, 0.0, -gain * r];
         self.a = [
             1.0,
-            -2.0 * r * (2.0 * PI * center_frequency as f64 * period as f64).cos(),
+            -2.0 * r * (2.0 * PI * self.center_frequency as f64 * period as f64).cos(),
             r * r,
         ];
     }
@@ -97,6 +96,11 @@ impl Filter for ResonantBandpassFilter {
         vec![output]
     }
 
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.update_coefficients();
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
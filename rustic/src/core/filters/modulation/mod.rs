@@ -1,3 +1,5 @@
+pub mod phaser;
 pub mod tremolo;
 
+pub use phaser::*;
 pub use tremolo::*;
@@ -0,0 +1,145 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+
+/// Lowest frequency swept by the allpass stages, in Hz
+const MIN_FREQUENCY: f32 = 200.0;
+/// Highest frequency swept by the allpass stages, in Hz
+const MAX_FREQUENCY: f32 = 2000.0;
+/// Offset between the LFOs of two successive channels
+const CHANNEL_PHASE_OFFSET: f32 = FRAC_PI_2;
+
+/// First-order allpass section, `H(z) = (a + z^-1) / (1 + a z^-1)`, whose
+/// coefficient may change on every sample
+#[derive(Debug, Clone, Copy, Default)]
+struct AllPassStage {
+    state: f32,
+}
+
+impl AllPassStage {
+    fn process(&mut self, input: f32, coefficient: f32) -> f32 {
+        let output = coefficient * input + self.state;
+        self.state = input - coefficient * output;
+        output
+    }
+}
+
+/// A phaser: the input goes through a cascade of allpass stages whose
+/// center frequency is swept by an LFO, and is mixed back with the dry
+/// signal. Where the stages shift the phase by half a turn the two cancel,
+/// carving notches that move up and down the spectrum.
+///
+/// `depth` sets the wet/dry balance (0 is passthrough, 1 gives the deepest
+/// notches) and `feedback` sends the wet signal back into the stages to
+/// sharpen them. Each channel's LFO is a quarter turn ahead of the previous
+/// one, spreading the sweep across the stereo field.
+#[derive(FilterMetaData, Debug, Clone)]
+pub struct Phaser {
+    #[filter_source]
+    source: Arc<Block>,
+    #[filter_parameter(val, 4, 1, 12)]
+    pub stages: usize,
+    #[filter_parameter(range, 0.0, 10.0, 0.5, unit = "Hz")]
+    pub rate: f32,
+    #[filter_parameter(range, 0.0, 1.0, 1.0)]
+    pub depth: f32,
+    #[filter_parameter(range, 0.0, 0.95, 0.0)]
+    pub feedback: f32,
+    sample_rate: f32,
+    phase: f32,
+    /// Allpass stages of each channel
+    sections: [Vec<AllPassStage>; CHANNELS],
+    /// Last wet sample of each channel, fed back into the stages
+    last_wet: [f32; CHANNELS],
+}
+
+impl Default for Phaser {
+    fn default() -> Self {
+        Self::new(4, 0.5, 1.0, 0.0, 44100.0)
+    }
+}
+
+impl Phaser {
+    pub fn new(stages: usize, rate: f32, depth: f32, feedback: f32, sample_rate: f32) -> Self {
+        Self {
+            source: Arc::new(Vec::new()),
+            stages,
+            rate,
+            depth,
+            feedback,
+            sample_rate,
+            phase: 0.0,
+            sections: std::array::from_fn(|_| vec![AllPassStage::default(); stages]),
+            last_wet: [0.0; CHANNELS],
+        }
+    }
+
+    /// Allpass coefficient placing the half-turn phase shift of a stage at
+    /// the frequency swept at LFO `phase`
+    fn coefficient(&self, phase: f32) -> f32 {
+        let sweep = 0.5 * (1.0 + phase.sin());
+        let frequency = MIN_FREQUENCY * (MAX_FREQUENCY / MIN_FREQUENCY).powf(sweep);
+        let t = (PI * frequency / self.sample_rate.max(1.0)).tan();
+        (t - 1.0) / (t + 1.0)
+    }
+}
+
+impl fmt::Display for Phaser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Phaser: {} stages, {}Hz, depth: {}, feedback: {}",
+            self.stages, self.rate, self.depth, self.feedback
+        )
+    }
+}
+
+impl Entry for Phaser {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl Filter for Phaser {
+    fn transform(&mut self) -> Vec<Block> {
+        let stages = self.stages.max(1);
+        for section in &mut self.sections {
+            section.resize(stages, AllPassStage::default());
+        }
+        let phase_increment = 2.0 * PI * self.rate / self.sample_rate.max(1.0);
+        let (dry, wet_gain) = (1.0 - 0.5 * self.depth, 0.5 * self.depth);
+
+        let source = self.source.clone();
+        let output: Block = source
+            .iter()
+            .map(|frame| {
+                let frame = std::array::from_fn(|ch| {
+                    let coefficient =
+                        self.coefficient(self.phase + ch as f32 * CHANNEL_PHASE_OFFSET);
+                    let input = frame[ch] + self.feedback * self.last_wet[ch];
+                    let wet = self.sections[ch]
+                        .iter_mut()
+                        .fold(input, |signal, stage| stage.process(signal, coefficient));
+                    self.last_wet[ch] = wet;
+                    frame[ch] * dry + wet * wet_gain
+                });
+                self.phase = (self.phase + phase_increment) % (2.0 * PI);
+                frame
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -13,7 +13,7 @@ pub struct Tremolo {
     #[filter_source]
     source: Arc<Block>,
     phase: f32,
-    #[filter_parameter(range, 0.0, 20.0, 1.0)]
+    #[filter_parameter(range, 0.0, 20.0, 1.0, unit = "Hz")]
     pub frequency: f32,
     #[filter_parameter(range, 0.0, 1.0, 0.5)]
     pub depth: f32,
@@ -65,6 +65,10 @@ impl Filter for Tremolo {
         vec![output]
     }
 
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
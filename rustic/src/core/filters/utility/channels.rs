@@ -0,0 +1,100 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+use rustic_derive::FilterMetaData;
+
+/// Copies the first channel of its input to every channel, turning the
+/// output of a mono stage into centered stereo.
+#[derive(FilterMetaData, Clone, Default)]
+pub struct MonoToStereo {
+    #[filter_source]
+    source: Arc<Block>,
+}
+
+impl MonoToStereo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Entry for MonoToStereo {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl fmt::Display for MonoToStereo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mono to Stereo")
+    }
+}
+
+impl fmt::Debug for MonoToStereo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MonoToStereo")
+    }
+}
+
+impl Filter for MonoToStereo {
+    fn transform(&mut self) -> Vec<Block> {
+        vec![
+            self.source
+                .iter()
+                .map(|frame| [frame[0]; CHANNELS])
+                .collect(),
+        ]
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Averages the channels of its input and outputs the result on every
+/// channel, folding a stereo stage down to mono.
+#[derive(FilterMetaData, Clone, Default)]
+pub struct StereoToMono {
+    #[filter_source]
+    source: Arc<Block>,
+}
+
+impl StereoToMono {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Entry for StereoToMono {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl fmt::Display for StereoToMono {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Stereo to Mono")
+    }
+}
+
+impl fmt::Debug for StereoToMono {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StereoToMono")
+    }
+}
+
+impl Filter for StereoToMono {
+    fn transform(&mut self) -> Vec<Block> {
+        vec![
+            self.source
+                .iter()
+                .map(|frame| [frame.iter().sum::<f32>() / CHANNELS as f32; CHANNELS])
+                .collect(),
+        ]
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustfft::{Fft, FftPlanner, num_complex::Complex};
+use rustic_derive::FilterMetaData;
+
+use crate::core::graph::sources::read_wav;
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS, Frame};
+
+/// Partition size, in frames, of [`ConvolutionReverb::new`]
+pub const DEFAULT_PARTITION_SIZE: usize = 256;
+
+/// One spectrum per channel
+type Spectra = [Vec<Complex<f32>>; CHANNELS];
+
+/// Reverb convolving its input with the impulse response (IR) of a real
+/// space, usually loaded from a WAV file.
+///
+/// The IR is cut into partitions of equal size, and each partition is
+/// convolved in the frequency domain (uniformly partitioned overlap-save),
+/// so the cost stays low even for IRs several seconds long. The output
+/// lags the input by [`latency`](Self::latency) frames, one partition
+/// minus one; the dry signal is delayed as much to stay aligned with the
+/// reverb. Each channel of a stereo IR is applied to the matching channel.
+#[derive(FilterMetaData, Clone)]
+pub struct ConvolutionReverb {
+    #[filter_source]
+    source: Arc<Block>,
+    #[filter_parameter(range, 0.0, 1.0, 0.3)]
+    mix: f32,
+    partition_size: usize,
+    fft: Arc<dyn Fft<f32>>,
+    ifft: Arc<dyn Fft<f32>>,
+    /// Spectra of the IR partitions, the earliest first
+    ir_spectra: Vec<Spectra>,
+    /// Spectra of the latest input partitions, the newest first
+    input_spectra: VecDeque<Spectra>,
+    /// Previous and current input partitions
+    window: Vec<Frame>,
+    /// Frames gathered for the current input partition
+    filled: usize,
+    /// Dry and wet frames waiting to be output
+    pending: VecDeque<(Frame, Frame)>,
+}
+
+impl ConvolutionReverb {
+    /// A reverb applying `ir`, with partitions of [`DEFAULT_PARTITION_SIZE`].
+    pub fn new(ir: &[Frame], mix: f32) -> Self {
+        Self::with_partition_size(ir, mix, DEFAULT_PARTITION_SIZE)
+    }
+
+    /// A reverb applying `ir`, with partitions of `partition_size` frames.
+    /// Smaller partitions lower the latency but cost more per frame.
+    pub fn with_partition_size(ir: &[Frame], mix: f32, partition_size: usize) -> Self {
+        let partition_size = partition_size.max(1);
+        let fft_size = 2 * partition_size;
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(fft_size);
+        let ifft = planner.plan_fft_inverse(fft_size);
+
+        let ir_spectra: Vec<Spectra> = ir
+            .chunks(partition_size)
+            .map(|partition| {
+                std::array::from_fn(|ch| {
+                    let mut spectrum = vec![Complex::default(); fft_size];
+                    for (bin, frame) in spectrum.iter_mut().zip(partition) {
+                        bin.re = frame[ch];
+                    }
+                    fft.process(&mut spectrum);
+                    spectrum
+                })
+            })
+            .collect();
+        let input_spectra = (0..ir_spectra.len())
+            .map(|_| std::array::from_fn(|_| vec![Complex::default(); fft_size]))
+            .collect();
+
+        Self {
+            source: Arc::new(Vec::new()),
+            mix: mix.clamp(0.0, 1.0),
+            partition_size,
+            fft,
+            ifft,
+            ir_spectra,
+            input_spectra,
+            window: vec![[0.0; CHANNELS]; fft_size],
+            filled: 0,
+            pending: VecDeque::from(vec![([0.0; CHANNELS], [0.0; CHANNELS]); partition_size - 1]),
+        }
+    }
+
+    /// Loads the IR from the WAV file at `path`, resampled to `sample_rate`.
+    ///
+    /// # Errors
+    /// Returns the `hound` error if the file cannot be opened or decoded.
+    pub fn from_wav<P: AsRef<Path>>(path: P, sample_rate: u32, mix: f32) -> hound::Result<Self> {
+        Ok(Self::new(&read_wav(path, sample_rate)?, mix))
+    }
+
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    /// Delay between the input and the output, in frames.
+    pub fn latency(&self) -> usize {
+        self.partition_size - 1
+    }
+
+    /// Length of the IR, rounded up to a whole number of partitions.
+    pub fn ir_len(&self) -> usize {
+        self.ir_spectra.len() * self.partition_size
+    }
+
+    /// Convolves the completed input partition and queues its output.
+    fn process_partition(&mut self) {
+        let size = self.partition_size;
+        let Some(mut spectra) = self.input_spectra.pop_back() else {
+            // Empty IR
+            let dry = self.window[size..].to_vec();
+            self.pending
+                .extend(dry.into_iter().map(|frame| (frame, [0.0; CHANNELS])));
+            self.window.copy_within(size.., 0);
+            return;
+        };
+        for (ch, spectrum) in spectra.iter_mut().enumerate() {
+            for (bin, frame) in spectrum.iter_mut().zip(&self.window) {
+                *bin = Complex::new(frame[ch], 0.0);
+            }
+            self.fft.process(spectrum);
+        }
+        self.input_spectra.push_front(spectra);
+
+        let fft_size = 2 * size;
+        let mut wet = vec![[0.0; CHANNELS]; size];
+        for ch in 0..CHANNELS {
+            let mut accumulator = vec![Complex::default(); fft_size];
+            for (input, ir) in self.input_spectra.iter().zip(&self.ir_spectra) {
+                for ((acc, x), h) in accumulator.iter_mut().zip(&input[ch]).zip(&ir[ch]) {
+                    *acc += x * h;
+                }
+            }
+            self.ifft.process(&mut accumulator);
+            // Overlap-save: the first half wraps around and is discarded
+            for (frame, value) in wet.iter_mut().zip(&accumulator[size..]) {
+                frame[ch] = value.re / fft_size as f32;
+            }
+        }
+
+        self.pending
+            .extend(self.window[size..].iter().copied().zip(wet));
+        self.window.copy_within(size.., 0);
+    }
+}
+
+impl Default for ConvolutionReverb {
+    /// A reverb with a unit impulse as IR, which leaves the signal unchanged
+    /// until a real IR is loaded.
+    fn default() -> Self {
+        Self::new(&[[1.0; CHANNELS]], 0.3)
+    }
+}
+
+impl Entry for ConvolutionReverb {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl fmt::Display for ConvolutionReverb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Convolution Reverb - {} frames IR, mix: {}",
+            self.ir_len(),
+            self.mix
+        )
+    }
+}
+
+impl fmt::Debug for ConvolutionReverb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConvolutionReverb")
+            .field("mix", &self.mix)
+            .field("partition_size", &self.partition_size)
+            .field("partitions", &self.ir_spectra.len())
+            .finish()
+    }
+}
+
+impl Filter for ConvolutionReverb {
+    fn transform(&mut self) -> Vec<Block> {
+        let source = std::mem::replace(&mut self.source, Arc::new(Vec::new()));
+        let size = self.partition_size;
+        let output = source
+            .iter()
+            .map(|frame| {
+                self.window[size + self.filled] = *frame;
+                self.filled += 1;
+                if self.filled == size {
+                    self.process_partition();
+                    self.filled = 0;
+                }
+                let (dry, wet) = self
+                    .pending
+                    .pop_front()
+                    .unwrap_or(([0.0; CHANNELS], [0.0; CHANNELS]));
+                std::array::from_fn(|ch| dry[ch] + (wet[ch] - dry[ch]) * self.mix)
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -1,34 +1,76 @@
+use std::fmt;
 use std::sync::Arc;
-use std::{collections::VecDeque, fmt};
 
 use rustic_derive::FilterMetaData;
 
 use crate::core::graph::{Entry, Filter};
 use crate::core::{Block, CHANNELS, Frame};
 
-/// Delays its input by a fixed number of seconds.
+/// Longest delay [`DelayFilter::new`] can be modulated up to, in seconds,
+/// unless its initial delay is longer. The `delay` parameter advertises
+/// this range, so that every value offered to a GUI fits the buffer.
+pub const DEFAULT_MAX_DELAY: f32 = 2.0;
+
+/// Delays its input by a number of seconds.
+///
+/// The delay line is a ring buffer allocated once for the longest delay the
+/// filter supports. Changing the `delay` parameter only moves the read
+/// position, and fractional delays are linearly interpolated, so the delay
+/// time can be modulated while the filter runs. Delays longer than the
+/// maximum are clamped to it; use [`DelayFilter::with_max_delay`] for delays
+/// beyond [`DEFAULT_MAX_DELAY`].
 #[derive(FilterMetaData, Clone)]
 pub struct DelayFilter {
     #[filter_source]
     source: Arc<Block>,
-    #[filter_parameter(range, 0.0, 20.0, 0.5)]
-    delay_for: f32,
-    buffer: VecDeque<Frame>,
-    /// Stored for future use (e.g. recomputing buffer size on set_parameter)
-    #[allow(dead_code)]
+    #[filter_parameter(range, 0.0, 2.0, 0.5, unit = "s")]
+    delay: f32,
+    /// Latest input frames; `write` is the index of the newest one
+    buffer: Vec<Frame>,
+    write: usize,
     sample_rate: f32,
 }
 
 impl DelayFilter {
+    /// A delay of `delay` seconds, which can later be raised up to
+    /// [`DEFAULT_MAX_DELAY`] or `delay`, whichever is longer.
     pub fn new(sample_rate: f32, delay: f32) -> Self {
-        let n_frames = (delay * sample_rate) as usize;
+        Self::with_max_delay(sample_rate, delay, delay.max(DEFAULT_MAX_DELAY))
+    }
+
+    /// A delay of `delay` seconds, with a buffer sized for delays up to
+    /// `max_delay` seconds.
+    pub fn with_max_delay(sample_rate: f32, delay: f32, max_delay: f32) -> Self {
+        // One more frame for the current input and one for interpolation
+        let n_frames = (max_delay.max(0.0) * sample_rate).ceil() as usize + 2;
         Self {
             source: Arc::new(Vec::new()),
-            delay_for: delay,
-            buffer: VecDeque::from(vec![[0.0; CHANNELS]; n_frames]),
+            delay,
+            buffer: vec![[0.0; CHANNELS]; n_frames],
+            write: 0,
             sample_rate,
         }
     }
+
+    pub fn delay(&self) -> f32 {
+        self.delay
+    }
+
+    /// Longest delay the buffer can hold, in seconds.
+    pub fn max_delay(&self) -> f32 {
+        (self.buffer.len() - 2) as f32 / self.sample_rate
+    }
+
+    /// Frame delayed by `frames` from the newest one, interpolated between
+    /// the two closest stored frames.
+    fn read(&self, frames: f32) -> Frame {
+        let len = self.buffer.len();
+        let whole = frames as usize;
+        let frac = frames - whole as f32;
+        let newer = self.buffer[(self.write + len - whole) % len];
+        let older = self.buffer[(self.write + len - whole - 1) % len];
+        std::array::from_fn(|ch| newer[ch] + (older[ch] - newer[ch]) * frac)
+    }
 }
 
 impl Default for DelayFilter {
@@ -45,28 +87,43 @@ impl Entry for DelayFilter {
 
 impl fmt::Display for DelayFilter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Delay Filter - {}s", self.delay_for)
+        write!(f, "Delay Filter - {}s", self.delay)
     }
 }
 
 impl fmt::Debug for DelayFilter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "DelayFilter {{ delay_for: {} }}", self.delay_for)
+        write!(f, "DelayFilter {{ delay: {} }}", self.delay)
     }
 }
 
 impl Filter for DelayFilter {
     fn transform(&mut self) -> Vec<Block> {
-        self.buffer.extend(self.source.iter());
-        let output = self.buffer.drain(0..self.source.len());
-        self.source = Arc::new(Vec::new());
-        vec![output.collect()]
+        let max_frames = (self.buffer.len() - 2) as f32;
+        let frames = (self.delay * self.sample_rate).clamp(0.0, max_frames);
+        let source = std::mem::replace(&mut self.source, Arc::new(Vec::new()));
+        let output = source
+            .iter()
+            .map(|frame| {
+                self.write = (self.write + 1) % self.buffer.len();
+                self.buffer[self.write] = *frame;
+                self.read(frames)
+            })
+            .collect();
+        vec![output]
     }
 
     fn postponable(&self) -> bool {
         true
     }
 
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        // The buffer holds the same maximum delay in seconds, emptied
+        let source = std::mem::take(&mut self.source);
+        *self = Self::with_max_delay(sample_rate, self.delay, self.max_delay());
+        self.source = source;
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
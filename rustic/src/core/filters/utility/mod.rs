@@ -1,5 +1,15 @@
+pub mod channels;
+pub mod convolution_reverb;
 pub mod delay;
+pub mod oversample;
 pub mod pan;
+pub mod parallel_mix;
+pub mod ping_pong;
 
+pub use channels::*;
+pub use convolution_reverb::*;
 pub use delay::*;
+pub use oversample::*;
 pub use pan::*;
+pub use parallel_mix::*;
+pub use ping_pong::*;
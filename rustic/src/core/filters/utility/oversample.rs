@@ -0,0 +1,204 @@
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS, Frame};
+use rustic_meta::{FilterInfo, MetaFilter};
+
+/// Taps of the anti-aliasing filters for each step of the oversampling factor
+const TAPS_PER_PHASE: usize = 48;
+
+/// Windowed-sinc (Blackman) low-pass with unity DC gain, cutting slightly
+/// below the Nyquist frequency of the original rate
+fn anti_aliasing_taps(factor: usize) -> Vec<f32> {
+    let len = TAPS_PER_PHASE * factor;
+    let cutoff = 0.45 / factor as f32;
+    let mid = (len - 1) as f32 / 2.0;
+    let taps: Vec<f32> = (0..len)
+        .map(|i| {
+            let t = i as f32 - mid;
+            let sinc = if t == 0.0 {
+                2.0 * cutoff
+            } else {
+                (2.0 * PI * cutoff * t).sin() / (PI * t)
+            };
+            let phase = 2.0 * PI * i as f32 / (len - 1) as f32;
+            sinc * (0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+        })
+        .collect();
+    let sum: f32 = taps.iter().sum();
+    taps.into_iter().map(|tap| tap / sum).collect()
+}
+
+/// Runs a filter at a multiple of the graph's sample rate, so that the
+/// harmonics generated by non-linear filters (`Clipper`, `BitCrusher`, ...)
+/// are filtered out instead of folding back into the audible band.
+///
+/// The input is upsampled by zero-stuffing and a polyphase low-pass, the
+/// inner filter processes the oversampled block, and its output is low-passed
+/// again before being decimated back to the original rate. Both filters are
+/// linear phase and together delay the signal by about 48 frames.
+///
+/// The inner filter's parameters are exposed as the wrapper's own.
+///
+/// ```
+/// use rustic::core::filters::prelude::{Clipper, Oversample};
+///
+/// let clipper = Oversample::new(Box::new(Clipper::new(0.5)), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Oversample {
+    inner: Box<dyn Filter>,
+    factor: usize,
+    taps: Vec<f32>,
+    source: Arc<Block>,
+    /// Latest input frames, newest first
+    input_history: VecDeque<Frame>,
+    /// Latest oversampled frames out of the inner filter, newest first
+    output_history: VecDeque<Frame>,
+}
+
+impl Oversample {
+    /// Wraps `inner`, running it at `factor` times the sample rate
+    /// (typically 2 or 4). A factor of 1 runs the inner filter as is.
+    pub fn new(inner: Box<dyn Filter>, factor: usize) -> Self {
+        let factor = factor.max(1);
+        let taps = anti_aliasing_taps(factor);
+        Self {
+            inner,
+            factor,
+            input_history: VecDeque::from(vec![[0.0; CHANNELS]; TAPS_PER_PHASE]),
+            output_history: VecDeque::from(vec![[0.0; CHANNELS]; taps.len()]),
+            taps,
+            source: Arc::new(Vec::new()),
+        }
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    pub fn inner(&self) -> &dyn Filter {
+        self.inner.as_ref()
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Box<dyn Filter> {
+        &mut self.inner
+    }
+
+    fn upsample(&mut self) -> Block {
+        let mut block = Vec::with_capacity(self.source.len() * self.factor);
+        for frame in self.source.iter() {
+            self.input_history.pop_back();
+            self.input_history.push_front(*frame);
+            for phase in 0..self.factor {
+                let mut out = [0.0; CHANNELS];
+                let taps = self.taps.iter().skip(phase).step_by(self.factor);
+                for (tap, input) in taps.zip(&self.input_history) {
+                    for ch in 0..CHANNELS {
+                        out[ch] += tap * input[ch];
+                    }
+                }
+                // Zero-stuffing divides the energy by the factor
+                block.push(out.map(|sample| sample * self.factor as f32));
+            }
+        }
+        block
+    }
+
+    fn downsample(&mut self, oversampled: &[Frame]) -> Block {
+        let mut block = Vec::with_capacity(oversampled.len() / self.factor);
+        for chunk in oversampled.chunks(self.factor) {
+            for frame in chunk {
+                self.output_history.pop_back();
+                self.output_history.push_front(*frame);
+            }
+            let mut out = [0.0; CHANNELS];
+            for (tap, input) in self.taps.iter().zip(&self.output_history) {
+                for ch in 0..CHANNELS {
+                    out[ch] += tap * input[ch];
+                }
+            }
+            block.push(out);
+        }
+        block
+    }
+}
+
+impl Entry for Oversample {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl fmt::Display for Oversample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Oversample x{} - {}", self.factor, self.inner)
+    }
+}
+
+impl MetaFilter for Oversample {
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        self.inner.set_parameter(name, value);
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<f32> {
+        self.inner.get_parameter(name)
+    }
+
+    fn filter_info(&self) -> FilterInfo {
+        let inner = self.inner.filter_info();
+        FilterInfo {
+            inputs: inner.inputs,
+            outputs: inner.outputs,
+            ..Self::metadata()
+        }
+    }
+
+    fn metadata() -> FilterInfo {
+        FilterInfo {
+            name: "Oversample",
+            type_id: "Oversample",
+            description: "Runs a filter at a higher sample rate to reduce aliasing",
+            inputs: vec![rustic_meta::FilterInput {
+                label: None,
+                parameter: None,
+            }],
+            outputs: 1,
+        }
+    }
+}
+
+impl Filter for Oversample {
+    fn transform(&mut self) -> Vec<Block> {
+        if self.factor == 1 {
+            self.inner.push(self.source.clone(), 0);
+            return self.inner.transform();
+        }
+
+        let oversampled = self.upsample();
+        self.inner.push(Arc::new(oversampled), 0);
+        let processed = self
+            .inner
+            .transform()
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        vec![self.downsample(&processed)]
+    }
+
+    fn postponable(&self) -> bool {
+        self.inner.postponable()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        // The inner filter runs on the oversampled signal
+        self.inner.set_sample_rate(sample_rate * self.factor as f32);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+use rustic_meta::{FilterInfo, FilterInput, MetaFilter, Parameter, ParameterScale};
+
+/// Runs a filter in parallel with the unprocessed signal and blends both,
+/// the usual dry/wet control of an effect.
+///
+/// The input is sent both to the dry path and to the inner filter. `mix`
+/// goes from 0 (only the dry signal) to 1 (only the inner filter's output).
+/// The dry path is not delayed, so inner filters with latency (e.g. an
+/// [`Oversample`](super::Oversample)) are blended slightly out of phase.
+///
+/// The inner filter's parameters are exposed as the wrapper's own, next to
+/// `mix`.
+///
+/// ```
+/// use rustic::core::filters::prelude::{DelayFilter, ParallelMix};
+///
+/// let echo = ParallelMix::new(Box::new(DelayFilter::new(44100.0, 0.25)), 0.3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParallelMix {
+    inner: Box<dyn Filter>,
+    mix: f32,
+    source: Arc<Block>,
+}
+
+impl ParallelMix {
+    /// Wraps `inner`, blending `mix` of its output with `1 - mix` of the input.
+    pub fn new(inner: Box<dyn Filter>, mix: f32) -> Self {
+        Self {
+            inner,
+            mix: mix.clamp(0.0, 1.0),
+            source: Arc::new(Vec::new()),
+        }
+    }
+
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    pub fn inner(&self) -> &dyn Filter {
+        self.inner.as_ref()
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Box<dyn Filter> {
+        &mut self.inner
+    }
+
+    fn mix_parameter(&self) -> Parameter<&'static str> {
+        Parameter::Range {
+            title: "Mix",
+            field_name: "mix",
+            min: 0.0,
+            max: 1.0,
+            default: 0.5,
+            value: self.mix,
+            unit: None,
+            scale: ParameterScale::Linear,
+        }
+    }
+}
+
+impl Entry for ParallelMix {
+    fn push(&mut self, block: Arc<Block>, port: usize) {
+        if port == 0 {
+            self.source = block.clone();
+        }
+        self.inner.push(block, port);
+    }
+}
+
+impl fmt::Display for ParallelMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parallel Mix {} - {}", self.mix, self.inner)
+    }
+}
+
+impl MetaFilter for ParallelMix {
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        match name {
+            "mix" => self.set_mix(value),
+            _ => self.inner.set_parameter(name, value),
+        }
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<f32> {
+        match name {
+            "mix" => Some(self.mix),
+            _ => self.inner.get_parameter(name),
+        }
+    }
+
+    fn filter_info(&self) -> FilterInfo {
+        let mut inputs = self.inner.filter_info().inputs;
+        inputs.push(FilterInput {
+            label: Some("Mix"),
+            parameter: Some(self.mix_parameter()),
+        });
+        FilterInfo {
+            inputs,
+            ..Self::metadata()
+        }
+    }
+
+    fn metadata() -> FilterInfo {
+        FilterInfo {
+            name: "Parallel Mix",
+            type_id: "ParallelMix",
+            description: "Blends a filter's output with its dry input",
+            inputs: vec![FilterInput {
+                label: None,
+                parameter: None,
+            }],
+            outputs: 1,
+        }
+    }
+}
+
+impl Filter for ParallelMix {
+    fn transform(&mut self) -> Vec<Block> {
+        let wet = self
+            .inner
+            .transform()
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        let dry = std::mem::replace(&mut self.source, Arc::new(Vec::new()));
+        let output = dry
+            .iter()
+            .enumerate()
+            .map(|(idx, frame)| {
+                let wet = wet.get(idx).copied().unwrap_or([0.0; CHANNELS]);
+                std::array::from_fn(|ch| frame[ch] + (wet[ch] - frame[ch]) * self.mix)
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn postponable(&self) -> bool {
+        self.inner.postponable()
+    }
+
+    fn block_size(&self) -> Option<usize> {
+        self.inner.block_size()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.inner.set_sample_rate(sample_rate);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+use rustic_derive::FilterMetaData;
+
+use crate::core::filters::frequency::comb::resize_delay_line;
+use crate::core::graph::{Entry, Filter};
+use crate::core::{Block, CHANNELS};
+
+/// Stereo delay whose echoes bounce between the channels.
+///
+/// The input is folded to mono and feeds the left delay line; each echo
+/// then crosses over to the other line, scaled by `feedback`, so the
+/// repeats alternate left, right, left, ... The dry signal keeps its
+/// original stereo image and is mixed with the echoes according to `mix`.
+#[derive(FilterMetaData, Debug, Clone)]
+pub struct PingPongDelay {
+    #[filter_source]
+    source: Arc<Block>,
+    #[filter_parameter(range, 0.001, 2.0, 0.3, unit = "s")]
+    pub delay_time: f32,
+    #[filter_parameter(range, 0.0, 0.95, 0.5)]
+    pub feedback: f32,
+    #[filter_parameter(range, 0.0, 1.0, 0.5)]
+    pub mix: f32,
+    sample_rate: f32,
+    left: VecDeque<f32>,
+    right: VecDeque<f32>,
+}
+
+impl Default for PingPongDelay {
+    fn default() -> Self {
+        Self::new(0.3, 0.5, 0.5, 44100.0)
+    }
+}
+
+impl PingPongDelay {
+    pub fn new(delay_time: f32, feedback: f32, mix: f32, sample_rate: f32) -> Self {
+        let mut delay = Self {
+            source: Arc::new(Vec::new()),
+            delay_time,
+            feedback,
+            mix,
+            sample_rate,
+            left: VecDeque::new(),
+            right: VecDeque::new(),
+        };
+        delay.resize_lines();
+        delay
+    }
+
+    /// Length of the delay lines in frames
+    fn delay_frames(&self) -> usize {
+        ((self.delay_time * self.sample_rate).round() as usize).max(1)
+    }
+
+    fn resize_lines(&mut self) {
+        let frames = self.delay_frames();
+        resize_delay_line(&mut self.left, frames);
+        resize_delay_line(&mut self.right, frames);
+    }
+}
+
+impl fmt::Display for PingPongDelay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ping-pong delay - {}s, feedback: {}, mix: {}",
+            self.delay_time, self.feedback, self.mix
+        )
+    }
+}
+
+impl Entry for PingPongDelay {
+    fn push(&mut self, block: Arc<Block>, _port: usize) {
+        self.source = block;
+    }
+}
+
+impl Filter for PingPongDelay {
+    fn transform(&mut self) -> Vec<Block> {
+        self.resize_lines();
+
+        let source = self.source.clone();
+        let output: Block = source
+            .iter()
+            .map(|frame| {
+                let mono = frame.iter().sum::<f32>() / CHANNELS as f32;
+                let left_echo = self.left.pop_front().unwrap_or(0.0);
+                let right_echo = self.right.pop_front().unwrap_or(0.0);
+                self.left.push_back(mono + self.feedback * right_echo);
+                self.right.push_back(self.feedback * left_echo);
+
+                let wet: [f32; CHANNELS] = [left_echo, right_echo];
+                std::array::from_fn(|ch| frame[ch] * (1.0 - self.mix) + wet[ch] * self.mix)
+            })
+            .collect();
+        vec![output]
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.resize_lines();
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::{
     envelope::Envelope,
-    generator::{prelude::MixMode, tone::SingleToneGenerator},
+    generator::{
+        prelude::MixMode,
+        tone::SingleToneGenerator,
+        voice_filter::{FilterConfig, VoiceFilter},
+    },
 };
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +20,12 @@ pub struct MultiToneGenerator {
     mix_mode: MixMode,
     global_pitch_envelope: Option<Box<dyn Envelope>>,
     global_amplitude_envelope: Option<Box<dyn Envelope>>,
+    /// Optional low-pass filter owned by this voice.
+    #[serde(default)]
+    voice_filter: Option<VoiceFilter>,
+    /// Divides the output by the number of active tones in `MixMode::Sum`.
+    #[serde(default)]
+    auto_normalize: bool,
     time: f32,
     note_off: Option<f32>,
 }
@@ -43,6 +53,8 @@ impl MultiToneGenerator {
             mix_mode,
             global_pitch_envelope,
             global_amplitude_envelope,
+            voice_filter: None,
+            auto_normalize: false,
             time: 0.0,
             note_off: None,
         }
@@ -54,6 +66,9 @@ impl MultiToneGenerator {
         self.note_off = None;
         // Reset all child tone generators to ensure clean retriggering
         self.tone_generators.iter_mut().for_each(|tg| tg.start());
+        if let Some(filter) = &mut self.voice_filter {
+            filter.start();
+        }
     }
 
     pub fn stop(&mut self) {
@@ -63,6 +78,9 @@ impl MultiToneGenerator {
         );
         self.note_off = Some(self.time);
         self.tone_generators.iter_mut().for_each(|tg| tg.stop());
+        if let Some(filter) = &mut self.voice_filter {
+            filter.stop();
+        }
     }
 
     pub fn completed(&self) -> bool {
@@ -83,6 +101,19 @@ impl MultiToneGenerator {
         self.tone_generators.iter().all(|tg| tg.completed())
     }
 
+    /// Returns the current amplitude envelope level. The global amplitude envelope
+    /// takes precedence; without one, the loudest tone envelope is reported.
+    pub fn envelope_level(&self) -> f32 {
+        if let Some(envelope) = &self.global_amplitude_envelope {
+            envelope.at(self.time, self.note_off.unwrap_or(0.0))
+        } else {
+            self.tone_generators
+                .iter()
+                .map(|tg| tg.envelope_level())
+                .fold(0.0, f32::max)
+        }
+    }
+
     /// Runs the generator for 1 sample
     pub fn tick(&mut self, time_elapsed: f32) -> f32 {
         let actual_elapsed = if let Some(envelope) = &self.global_pitch_envelope {
@@ -104,13 +135,26 @@ impl MultiToneGenerator {
             MixMode::Average => values.iter().sum::<f32>() / values.len() as f32,
             MixMode::Multiply => values.iter().fold(1.0, |a, v| a * v),
             MixMode::Max => values.iter().fold(f32::NEG_INFINITY, |a, v| a.max(*v)),
+            MixMode::Sum if self.auto_normalize => {
+                let active = self
+                    .tone_generators
+                    .iter()
+                    .filter(|tg| !tg.completed())
+                    .count();
+                values.iter().sum::<f32>() / active.max(1) as f32
+            }
             MixMode::Sum => values.iter().sum(),
         };
 
-        if let Some(envelope) = &self.global_amplitude_envelope {
+        let ampl = if let Some(envelope) = &self.global_amplitude_envelope {
             ampl * envelope.at(self.time, self.note_off.unwrap_or(0.0))
         } else {
             ampl
+        };
+
+        match &mut self.voice_filter {
+            Some(filter) => filter.tick(ampl, time_elapsed),
+            None => ampl,
         }
     }
 
@@ -137,4 +181,20 @@ impl MultiToneGenerator {
     pub fn set_global_amplitude_envelope(&mut self, envelope: Box<dyn Envelope>) {
         self.global_amplitude_envelope = Some(envelope);
     }
+
+    /// When enabled, the sum of the tones (in `MixMode::Sum`) is divided by
+    /// the number of tones still playing, keeping full-scale tones within
+    /// [-1, 1]. The other mix modes already stay within the tones' range.
+    pub fn set_auto_normalize(&mut self, auto_normalize: bool) {
+        self.auto_normalize = auto_normalize;
+    }
+
+    pub fn auto_normalize(&self) -> bool {
+        self.auto_normalize
+    }
+
+    /// Sets (or clears) the low-pass filter applied to this voice's output.
+    pub fn set_voice_filter(&mut self, config: Option<FilterConfig>) {
+        self.voice_filter = config.map(VoiceFilter::new);
+    }
 }
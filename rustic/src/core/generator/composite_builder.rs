@@ -9,6 +9,7 @@ pub struct MultiToneGeneratorBuilder {
     mix_mode: super::prelude::MixMode,
     pitch: Option<Box<dyn Envelope>>,
     amplitude: Option<Box<dyn Envelope>>,
+    auto_normalize: bool,
 }
 
 impl Default for MultiToneGeneratorBuilder {
@@ -19,6 +20,7 @@ impl Default for MultiToneGeneratorBuilder {
             mix_mode: super::prelude::MixMode::Sum,
             pitch: None,
             amplitude: None,
+            auto_normalize: false,
         }
     }
 }
@@ -48,6 +50,12 @@ impl MultiToneGeneratorBuilder {
         self
     }
 
+    /// See [`MultiToneGenerator::set_auto_normalize`].
+    pub fn with_auto_normalize(mut self, auto_normalize: bool) -> Self {
+        self.auto_normalize = auto_normalize;
+        self
+    }
+
     pub fn add_generator(mut self, generator: SingleToneGenerator) -> Self {
         if !generator.has_frequency_relation()
             && !matches!(
@@ -65,12 +73,14 @@ impl MultiToneGeneratorBuilder {
     }
 
     pub fn build(self) -> MultiToneGenerator {
-        MultiToneGenerator::new(
+        let mut generator = MultiToneGenerator::new(
             self.base_freq,
             self.generators,
             self.mix_mode,
             self.pitch,
             self.amplitude,
-        )
+        );
+        generator.set_auto_normalize(self.auto_normalize);
+        generator
     }
 }
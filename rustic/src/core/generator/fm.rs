@@ -0,0 +1,140 @@
+use std::f32::consts::TAU;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::envelope::{Envelope, prelude::ADSREnvelope};
+
+/// A sine oscillator running at a ratio of the voice frequency, shaped by its
+/// own ADSR envelope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FmOperator {
+    /// Frequency of the operator relative to the voice base frequency
+    pub ratio: f32,
+    pub envelope: ADSREnvelope,
+    /// Current phase, in radians
+    phase: f32,
+}
+
+impl FmOperator {
+    pub fn new(ratio: f32, envelope: ADSREnvelope) -> Self {
+        Self {
+            ratio,
+            envelope,
+            phase: 0.0,
+        }
+    }
+
+    /// Returns the operator output with its phase offset by `modulation`
+    /// radians, then advances its phase.
+    fn tick(
+        &mut self,
+        base_frequency: f32,
+        modulation: f32,
+        time: f32,
+        note_off: f32,
+        time_elapsed: f32,
+    ) -> f32 {
+        let value = (self.phase + modulation).sin() * self.envelope.at(time, note_off);
+        self.phase = (self.phase + TAU * base_frequency * self.ratio * time_elapsed) % TAU;
+        value
+    }
+}
+
+/// A two-operator FM voice: a modulator sine drives the phase of a carrier
+/// sine.
+///
+/// The modulator runs at `ratio` times the base frequency and its output,
+/// scaled by `modulation_index` and its envelope, is added to the carrier
+/// phase. An index of 0 yields a pure carrier sine; larger indices spread
+/// energy into sidebands at `carrier ± k * modulator` Hz. Integer ratios
+/// give harmonic spectra, other ratios inharmonic (bell-like) ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FmVoice {
+    carrier: FmOperator,
+    modulator: FmOperator,
+    modulation_index: f32,
+    base_frequency: f32,
+    time: f32,
+    note_off: Option<f32>,
+}
+
+impl FmVoice {
+    /// Creates a voice at `frequency` with a modulator at `ratio` times the
+    /// carrier frequency. Both operators use the default ADSR envelope.
+    pub fn new(frequency: f32, ratio: f32, modulation_index: f32) -> Self {
+        Self {
+            carrier: FmOperator::new(1.0, ADSREnvelope::default()),
+            modulator: FmOperator::new(ratio, ADSREnvelope::default()),
+            modulation_index,
+            base_frequency: frequency,
+            time: 0.0,
+            note_off: None,
+        }
+    }
+
+    /// Builder-style setter for the carrier (amplitude) envelope.
+    pub fn with_carrier_envelope(mut self, envelope: ADSREnvelope) -> Self {
+        self.carrier.envelope = envelope;
+        self
+    }
+
+    /// Builder-style setter for the modulator envelope, which shapes the
+    /// modulation index (and therefore the brightness) over time.
+    pub fn with_modulator_envelope(mut self, envelope: ADSREnvelope) -> Self {
+        self.modulator.envelope = envelope;
+        self
+    }
+
+    /// Sets the base frequency; both operators follow it through their ratios.
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.base_frequency = frequency;
+    }
+
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.modulator.ratio = ratio;
+    }
+
+    pub fn set_modulation_index(&mut self, modulation_index: f32) {
+        self.modulation_index = modulation_index;
+    }
+
+    pub fn carrier(&self) -> &FmOperator {
+        &self.carrier
+    }
+
+    pub fn modulator(&self) -> &FmOperator {
+        &self.modulator
+    }
+
+    pub fn start(&mut self) {
+        self.time = 0.0;
+        self.note_off = None;
+    }
+
+    pub fn stop(&mut self) {
+        self.note_off = Some(self.time);
+    }
+
+    pub fn completed(&self) -> bool {
+        self.note_off
+            .is_some_and(|note_off| self.carrier.envelope.completed(self.time, note_off))
+    }
+
+    /// Runs the voice for 1 sample
+    pub fn tick(&mut self, time_elapsed: f32) -> f32 {
+        let note_off = self.note_off.unwrap_or(0.0);
+        let modulation = self.modulation_index
+            * self
+                .modulator
+                .tick(self.base_frequency, 0.0, self.time, note_off, time_elapsed);
+        let value = self.carrier.tick(
+            self.base_frequency,
+            modulation,
+            self.time,
+            note_off,
+            time_elapsed,
+        );
+        self.time += time_elapsed;
+        value
+    }
+}
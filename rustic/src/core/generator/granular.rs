@@ -0,0 +1,201 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// A single grain: a windowed segment of the buffer being played back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Grain {
+    /// Read position in the buffer when the grain was triggered, in samples
+    start: f32,
+    /// Time since the grain was triggered, in samples
+    age: f32,
+}
+
+/// A granular sampler playing overlapping grains taken from a sample buffer.
+///
+/// A playhead moves through the buffer at `speed` times real time (0 freezes
+/// it), and a new grain is triggered every `grain_size / density` seconds at
+/// the playhead position, offset by a random amount of up to `spray` seconds.
+/// Each grain plays `grain_size` seconds of audio at `pitch` times the
+/// original rate, shaped by a trapezoidal window whose linear ramps span the
+/// overlap with the neighbouring grains. Reads wrap around the buffer.
+///
+/// The grain sum is divided by the window sum whenever it exceeds 1, so with
+/// no spray and a pitch of 1 the source is reproduced unchanged at any
+/// density of at least 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GranularSampler {
+    buffer: Vec<f32>,
+    sample_rate: f32,
+    grain_size: f32,
+    density: f32,
+    pitch: f32,
+    speed: f32,
+    spray: f32,
+    /// Playhead position in the buffer, in samples
+    playhead: f32,
+    /// Time since the last grain was triggered, in samples
+    since_trigger: f32,
+    grains: Vec<Grain>,
+    stopped: bool,
+}
+
+impl GranularSampler {
+    /// Creates a sampler over `buffer`, recorded at `sample_rate`, with 50 ms
+    /// grains, a density of 2, no spray and the original pitch and speed.
+    pub fn new(buffer: Vec<f32>, sample_rate: f32) -> Self {
+        Self {
+            buffer,
+            sample_rate,
+            grain_size: 0.05,
+            density: 2.0,
+            pitch: 1.0,
+            speed: 1.0,
+            spray: 0.0,
+            playhead: 0.0,
+            since_trigger: f32::INFINITY,
+            grains: Vec::new(),
+            stopped: false,
+        }
+    }
+
+    /// Builder-style setter for the grain length, in seconds.
+    pub fn with_grain_size(mut self, seconds: f32) -> Self {
+        self.set_grain_size(seconds);
+        self
+    }
+
+    /// Builder-style setter for the number of overlapping grains.
+    pub fn with_density(mut self, density: f32) -> Self {
+        self.set_density(density);
+        self
+    }
+
+    /// Builder-style setter for the grain playback rate.
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.set_pitch(pitch);
+        self
+    }
+
+    /// Builder-style setter for the playhead rate.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Builder-style setter for the grain position randomization, in seconds.
+    pub fn with_spray(mut self, seconds: f32) -> Self {
+        self.set_spray(seconds);
+        self
+    }
+
+    pub fn set_grain_size(&mut self, seconds: f32) {
+        self.grain_size = seconds.max(1.0 / self.sample_rate);
+    }
+
+    pub fn set_density(&mut self, density: f32) {
+        self.density = density.max(f32::EPSILON);
+    }
+
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch.max(0.0);
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    pub fn set_spray(&mut self, seconds: f32) {
+        self.spray = seconds.max(0.0);
+    }
+
+    /// Moves the playhead to `position`, normalized over the buffer length.
+    pub fn set_position(&mut self, position: f32) {
+        self.playhead = position.clamp(0.0, 1.0) * self.buffer.len() as f32;
+    }
+
+    /// Number of grains currently playing.
+    pub fn active_grains(&self) -> usize {
+        self.grains.len()
+    }
+
+    /// Rewinds the playhead and triggers a grain on the next tick.
+    pub fn start(&mut self) {
+        self.playhead = 0.0;
+        self.since_trigger = f32::INFINITY;
+        self.grains.clear();
+        self.stopped = false;
+    }
+
+    /// Stops triggering grains; the ones already playing finish normally.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    pub fn completed(&self) -> bool {
+        self.stopped && self.grains.is_empty()
+    }
+
+    /// Runs the sampler for 1 sample, mixing every active grain.
+    pub fn tick(&mut self, time_elapsed: f32) -> f32 {
+        if self.buffer.is_empty() {
+            return 0.0;
+        }
+
+        let step = time_elapsed * self.sample_rate;
+        let size = self.grain_size * self.sample_rate;
+        let interval = size / self.density;
+        let ramp = if self.density >= 1.0 {
+            (size - interval).min(size / 2.0)
+        } else {
+            size / 2.0 * (1.0 - self.density)
+        };
+
+        // Half a step of tolerance so rounding errors never delay a trigger
+        if !self.stopped && self.since_trigger + step / 2.0 >= interval {
+            let spray = if self.spray > 0.0 {
+                let spray = self.spray * self.sample_rate;
+                rand::thread_rng().gen_range(-spray..=spray)
+            } else {
+                0.0
+            };
+            self.grains.push(Grain {
+                start: self.playhead + spray,
+                age: 0.0,
+            });
+            self.since_trigger = 0.0;
+        }
+
+        let (mut sum, mut weight) = (0.0, 0.0);
+        for grain in &self.grains {
+            let window = if grain.age < ramp {
+                grain.age / ramp
+            } else if size - grain.age < ramp {
+                (size - grain.age) / ramp
+            } else {
+                1.0
+            };
+            sum += window * self.read(grain.start + grain.age * self.pitch);
+            weight += window;
+        }
+
+        for grain in &mut self.grains {
+            grain.age += step;
+        }
+        self.grains.retain(|grain| grain.age < size);
+        self.since_trigger += step;
+        self.playhead = (self.playhead + step * self.speed).rem_euclid(self.buffer.len() as f32);
+
+        if weight > 1.0 { sum / weight } else { sum }
+    }
+
+    /// Reads the buffer at a fractional position, wrapping around its ends.
+    fn read(&self, position: f32) -> f32 {
+        let len = self.buffer.len();
+        let position = position.rem_euclid(len as f32);
+        let index = position.floor() as usize % len;
+        let fraction = position - position.floor();
+        let a = self.buffer[index];
+        let b = self.buffer[(index + 1) % len];
+        a + (b - a) * fraction
+    }
+}
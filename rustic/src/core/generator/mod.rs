@@ -14,19 +14,35 @@
 //! - `SingleToneGenerator` exposes fine-grained control of frequency and phase.
 //! - `MultiToneGenerator` and `CompositeGenerator` provide mixing strategies
 //!   (`MixMode`) to sum, multiply or average multiple tone sources.
+//! - `FmVoice` is a two-operator FM voice: a modulator sine drives the phase
+//!   of a carrier sine.
+//! - `GranularSampler` plays overlapping windowed grains of a sample buffer.
+//! - `WavetableOscillator` reads single-cycle tables and morphs between them.
 //! - Be mindful of Nyquist (sample_rate/2) when composing high-frequency
 //!   content; aliasing can occur without bandlimiting.
 
 mod composite;
 mod composite_builder;
+mod fm;
+mod granular;
 mod tone;
 mod tone_builder;
+mod voice_filter;
+mod waveforms;
+mod wavetable;
 
 pub mod prelude {
     use serde::{Deserialize, Serialize};
 
     pub use super::composite::MultiToneGenerator;
+    pub use super::fm::{FmOperator, FmVoice};
+    pub use super::granular::GranularSampler;
     pub use super::tone::SingleToneGenerator;
+    pub use super::voice_filter::{FilterConfig, VoiceFilter};
+    pub use super::waveforms::{
+        Blank, PinkNoise, SawtoothWave, SineWave, SquareWave, TriangleWave, WhiteNoise,
+    };
+    pub use super::wavetable::{WavetableInterpolation, WavetableOscillator};
 
     pub mod builder {
         pub use super::super::composite_builder::MultiToneGeneratorBuilder;
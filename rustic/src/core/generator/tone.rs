@@ -14,6 +14,10 @@ pub struct SingleToneGenerator {
     pitch_envelope: Option<Box<dyn Envelope>>,
     amplitude_envelope: Box<dyn Envelope>,
     phase: f32,
+    /// Phase the oscillator starts from on each `start`, in radians. Without
+    /// it, the oscillator starts from a random phase and runs freely.
+    #[serde(default)]
+    initial_phase: Option<f32>,
     note_off: Option<f32>, // Time when the note turned off (stop was called)
     time: f32,
     current_frequency: f32,
@@ -34,6 +38,7 @@ impl SingleToneGenerator {
             pitch_envelope,
             amplitude_envelope,
             phase: rand::random::<f32>().rem(360.0),
+            initial_phase: None,
             time: 0.0,
             note_off: None,
             current_frequency: frequency,
@@ -45,10 +50,13 @@ impl SingleToneGenerator {
         self.time = 0.0;
         self.note_off = None;
         self.pink_b = [0.0; 7];
-        // Note: We intentionally do NOT reset phase here to avoid phase discontinuities.
-        // Each oscillator maintains its phase across note boundaries, which prevents clicks
-        // and allows for smooth retriggering. For most musical contexts, this is desirable.
-        // For a phase-reset behavior, we can consider adding a separate reset() method.
+        // Note: Unless an initial phase is set, we intentionally do NOT reset phase here to
+        // avoid phase discontinuities. Each oscillator maintains its phase across note
+        // boundaries, which prevents clicks and allows for smooth retriggering. For most
+        // musical contexts, this is desirable.
+        if let Some(phase) = self.initial_phase {
+            self.phase = phase;
+        }
     }
 
     pub fn stop(&mut self) {
@@ -109,6 +117,24 @@ impl SingleToneGenerator {
                 .at(self.time, self.note_off.unwrap_or(0.0))
     }
 
+    /// Returns the current value of the amplitude envelope.
+    pub fn envelope_level(&self) -> f32 {
+        self.amplitude_envelope
+            .at(self.time, self.note_off.unwrap_or(0.0))
+    }
+
+    /// Makes the oscillator start from `phase` (in radians) on every `start`,
+    /// e.g. to offset tones stacked in a [`MultiToneGenerator`].
+    pub fn set_initial_phase(&mut self, phase: f32) {
+        let phase = phase.rem_euclid(f32::consts::TAU);
+        self.initial_phase = Some(phase);
+        self.phase = phase;
+    }
+
+    pub fn initial_phase(&self) -> Option<f32> {
+        self.initial_phase
+    }
+
     pub fn set_frequency(&mut self, frequency: f32) {
         self.current_frequency = frequency;
     }
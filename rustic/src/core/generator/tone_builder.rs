@@ -9,6 +9,7 @@ pub struct ToneGeneratorBuilder {
     pitch_envelope: Option<Box<dyn Envelope>>,
     amplitude_envelope: Box<dyn Envelope>,
     current_frequency: f32,
+    phase: Option<f32>,
 }
 
 impl Default for ToneGeneratorBuilder {
@@ -19,6 +20,7 @@ impl Default for ToneGeneratorBuilder {
             pitch_envelope: None,
             amplitude_envelope: Box::new(ConstantSegment::new(1.0, None)),
             current_frequency: 440.0,
+            phase: None,
         }
     }
 }
@@ -43,6 +45,13 @@ impl ToneGeneratorBuilder {
         self
     }
 
+    /// Initial phase of the oscillator, in radians. By default, oscillators
+    /// start from a random phase.
+    pub fn phase(mut self, phase: f32) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
     pub fn pitch_envelope(mut self, envelope: Option<Box<dyn Envelope>>) -> Self {
         self.pitch_envelope = envelope;
         self
@@ -54,12 +63,16 @@ impl ToneGeneratorBuilder {
     }
 
     pub fn build(self) -> SingleToneGenerator {
-        SingleToneGenerator::new(
+        let mut generator = SingleToneGenerator::new(
             self.waveform,
             self.freq_relation,
             self.pitch_envelope,
             self.amplitude_envelope,
             self.current_frequency,
-        )
+        );
+        if let Some(phase) = self.phase {
+            generator.set_initial_phase(phase);
+        }
+        generator
     }
 }
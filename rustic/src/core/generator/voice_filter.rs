@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::envelope::{Envelope, prelude::ADSREnvelope};
+
+/// Configuration of a per-voice resonant low-pass filter.
+/// The effective cutoff is `cutoff + envelope_amount * envelope(t)`, so every
+/// voice sweeps its own filter from the moment its note starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterConfig {
+    /// Base cutoff frequency in Hz.
+    pub cutoff: f32,
+    /// Filter quality factor; 0.707 is a flat (Butterworth) response.
+    pub resonance: f32,
+    /// Envelope driving the cutoff, normalized in [0, 1].
+    pub envelope: Box<dyn Envelope>,
+    /// Cutoff offset in Hz reached when the envelope is at 1.0.
+    pub envelope_amount: f32,
+}
+
+impl Default for FilterConfig {
+    fn default() -> Self {
+        Self {
+            cutoff: 1000.0,
+            resonance: std::f32::consts::FRAC_1_SQRT_2,
+            envelope: Box::new(ADSREnvelope::default()),
+            envelope_amount: 0.0,
+        }
+    }
+}
+
+/// A voice-local state-variable low-pass filter (TPT/Zavalishin topology)
+/// with its own envelope clock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceFilter {
+    config: FilterConfig,
+    ic1eq: f32,
+    ic2eq: f32,
+    time: f32,
+    note_off: Option<f32>,
+}
+
+impl VoiceFilter {
+    pub fn new(config: FilterConfig) -> Self {
+        Self {
+            config,
+            ic1eq: 0.0,
+            ic2eq: 0.0,
+            time: 0.0,
+            note_off: None,
+        }
+    }
+
+    /// Restarts the filter envelope and clears the filter state.
+    pub fn start(&mut self) {
+        self.time = 0.0;
+        self.note_off = None;
+        self.ic1eq = 0.0;
+        self.ic2eq = 0.0;
+    }
+
+    /// Moves the filter envelope into its release phase.
+    pub fn stop(&mut self) {
+        self.note_off = Some(self.time);
+    }
+
+    /// Returns the current cutoff frequency, before Nyquist clamping.
+    pub fn cutoff(&self) -> f32 {
+        self.config.cutoff
+            + self.config.envelope_amount
+                * self
+                    .config
+                    .envelope
+                    .at(self.time, self.note_off.unwrap_or(0.0))
+    }
+
+    /// Filters one sample and advances the envelope by `time_elapsed` seconds.
+    pub fn tick(&mut self, input: f32, time_elapsed: f32) -> f32 {
+        let sample_rate = 1.0 / time_elapsed.max(f32::EPSILON);
+        let cutoff = self.cutoff().clamp(20.0, sample_rate * 0.45);
+        self.time += time_elapsed;
+
+        let g = (std::f32::consts::PI * cutoff / sample_rate).tan();
+        let k = 1.0 / self.config.resonance.max(0.1);
+        let a1 = 1.0 / (1.0 + g * (g + k));
+        let a2 = g * a1;
+        let a3 = g * a2;
+
+        let v3 = input - self.ic2eq;
+        let v1 = a1 * self.ic1eq + a2 * v3;
+        let v2 = self.ic2eq + a2 * self.ic1eq + a3 * v3;
+        self.ic1eq = 2.0 * v1 - self.ic1eq;
+        self.ic2eq = 2.0 * v2 - self.ic2eq;
+        v2
+    }
+}
@@ -0,0 +1,125 @@
+//! Built-in single-waveform generators, registered for the graph editor and
+//! available by type id through the [`GeneratorRegistry`](crate::meta::GeneratorRegistry).
+
+use rustic_derive::GeneratorMetaData;
+
+use super::prelude::builder::ToneGeneratorBuilder;
+use super::prelude::{FrequencyRelation, MultiToneGenerator, Waveform};
+use crate::core::graph::{SimpleSource, Source};
+use crate::meta::traits::GeneratorFactory;
+
+/// Builds a `SimpleSource` playing `waveform`. `FrequencyRelation::Identity`
+/// makes the tone track the base frequency in real time (1:1 ratio), so
+/// changing the "frequency" parameter while a note plays immediately affects
+/// the pitch.
+fn waveform_source(
+    waveform: Waveform,
+    frequency: f32,
+    amplitude: f32,
+    sample_rate: f32,
+) -> Box<dyn Source> {
+    let generator: MultiToneGenerator = ToneGeneratorBuilder::new()
+        .waveform(waveform)
+        .frequency_relation(FrequencyRelation::Identity)
+        .frequency(frequency)
+        .build()
+        .into();
+    let mut source = SimpleSource::new(generator, sample_rate).boxed();
+    source.set_parameter("amplitude", amplitude);
+    source
+}
+
+/// Declares a pitched waveform generator with frequency and amplitude.
+macro_rules! tonal_generator {
+    ($(#[$meta:meta])* $name:ident => $waveform:ident) => {
+        #[derive(GeneratorMetaData, Debug, Clone)]
+        $(#[$meta])*
+        pub struct $name {
+            #[generator_parameter(range, 1.0, 20000.0, 440.0, unit = "Hz", scale = log)]
+            pub frequency: f32,
+            #[generator_parameter(range, 0.0, 1.0, 0.5)]
+            pub amplitude: f32,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    frequency: 440.0,
+                    amplitude: 0.5,
+                }
+            }
+        }
+
+        impl GeneratorFactory for $name {
+            fn create_source(&self, sample_rate: f32) -> Box<dyn Source> {
+                waveform_source(Waveform::$waveform, self.frequency, self.amplitude, sample_rate)
+            }
+        }
+    };
+}
+
+/// Declares an unpitched generator with amplitude only.
+macro_rules! unpitched_generator {
+    ($(#[$meta:meta])* $name:ident => $waveform:ident) => {
+        #[derive(GeneratorMetaData, Debug, Clone)]
+        $(#[$meta])*
+        pub struct $name {
+            #[generator_parameter(range, 0.0, 1.0, 0.5)]
+            pub amplitude: f32,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { amplitude: 0.5 }
+            }
+        }
+
+        impl GeneratorFactory for $name {
+            fn create_source(&self, sample_rate: f32) -> Box<dyn Source> {
+                waveform_source(Waveform::$waveform, 440.0, self.amplitude, sample_rate)
+            }
+        }
+    };
+}
+
+tonal_generator! {
+    /// Smooth periodic oscillation
+    #[generator(type_id = "sine", envelope)]
+    SineWave => Sine
+}
+
+tonal_generator! {
+    /// Alternates between high and low states
+    #[generator(type_id = "square", envelope)]
+    SquareWave => Square
+}
+
+tonal_generator! {
+    /// Rises linearly then drops sharply
+    #[generator(type_id = "saw", name = "Sawtooth", envelope)]
+    SawtoothWave => Sawtooth
+}
+
+tonal_generator! {
+    /// Rises and falls linearly
+    #[generator(type_id = "triangle", name = "Triangle", envelope)]
+    TriangleWave => Triangle
+}
+
+unpitched_generator! {
+    /// Random signal, equal intensity per frequency
+    #[generator(type_id = "whitenoise", envelope)]
+    WhiteNoise => WhiteNoise
+}
+
+unpitched_generator! {
+    /// Random signal, equal energy per octave
+    #[generator(type_id = "pinknoise", envelope)]
+    PinkNoise => PinkNoise
+}
+
+unpitched_generator! {
+    /// Constant DC output
+    #[generator(type_id = "blank", name = "Blank (DC)", envelope)]
+    Blank => Blank
+}
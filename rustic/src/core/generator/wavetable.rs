@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+/// How samples are read between the points of a wavetable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WavetableInterpolation {
+    /// Straight line between the two surrounding points
+    #[default]
+    Linear,
+    /// Catmull-Rom spline through the four surrounding points
+    Cubic,
+}
+
+/// An oscillator reading through single-cycle wavetables.
+///
+/// Each table holds one period of a waveform and is read at the oscillator
+/// frequency, interpolating between points for fractional indices. With
+/// several tables, `position` (0 to 1) morphs across them: the output
+/// crossfades linearly between the two tables surrounding
+/// `position * (tables - 1)`. Tables may have different lengths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WavetableOscillator {
+    tables: Vec<Vec<f32>>,
+    interpolation: WavetableInterpolation,
+    frequency: f32,
+    position: f32,
+    /// Current phase, as a fraction of the period
+    phase: f32,
+}
+
+impl WavetableOscillator {
+    /// Creates an oscillator over `tables` playing at `frequency`. Empty
+    /// tables are ignored.
+    pub fn new(tables: Vec<Vec<f32>>, frequency: f32) -> Self {
+        Self {
+            tables: tables.into_iter().filter(|t| !t.is_empty()).collect(),
+            interpolation: WavetableInterpolation::default(),
+            frequency,
+            position: 0.0,
+            phase: 0.0,
+        }
+    }
+
+    /// Creates an oscillator with a single table of `size` points filled by
+    /// `f`, which receives the phase of each point in `0..1`.
+    pub fn from_fn<F: Fn(f32) -> f32>(size: usize, frequency: f32, f: F) -> Self {
+        let table = (0..size).map(|i| f(i as f32 / size as f32)).collect();
+        Self::new(vec![table], frequency)
+    }
+
+    /// Builder-style setter for the interpolation mode.
+    pub fn with_interpolation(mut self, interpolation: WavetableInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Builder-style setter for the morph position.
+    pub fn with_position(mut self, position: f32) -> Self {
+        self.set_position(position);
+        self
+    }
+
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+    }
+
+    /// Sets the morph position across the tables, clamped to `0..=1`.
+    pub fn set_position(&mut self, position: f32) {
+        self.position = position.clamp(0.0, 1.0);
+    }
+
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Resets the phase to the start of the period.
+    pub fn start(&mut self) {
+        self.phase = 0.0;
+    }
+
+    /// Runs the oscillator for 1 sample.
+    pub fn tick(&mut self, time_elapsed: f32) -> f32 {
+        let value = match self.tables.len() {
+            0 => 0.0,
+            1 => self.read(0),
+            count => {
+                let index = self.position * (count - 1) as f32;
+                let lower = (index.floor() as usize).min(count - 2);
+                let mix = index - lower as f32;
+                let a = self.read(lower);
+                if mix == 0.0 {
+                    a
+                } else {
+                    a + (self.read(lower + 1) - a) * mix
+                }
+            }
+        };
+
+        self.phase = (self.phase + time_elapsed * self.frequency).rem_euclid(1.0);
+        value
+    }
+
+    /// Reads table `table` at the current phase.
+    fn read(&self, table: usize) -> f32 {
+        let table = &self.tables[table];
+        let len = table.len();
+        let position = self.phase * len as f32;
+        let index = position.floor() as usize % len;
+        let t = position - position.floor();
+        let at = |offset: usize| table[(index + offset) % len];
+
+        match self.interpolation {
+            WavetableInterpolation::Linear => at(0) + (at(1) - at(0)) * t,
+            WavetableInterpolation::Cubic => {
+                let (p0, p1, p2, p3) = (at(len - 1), at(0), at(1), at(2));
+                p1 + 0.5
+                    * t
+                    * (p2 - p0
+                        + t * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3
+                            + t * (3.0 * (p1 - p2) + p3 - p0)))
+            }
+        }
+    }
+}
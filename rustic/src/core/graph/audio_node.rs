@@ -2,25 +2,61 @@ use std::sync::Arc;
 
 use rustic_meta::MixMode;
 
-use crate::core::audio::{Block, CHANNELS, silent_block};
+use crate::core::audio::{Block, CHANNELS, add_block, block_mean, silent_block};
 use crate::core::graph::Filter;
 
+use super::system::Priority;
+
 /// A node in the audio graph. Wraps a [`Filter`], owns the per-port input
 /// accumulator and the mix strategy, so [`System`] needs no global maps for
 /// pending blocks or mix modes.
+///
+/// Blocks arriving on a parameter port (after the filter's audio inputs)
+/// are not pushed to the filter: their mean sets the parameter instead.
 pub(super) struct AudioNode {
     pub(super) filter: Box<dyn Filter>,
     /// Per-port incoming blocks, accumulated between pushes and cleared after each process().
     inputs: Vec<Vec<Arc<Block>>>,
+    /// Number of audio input ports of the filter.
+    audio_ports: usize,
+    /// Parameter names of the filter's parameter ports, in port order.
+    parameter_ports: Vec<&'static str>,
     mix_mode: MixMode,
+    priority: Priority,
+    /// Number of output ports produced by the last `process()`, used to shape skipped output.
+    output_count: usize,
+    /// Output buffers handed to the filter's `transform_into`, refilled with
+    /// blocks reclaimed from `in_flight`.
+    scratch: Vec<Block>,
+    /// Blocks sent downstream by the latest `process()` calls. Once every
+    /// downstream node has dropped one, its buffer is reused for the next output.
+    in_flight: Vec<Arc<Block>>,
 }
 
+/// Number of `process()` calls a block may stay referenced downstream before
+/// the node stops waiting to reclaim its buffer. Downstream filters keep their
+/// last input until the next push, so two generations are usually alive.
+const IN_FLIGHT_GENERATIONS: usize = 3;
+
 impl AudioNode {
     pub(super) fn new(filter: Box<dyn Filter>, mix_mode: MixMode) -> Self {
+        let info = filter.filter_info();
+        let audio_ports = info.audio_port_count();
+        let parameter_ports = info
+            .inputs
+            .iter()
+            .filter_map(|input| input.parameter.as_ref().map(|p| *p.field_name()))
+            .collect();
         Self {
             filter,
             inputs: Vec::new(),
+            audio_ports,
+            parameter_ports,
             mix_mode,
+            priority: Priority::default(),
+            output_count: 1,
+            scratch: Vec::new(),
+            in_flight: Vec::new(),
         }
     }
 
@@ -38,10 +74,54 @@ impl AudioNode {
         for (port, blocks) in self.inputs.iter_mut().enumerate() {
             if !blocks.is_empty() {
                 let mixed = mix_blocks(std::mem::take(blocks), &self.mix_mode, block_size);
-                self.filter.push(mixed, port);
+                let parameter = port
+                    .checked_sub(self.audio_ports)
+                    .and_then(|idx| self.parameter_ports.get(idx));
+                match parameter {
+                    Some(name) => self.filter.set_parameter(name, block_mean(&mixed)),
+                    None => self.filter.push(mixed, port),
+                }
             }
         }
-        self.filter.transform().into_iter().map(Arc::new).collect()
+        self.reclaim_buffers();
+        self.filter.transform_into(&mut self.scratch);
+        let outputs: Vec<Arc<Block>> = self.scratch.drain(..).map(Arc::new).collect();
+        self.output_count = outputs.len();
+
+        self.in_flight.extend(outputs.iter().cloned());
+        let excess = self
+            .in_flight
+            .len()
+            .saturating_sub(IN_FLIGHT_GENERATIONS * self.output_count);
+        self.in_flight.drain(..excess);
+        outputs
+    }
+
+    /// Moves the buffers of in-flight blocks nobody else references anymore
+    /// to `scratch`, one per output port.
+    fn reclaim_buffers(&mut self) {
+        let mut idx = 0;
+        while idx < self.in_flight.len() && self.scratch.len() < self.output_count {
+            if Arc::strong_count(&self.in_flight[idx]) == 1 {
+                if let Some(block) = Arc::into_inner(self.in_flight.remove(idx)) {
+                    self.scratch.push(block);
+                }
+            } else {
+                idx += 1;
+            }
+        }
+    }
+
+    /// Drops the accumulated inputs without running the filter and returns
+    /// silent blocks on the same output ports as the last processed block.
+    pub(super) fn skip(&mut self, block_size: usize) -> Vec<Arc<Block>> {
+        self.inputs.iter_mut().for_each(Vec::clear);
+        let silence = Arc::new(silent_block(block_size));
+        vec![silence; self.output_count]
+    }
+
+    pub(super) fn filter(&self) -> &dyn Filter {
+        self.filter.as_ref()
     }
 
     pub(super) fn filter_mut(&mut self) -> &mut Box<dyn Filter> {
@@ -56,6 +136,14 @@ impl AudioNode {
         self.mix_mode.clone()
     }
 
+    pub(super) fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+    }
+
+    pub(super) fn priority(&self) -> Priority {
+        self.priority
+    }
+
     pub(super) fn postponable(&self) -> bool {
         self.filter.postponable()
     }
@@ -66,7 +154,13 @@ impl Clone for AudioNode {
         Self {
             filter: dyn_clone::clone_box(&*self.filter),
             inputs: self.inputs.clone(),
+            audio_ports: self.audio_ports,
+            parameter_ports: self.parameter_ports.clone(),
             mix_mode: self.mix_mode.clone(),
+            priority: self.priority,
+            output_count: self.output_count,
+            scratch: Vec::new(),
+            in_flight: Vec::new(),
         }
     }
 }
@@ -88,11 +182,7 @@ fn mix_blocks(blocks: Vec<Arc<Block>>, mode: &MixMode, block_size: usize) -> Arc
                 MixMode::Sum | MixMode::Average => {
                     let mut acc = silent_block(block_size);
                     for block in &blocks {
-                        for (af, bf) in acc.iter_mut().zip(block.iter()) {
-                            for ch in 0..CHANNELS {
-                                af[ch] += bf[ch];
-                            }
-                        }
+                        add_block(&mut acc, block);
                     }
                     if matches!(mode, MixMode::Average) {
                         let inv = 1.0 / count as f32;
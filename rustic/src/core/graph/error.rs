@@ -23,6 +23,53 @@ pub enum AudioGraphError {
     #[error("audio graph cycle detected")]
     CycleDetected,
 
+    #[error(
+        "connecting `{from}` to `{to}` would close a feedback loop without a delay; route the loop through a delay filter, which reads its input one block late"
+    )]
+    FeedbackWithoutDelay { from: String, to: String },
+
+    #[error("unknown filter type: {0}")]
+    UnknownFilter(String),
+
+    #[error("descriptor expects {expected} {kind}, got {got}")]
+    DescriptorMismatch {
+        kind: &'static str,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("filter `{filter}` has no input port {port} (it has {inputs})")]
+    InputPortOutOfRange {
+        filter: String,
+        port: usize,
+        inputs: usize,
+    },
+
+    #[error("filter `{filter}` has no output port {port} (it has {outputs})")]
+    OutputPortOutOfRange {
+        filter: String,
+        port: usize,
+        outputs: usize,
+    },
+
+    #[error(
+        "filter `{filter}` expects {expected} audio inputs, but its port {port} is not connected"
+    )]
+    MissingInput {
+        filter: String,
+        port: usize,
+        expected: usize,
+    },
+
+    #[error(
+        "filter `{filter}` processes blocks of {expected} frames, the system's block size {got} is not a multiple of it"
+    )]
+    BlockSizeMismatch {
+        filter: String,
+        expected: usize,
+        got: usize,
+    },
+
     #[error("processing error: {0}")]
     ProcessingError(&'static str),
 }
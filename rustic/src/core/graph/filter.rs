@@ -8,6 +8,20 @@ pub trait Filter: Entry + fmt::Display + rustic_meta::MetaFilter + Send + Sync {
     /// Returns a Vector of Blocks. Each block correspond to output port `x` of the filter
     fn transform(&mut self) -> Vec<Block>;
 
+    /// Same as [`transform`](Self::transform), but writes the output blocks
+    /// into `outputs`, reusing the buffers it already holds to avoid
+    /// allocating on every block.
+    ///
+    /// `outputs` may contain blocks left over from previous calls: their
+    /// contents are meaningless, only their capacity is meant to be reused.
+    /// After the call it holds one block per output port.
+    ///
+    /// The default implementation replaces `outputs` with the result of
+    /// `transform`; filters on the hot path override it.
+    fn transform_into(&mut self, outputs: &mut Vec<Block>) {
+        *outputs = self.transform();
+    }
+
     /// Returns true if the filter's execution can be postponed to the end of the execution cycle of the graph.
     /// A postponable element must be present in a cycle of the graph to avoid infinite looping.
     /// E.g. a delay filter can be postponed if it lies within a feedback loop.
@@ -15,6 +29,21 @@ pub trait Filter: Entry + fmt::Display + rustic_meta::MetaFilter + Send + Sync {
         false
     }
 
+    /// Number of frames the filter processes at once, when it only works on
+    /// blocks of a given size (e.g. FFT frames). The system's block size must
+    /// be a multiple of it, which [`System::compute`] checks.
+    ///
+    /// [`System::compute`]: crate::core::graph::System::compute
+    fn block_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Moves the filter to `sample_rate`, recomputing whatever it derived
+    /// from the previous one (coefficients, delay line lengths) so that it
+    /// keeps the same response in Hz and seconds. Filters that do not depend
+    /// on the sample rate keep the default, which does nothing.
+    fn set_sample_rate(&mut self, _sample_rate: f32) {}
+
     /// Enables downcasting from trait object to concrete type.
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
 }
@@ -23,13 +23,14 @@ pub use error::AudioGraphError;
 pub use entry::Entry;
 pub use filter::Filter;
 pub use sink::Sink;
-pub use source::Source;
+pub use source::{ConstantSource, SilenceSource, Source};
 
 pub use simple_sink::SimpleSink;
 pub use simple_source::{SimpleSource, simple_source};
 pub use sources::{
-    MonophonicAllocationStrategy, MonophonicSource, PolyphonicAllocationStrategy, PolyphonicSource,
+    ExternalInputHandle, ExternalInputSource, FileSource, MonophonicAllocationStrategy,
+    MonophonicSource, PolyphonicAllocationStrategy, PolyphonicSource,
 };
 
 /// The system module contains the implementation of the system element.
-pub use system::{ModTarget, ModWire, System};
+pub use system::{ModTarget, ModWire, Priority, System};
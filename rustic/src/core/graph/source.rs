@@ -1,4 +1,4 @@
-use crate::core::audio::Block;
+use crate::core::audio::{Block, Frame, mono_to_frame, silent_block};
 use crate::core::utils::Note;
 use dyn_clone::DynClone;
 
@@ -33,3 +33,55 @@ pub trait Source: std::fmt::Debug + DynClone + Send + Sync {
     fn set_parameter(&mut self, _name: &str, _value: f32) {}
 }
 dyn_clone::clone_trait_object!(Source);
+
+/// A source emitting the same frame forever, e.g. a DC offset or a constant
+/// control signal.
+///
+/// ```
+/// use rustic::core::graph::{ConstantSource, Source};
+///
+/// let mut source = ConstantSource::new(0.5);
+/// assert_eq!(source.pull(4), vec![[0.5; 2]; 4]);
+///
+/// let mut left_only = ConstantSource::per_channel([1.0, 0.0]);
+/// assert_eq!(left_only.pull(2), vec![[1.0, 0.0]; 2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstantSource {
+    pub value: Frame,
+}
+
+impl ConstantSource {
+    /// Emits `value` on every channel.
+    pub fn new(value: f32) -> Self {
+        Self {
+            value: mono_to_frame(value),
+        }
+    }
+
+    /// Emits its own value on each channel.
+    pub fn per_channel(value: Frame) -> Self {
+        Self { value }
+    }
+}
+
+impl Source for ConstantSource {
+    fn pull(&mut self, block_size: usize) -> Block {
+        vec![self.value; block_size]
+    }
+
+    fn is_active(&self) -> bool {
+        self.value.iter().any(|&sample| sample != 0.0)
+    }
+}
+
+/// A source emitting silence, for inputs that must be connected but carry
+/// no signal.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SilenceSource;
+
+impl Source for SilenceSource {
+    fn pull(&mut self, block_size: usize) -> Block {
+        silent_block(block_size)
+    }
+}
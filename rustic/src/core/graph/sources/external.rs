@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use crossbeam::queue::ArrayQueue;
+
+use crate::core::audio::{Block, CHANNELS, Frame};
+use crate::core::graph::Source;
+
+/// A source fed with samples produced outside the graph (a microphone,
+/// another application, a network stream, ...).
+///
+/// Frames are exchanged through a lock-free ring buffer: the producer side
+/// pushes with an [`ExternalInputHandle`] from any thread, and the render
+/// thread drains up to one block per `pull()`. Missing frames are rendered
+/// as silence, so an underrunning producer never stalls the graph.
+///
+/// Clones share the same ring buffer.
+#[derive(Debug, Clone)]
+pub struct ExternalInputSource {
+    queue: Arc<ArrayQueue<Frame>>,
+    active: bool,
+}
+
+/// Producer side of an [`ExternalInputSource`].
+#[derive(Debug, Clone)]
+pub struct ExternalInputHandle {
+    queue: Arc<ArrayQueue<Frame>>,
+}
+
+impl ExternalInputSource {
+    /// Creates a source buffering up to `capacity` frames, and the handle
+    /// used to feed it.
+    pub fn new(capacity: usize) -> (Self, ExternalInputHandle) {
+        let queue = Arc::new(ArrayQueue::new(capacity.max(1)));
+        (
+            Self {
+                queue: Arc::clone(&queue),
+                active: true,
+            },
+            ExternalInputHandle { queue },
+        )
+    }
+
+    /// Number of frames waiting to be rendered.
+    pub fn buffered(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+impl ExternalInputHandle {
+    /// Pushes stereo frames, returning how many were accepted before the
+    /// ring buffer filled up.
+    pub fn push(&self, frames: &[Frame]) -> usize {
+        frames
+            .iter()
+            .take_while(|&&frame| self.queue.push(frame).is_ok())
+            .count()
+    }
+
+    /// Pushes interleaved stereo samples (`L R L R ...`), returning how many
+    /// frames were accepted. A trailing incomplete frame is ignored.
+    pub fn push_interleaved(&self, samples: &[f32]) -> usize {
+        samples
+            .chunks_exact(CHANNELS)
+            .take_while(|chunk| {
+                let frame: Frame = std::array::from_fn(|ch| chunk[ch]);
+                self.queue.push(frame).is_ok()
+            })
+            .count()
+    }
+
+    /// Pushes mono samples, duplicated on every channel.
+    pub fn push_mono(&self, samples: &[f32]) -> usize {
+        samples
+            .iter()
+            .take_while(|&&sample| self.queue.push([sample; CHANNELS]).is_ok())
+            .count()
+    }
+
+    /// Free space left in the ring buffer, in frames.
+    pub fn available(&self) -> usize {
+        self.queue.capacity() - self.queue.len()
+    }
+}
+
+impl Source for ExternalInputSource {
+    fn pull(&mut self, block_size: usize) -> Block {
+        (0..block_size)
+            .map(|_| {
+                let frame = self.queue.pop().unwrap_or([0.0; CHANNELS]);
+                if self.active { frame } else { [0.0; CHANNELS] }
+            })
+            .collect()
+    }
+
+    fn start(&mut self) {
+        self.active = true;
+    }
+
+    fn stop(&mut self) {
+        self.active = false;
+    }
+
+    fn kill(&mut self) {
+        self.active = false;
+        while self.queue.pop().is_some() {}
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+}
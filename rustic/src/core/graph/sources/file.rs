@@ -0,0 +1,158 @@
+use std::path::Path;
+
+use hound::{SampleFormat, WavReader};
+
+use crate::core::audio::{Block, CHANNELS, Frame};
+use crate::core::graph::Source;
+
+/// A source playing back audio loaded from a WAV file.
+///
+/// The whole file is decoded and resampled to the system sample rate when
+/// the source is created, so `pull()` only copies frames. Mono files are
+/// duplicated on every channel and channels beyond [`CHANNELS`] are dropped.
+///
+/// Playback starts right away; at the end of the file the source either
+/// wraps around to the first frame (when looping) or renders silence and
+/// becomes inactive. `start()` rewinds to the beginning.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    frames: Vec<Frame>,
+    position: usize,
+    looping: bool,
+    active: bool,
+}
+
+impl FileSource {
+    /// Loads the WAV file at `path`, resampled to `sample_rate`.
+    ///
+    /// # Errors
+    /// Returns the `hound` error if the file cannot be opened or decoded.
+    pub fn open<P: AsRef<Path>>(path: P, sample_rate: u32) -> hound::Result<Self> {
+        Ok(Self::from_frames(read_wav(path, sample_rate)?, false))
+    }
+
+    /// Creates a source playing `frames` as they are.
+    pub fn from_frames(frames: Vec<Frame>, looping: bool) -> Self {
+        Self {
+            frames,
+            position: 0,
+            looping,
+            active: true,
+        }
+    }
+
+    /// Builder-style setter for looping at the end of the file.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Length of the loaded audio, in frames at the system sample rate.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Index of the next frame to be played.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Decodes the WAV file at `path` into frames resampled to `sample_rate`.
+/// Mono files are duplicated on every channel and channels beyond
+/// [`CHANNELS`] are dropped.
+///
+/// # Errors
+/// Returns the `hound` error if the file cannot be opened or decoded.
+pub fn read_wav<P: AsRef<Path>>(path: P, sample_rate: u32) -> hound::Result<Vec<Frame>> {
+    let mut reader = WavReader::open(path)?;
+    let spec = reader.spec();
+    let samples: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let channels = usize::from(spec.channels.max(1));
+    let frames = samples
+        .chunks_exact(channels)
+        .map(|chunk| std::array::from_fn(|ch| chunk[ch.min(channels - 1)]))
+        .collect();
+
+    Ok(resample(frames, spec.sample_rate, sample_rate))
+}
+
+/// Linearly resamples `frames` from `from` Hz to `to` Hz, the same way the
+/// analyser's `resample` does for mono buffers.
+fn resample(frames: Vec<Frame>, from: u32, to: u32) -> Vec<Frame> {
+    if from == to || from == 0 || to == 0 || frames.is_empty() {
+        return frames;
+    }
+
+    let step = from as f64 / to as f64;
+    let len = (frames.len() as f64 / step).round() as usize;
+    (0..len)
+        .map(|i| {
+            let position = i as f64 * step;
+            let index = position.floor() as usize;
+            let fraction = (position - index as f64) as f32;
+            let a = frames.get(index).copied().unwrap_or([0.0; CHANNELS]);
+            let b = frames.get(index + 1).copied().unwrap_or(a);
+            std::array::from_fn(|ch| a[ch] + (b[ch] - a[ch]) * fraction)
+        })
+        .collect()
+}
+
+impl Source for FileSource {
+    fn pull(&mut self, block_size: usize) -> Block {
+        (0..block_size)
+            .map(|_| {
+                if self.looping && self.position >= self.frames.len() && !self.frames.is_empty() {
+                    self.position = 0;
+                }
+                match self.frames.get(self.position) {
+                    Some(&frame) if self.active => {
+                        self.position += 1;
+                        frame
+                    }
+                    _ => {
+                        self.active = false;
+                        [0.0; CHANNELS]
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn start(&mut self) {
+        self.position = 0;
+        self.active = true;
+    }
+
+    fn stop(&mut self) {
+        self.active = false;
+    }
+
+    fn kill(&mut self) {
+        self.active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) {
+        if name == "loop" {
+            self.looping = value >= 0.5;
+        }
+    }
+}
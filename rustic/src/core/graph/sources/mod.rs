@@ -1,5 +1,9 @@
+pub mod external;
+pub mod file;
 pub mod monophonic;
 pub mod polyphonic;
 
+pub use external::{ExternalInputHandle, ExternalInputSource};
+pub use file::{FileSource, read_wav};
 pub use monophonic::{MonophonicAllocationStrategy, MonophonicSource};
 pub use polyphonic::{PolyphonicAllocationStrategy, PolyphonicSource};
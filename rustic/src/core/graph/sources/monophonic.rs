@@ -4,6 +4,7 @@ use crate::{
         Block,
         audio::{mono_to_frame, silent_block},
         generator::prelude::MultiToneGenerator,
+        utils::TuningTable,
     },
 };
 
@@ -27,6 +28,8 @@ pub struct MonophonicSource {
     /// When false, `start_note()` triggers the generator without updating its frequency.
     /// Set to false for percussive instruments with fixed tuning (kick, snare, etc.).
     track_pitch: bool,
+    /// Detunes the notes played when tracking pitch.
+    tuning: TuningTable,
     active: bool,
     released: bool,
     current_note: Option<Note>,
@@ -43,6 +46,7 @@ impl MonophonicSource {
             replacement_strategy,
             sample_rate,
             track_pitch: true,
+            tuning: TuningTable::default(),
             active: false,
             released: false,
             current_note: None,
@@ -59,12 +63,19 @@ impl MonophonicSource {
             replacement_strategy,
             sample_rate,
             track_pitch: false,
+            tuning: TuningTable::default(),
             active: false,
             released: false,
             current_note: None,
         }
     }
 
+    /// Builder-style setter for the tuning applied to played notes.
+    pub fn with_tuning(mut self, tuning: TuningTable) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
     fn should_replace(&self) -> bool {
         // TODO: Update with power based replacement strategy
         matches!(
@@ -127,7 +138,8 @@ impl Source for MonophonicSource {
         if self.should_replace() {
             self.current_note = Some(note);
             if self.track_pitch {
-                self.generator.set_base_frequency(note.frequency());
+                self.generator
+                    .set_base_frequency(self.tuning.frequency(note));
             }
             self.start();
         }
@@ -6,6 +6,7 @@ use crate::{
         Block,
         audio::{mono_to_frame, silent_block},
         generator::prelude::MultiToneGenerator,
+        utils::TuningTable,
     },
 };
 
@@ -32,6 +33,8 @@ pub struct PolyphonicSource {
     max_voices: usize,
     replacement_strategy: PolyphonicAllocationStrategy,
     sample_rate: f32,
+    /// Detunes the notes played.
+    tuning: TuningTable,
     // Map Note to generator index in the pool
     current_notes: HashMap<Note, usize>,
     notes_age: VecDeque<usize>, // Active generator indices, oldest first
@@ -50,11 +53,18 @@ impl PolyphonicSource {
             max_voices,
             replacement_strategy,
             sample_rate,
+            tuning: TuningTable::default(),
             current_notes: HashMap::new(),
             notes_age: VecDeque::new(),
         }
     }
 
+    /// Builder-style setter for the tuning applied to played notes.
+    pub fn with_tuning(mut self, tuning: TuningTable) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
     /// Find the index of the first inactive generator slot in the pool.
     fn find_free_slot(&self) -> Option<usize> {
         self.generators.iter().position(|(_, active, _)| !*active)
@@ -138,7 +148,7 @@ impl Source for PolyphonicSource {
     }
 
     fn start_note(&mut self, note: Note, _velocity: f32) {
-        let freq = note.frequency();
+        let freq = self.tuning.frequency(note);
 
         // If the note is already held, retrigger in place
         if let Some(&gen_index) = self.current_notes.get(&note) {
@@ -3,17 +3,28 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use petgraph::Graph;
 use petgraph::dot::Dot;
 use petgraph::prelude::NodeIndex;
-use petgraph::{Direction, algo::toposort};
+use petgraph::visit::{EdgeFiltered, EdgeRef};
+use petgraph::{
+    Direction,
+    algo::{has_path_connecting, toposort},
+};
 use rustic_meta::MixMode;
 
 use super::audio_node::AudioNode;
 use super::{Filter, Sink, Source};
-use crate::core::audio::Block;
+use crate::core::audio::{Block, block_mean};
 use crate::core::graph::error::AudioGraphError;
+use crate::core::modulation::{Lfo, ModulationRouter};
+use crate::meta::GraphDescriptor;
+use crate::meta::descriptor::{
+    EdgeDescriptor, LfoRouteDescriptor, ModTargetDescriptor, ModulationDescriptor, NodeDescriptor,
+    SinkDescriptor, SourceDescriptor,
+};
 
 /// Target of a modulation wire.
 #[derive(Debug, Clone, PartialEq)]
@@ -35,6 +46,17 @@ pub struct ModWire {
     pub param_name: String,
 }
 
+/// Scheduling priority of a filter node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Priority {
+    /// Always processed.
+    #[default]
+    Normal,
+    /// Skipped (outputs silence) for the rest of a block once the system's
+    /// execution budget is exceeded, e.g. reverb tails or visual-only analysis.
+    Low,
+}
+
 /// ## A Pipe & Filter system
 /// The system is composed of filters, sources and sinks.
 /// It is represented as a directed graph where the filters are the nodes.
@@ -75,8 +97,14 @@ pub struct System {
     source_sink_wires: Vec<(usize, usize)>,
     /// Live modulation wires: a source's block-mean drives a named parameter.
     mod_wires: Vec<ModWire>,
+    /// LFOs driving filter parameters, sampled once per `run()`.
+    modulation: ModulationRouter,
     /// Number of frames to produce per `run()` call
     block_size: usize,
+    /// Time allowed for one `run()` before low-priority nodes are skipped.
+    budget: Option<Duration>,
+    /// Number of low-priority nodes skipped during the last `run()`.
+    skipped: usize,
 }
 
 impl Default for System {
@@ -88,7 +116,10 @@ impl Default for System {
             sinks: Vec::new(),
             source_sink_wires: Vec::new(),
             mod_wires: Vec::new(),
+            modulation: ModulationRouter::new(),
             block_size: 512,
+            budget: None,
+            skipped: 0,
         }
     }
 }
@@ -114,6 +145,38 @@ impl System {
             .unwrap_or_default()
     }
 
+    /// Sets the scheduling priority of a filter node.
+    pub fn set_priority(&mut self, node: NodeIndex<u32>, priority: Priority) {
+        if let Some(n) = self.graph.node_weight_mut(node) {
+            n.set_priority(priority);
+        }
+    }
+
+    /// Returns the scheduling priority of a filter node, defaulting to `Normal`.
+    pub fn get_priority(&self, node: NodeIndex<u32>) -> Priority {
+        self.graph
+            .node_weight(node)
+            .map(|n| n.priority())
+            .unwrap_or_default()
+    }
+
+    /// Sets the per-block execution budget. Once a `run()` has taken longer than
+    /// the budget, the remaining low-priority nodes output silence for that block.
+    /// `None` (the default) disables the budget.
+    pub fn set_budget(&mut self, budget: Option<Duration>) {
+        self.budget = budget;
+    }
+
+    /// Returns the per-block execution budget, if any.
+    pub fn budget(&self) -> Option<Duration> {
+        self.budget
+    }
+
+    /// Returns how many low-priority nodes were skipped during the last `run()`.
+    pub fn skipped_nodes(&self) -> usize {
+        self.skipped
+    }
+
     /// Builder-style setter for the block size.
     pub fn with_block_size(mut self, n: usize) -> Self {
         self.block_size = n;
@@ -228,6 +291,15 @@ impl System {
             })
             .collect();
 
+        for route in other.modulation.routes() {
+            self.modulation.add_route(
+                route.lfo.clone(),
+                new_edge_map[&route.target],
+                route.param_name.clone(),
+                route.base,
+            );
+        }
+
         let new_system: System = System {
             graph: self.graph,
             layers: self.layers,
@@ -235,7 +307,10 @@ impl System {
             sinks: new_sinks,
             source_sink_wires: Vec::new(),
             mod_wires: Vec::new(),
+            modulation: self.modulation,
             block_size: self.block_size,
+            budget: self.budget,
+            skipped: 0,
         };
 
         Ok(new_system)
@@ -249,14 +324,21 @@ impl System {
     }
 
     // Connects two filters together. This method connects the filter in the topology graph as well.
-    // Do not use this function to close a feedback loop. Use the connect_feedback method instead.
+    // A feedback loop must go through a postponable filter (e.g. a delay), which reads its input
+    // one block late; connecting a loop without one is refused.
     pub fn connect(
         &mut self,
         from: NodeIndex<u32>,
         to: NodeIndex<u32>,
         out_port: usize,
         in_port: usize,
-    ) {
+    ) -> Result<(), AudioGraphError> {
+        if self.closes_loop_without_delay(from, to) {
+            return Err(AudioGraphError::FeedbackWithoutDelay {
+                from: self.graph[from].filter().to_string(),
+                to: self.graph[to].filter().to_string(),
+            });
+        }
         log::trace!(
             "[Graph] Connecting {:?} (p: {}) to {:?} (p: {})",
             self.graph[from],
@@ -265,6 +347,19 @@ impl System {
             in_port
         );
         self.graph.add_edge(from, to, (out_port, in_port));
+        Ok(())
+    }
+
+    /// Whether an edge `from → to` would close a loop that `compute` can't
+    /// order. Edges into postponable filters are left out of the topological
+    /// sort, so only loops made entirely of other filters are a problem.
+    fn closes_loop_without_delay(&self, from: NodeIndex<u32>, to: NodeIndex<u32>) -> bool {
+        if self.graph[to].postponable() {
+            return false;
+        }
+        let immediate =
+            EdgeFiltered::from_fn(&self.graph, |edge| !self.graph[edge.target()].postponable());
+        has_path_connecting(&immediate, to, from, None)
     }
 
     /// Connects a source directly to a sink, bypassing any filters.
@@ -285,6 +380,22 @@ impl System {
         self.sources[source].1.push((to, in_port));
     }
 
+    /// Removes the connection from a source to one input port of a filter.
+    pub fn disconnect_source_port(
+        &mut self,
+        source: usize,
+        filter: NodeIndex<u32>,
+        in_port: usize,
+    ) {
+        if let Some((_, connections)) = self.sources.get_mut(source)
+            && let Some(position) = connections
+                .iter()
+                .position(|&connection| connection == (filter, in_port))
+        {
+            connections.remove(position);
+        }
+    }
+
     /// Removes the connection from a source to a specific filter.
     pub fn disconnect_source(&mut self, source: usize, filter: NodeIndex<u32>) {
         if let Some((_, connections)) = self.sources.get_mut(source) {
@@ -299,6 +410,15 @@ impl System {
         self.sinks[sink].0.push((from, out_port));
     }
 
+    /// Removes the connection from a filter node's output port to a sink.
+    pub fn disconnect_sink(&mut self, from: NodeIndex<u32>, sink: usize, out_port: usize) {
+        if let Some((inputs, _)) = self.sinks.get_mut(sink)
+            && let Some(position) = inputs.iter().position(|&input| input == (from, out_port))
+        {
+            inputs.remove(position);
+        }
+    }
+
     /// Sets the sink at index `index` to be the given sink object (preserves existing sources).
     pub fn set_sink(&mut self, index: usize, sink: Box<dyn Sink>) -> Result<(), AudioGraphError> {
         if index < self.sinks.len() {
@@ -325,6 +445,11 @@ impl System {
         }
     }
 
+    /// Returns the number of filters currently in the graph.
+    pub fn filters_len(&self) -> usize {
+        self.graph.node_count()
+    }
+
     /// Returns the number of sources currently registered in this system.
     pub fn sources_len(&self) -> usize {
         self.sources.len()
@@ -349,6 +474,15 @@ impl System {
         }
     }
 
+    /// Moves every filter of the graph to `sample_rate`, see
+    /// [`Filter::set_sample_rate`]. Call it whenever the engine runs at a
+    /// different rate than the one the filters were built for.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        for node in self.graph.node_weights_mut() {
+            node.filter_mut().set_sample_rate(sample_rate);
+        }
+    }
+
     /// Returns the number of computed layers (0 means graph not yet compiled).
     pub fn layers_len(&self) -> usize {
         self.layers.len()
@@ -412,6 +546,28 @@ impl System {
         });
     }
 
+    /// Routes `lfo` to `param_name` on the `target` filter: every `run()`
+    /// sets the parameter to `base` plus the LFO output at the start of the
+    /// block. Returns the index of the route in the modulation router.
+    pub fn add_lfo(
+        &mut self,
+        lfo: Lfo,
+        target: NodeIndex<u32>,
+        param_name: impl Into<String>,
+        base: f32,
+    ) -> usize {
+        self.modulation.add_route(lfo, target, param_name, base)
+    }
+
+    /// The LFO routes of this system.
+    pub fn modulation(&self) -> &ModulationRouter {
+        &self.modulation
+    }
+
+    pub fn modulation_mut(&mut self) -> &mut ModulationRouter {
+        &mut self.modulation
+    }
+
     /// Adds a sink and returns its index
     pub fn add_sink(&mut self, sink: Box<dyn Sink>) -> usize {
         let idx = self.sinks.len();
@@ -436,9 +592,55 @@ impl System {
         }
     }
 
-    /// Removes a filter from the graph
+    /// Removes a filter from the graph, along with the source, sink and
+    /// modulation connections to it
     pub fn remove_filter(&mut self, index: NodeIndex<u32>) -> Option<Box<dyn Filter>> {
-        self.graph.remove_node(index).map(|n| n.filter)
+        let last = NodeIndex::new(self.graph.node_count().checked_sub(1)?);
+        let removed = self.graph.remove_node(index)?;
+        // petgraph moves the last node into the freed index
+        self.modulation.remove_target(index);
+        self.modulation.retarget(last, index);
+        let reindex = |connections: &mut Vec<(NodeIndex<u32>, usize)>| {
+            connections.retain(|&(node, _)| node != index);
+            for (node, _) in connections.iter_mut() {
+                if *node == last {
+                    *node = index;
+                }
+            }
+        };
+        for (_, targets) in self.sources.iter_mut() {
+            reindex(targets);
+        }
+        for (inputs, _) in self.sinks.iter_mut() {
+            reindex(inputs);
+        }
+        self.mod_wires
+            .retain(|wire| wire.target != ModTarget::Filter(index));
+        for wire in self.mod_wires.iter_mut() {
+            if wire.target == ModTarget::Filter(last) {
+                wire.target = ModTarget::Filter(index);
+            }
+        }
+        Some(removed.filter)
+    }
+
+    /// Removes the pipe from output `out_port` of a filter to input `in_port`
+    /// of another, leaving any other pipe between them in place
+    pub fn disconnect_ports(
+        &mut self,
+        from: NodeIndex<u32>,
+        to: NodeIndex<u32>,
+        out_port: usize,
+        in_port: usize,
+    ) -> Result<(), AudioGraphError> {
+        let edge = self
+            .graph
+            .edges_connecting(from, to)
+            .find(|edge| *edge.weight() == (out_port, in_port))
+            .map(|edge| edge.id())
+            .ok_or(AudioGraphError::ConnectionNotAllowed)?;
+        self.graph.remove_edge(edge);
+        Ok(())
     }
 
     /// Disconnects two filters
@@ -455,10 +657,15 @@ impl System {
         }
     }
 
-    // Creates the execution layers by sorting the graph topologically.
-    #[allow(clippy::result_unit_err)]
+    /// Creates the execution layers by sorting the graph topologically.
+    ///
+    /// Connections are validated first: every port used must exist on its
+    /// filter, a filter with several audio inputs must have all of them
+    /// connected as soon as one is, and filters working on fixed-size blocks
+    /// must fit the system's block size.
     pub fn compute(&mut self) -> Result<(), AudioGraphError> {
         self.layers.clear();
+        self.validate_connections()?;
 
         // Makes the graph acyclic to be able to create a topology sort
         let acyclic_graph = self.graph.filter_map(
@@ -503,10 +710,82 @@ impl System {
         Ok(())
     }
 
+    /// Checks the connections of every filter against the ports it declares.
+    fn validate_connections(&self) -> Result<(), AudioGraphError> {
+        let mut connected_inputs: HashMap<NodeIndex, Vec<usize>> = HashMap::new();
+        let mut used_outputs: Vec<(NodeIndex, usize)> = Vec::new();
+        for edge in self.graph.raw_edges() {
+            let (out_port, in_port) = edge.weight;
+            used_outputs.push((edge.source(), out_port));
+            connected_inputs
+                .entry(edge.target())
+                .or_default()
+                .push(in_port);
+        }
+        for (_, connections) in &self.sources {
+            for &(node, in_port) in connections {
+                connected_inputs.entry(node).or_default().push(in_port);
+            }
+        }
+        for (connections, _) in &self.sinks {
+            used_outputs.extend(connections.iter().copied());
+        }
+
+        for (node, out_port) in used_outputs {
+            let filter = self.graph[node].filter();
+            let outputs = filter.filter_info().outputs;
+            if out_port >= outputs {
+                return Err(AudioGraphError::OutputPortOutOfRange {
+                    filter: filter.to_string(),
+                    port: out_port,
+                    outputs,
+                });
+            }
+        }
+
+        for node in self.graph.node_indices() {
+            let filter = self.graph[node].filter();
+            if let Some(expected) = filter.block_size()
+                && !self.block_size.is_multiple_of(expected)
+            {
+                return Err(AudioGraphError::BlockSizeMismatch {
+                    filter: filter.to_string(),
+                    expected,
+                    got: self.block_size,
+                });
+            }
+
+            let Some(ports) = connected_inputs.get(&node) else {
+                continue;
+            };
+            let info = filter.filter_info();
+            if let Some(&port) = ports.iter().find(|&&port| port >= info.inputs.len()) {
+                return Err(AudioGraphError::InputPortOutOfRange {
+                    filter: filter.to_string(),
+                    port,
+                    inputs: info.inputs.len(),
+                });
+            }
+            let audio_ports = info.audio_port_count();
+            if ports.iter().any(|&port| port < audio_ports)
+                && let Some(port) = (0..audio_ports).find(|port| !ports.contains(port))
+            {
+                return Err(AudioGraphError::MissingInput {
+                    filter: filter.to_string(),
+                    port,
+                    expected: audio_ports,
+                });
+            }
+        }
+        Ok(())
+    }
+
     // Performs one full run of the system, running every filter once in an order such that data
     // that entered the system this run can exit it this run as well.
     pub fn run(&mut self) {
         let block_size = self.block_size;
+        let started = self.budget.map(|_| Instant::now());
+        self.skipped = 0;
 
         // Pull from all sources; push directly to connected AudioNodes (fan-out).
         // Two-step collect releases the borrow on self.sources before we touch self.graph.
@@ -538,12 +817,11 @@ impl System {
             .iter()
             .filter_map(|wire| {
                 let block = source_blocks.get(wire.from_source)?;
-                let mean = if block.is_empty() {
-                    0.0
-                } else {
-                    block.iter().map(|f| (f[0] + f[1]) * 0.5).sum::<f32>() / block.len() as f32
-                };
-                Some((wire.target.clone(), wire.param_name.clone(), mean))
+                Some((
+                    wire.target.clone(),
+                    wire.param_name.clone(),
+                    block_mean(block),
+                ))
             })
             .collect();
         for (target, param_name, value) in mod_actions {
@@ -561,6 +839,15 @@ impl System {
             }
         }
 
+        // Apply LFO routes.
+        let graph = &mut self.graph;
+        self.modulation
+            .apply(block_size, |target, param_name, value| {
+                if let Some(node) = graph.node_weight_mut(target) {
+                    node.filter_mut().set_parameter(param_name, value);
+                }
+            });
+
         // Process filters layer by layer.
         // Hoisted outside the loop so the Vec is allocated once and reused each layer.
         // TODO: parallelize Phase 1 with rayon::par_iter — requires either adding a
@@ -572,7 +859,17 @@ impl System {
             layer_outputs.clear();
             for &f in layer.iter() {
                 let node_idx = NodeIndex::new(f);
-                let outputs = self.graph[node_idx].process(block_size);
+                let node = &mut self.graph[node_idx];
+                let over_budget = matches!(
+                    (started, self.budget),
+                    (Some(started), Some(budget)) if started.elapsed() >= budget
+                );
+                let outputs = if over_budget && node.priority() == Priority::Low {
+                    self.skipped += 1;
+                    node.skip(block_size)
+                } else {
+                    node.process(block_size)
+                };
                 layer_outputs.push((node_idx, outputs));
             }
 
@@ -720,8 +1017,215 @@ impl System {
         System::new()
     }
 
-    /// Absorbs all filter nodes, edges, and sources from `other` into `self`,
-    /// remapping `NodeIndex`es to the new graph.
+    /// Describes the topology of this system: every filter with its type id
+    /// and scalar parameter values, the pipes between filters and the wiring
+    /// of sources and sinks. See [`GraphDescriptor`].
+    pub fn to_descriptor(&self) -> GraphDescriptor {
+        let nodes = self
+            .graph
+            .node_weights()
+            .map(|node| {
+                let filter = node.filter();
+                let parameters = filter
+                    .filter_info()
+                    .inputs
+                    .iter()
+                    .filter_map(|input| input.parameter.as_ref())
+                    .filter_map(|parameter| {
+                        let name = *parameter.field_name();
+                        filter
+                            .get_parameter(name)
+                            .map(|value| (name.to_string(), value))
+                    })
+                    .collect();
+                NodeDescriptor {
+                    type_id: filter.filter_info().type_id.to_string(),
+                    parameters,
+                    mix_mode: node.mix_mode(),
+                }
+            })
+            .collect();
+
+        let edges = self
+            .graph
+            .edge_indices()
+            .filter_map(|edge| {
+                let (from, to) = self.graph.edge_endpoints(edge)?;
+                let (out_port, in_port) = self.graph[edge];
+                Some(EdgeDescriptor {
+                    from: from.index(),
+                    out_port,
+                    to: to.index(),
+                    in_port,
+                })
+            })
+            .collect();
+
+        let sources = self
+            .sources
+            .iter()
+            .map(|(_, targets)| SourceDescriptor {
+                targets: targets
+                    .iter()
+                    .map(|(node, port)| (node.index(), *port))
+                    .collect(),
+            })
+            .collect();
+
+        let sinks = self
+            .sinks
+            .iter()
+            .enumerate()
+            .map(|(index, (inputs, _))| SinkDescriptor {
+                inputs: inputs
+                    .iter()
+                    .map(|(node, port)| (node.index(), *port))
+                    .collect(),
+                direct_sources: self
+                    .source_sink_wires
+                    .iter()
+                    .filter(|&&(_, sink)| sink == index)
+                    .map(|&(source, _)| source)
+                    .collect(),
+            })
+            .collect();
+
+        let modulations = self
+            .mod_wires
+            .iter()
+            .map(|wire| ModulationDescriptor {
+                from_source: wire.from_source,
+                target: match wire.target {
+                    ModTarget::Source(index) => ModTargetDescriptor::Source(index),
+                    ModTarget::Filter(node) => ModTargetDescriptor::Node(node.index()),
+                },
+                param_name: wire.param_name.clone(),
+            })
+            .collect();
+
+        let lfo_routes = self
+            .modulation
+            .routes()
+            .iter()
+            .map(|route| LfoRouteDescriptor {
+                target: route.target.index(),
+                param_name: route.param_name.clone(),
+                base: route.base,
+                shape: route.lfo.shape(),
+                rate: route.lfo.rate(),
+                depth: route.lfo.depth(),
+                phase: route.lfo.phase_offset(),
+                polarity: route.lfo.polarity(),
+                bpm: route.lfo.bpm(),
+                sample_rate: route.lfo.sample_rate(),
+            })
+            .collect();
+
+        GraphDescriptor {
+            block_size: self.block_size,
+            nodes,
+            edges,
+            sources,
+            sinks,
+            modulations,
+            lfo_routes,
+        }
+    }
+
+    /// Rebuilds a system from `descriptor`, creating each filter by type id
+    /// with `factory` and restoring its parameters. Sources and sinks are not
+    /// described by type, so they are passed in the order of the descriptor.
+    /// The returned system is computed and ready to run.
+    pub fn from_descriptor<F>(
+        descriptor: &GraphDescriptor,
+        factory: F,
+        sources: Vec<Box<dyn Source>>,
+        sinks: Vec<Box<dyn Sink>>,
+    ) -> Result<System, AudioGraphError>
+    where
+        F: Fn(&str) -> Option<Box<dyn Filter>>,
+    {
+        for (kind, expected, got) in [
+            ("sources", descriptor.sources.len(), sources.len()),
+            ("sinks", descriptor.sinks.len(), sinks.len()),
+        ] {
+            if expected != got {
+                return Err(AudioGraphError::DescriptorMismatch {
+                    kind,
+                    expected,
+                    got,
+                });
+            }
+        }
+
+        let mut system = System::new().with_block_size(descriptor.block_size);
+        let mut nodes = Vec::with_capacity(descriptor.nodes.len());
+        for node in &descriptor.nodes {
+            let mut filter = factory(&node.type_id)
+                .ok_or_else(|| AudioGraphError::UnknownFilter(node.type_id.clone()))?;
+            for (name, value) in &node.parameters {
+                filter.set_parameter(name, *value);
+            }
+            let index = system.add_filter(filter);
+            system.set_mix_mode(index, node.mix_mode.clone());
+            nodes.push(index);
+        }
+        let node = |position: usize| {
+            nodes
+                .get(position)
+                .copied()
+                .ok_or(AudioGraphError::NodeNotFound)
+        };
+
+        for edge in &descriptor.edges {
+            system.connect(
+                node(edge.from)?,
+                node(edge.to)?,
+                edge.out_port,
+                edge.in_port,
+            )?;
+        }
+        for (source, description) in sources.into_iter().zip(&descriptor.sources) {
+            let index = system.add_source(source);
+            for &(target, port) in &description.targets {
+                system.connect_source(index, node(target)?, port);
+            }
+        }
+        for (sink, description) in sinks.into_iter().zip(&descriptor.sinks) {
+            let index = system.add_sink(sink);
+            for &(from, port) in &description.inputs {
+                system.connect_sink(node(from)?, index, port);
+            }
+            for &source in &description.direct_sources {
+                system.connect_source_to_sink(source, index);
+            }
+        }
+        for modulation in &descriptor.modulations {
+            let target = match modulation.target {
+                ModTargetDescriptor::Source(index) => ModTarget::Source(index),
+                ModTargetDescriptor::Node(position) => ModTarget::Filter(node(position)?),
+            };
+            system.add_mod_wire(
+                modulation.from_source,
+                target,
+                modulation.param_name.clone(),
+            );
+        }
+        for route in &descriptor.lfo_routes {
+            system.add_lfo(
+                route.lfo(),
+                node(route.target)?,
+                route.param_name.clone(),
+                route.base,
+            );
+        }
+
+        system.compute()?;
+        Ok(system)
+    }
+
+    /// Absorbs all filter nodes, edges, sources and LFO routes from `other`
+    /// into `self`, remapping `NodeIndex`es to the new graph.
     ///
     /// Returns the remapped `NodeIndex` of the filter that was feeding `other`'s
     /// first sink — the "output node" of the absorbed sub-graph — so the caller
@@ -767,6 +1271,18 @@ impl System {
             self.sources.push((source, remapped));
         }
 
+        // Transfer LFO routes, retargeting them to the imported filters
+        for route in other.modulation.routes() {
+            if let Some(&target) = remap.get(&route.target) {
+                self.modulation.add_route(
+                    route.lfo.clone(),
+                    target,
+                    route.param_name.clone(),
+                    route.base,
+                );
+            }
+        }
+
         // Return the remapped output node so the caller can connect it
         remap
             .get(&other_output_node)
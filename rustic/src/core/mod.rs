@@ -40,6 +40,9 @@ pub mod generator;
 /// Audio signal graph processing and routing
 pub mod graph;
 
+/// LFOs and the router applying them to filter parameters
+pub mod modulation;
+
 pub mod audio;
 /// Core utilities including note types, tones, and helper functions
 pub mod utils;
@@ -0,0 +1,318 @@
+//! Control-rate modulation sources
+//!
+//! An [`Lfo`] produces a slow periodic control signal. A [`ModulationRouter`]
+//! maps LFOs to named filter parameters; the [`System`](crate::core::graph::System)
+//! owning it samples every LFO once per block in `run()` and forwards the
+//! result to the target filter's `set_parameter`.
+
+use std::f32::consts::TAU;
+
+use petgraph::prelude::NodeIndex;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Waveform of an [`Lfo`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LfoShape {
+    #[default]
+    Sine,
+    Triangle,
+    Square,
+    /// A new random value held for every period
+    SampleAndHold,
+}
+
+/// Output range of an [`Lfo`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LfoPolarity {
+    /// Swings between `-depth` and `depth`
+    #[default]
+    Bipolar,
+    /// Swings between 0 and `depth`
+    Unipolar,
+}
+
+/// Tempo used to resolve [`LfoRate::Beats`] until [`Lfo::set_bpm`] is called
+pub const DEFAULT_LFO_BPM: f64 = 120.0;
+
+/// Speed of an [`Lfo`], either free-running or locked to the tempo.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LfoRate {
+    /// Cycles per second
+    Hz(f32),
+    /// Length of one cycle, in beats (quarter notes)
+    Beats(f64),
+}
+
+impl LfoRate {
+    /// One cycle per `1/denominator` note, e.g. `note(8)` for an eighth note.
+    pub fn note(denominator: u32) -> Self {
+        Self::Beats(4.0 / denominator.max(1) as f64)
+    }
+
+    /// One cycle per dotted `1/denominator` note (one and a half times as long).
+    pub fn dotted(denominator: u32) -> Self {
+        Self::Beats(6.0 / denominator.max(1) as f64)
+    }
+
+    /// One cycle per `1/denominator` triplet note (two thirds as long).
+    pub fn triplet(denominator: u32) -> Self {
+        Self::Beats(8.0 / (3.0 * denominator.max(1) as f64))
+    }
+
+    /// Resolves the rate to cycles per second at `bpm` beats per minute.
+    pub fn to_hz(&self, bpm: f64) -> f32 {
+        match *self {
+            Self::Hz(hz) => hz,
+            Self::Beats(beats) if beats > 0.0 => (bpm / 60.0 / beats) as f32,
+            Self::Beats(_) => 0.0,
+        }
+    }
+}
+
+impl From<f32> for LfoRate {
+    fn from(hz: f32) -> Self {
+        Self::Hz(hz)
+    }
+}
+
+/// A low-frequency oscillator producing a control signal.
+///
+/// The phase advances by the rate in Hz divided by the sample rate per
+/// frame; `phase` offsets the start of the cycle, as a fraction of the
+/// period. Tempo-synced rates follow the BPM given to [`Lfo::set_bpm`],
+/// e.g. the `Session` tempo of `rustic-lang`.
+#[derive(Debug, Clone)]
+pub struct Lfo {
+    shape: LfoShape,
+    rate: LfoRate,
+    bpm: f64,
+    depth: f32,
+    phase_offset: f32,
+    polarity: LfoPolarity,
+    sample_rate: f32,
+    /// Position in the current cycle, from 0 to 1
+    phase: f32,
+    held: f32,
+    rng: SmallRng,
+}
+
+impl Lfo {
+    /// Creates a bipolar LFO of full depth. `rate` is either an [`LfoRate`]
+    /// or a frequency in Hz.
+    pub fn new(shape: LfoShape, rate: impl Into<LfoRate>, sample_rate: f32) -> Self {
+        let mut rng = SmallRng::from_entropy();
+        Self {
+            shape,
+            rate: rate.into(),
+            bpm: DEFAULT_LFO_BPM,
+            depth: 1.0,
+            phase_offset: 0.0,
+            polarity: LfoPolarity::default(),
+            sample_rate,
+            phase: 0.0,
+            held: rng.gen_range(-1.0..=1.0),
+            rng,
+        }
+    }
+
+    /// Builder-style setter for the output amplitude.
+    pub fn with_depth(mut self, depth: f32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Builder-style setter for the phase offset, as a fraction of the period.
+    pub fn with_phase(mut self, phase: f32) -> Self {
+        self.phase_offset = phase.rem_euclid(1.0);
+        self
+    }
+
+    /// Builder-style setter for the output range.
+    pub fn with_polarity(mut self, polarity: LfoPolarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Builder-style setter for the tempo followed by tempo-synced rates.
+    pub fn with_bpm(mut self, bpm: f64) -> Self {
+        self.bpm = bpm;
+        self
+    }
+
+    pub fn shape(&self) -> LfoShape {
+        self.shape
+    }
+
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    /// Phase offset, as a fraction of the period.
+    pub fn phase_offset(&self) -> f32 {
+        self.phase_offset
+    }
+
+    pub fn polarity(&self) -> LfoPolarity {
+        self.polarity
+    }
+
+    /// Tempo followed by tempo-synced rates.
+    pub fn bpm(&self) -> f64 {
+        self.bpm
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    pub fn set_rate(&mut self, rate: impl Into<LfoRate>) {
+        self.rate = rate.into();
+    }
+
+    pub fn rate(&self) -> LfoRate {
+        self.rate
+    }
+
+    /// Sets the tempo followed by tempo-synced rates.
+    pub fn set_bpm(&mut self, bpm: f64) {
+        self.bpm = bpm;
+    }
+
+    /// The current rate, in Hz.
+    pub fn frequency(&self) -> f32 {
+        self.rate.to_hz(self.bpm)
+    }
+
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth;
+    }
+
+    /// Restarts the cycle at the phase offset.
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+    }
+
+    /// Returns the current output.
+    pub fn value(&self) -> f32 {
+        let phase = (self.phase + self.phase_offset).fract();
+        let wave = match self.shape {
+            LfoShape::Sine => (TAU * phase).sin(),
+            LfoShape::Triangle => 1.0 - 4.0 * ((phase + 0.25).fract() - 0.5).abs(),
+            LfoShape::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            LfoShape::SampleAndHold => self.held,
+        };
+
+        match self.polarity {
+            LfoPolarity::Bipolar => wave * self.depth,
+            LfoPolarity::Unipolar => (wave + 1.0) * 0.5 * self.depth,
+        }
+    }
+
+    /// Advances the LFO by `frames` frames.
+    pub fn advance(&mut self, frames: usize) {
+        let phase = self.phase + self.frequency() * frames as f32 / self.sample_rate;
+        if phase >= 1.0 && self.shape == LfoShape::SampleAndHold {
+            self.held = self.rng.gen_range(-1.0..=1.0);
+        }
+        self.phase = phase.rem_euclid(1.0);
+    }
+
+    /// Returns the output at the start of a block of `frames` frames, then
+    /// advances past it.
+    pub fn tick_block(&mut self, frames: usize) -> f32 {
+        let value = self.value();
+        self.advance(frames);
+        value
+    }
+}
+
+/// An LFO driving a named parameter of a filter.
+#[derive(Debug, Clone)]
+pub struct ModulationRoute {
+    pub lfo: Lfo,
+    pub target: NodeIndex<u32>,
+    pub param_name: String,
+    /// Value the LFO output is added to
+    pub base: f32,
+}
+
+/// Maps LFOs to filter parameters.
+#[derive(Debug, Clone, Default)]
+pub struct ModulationRouter {
+    routes: Vec<ModulationRoute>,
+}
+
+impl ModulationRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes `lfo` to `param_name` on the `target` filter, around `base`.
+    /// Returns the index of the route.
+    pub fn add_route(
+        &mut self,
+        lfo: Lfo,
+        target: NodeIndex<u32>,
+        param_name: impl Into<String>,
+        base: f32,
+    ) -> usize {
+        self.routes.push(ModulationRoute {
+            lfo,
+            target,
+            param_name: param_name.into(),
+            base,
+        });
+        self.routes.len() - 1
+    }
+
+    /// Removes a route by index.
+    pub fn remove_route(&mut self, index: usize) -> Option<ModulationRoute> {
+        (index < self.routes.len()).then(|| self.routes.remove(index))
+    }
+
+    /// Removes every route driving `target`.
+    pub fn remove_target(&mut self, target: NodeIndex<u32>) {
+        self.routes.retain(|route| route.target != target);
+    }
+
+    /// Points every route driving `from` at `to` instead.
+    pub fn retarget(&mut self, from: NodeIndex<u32>, to: NodeIndex<u32>) {
+        self.routes
+            .iter_mut()
+            .filter(|route| route.target == from)
+            .for_each(|route| route.target = to);
+    }
+
+    pub fn routes(&self) -> &[ModulationRoute] {
+        &self.routes
+    }
+
+    pub fn route_mut(&mut self, index: usize) -> Option<&mut ModulationRoute> {
+        self.routes.get_mut(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Samples every LFO for a block of `block_size` frames and calls `set`
+    /// with the target, parameter name and modulated value of each route.
+    pub fn apply<F: FnMut(NodeIndex<u32>, &str, f32)>(&mut self, block_size: usize, mut set: F) {
+        for route in &mut self.routes {
+            let value = route.base + route.lfo.tick_block(block_size);
+            set(route.target, &route.param_name, value);
+        }
+    }
+}
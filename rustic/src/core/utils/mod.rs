@@ -11,6 +11,10 @@
 //!   lookups. Frequencies are provided per semitone and octave to simplify
 //!   instrument construction.
 //!
+//! ## Tuning
+//! - `TuningTable`: per-pitch-class cents offsets applied by instruments when
+//!   converting notes to frequencies. Defaults to equal temperament.
+//!
 //! ## Input helpers
 //! Keyboard-related types and key mappings are provided to support the
 //! `inputs` module and example frontends.
@@ -27,9 +31,13 @@ pub mod macros;
 /// Musical note representation
 pub mod note;
 
+/// Microtuning tables
+pub mod tuning;
+
 // Re-export commonly used types
 pub use note::Note;
 pub use tones::{NOTES, TONES_FREQ};
+pub use tuning::{TuningError, TuningTable};
 
 // Re-export key types for input handling
 pub use keys::{EventType, Key, KeyCode, KeyType};
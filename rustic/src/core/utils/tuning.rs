@@ -0,0 +1,137 @@
+//! Microtuning tables
+//!
+//! A [`TuningTable`] detunes each of the 12 pitch classes by a number of
+//! cents, applied on top of the equal-tempered frequency of a [`Note`].
+//! Instruments consult it when converting notes to frequencies, so the same
+//! offsets apply in every octave.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::note::Note;
+use super::tones::NOTES;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TuningError {
+    #[error("Line {line}: expected `<pitch class> <cents>`, got `{content}`")]
+    InvalidLine { line: usize, content: String },
+    #[error("Line {line}: unknown pitch class `{name}`")]
+    UnknownPitchClass { line: usize, name: String },
+    #[error("Unable to read tuning file: {0}")]
+    Io(String),
+}
+
+/// Cents offsets of the 12 pitch classes, indexed from C.
+///
+/// The default table is equal temperament: every offset is zero.
+///
+/// ```
+/// use rustic::core::utils::{NOTES, Note, TuningTable};
+///
+/// // Quarter-tone flat E, as in maqam Rast
+/// let tuning = TuningTable::equal().with_cents(NOTES::E, -50.0);
+/// let e4 = Note(NOTES::E, 4);
+/// assert!(tuning.frequency(e4) < e4.frequency());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TuningTable {
+    cents: [f32; 12],
+}
+
+impl TuningTable {
+    /// Creates a table from the offsets of C, C#, ..., B.
+    pub fn new(cents: [f32; 12]) -> Self {
+        Self { cents }
+    }
+
+    /// Twelve-tone equal temperament.
+    pub fn equal() -> Self {
+        Self::default()
+    }
+
+    /// Builder-style setter for the offset of `pitch_class`.
+    pub fn with_cents(mut self, pitch_class: NOTES, cents: f32) -> Self {
+        self.set_cents(pitch_class, cents);
+        self
+    }
+
+    pub fn set_cents(&mut self, pitch_class: NOTES, cents: f32) {
+        self.cents[pitch_class as usize] = cents;
+    }
+
+    /// Offset of `pitch_class`, in cents.
+    pub fn cents(&self, pitch_class: NOTES) -> f32 {
+        self.cents[pitch_class as usize]
+    }
+
+    pub fn is_equal_temperament(&self) -> bool {
+        self.cents.iter().all(|&c| c == 0.0)
+    }
+
+    /// Frequency of `note` under this tuning, in Hz.
+    pub fn frequency(&self, note: Note) -> f32 {
+        note.frequency() * 2f32.powf(self.cents(note.note()) / 1200.0)
+    }
+
+    /// Parses the simple tuning format: one `<pitch class> <cents>` pair per
+    /// line, e.g. `Eb -50`. Pitch classes may use `#` or `b`; pitch classes
+    /// that are not listed stay in equal temperament. Lines starting with `!`
+    /// (as in Scala files) or `#` are comments.
+    pub fn parse(contents: &str) -> Result<Self, TuningError> {
+        let mut table = Self::equal();
+        for (idx, raw) in contents.lines().enumerate() {
+            let line = idx + 1;
+            let content = raw.trim();
+            if content.is_empty() || content.starts_with('!') || content.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || TuningError::InvalidLine {
+                line,
+                content: content.to_string(),
+            };
+            let mut fields = content.split_whitespace();
+            let (Some(name), Some(cents), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid());
+            };
+            let cents: f32 = cents.parse().map_err(|_| invalid())?;
+            let pitch_class = pitch_class(name).ok_or_else(|| TuningError::UnknownPitchClass {
+                line,
+                name: name.to_string(),
+            })?;
+            table.set_cents(pitch_class, cents);
+        }
+        Ok(table)
+    }
+
+    /// Loads a table in the format read by [`parse`](Self::parse).
+    pub fn from_file(path: &Path) -> Result<Self, TuningError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| TuningError::Io(e.to_string()))?;
+        Self::parse(&contents)
+    }
+}
+
+/// Parses a pitch class name such as `C`, `F#` or `Bb`.
+fn pitch_class(name: &str) -> Option<NOTES> {
+    let mut chars = name.chars();
+    let natural: i8 = match chars.next()?.to_ascii_uppercase() {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+    let accidental: i8 = match chars.as_str() {
+        "" => 0,
+        "#" | "s" => 1,
+        "b" => -1,
+        _ => return None,
+    };
+    Some(NOTES::from((natural + accidental).rem_euclid(12) as u8))
+}
@@ -1,10 +1,15 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use atomic_float::AtomicF32;
+
 use crate::Note;
 use crate::core::envelope::prelude::{
     ADSREnvelopeBuilder, BezierSegment, ConstantSegment, LinearSegment,
 };
 use crate::core::filters::prelude::GainFilter;
 use crate::core::generator::prelude::{
-    FrequencyRelation, MixMode, MultiToneGenerator, Waveform,
+    FilterConfig, FrequencyRelation, MixMode, MultiToneGenerator, Waveform,
     builder::{MultiToneGeneratorBuilder, ToneGeneratorBuilder},
 };
 use crate::core::graph::{MonophonicAllocationStrategy, MonophonicSource, SimpleSink, System};
@@ -16,6 +21,7 @@ pub struct Kick {
     current_tick: u32,
     output: f32,
     playing: bool,
+    envelope_level: Arc<AtomicF32>,
 }
 
 impl Kick {
@@ -61,6 +67,7 @@ impl Kick {
             current_tick: 0,
             output: 0.0,
             playing: false,
+            envelope_level: Arc::new(AtomicF32::new(0.0)),
         }
     }
 }
@@ -83,14 +90,25 @@ impl Instrument for Kick {
     fn tick(&mut self) {
         if !self.playing {
             self.output = 0.0;
+            self.envelope_level.store(0.0, Ordering::Relaxed);
             return;
         }
         self.output = self.generator.tick(1.0 / 44100.0);
+        self.envelope_level
+            .store(self.generator.envelope_level(), Ordering::Relaxed);
         if self.generator.completed() {
             self.playing = false;
         }
     }
 
+    fn set_voice_filter(&mut self, config: FilterConfig) {
+        self.generator.set_voice_filter(Some(config));
+    }
+
+    fn envelope_meter(&self) -> Option<Arc<AtomicF32>> {
+        Some(Arc::clone(&self.envelope_level))
+    }
+
     fn into_system(self: Box<Self>, sample_rate: f32) -> System {
         let source = MonophonicSource::new_percussive(
             self.generator,
@@ -0,0 +1,222 @@
+use crate::core::audio::{Block, CHANNELS};
+use crate::core::filters::prelude::GainFilter;
+use crate::core::graph::{SimpleSink, Source, System};
+use crate::instruments::Instrument;
+use crate::{NOTES, Note};
+
+use super::{HiHat, Kick, Snare};
+
+/// A drum sound of a [`DrumKit`], played when the kit receives `note`.
+#[derive(Debug)]
+pub struct DrumPad {
+    pub name: String,
+    pub note: Note,
+    /// Linear gain applied to the sound.
+    pub gain: f32,
+    instrument: Box<dyn Instrument>,
+    velocity: f32,
+}
+
+impl DrumPad {
+    /// The instrument voicing the pad.
+    pub fn instrument(&self) -> &dyn Instrument {
+        self.instrument.as_ref()
+    }
+}
+
+/// A whole drum kit in one instrument: each note maps to a named pad voiced
+/// by its own instrument (a synthesized drum, a [`MultiSampleInstrument`], ...)
+/// with its own envelope and gain.
+///
+/// Notes without a pad are ignored. The pad output is scaled by the velocity
+/// of its last hit.
+///
+/// [`MultiSampleInstrument`]: crate::instruments::prelude::MultiSampleInstrument
+#[derive(Debug, Default)]
+pub struct DrumKit {
+    pads: Vec<DrumPad>,
+    output: f32,
+}
+
+impl DrumKit {
+    /// The default kit, on the General MIDI drum notes: `kick` on C2 (36),
+    /// `snare` on D2 (38) and `hihat` on F#2 (42).
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::empty()
+            .with_pad("kick", Note(NOTES::C, 2), 1.0, Box::new(Kick::new()))
+            .with_pad("snare", Note(NOTES::D, 2), 1.0, Box::new(Snare::new()))
+            .with_pad("hihat", Note(NOTES::FS, 2), 1.0, Box::new(HiHat::new()?)))
+    }
+
+    /// A kit without any pad.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pad playing `instrument` on `note`, replacing any pad already
+    /// mapped to that note.
+    pub fn with_pad(
+        mut self,
+        name: impl Into<String>,
+        note: Note,
+        gain: f32,
+        instrument: Box<dyn Instrument>,
+    ) -> Self {
+        self.pads.retain(|pad| pad.note != note);
+        self.pads.push(DrumPad {
+            name: name.into(),
+            note,
+            gain,
+            instrument,
+            velocity: 0.0,
+        });
+        self
+    }
+
+    pub fn pads(&self) -> &[DrumPad] {
+        &self.pads
+    }
+
+    /// The pad named `name`.
+    pub fn pad(&self, name: &str) -> Option<&DrumPad> {
+        self.pads.iter().find(|pad| pad.name == name)
+    }
+
+    /// The note triggering the pad named `name`.
+    pub fn note_of(&self, name: &str) -> Option<Note> {
+        self.pad(name).map(|pad| pad.note)
+    }
+
+    /// Triggers the pad named `name`. Returns false when there is no such pad.
+    pub fn trigger(&mut self, name: &str, velocity: f32) -> bool {
+        match self.note_of(name) {
+            Some(note) => {
+                self.start_note(note, velocity);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Instrument for DrumKit {
+    fn start_note(&mut self, note: Note, velocity: f32) {
+        if let Some(pad) = self.pads.iter_mut().find(|pad| pad.note == note) {
+            pad.velocity = velocity;
+            pad.instrument.start_note(note, velocity);
+        }
+    }
+
+    fn stop_note(&mut self, note: Note) {
+        if let Some(pad) = self.pads.iter_mut().find(|pad| pad.note == note) {
+            pad.instrument.stop_note(note);
+        }
+    }
+
+    fn get_output(&mut self) -> f32 {
+        self.output
+    }
+
+    fn tick(&mut self) {
+        self.output = self
+            .pads
+            .iter_mut()
+            .map(|pad| {
+                pad.instrument.tick();
+                pad.instrument.get_output() * pad.gain * pad.velocity
+            })
+            .sum();
+    }
+
+    fn into_system(self: Box<Self>, sample_rate: f32) -> System {
+        let pads = self
+            .pads
+            .into_iter()
+            .map(|pad| PadSystem {
+                note: pad.note,
+                gain: pad.gain,
+                velocity: pad.velocity,
+                system: pad.instrument.into_system(sample_rate),
+                pending: Vec::new(),
+            })
+            .collect();
+
+        let mut system = System::new();
+        let source_idx = system.add_source(Box::new(DrumKitSource { pads }));
+        let output = system.add_filter(Box::new(GainFilter::new(1.0)));
+        system.connect_source(source_idx, output, 0);
+        let sink_idx = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_sink(output, sink_idx, 0);
+        system.compute().expect("DrumKit system compute failed");
+        system
+    }
+}
+
+/// The compiled sub-graph of a pad.
+#[derive(Debug, Clone)]
+struct PadSystem {
+    note: Note,
+    gain: f32,
+    velocity: f32,
+    system: System,
+    /// Frames rendered by the pad but not pulled yet, when its block size
+    /// does not divide the kit's.
+    pending: Block,
+}
+
+/// Source rendering every pad sub-graph of a [`DrumKit`] and routing notes to
+/// the pad mapped to them.
+#[derive(Debug, Clone)]
+pub struct DrumKitSource {
+    pads: Vec<PadSystem>,
+}
+
+impl Source for DrumKitSource {
+    fn pull(&mut self, block_size: usize) -> Block {
+        let mut block = vec![[0.0; CHANNELS]; block_size];
+        for pad in &mut self.pads {
+            while pad.pending.len() < block_size {
+                pad.system.run();
+                let Ok(sink) = pad.system.get_sink(0) else {
+                    break;
+                };
+                let frames = sink.consume();
+                if frames.is_empty() {
+                    break;
+                }
+                pad.pending.extend(frames);
+            }
+            let level = pad.gain * pad.velocity;
+            let take = block_size.min(pad.pending.len());
+            for (out, frame) in block.iter_mut().zip(pad.pending.drain(..take)) {
+                for ch in 0..CHANNELS {
+                    out[ch] += frame[ch] * level;
+                }
+            }
+        }
+        block
+    }
+
+    fn start_note(&mut self, note: Note, velocity: f32) {
+        if let Some(pad) = self.pads.iter_mut().find(|pad| pad.note == note) {
+            pad.velocity = velocity;
+            pad.system.start_note(0, note, velocity);
+        }
+    }
+
+    fn stop_note(&mut self, note: Note) {
+        if let Some(pad) = self.pads.iter_mut().find(|pad| pad.note == note) {
+            pad.system.stop_note(0, note);
+        }
+    }
+
+    fn kill(&mut self) {
+        for pad in &mut self.pads {
+            pad.system.kill_source(0);
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.pads.iter().any(|pad| pad.system.is_source_active(0))
+    }
+}
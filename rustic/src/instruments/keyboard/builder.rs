@@ -1,5 +1,7 @@
 use super::super::voices::PolyVoiceAllocator;
 use crate::core::envelope::prelude::ADSREnvelope;
+use crate::core::utils::TuningTable;
+use crate::instruments::Instrument;
 
 use super::Keyboard;
 
@@ -8,6 +10,7 @@ pub struct KeyboardBuilder {
     voices: usize,
     allocator: PolyVoiceAllocator,
     envelope: ADSREnvelope,
+    tuning: TuningTable,
 }
 
 impl KeyboardBuilder {
@@ -30,7 +33,14 @@ impl KeyboardBuilder {
         self
     }
 
+    pub fn with_tuning(mut self, tuning: TuningTable) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
     pub fn build(self) -> Keyboard {
-        Keyboard::new(self.voices, self.allocator, self.envelope)
+        let mut keyboard = Keyboard::new(self.voices, self.allocator, self.envelope);
+        keyboard.set_tuning(self.tuning);
+        keyboard
     }
 }
@@ -1,4 +1,8 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use atomic_float::AtomicF32;
 
 use crate::Note;
 use crate::core::envelope::prelude::{ADSREnvelopeBuilder, BezierSegment, LinearSegment};
@@ -8,12 +12,12 @@ use crate::core::envelope::{
 };
 use crate::core::filters::prelude::GainFilter;
 use crate::core::generator::prelude::{
-    FrequencyRelation, MultiToneGenerator, Waveform,
+    FilterConfig, FrequencyRelation, MultiToneGenerator, Waveform,
     builder::{MultiToneGeneratorBuilder, ToneGeneratorBuilder},
 };
 use crate::core::graph::sources::{PolyphonicAllocationStrategy, PolyphonicSource};
 use crate::core::graph::{SimpleSink, System};
-use crate::core::utils::tones::TONES_FREQ;
+use crate::core::utils::TuningTable;
 use crate::instruments::Instrument;
 use crate::instruments::voices::{PolyVoiceAllocator, PolyphonicVoice};
 
@@ -22,7 +26,9 @@ pub struct Keyboard {
     generators: Vec<(MultiToneGenerator, bool)>,
     allocator: PolyVoiceAllocator,
     note_indices: HashMap<Note, usize>,
+    tuning: TuningTable,
     output: f32,
+    envelope_level: Arc<AtomicF32>,
 }
 
 impl PolyphonicVoice for Keyboard {
@@ -96,7 +102,9 @@ impl Keyboard {
             generators,
             allocator: voice_allocator,
             note_indices: HashMap::new(),
+            tuning: TuningTable::default(),
             output: 0.0,
+            envelope_level: Arc::new(AtomicF32::new(0.0)),
         }
     }
 
@@ -116,7 +124,7 @@ impl Instrument for Keyboard {
             // If there is a free generator, we use it
             self.generators[position]
                 .0
-                .set_base_frequency(TONES_FREQ[note.0 as usize][note.1 as usize]);
+                .set_base_frequency(self.tuning.frequency(note));
             self.generators[position].0.start();
             self.generators[position].1 = true;
             self.note_indices.insert(note, position);
@@ -155,7 +163,29 @@ impl Instrument for Keyboard {
                 }
             })
             .sum::<f32>()
-            / self.generators.len() as f32
+            / self.generators.len() as f32;
+
+        let level = self
+            .generators
+            .iter()
+            .filter(|(_, is_playing)| *is_playing)
+            .map(|(generator, _)| generator.envelope_level())
+            .fold(0.0, f32::max);
+        self.envelope_level.store(level, Ordering::Relaxed);
+    }
+
+    fn set_voice_filter(&mut self, config: FilterConfig) {
+        for (generator, _) in self.generators.iter_mut() {
+            generator.set_voice_filter(Some(config.clone()));
+        }
+    }
+
+    fn set_tuning(&mut self, tuning: TuningTable) {
+        self.tuning = tuning;
+    }
+
+    fn envelope_meter(&self) -> Option<Arc<AtomicF32>> {
+        Some(Arc::clone(&self.envelope_level))
     }
 
     fn into_system(self: Box<Self>, sample_rate: f32) -> System {
@@ -172,7 +202,8 @@ impl Instrument for Keyboard {
             voice_count.max(1),
             sample_rate,
             PolyphonicAllocationStrategy::default(),
-        );
+        )
+        .with_tuning(self.tuning);
 
         let mut system = System::new();
         let source_idx = system.add_source(Box::new(source));
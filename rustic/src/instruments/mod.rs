@@ -1,15 +1,23 @@
+use std::sync::Arc;
+
+use atomic_float::AtomicF32;
+
 use crate::Note;
+use crate::core::generator::prelude::FilterConfig;
 use crate::core::graph::System;
+use crate::core::utils::TuningTable;
 
 mod custom;
 mod drum;
 mod keyboard;
+mod sampler;
 mod voices;
 
 pub mod prelude {
     pub use super::custom::*;
     pub use super::drum::*;
     pub use super::keyboard::*;
+    pub use super::sampler::*;
     pub use super::voices::*;
 }
 
@@ -26,6 +34,28 @@ pub trait Instrument: std::fmt::Debug + Send + Sync {
     /// Advances the instrument by one tick
     fn tick(&mut self);
 
+    /// Returns the current amplitude envelope level of the instrument, in `[0.0, 1.0]`.
+    /// Instruments that do not track their envelope report `0.0`.
+    fn envelope_level(&self) -> f32 {
+        self.envelope_meter()
+            .map(|level| level.load(std::sync::atomic::Ordering::Relaxed))
+            .unwrap_or(0.0)
+    }
+
+    /// Gives every voice of the instrument its own low-pass filter, so filter
+    /// sweeps follow each note independently. Instruments without voices ignore it.
+    fn set_voice_filter(&mut self, _config: FilterConfig) {}
+
+    /// Detunes the notes the instrument plays. Instruments with a fixed pitch
+    /// ignore it.
+    fn set_tuning(&mut self, _tuning: TuningTable) {}
+
+    /// Returns a shared handle to the envelope level, updated on every `tick()`.
+    /// Frontends can keep this handle to draw the envelope shape in real time.
+    fn envelope_meter(&self) -> Option<Arc<AtomicF32>> {
+        None
+    }
+
     /// Converts this instrument into a self-contained `System` sub-graph.
     /// Used by `AudioGraph::compile()` to assemble all instruments into a
     /// single unified graph for the render thread.
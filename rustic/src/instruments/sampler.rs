@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use crate::Note;
+use crate::core::audio::{Block, CHANNELS};
+use crate::core::filters::prelude::GainFilter;
+use crate::core::graph::{FileSource, SimpleSink, Source, System};
+use crate::instruments::Instrument;
+
+/// The samples played for the velocities in `velocities`.
+///
+/// Hits landing on the layer cycle through its samples (round-robin), so
+/// repeated notes do not sound like the exact same recording.
+#[derive(Debug, Clone)]
+pub struct VelocityLayer {
+    pub velocities: RangeInclusive<f32>,
+    samples: Vec<FileSource>,
+    next: usize,
+}
+
+impl VelocityLayer {
+    pub fn new(velocities: RangeInclusive<f32>, samples: Vec<FileSource>) -> Self {
+        let samples = samples
+            .into_iter()
+            .map(|mut sample| {
+                // Sources play right away; wait for a hit instead
+                sample.kill();
+                sample
+            })
+            .collect();
+        Self {
+            velocities,
+            samples,
+            next: 0,
+        }
+    }
+
+    pub fn samples(&self) -> &[FileSource] {
+        &self.samples
+    }
+
+    /// Rewinds the next round-robin sample and returns its index.
+    fn trigger(&mut self) -> Option<usize> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let idx = self.next % self.samples.len();
+        self.next = idx + 1;
+        self.samples[idx].start();
+        Some(idx)
+    }
+}
+
+/// Source playing multi-sampled notes: each note maps to velocity layers of
+/// [`FileSource`]s, picked on `start_note` by velocity and round-robin.
+///
+/// Samples are one-shots by default: note-offs are ignored and each sample
+/// plays to its end. Several samples ring together, so a new hit does not
+/// cut the tail of the previous one.
+#[derive(Debug, Clone)]
+pub struct MultiSampleSource {
+    notes: HashMap<Note, Vec<VelocityLayer>>,
+    one_shot: bool,
+    /// `(layer, sample)` of the last hit of each note.
+    last_hit: HashMap<Note, (usize, usize)>,
+}
+
+impl MultiSampleSource {
+    pub fn new() -> Self {
+        Self {
+            notes: HashMap::new(),
+            one_shot: true,
+            last_hit: HashMap::new(),
+        }
+    }
+
+    /// Adds a velocity layer to `note`. Layers are matched in the order they
+    /// were added, so the first layer containing the velocity wins.
+    pub fn add_layer(&mut self, note: Note, layer: VelocityLayer) {
+        self.notes.entry(note).or_default().push(layer);
+    }
+
+    /// When disabled, a note-off stops the samples of its note.
+    pub fn set_one_shot(&mut self, one_shot: bool) {
+        self.one_shot = one_shot;
+    }
+
+    pub fn layers(&self, note: Note) -> &[VelocityLayer] {
+        self.notes.get(&note).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// `(layer, sample)` indices of the last hit of `note`.
+    pub fn last_hit(&self, note: Note) -> Option<(usize, usize)> {
+        self.last_hit.get(&note).copied()
+    }
+
+    fn samples_mut(&mut self) -> impl Iterator<Item = &mut FileSource> {
+        self.notes
+            .values_mut()
+            .flatten()
+            .flat_map(|layer| layer.samples.iter_mut())
+    }
+}
+
+impl Default for MultiSampleSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Source for MultiSampleSource {
+    fn pull(&mut self, block_size: usize) -> Block {
+        let mut block = vec![[0.0; CHANNELS]; block_size];
+        for sample in self.samples_mut().filter(|sample| sample.is_active()) {
+            for (out, frame) in block.iter_mut().zip(sample.pull(block_size)) {
+                for ch in 0..CHANNELS {
+                    out[ch] += frame[ch];
+                }
+            }
+        }
+        block
+    }
+
+    fn start_note(&mut self, note: Note, velocity: f32) {
+        let Some(layers) = self.notes.get_mut(&note) else {
+            return;
+        };
+        let hit = layers
+            .iter_mut()
+            .enumerate()
+            .find(|(_, layer)| layer.velocities.contains(&velocity))
+            .and_then(|(layer_idx, layer)| Some((layer_idx, layer.trigger()?)));
+        if let Some(hit) = hit {
+            self.last_hit.insert(note, hit);
+        }
+    }
+
+    fn stop_note(&mut self, note: Note) {
+        if self.one_shot {
+            return;
+        }
+        if let Some(layers) = self.notes.get_mut(&note) {
+            layers
+                .iter_mut()
+                .flat_map(|layer| layer.samples.iter_mut())
+                .for_each(|sample| sample.stop());
+        }
+    }
+
+    fn stop(&mut self) {
+        if !self.one_shot {
+            self.kill();
+        }
+    }
+
+    fn kill(&mut self) {
+        self.samples_mut().for_each(|sample| sample.kill());
+    }
+
+    fn is_active(&self) -> bool {
+        self.notes
+            .values()
+            .flatten()
+            .any(|layer| layer.samples.iter().any(|sample| sample.is_active()))
+    }
+}
+
+/// An instrument playing recorded samples, with velocity layers and
+/// round-robin variants per note (see [`MultiSampleSource`]).
+///
+/// ```
+/// use rustic::core::graph::FileSource;
+/// use rustic::instruments::prelude::MultiSampleInstrument;
+/// use rustic::{NOTES, Note};
+///
+/// let hit = |level: f32| FileSource::from_frames(vec![[level; 2]; 64], false);
+/// let snare = MultiSampleInstrument::new()
+///     .with_layer(Note(NOTES::D, 2), 0.0..=0.5, vec![hit(0.2), hit(0.25)])
+///     .with_layer(Note(NOTES::D, 2), 0.5..=1.0, vec![hit(0.8), hit(0.9)]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultiSampleInstrument {
+    source: MultiSampleSource,
+    output: f32,
+}
+
+impl MultiSampleInstrument {
+    pub fn new() -> Self {
+        Self {
+            source: MultiSampleSource::new(),
+            output: 0.0,
+        }
+    }
+
+    /// Adds a layer of `samples` played on `note` for the given velocities.
+    pub fn with_layer(
+        mut self,
+        note: Note,
+        velocities: RangeInclusive<f32>,
+        samples: Vec<FileSource>,
+    ) -> Self {
+        self.source
+            .add_layer(note, VelocityLayer::new(velocities, samples));
+        self
+    }
+
+    /// Builder-style setter for [`MultiSampleSource::set_one_shot`].
+    pub fn with_one_shot(mut self, one_shot: bool) -> Self {
+        self.source.set_one_shot(one_shot);
+        self
+    }
+
+    pub fn source(&self) -> &MultiSampleSource {
+        &self.source
+    }
+}
+
+impl Instrument for MultiSampleInstrument {
+    fn start_note(&mut self, note: Note, velocity: f32) {
+        self.source.start_note(note, velocity);
+    }
+
+    fn stop_note(&mut self, note: Note) {
+        self.source.stop_note(note);
+    }
+
+    fn get_output(&mut self) -> f32 {
+        self.output
+    }
+
+    fn tick(&mut self) {
+        let frame = self.source.pull(1)[0];
+        self.output = frame.iter().sum::<f32>() / CHANNELS as f32;
+    }
+
+    fn into_system(self: Box<Self>, _sample_rate: f32) -> System {
+        let mut system = System::new();
+        let source_idx = system.add_source(Box::new(self.source));
+        let output = system.add_filter(Box::new(GainFilter::new(1.0)));
+        system.connect_source(source_idx, output, 0);
+        let sink_idx = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_sink(output, sink_idx, 0);
+        system
+            .compute()
+            .expect("MultiSampleInstrument system compute failed");
+        system
+    }
+}
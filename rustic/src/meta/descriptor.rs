@@ -0,0 +1,132 @@
+//! Serializable description of a [`System`](crate::core::graph::System) topology.
+//!
+//! A [`GraphDescriptor`] records every filter (type id, scalar parameter
+//! values and mix mode), the pipes between them and how sources and sinks are
+//! wired. Filters are rebuilt by type id through a factory (usually
+//! [`FilterRegistry::create`](super::FilterRegistry::create)); sources and sinks
+//! are runtime objects without a registry, so only their wiring is stored and
+//! the caller provides them again, in the same order, when rebuilding.
+//! LFO routes are stored with their settings and restart from the beginning of
+//! their cycle once rebuilt.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use rustic_meta::MixMode;
+use serde::{Deserialize, Serialize};
+
+use crate::core::modulation::{Lfo, LfoPolarity, LfoRate, LfoShape};
+
+/// A filter node of the graph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeDescriptor {
+    /// Filter type id, as found in `FilterInfo::type_id`.
+    pub type_id: String,
+    /// Scalar parameter values by field name.
+    pub parameters: BTreeMap<String, f32>,
+    #[serde(default)]
+    pub mix_mode: MixMode,
+}
+
+/// A pipe between two filter nodes, by position in [`GraphDescriptor::nodes`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EdgeDescriptor {
+    pub from: usize,
+    pub out_port: usize,
+    pub to: usize,
+    pub in_port: usize,
+}
+
+/// The filter inputs a source feeds, as `(node, in_port)` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceDescriptor {
+    pub targets: Vec<(usize, usize)>,
+}
+
+/// The filter outputs feeding a sink, as `(node, out_port)` pairs, and the
+/// sources wired directly to it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SinkDescriptor {
+    pub inputs: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub direct_sources: Vec<usize>,
+}
+
+/// What a modulation wire drives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ModTargetDescriptor {
+    Source(usize),
+    Node(usize),
+}
+
+/// A modulation wire: the block mean of a source drives a named parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModulationDescriptor {
+    pub from_source: usize,
+    pub target: ModTargetDescriptor,
+    pub param_name: String,
+}
+
+/// An LFO driving a named parameter of a filter node, see
+/// [`ModulationRoute`](crate::core::modulation::ModulationRoute).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LfoRouteDescriptor {
+    pub target: usize,
+    pub param_name: String,
+    pub base: f32,
+    pub shape: LfoShape,
+    pub rate: LfoRate,
+    pub depth: f32,
+    pub phase: f32,
+    pub polarity: LfoPolarity,
+    pub bpm: f64,
+    pub sample_rate: f32,
+}
+
+impl LfoRouteDescriptor {
+    /// Builds the LFO described, at the start of its cycle.
+    pub fn lfo(&self) -> Lfo {
+        Lfo::new(self.shape, self.rate, self.sample_rate)
+            .with_depth(self.depth)
+            .with_phase(self.phase)
+            .with_polarity(self.polarity)
+            .with_bpm(self.bpm)
+    }
+}
+
+/// Serializable topology of a whole `System`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphDescriptor {
+    pub block_size: usize,
+    pub nodes: Vec<NodeDescriptor>,
+    pub edges: Vec<EdgeDescriptor>,
+    pub sources: Vec<SourceDescriptor>,
+    pub sinks: Vec<SinkDescriptor>,
+    #[serde(default)]
+    pub modulations: Vec<ModulationDescriptor>,
+    #[serde(default)]
+    pub lfo_routes: Vec<LfoRouteDescriptor>,
+}
+
+impl GraphDescriptor {
+    /// Serializes the descriptor to pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parses a descriptor from JSON.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes the descriptor to `path` as JSON.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_json()?)
+    }
+
+    /// Reads a descriptor from a JSON file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Ok(Self::from_json(&std::fs::read_to_string(path)?)?)
+    }
+}
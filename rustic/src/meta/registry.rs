@@ -0,0 +1,131 @@
+//! Name → constructor lookup for every filter deriving `FilterMetaData` and
+//! every generator deriving `GeneratorMetaData`.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use rustic_meta::MetaGenerator;
+
+use super::{FilterRegistration, GeneratorRegistration};
+use crate::core::graph::{Filter, Source};
+
+/// Maps filter type ids (the struct name, e.g. `"LowPassFilter"`) to their
+/// constructors, as registered by `#[derive(FilterMetaData)]`.
+///
+/// Filters are created with their `Default` values; use `set_parameter` to
+/// configure them afterwards.
+pub struct FilterRegistry {
+    factories: HashMap<&'static str, fn() -> Box<dyn Filter>>,
+}
+
+impl FilterRegistry {
+    /// Collects every registered filter type.
+    pub fn new() -> Self {
+        let factories = inventory::iter::<FilterRegistration>()
+            .map(|registration| ((registration.info)().type_id, registration.create))
+            .collect();
+        Self { factories }
+    }
+
+    /// Returns the process-wide registry, built on first use.
+    pub fn global() -> &'static FilterRegistry {
+        static REGISTRY: OnceLock<FilterRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(FilterRegistry::new)
+    }
+
+    /// Creates a new filter of the given type, or `None` if it is unknown.
+    pub fn create(&self, type_id: &str) -> Option<Box<dyn Filter>> {
+        self.factories.get(type_id).map(|create| create())
+    }
+
+    /// Returns `true` if a filter type with this id is registered.
+    pub fn contains(&self, type_id: &str) -> bool {
+        self.factories.contains_key(type_id)
+    }
+
+    /// Registered type ids, sorted alphabetically.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.factories.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn len(&self) -> usize {
+        self.factories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+}
+
+impl Default for FilterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maps generator type ids (e.g. `"sine"`) to their constructors and
+/// metadata, as registered by `#[derive(GeneratorMetaData)]`.
+///
+/// Sources are created with the generator's default settings; use
+/// `Source::set_parameter` to configure them afterwards.
+pub struct GeneratorRegistry {
+    generators: HashMap<&'static str, &'static GeneratorRegistration>,
+}
+
+impl GeneratorRegistry {
+    /// Collects every registered generator type.
+    pub fn new() -> Self {
+        let generators = inventory::iter::<GeneratorRegistration>()
+            .map(|registration| ((registration.info)().type_id, registration))
+            .collect();
+        Self { generators }
+    }
+
+    /// Returns the process-wide registry, built on first use.
+    pub fn global() -> &'static GeneratorRegistry {
+        static REGISTRY: OnceLock<GeneratorRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(GeneratorRegistry::new)
+    }
+
+    /// Creates a source of the given generator type, or `None` if it is unknown.
+    pub fn create(&self, type_id: &str, sample_rate: f32) -> Option<Box<dyn Source>> {
+        self.generators
+            .get(type_id)
+            .map(|registration| (registration.create)(sample_rate))
+    }
+
+    /// Returns the metadata of the given generator type.
+    pub fn metadata(&self, type_id: &str) -> Option<MetaGenerator> {
+        self.generators
+            .get(type_id)
+            .map(|registration| (registration.info)())
+    }
+
+    /// Returns `true` if a generator type with this id is registered.
+    pub fn contains(&self, type_id: &str) -> bool {
+        self.generators.contains_key(type_id)
+    }
+
+    /// Registered type ids, sorted alphabetically.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.generators.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+}
+
+impl Default for GeneratorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,16 @@
-use crate::core::graph::Filter;
+use crate::core::graph::{Filter, Source};
 use rustic_meta::Parameter;
 
 pub trait FilterFactory: Send + Sync {
     fn create_instance(&self) -> Box<dyn Filter>;
 }
 
+/// Builds a playable source from a generator description; required by
+/// `#[derive(GeneratorMetaData)]`.
+pub trait GeneratorFactory: Send + Sync {
+    fn create_source(&self, sample_rate: f32) -> Box<dyn Source>;
+}
+
 pub trait FilterMetadata: Send + Sync {
     fn name(&self) -> String;
     fn description(&self) -> String;
@@ -1,7 +1,11 @@
 //! Builder pattern for creating plots with customization options
 
+use crate::core::envelope::prelude::ADSREnvelope;
 use crate::plotting::{
     PlotError,
+    envelope::{envelope_points, envelope_stage_boundaries},
+    spectrogram::{ColorMap, ScaleMode},
+    spectrum::{SPECTRUM_FLOOR_DB, WindowType, magnitude_spectrum},
     types::{LineConfig, LineType, SeriesConfig},
 };
 use std::path::Path;
@@ -32,6 +36,7 @@ pub struct PlotBuilder {
     pub(crate) title: String,
     pub(crate) x_range: (f32, f32),
     pub(crate) y_range: (f32, f32),
+    pub(crate) x_log_scale: bool,
     pub(crate) x_label: Option<String>,
     pub(crate) y_label: Option<String>,
     pub(crate) series: Vec<SeriesConfig>,
@@ -52,6 +57,7 @@ impl Default for PlotBuilder {
             title: "Plot".to_string(),
             x_range: (0.0, 1.0),
             y_range: (0.0, 1.0),
+            x_log_scale: false,
             x_label: None,
             y_label: None,
             series: Vec::new(),
@@ -105,6 +111,12 @@ impl PlotBuilder {
         self
     }
 
+    /// Uses a logarithmic X axis; the X range must then be strictly positive
+    pub fn x_log_scale(mut self, log: bool) -> Self {
+        self.x_log_scale = log;
+        self
+    }
+
     /// Sets the X-axis label
     pub fn x_label<S: AsRef<str>>(mut self, label: S) -> Self {
         log::trace!("Setting plot x label to {}", label.as_ref());
@@ -153,6 +165,81 @@ impl PlotBuilder {
         self
     }
 
+    /// Adds the magnitude spectrum of `samples` as a series
+    ///
+    /// The spectrum is computed with an FFT over the whole input (zero-padded
+    /// to a power of two) after applying `window`. The X axis switches to a
+    /// logarithmic frequency scale from the first bin (at least 20 Hz) to
+    /// Nyquist, and the Y axis shows magnitudes in dB from the strongest bin
+    /// down 100 dB. Call the range setters afterwards to override them.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustic::plotting::{PlotBuilder, WindowType};
+    /// let samples = vec![0.0; 1024];
+    /// PlotBuilder::new().plot_spectrum(&samples, 44100, WindowType::Hann);
+    /// ```
+    pub fn plot_spectrum(mut self, samples: &[f32], sample_rate: u32, window: WindowType) -> Self {
+        let spectrum = magnitude_spectrum(samples, sample_rate, window);
+        let nyquist = sample_rate as f32 / 2.0;
+        let low = spectrum
+            .first()
+            .map_or(20.0, |(f, _)| f.max(20.0))
+            .min(nyquist / 2.0);
+        let peak = spectrum
+            .iter()
+            .map(|(_, db)| *db)
+            .fold(SPECTRUM_FLOOR_DB, f32::max);
+
+        self = self
+            .x_log_scale(true)
+            .x_range(low, nyquist)
+            .y_range((peak - 100.0).max(SPECTRUM_FLOOR_DB), peak + 6.0);
+        if self.x_label.is_none() {
+            self = self.x_label("Frequency (Hz)");
+        }
+        if self.y_label.is_none() {
+            self = self.y_label("Magnitude (dB)");
+        }
+        let data = spectrum.into_iter().filter(|(f, _)| *f >= low).collect();
+        self.add_series(data, "Spectrum", None)
+    }
+
+    /// Adds the curve of an ADSR envelope for a note held `note_duration`
+    /// seconds, with vertical markers at the stage boundaries
+    ///
+    /// The X axis spans from the note on to the end of the release, in
+    /// seconds, and the Y axis from 0 to the envelope's peak.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustic::plotting::PlotBuilder;
+    /// use rustic::core::envelope::prelude::ADSREnvelope;
+    ///
+    /// PlotBuilder::new()
+    ///     .title("ADSR")
+    ///     .plot_envelope(&ADSREnvelope::default(), 1.0);
+    /// ```
+    pub fn plot_envelope(mut self, env: &ADSREnvelope, note_duration: f32) -> Self {
+        let points = envelope_points(env, note_duration);
+        let boundaries = envelope_stage_boundaries(env, note_duration);
+        let peak = points.iter().map(|(_, y)| *y).fold(1.0, f32::max);
+
+        self = self
+            .x_range(0.0, boundaries[3].max(f32::EPSILON))
+            .y_range(0.0, peak * 1.05);
+        if self.x_label.is_none() {
+            self = self.x_label("Time (s)");
+        }
+        if self.y_label.is_none() {
+            self = self.y_label("Amplitude");
+        }
+        for boundary in &boundaries[..3] {
+            self = self.add_vertical_line(*boundary, None);
+        }
+        self.add_series(points, "Envelope", None)
+    }
+
     // ==================== Line Annotations ====================
 
     /// Adds a vertical line annotation at the specified x coordinate
@@ -280,6 +367,42 @@ impl PlotBuilder {
         crate::plotting::render::render_plot(&self, path.as_ref())
     }
 
+    /// Renders `frames` as a spectrogram heatmap and saves it to a file
+    ///
+    /// Each frame is one time step (left to right) holding magnitudes from the
+    /// lowest to the highest frequency bin (bottom to top), as returned by
+    /// [`compute_spectrogram`](crate::plotting::compute_spectrogram). Cells
+    /// span the X and Y ranges of the plot, so set them to the duration and
+    /// Nyquist frequency to get physical axes. Series and lines are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use rustic::plotting::{ColorMap, PlotBuilder, ScaleMode, WindowType, compute_spectrogram};
+    ///
+    /// let samples: Vec<f32> = (0..8192).map(|i| (i as f32 * 0.05).sin()).collect();
+    /// let frames = compute_spectrogram(&samples, 512, 256, WindowType::Hann);
+    /// PlotBuilder::new()
+    ///     .title("Spectrogram")
+    ///     .x_range(0.0, 8192.0 / 44100.0)
+    ///     .y_range(0.0, 22050.0)
+    ///     .render_spectrogram(&frames, ColorMap::Viridis, ScaleMode::Decibel, "spectrogram.png")?;
+    /// # Ok::<(), rustic::plotting::PlotError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `PlotError` if `frames` is empty or has empty frames
+    /// (`EmptyData`), the ranges are invalid (`InvalidRange`) or the image
+    /// cannot be rendered or written.
+    pub fn render_spectrogram<P: AsRef<Path>>(
+        self,
+        frames: &[Vec<f32>],
+        color_map: ColorMap,
+        scale: ScaleMode,
+        path: P,
+    ) -> Result<(), PlotError> {
+        crate::plotting::render::render_spectrogram(&self, frames, color_map, scale, path.as_ref())
+    }
+
     // ==================== Helper Methods ====================
 
     /// Automatically assigns a color based on the series index
@@ -0,0 +1,51 @@
+//! Sampling of envelopes for envelope plots
+
+use crate::core::envelope::Envelope;
+use crate::core::envelope::prelude::ADSREnvelope;
+
+/// Number of points sampled in each of the four ADSR stages
+pub const ENVELOPE_POINTS_PER_STAGE: usize = 250;
+
+/// Start times of the decay, sustain and release stages of `env` for a note
+/// held `note_duration` seconds, followed by the end of the release.
+pub fn envelope_stage_boundaries(env: &ADSREnvelope, note_duration: f32) -> [f32; 4] {
+    let attack_end = env.attack.get_duration();
+    let decay_end = attack_end + env.decay.get_duration();
+    let note_off = note_duration.max(decay_end);
+    [
+        attack_end,
+        decay_end,
+        note_off,
+        note_off + env.release.get_duration(),
+    ]
+}
+
+/// Samples `env.at(time, note_off)` for a note held `note_duration` seconds,
+/// as `(time, amplitude)` pairs.
+///
+/// Each stage (attack, decay, sustain, release) is sampled with
+/// [`ENVELOPE_POINTS_PER_STAGE`] points starting at its first instant, and
+/// the end of the release is appended, so every stage boundary is an exact
+/// sample. The note off is moved to the end of the decay when `note_duration`
+/// is shorter, as the envelope does not release before reaching its sustain.
+pub fn envelope_points(env: &ADSREnvelope, note_duration: f32) -> Vec<(f32, f32)> {
+    let boundaries = envelope_stage_boundaries(env, note_duration);
+    let note_off = boundaries[2];
+    let mut start = 0.0;
+
+    let mut points: Vec<(f32, f32)> = boundaries
+        .iter()
+        .flat_map(|&end| {
+            let stage_start = start;
+            start = end;
+            (0..ENVELOPE_POINTS_PER_STAGE).map(move |i| {
+                stage_start + (end - stage_start) * i as f32 / ENVELOPE_POINTS_PER_STAGE as f32
+            })
+        })
+        .map(|time| (time, env.at(time, note_off)))
+        .collect();
+
+    let end = boundaries[3];
+    points.push((end, env.at(end, note_off)));
+    points
+}
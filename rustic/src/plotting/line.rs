@@ -2,17 +2,18 @@
 
 use plotters::style::RGBColor;
 
+use crate::plotting::PlotBuilder;
+use crate::plotting::types::{LineConfig, LineType};
+
 /// Internal type for line annotations
 /// This is not part of the public API - use PlotBuilder's line methods instead
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub(crate) struct Line {
     pub from: (f32, f32),
     pub to: (f32, f32),
     pub color: RGBColor,
 }
 
-#[allow(dead_code)]
 impl Line {
     pub fn new(from: (f32, f32), to: (f32, f32), color: (u8, u8, u8)) -> Self {
         Self {
@@ -21,4 +22,15 @@ impl Line {
             color: RGBColor(color.0, color.1, color.2),
         }
     }
+
+    /// Resolves a line annotation to its end points in chart coordinates;
+    /// vertical and horizontal lines span the whole axis range.
+    pub fn resolve(config: &LineConfig, plot: &PlotBuilder) -> Self {
+        let (from, to) = match config.line_type {
+            LineType::Vertical(x) => ((x, plot.y_range.0), (x, plot.y_range.1)),
+            LineType::Horizontal(y) => ((plot.x_range.0, y), (plot.x_range.1, y)),
+            LineType::Custom { from, to } => (from, to),
+        };
+        Self::new(from, to, config.color)
+    }
 }
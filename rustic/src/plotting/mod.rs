@@ -29,6 +29,22 @@
 //! # Ok::<(), rustic::plotting::PlotError>(())
 //! ```
 //!
+//! To plot the magnitude spectrum of a signal on a log-frequency axis, use
+//! [`PlotBuilder::plot_spectrum`]:
+//!
+//! ```
+//! use rustic::plotting::{PlotBuilder, WindowType};
+//!
+//! let samples: Vec<f32> = (0..4096)
+//!     .map(|i| (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 44100.0).sin())
+//!     .collect();
+//! PlotBuilder::new()
+//!     .title("Spectrum")
+//!     .plot_spectrum(&samples, 44100, WindowType::Hann)
+//!     .save("spectrum.png")?;
+//! # Ok::<(), rustic::plotting::PlotError>(())
+//! ```
+//!
 //! # Advanced Usage
 //!
 //! For full customization, use [`PlotBuilder`]:
@@ -54,30 +70,34 @@
 
 // Internal modules
 mod builder;
+mod envelope;
 mod error;
 mod line;
 mod render;
 mod serie;
+mod spectrogram;
+mod spectrum;
 mod types;
 
 // Public exports
 pub use builder::PlotBuilder;
+pub use envelope::{ENVELOPE_POINTS_PER_STAGE, envelope_points, envelope_stage_boundaries};
 pub use error::PlotError;
+pub use spectrogram::{ColorMap, SPECTROGRAM_RANGE_DB, ScaleMode};
+pub use spectrum::{SPECTRUM_FLOOR_DB, WindowType, compute_spectrogram, magnitude_spectrum};
 
 // Internal types (not re-exported)
 #[allow(unused_imports)]
-use line::Line;
-#[allow(unused_imports)]
 use serie::PlotSerie;
-#[allow(unused_imports)]
-use types::{LineConfig, LineType, SeriesConfig};
 
 /// Prelude module for convenient imports
 pub mod prelude {
     //! Convenient imports for plotting
     //!
     //! Use `use rustic::plotting::prelude::*;` to import commonly used types.
-    pub use super::{PlotBuilder, PlotError, plot_data, plot_multi};
+    pub use super::{
+        ColorMap, PlotBuilder, PlotError, ScaleMode, WindowType, plot_data, plot_multi,
+    };
 }
 
 // ==================== Convenience Functions ====================
@@ -1,6 +1,8 @@
 //! Core rendering logic for plots
 
-use crate::plotting::{PlotBuilder, PlotError, types::LineType};
+use crate::plotting::{ColorMap, PlotBuilder, PlotError, ScaleMode, line::Line};
+use plotters::coord::ranged1d::{Ranged, ValueFormatter};
+use plotters::coord::types::RangedCoordf32;
 use plotters::prelude::*;
 use std::path::Path;
 
@@ -54,16 +56,50 @@ pub(crate) fn render_plot(config: &PlotBuilder, path: &Path) -> Result<(), PlotE
     let title_font = (config.font_family.as_str(), config.title_font_size).into_font();
     let label_size = config.label_font_size;
 
-    let mut chart = ChartBuilder::on(&root)
+    let mut builder = ChartBuilder::on(&root);
+    builder
         .caption(&config.title, title_font)
         .margin(config.margin)
         .x_label_area_size(label_size)
-        .y_label_area_size(label_size)
-        .build_cartesian_2d(
-            config.x_range.0..config.x_range.1,
-            config.y_range.0..config.y_range.1,
+        .y_label_area_size(label_size);
+
+    let (x_min, x_max) = config.x_range;
+    let y_range = config.y_range.0..config.y_range.1;
+    if config.x_log_scale {
+        if x_min <= 0.0 {
+            return Err(PlotError::InvalidRange {
+                axis: "X (log)".to_string(),
+                min: x_min,
+                max: x_max,
+            });
+        }
+        draw_chart(
+            config,
+            builder.build_cartesian_2d((x_min..x_max).log_scale(), y_range)?,
         )?;
+    } else {
+        draw_chart(config, builder.build_cartesian_2d(x_min..x_max, y_range)?)?;
+    }
 
+    log::info!("Presenting final plot");
+    root.present()
+        .map_err(|e| PlotError::Rendering(e.to_string()))?;
+
+    log::info!("Plot presented");
+    Ok(())
+}
+
+/// Draws the grid, data series, legend and line annotations on `chart`,
+/// whatever the scale of its X axis.
+fn draw_chart<'a, DB, X>(
+    config: &PlotBuilder,
+    mut chart: ChartContext<'a, DB, Cartesian2d<X, RangedCoordf32>>,
+) -> Result<(), PlotError>
+where
+    DB: DrawingBackend + 'a,
+    DB::ErrorType: 'static,
+    X: Ranged<ValueType = f32> + ValueFormatter<f32>,
+{
     // Configure mesh (grid)
     if config.show_grid {
         let mut mesh = chart.configure_mesh();
@@ -89,50 +125,108 @@ pub(crate) fn render_plot(config: &PlotBuilder, path: &Path) -> Result<(), PlotE
         chart
             .draw_series(LineSeries::new(serie_config.data.iter().copied(), &color))?
             .label(&serie_config.label)
-            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &color));
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
     }
 
     // Draw legend
     if config.show_legend && !config.series.is_empty() {
         chart
             .configure_series_labels()
-            .background_style(&WHITE.mix(0.8))
-            .border_style(&BLACK)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
             .draw()?;
     }
 
-    // Draw line annotations
-    for line_config in &config.lines {
-        let color = RGBColor(
-            line_config.color.0,
-            line_config.color.1,
-            line_config.color.2,
-        );
-        let style = ShapeStyle::from(&color).stroke_width(2);
-
-        match line_config.line_type {
-            LineType::Vertical(x) => {
-                chart.draw_series(std::iter::once(PathElement::new(
-                    vec![(x, config.y_range.0), (x, config.y_range.1)],
-                    style,
-                )))?;
-            }
-            LineType::Horizontal(y) => {
-                chart.draw_series(std::iter::once(PathElement::new(
-                    vec![(config.x_range.0, y), (config.x_range.1, y)],
-                    style,
-                )))?;
-            }
-            LineType::Custom { from, to } => {
-                chart.draw_series(std::iter::once(PathElement::new(vec![from, to], style)))?;
-            }
+    // Draw line annotations over the series, in chart coordinates
+    for line in config.lines.iter().map(|l| Line::resolve(l, config)) {
+        let style = ShapeStyle::from(&line.color).stroke_width(2);
+        chart.draw_series(std::iter::once(PathElement::new(
+            vec![line.from, line.to],
+            style,
+        )))?;
+    }
+
+    Ok(())
+}
+
+/// Renders a spectrogram heatmap, one rectangle per time-frequency cell
+///
+/// # Errors
+/// Returns `PlotError` if:
+/// - `frames` is empty or its first frame is empty (`EmptyData`)
+/// - Axis ranges are invalid (`InvalidRange`)
+/// - Rendering fails (`Rendering`)
+pub(crate) fn render_spectrogram(
+    config: &PlotBuilder,
+    frames: &[Vec<f32>],
+    color_map: ColorMap,
+    scale: ScaleMode,
+    path: &Path,
+) -> Result<(), PlotError> {
+    let bins = frames.first().map_or(0, Vec::len);
+    if bins == 0 {
+        return Err(PlotError::EmptyData);
+    }
+
+    for (axis, (min, max)) in [("X", config.x_range), ("Y", config.y_range)] {
+        if min >= max {
+            return Err(PlotError::InvalidRange {
+                axis: axis.to_string(),
+                min,
+                max,
+            });
         }
     }
 
-    log::info!("Presenting final plot");
+    let root = BitMapBackend::new(path, config.resolution).into_drawing_area();
+    root.fill(&RGBColor(
+        config.background_color.0,
+        config.background_color.1,
+        config.background_color.2,
+    ))?;
+
+    let title_font = (config.font_family.as_str(), config.title_font_size).into_font();
+    let mut chart = ChartBuilder::on(&root)
+        .caption(&config.title, title_font)
+        .margin(config.margin)
+        .x_label_area_size(config.label_font_size)
+        .y_label_area_size(config.label_font_size)
+        .build_cartesian_2d(
+            config.x_range.0..config.x_range.1,
+            config.y_range.0..config.y_range.1,
+        )?;
+
+    let mut mesh = chart.configure_mesh();
+    mesh.disable_mesh();
+    if let Some(ref x_label) = config.x_label {
+        mesh.x_desc(x_label);
+    }
+    if let Some(ref y_label) = config.y_label {
+        mesh.y_desc(y_label);
+    }
+    mesh.draw()?;
+
+    let peak = frames.iter().flatten().copied().fold(0.0, f32::max);
+    let cell_width = (config.x_range.1 - config.x_range.0) / frames.len() as f32;
+    let cell_height = (config.y_range.1 - config.y_range.0) / bins as f32;
+
+    chart.draw_series(frames.iter().enumerate().flat_map(|(t, frame)| {
+        frame
+            .iter()
+            .take(bins)
+            .enumerate()
+            .map(move |(f, &magnitude)| {
+                let (r, g, b) = color_map.color(scale.intensity(magnitude, peak));
+                let x = config.x_range.0 + t as f32 * cell_width;
+                let y = config.y_range.0 + f as f32 * cell_height;
+                Rectangle::new(
+                    [(x, y), (x + cell_width, y + cell_height)],
+                    RGBColor(r, g, b).filled(),
+                )
+            })
+    }))?;
+
     root.present()
         .map_err(|e| PlotError::Rendering(e.to_string()))?;
-
-    log::info!("Plot presented");
     Ok(())
 }
@@ -0,0 +1,88 @@
+//! Color mapping of spectrogram magnitudes
+
+/// Color schemes for spectrogram cells, matching the analyser's schemes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMap {
+    Heat,
+    Plasma,
+    #[default]
+    Viridis,
+    Grayscale,
+}
+
+impl ColorMap {
+    fn stops(&self) -> &'static [(u8, u8, u8)] {
+        match self {
+            Self::Heat => &[
+                (0x00, 0x00, 0x00),
+                (0x8b, 0x00, 0x00),
+                (0xff, 0x00, 0x00),
+                (0xff, 0x8c, 0x00),
+                (0xff, 0xff, 0x00),
+                (0xff, 0xff, 0xff),
+            ],
+            Self::Plasma => &[
+                (0x0d, 0x08, 0x87),
+                (0x6a, 0x00, 0xa8),
+                (0xb1, 0x2a, 0x90),
+                (0xe1, 0x64, 0x62),
+                (0xfc, 0xa6, 0x36),
+                (0xf0, 0xf9, 0x21),
+            ],
+            Self::Viridis => &[
+                (0x44, 0x01, 0x54),
+                (0x44, 0x39, 0x83),
+                (0x31, 0x68, 0x8e),
+                (0x21, 0x91, 0x8c),
+                (0x35, 0xb7, 0x79),
+                (0x90, 0xd7, 0x43),
+                (0xfd, 0xe7, 0x25),
+            ],
+            Self::Grayscale => &[(0x00, 0x00, 0x00), (0xff, 0xff, 0xff)],
+        }
+    }
+
+    /// Returns the color for an intensity in `0..=1`, interpolating linearly
+    /// between the scheme's stops.
+    pub fn color(&self, intensity: f32) -> (u8, u8, u8) {
+        let stops = self.stops();
+        let position = intensity.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        let t = position - index as f32;
+        let (from, to) = (stops[index], stops[index + 1]);
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        (lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
+    }
+}
+
+/// Dynamic range shown by [`ScaleMode::Decibel`], in dB below the loudest cell
+pub const SPECTROGRAM_RANGE_DB: f32 = 80.0;
+
+/// How magnitudes are mapped to color intensities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScaleMode {
+    /// Intensity proportional to the magnitude, relative to the loudest cell
+    Linear,
+    /// Intensity proportional to the level in dB, from
+    /// [`SPECTROGRAM_RANGE_DB`] below the loudest cell (or quieter) to 0 dB
+    #[default]
+    Decibel,
+}
+
+impl ScaleMode {
+    /// Maps `magnitude` to an intensity in `0..=1` given the loudest
+    /// magnitude of the spectrogram.
+    pub fn intensity(&self, magnitude: f32, peak: f32) -> f32 {
+        if peak <= 0.0 {
+            return 0.0;
+        }
+        let relative = (magnitude / peak).clamp(0.0, 1.0);
+        match self {
+            Self::Linear => relative,
+            Self::Decibel => {
+                let db = 20.0 * relative.max(f32::MIN_POSITIVE).log10();
+                (1.0 + db / SPECTROGRAM_RANGE_DB).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
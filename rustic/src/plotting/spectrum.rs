@@ -0,0 +1,127 @@
+//! Magnitude spectrum and spectrogram computation for spectrum plots
+
+use rustfft::{FftPlanner, num_complex::Complex};
+use std::f32::consts::PI;
+
+/// Lowest magnitude reported by [`magnitude_spectrum`], in dB
+pub const SPECTRUM_FLOOR_DB: f32 = -120.0;
+
+/// Window functions applied before the FFT to reduce spectral leakage
+///
+/// Mirrors the analyser's window set in the toolkit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowType {
+    Rectangular,
+    #[default]
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl WindowType {
+    /// Returns the window coefficients for `size` points.
+    pub fn coefficients(&self, size: usize) -> Vec<f32> {
+        if size < 2 {
+            return vec![1.0; size];
+        }
+        let last = size as f32 - 1.0;
+        (0..size)
+            .map(|i| {
+                let x = 2.0 * PI * i as f32 / last;
+                match self {
+                    Self::Rectangular => 1.0,
+                    Self::Hann => 0.5 * (1.0 - x.cos()),
+                    Self::Hamming => 0.54 - 0.46 * x.cos(),
+                    Self::Blackman => 0.42 - 0.5 * x.cos() + 0.08 * (2.0 * x).cos(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Computes the magnitude spectrum of `samples` as `(frequency in Hz, dB)` pairs.
+///
+/// The windowed input is zero-padded to the next power of two. Magnitudes are
+/// normalised by the window's coherent gain so that a full-scale sine peaks
+/// near 0 dB, and clamped to [`SPECTRUM_FLOOR_DB`]. The DC bin is omitted so
+/// the result can be drawn on a logarithmic frequency axis.
+pub fn magnitude_spectrum(
+    samples: &[f32],
+    sample_rate: u32,
+    window: WindowType,
+) -> Vec<(f32, f32)> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let fft_size = samples.len().next_power_of_two();
+    let coefficients = window.coefficients(samples.len());
+    let gain: f32 = coefficients.iter().sum::<f32>() / 2.0;
+
+    let mut buffer: Vec<Complex<f32>> = samples
+        .iter()
+        .zip(&coefficients)
+        .map(|(&sample, &w)| Complex {
+            re: sample * w,
+            im: 0.0,
+        })
+        .collect();
+    buffer.resize(fft_size, Complex { re: 0.0, im: 0.0 });
+
+    FftPlanner::new()
+        .plan_fft_forward(fft_size)
+        .process(&mut buffer);
+
+    let bin_width = sample_rate as f32 / fft_size as f32;
+    buffer
+        .iter()
+        .take(fft_size / 2)
+        .enumerate()
+        .skip(1)
+        .map(|(i, bin)| {
+            let magnitude = bin.norm() / gain.max(f32::EPSILON);
+            let db = (20.0 * magnitude.max(f32::MIN_POSITIVE).log10()).max(SPECTRUM_FLOOR_DB);
+            (i as f32 * bin_width, db)
+        })
+        .collect()
+}
+
+/// Computes a magnitude spectrogram with a Short-Time Fourier Transform.
+///
+/// Each frame spans `fft_size` samples, windowed and zero-padded to the next
+/// power of two, and frames start `hop_size` samples apart. Every returned
+/// frame holds the linear magnitudes of the bins below Nyquist, DC included.
+pub fn compute_spectrogram(
+    samples: &[f32],
+    fft_size: usize,
+    hop_size: usize,
+    window: WindowType,
+) -> Vec<Vec<f32>> {
+    let window_size = fft_size.max(1);
+    let hop_size = hop_size.max(1);
+    let padded_size = window_size.next_power_of_two();
+    let num_frames = samples.len().saturating_sub(window_size) / hop_size + 1;
+    let coefficients = window.coefficients(window_size);
+    let fft = FftPlanner::new().plan_fft_forward(padded_size);
+
+    (0..num_frames)
+        .map(|frame| {
+            let start = frame * hop_size;
+            let mut buffer: Vec<Complex<f32>> = coefficients
+                .iter()
+                .enumerate()
+                .map(|(i, &w)| Complex {
+                    re: samples.get(start + i).copied().unwrap_or(0.0) * w,
+                    im: 0.0,
+                })
+                .collect();
+            buffer.resize(padded_size, Complex { re: 0.0, im: 0.0 });
+            fft.process(&mut buffer);
+            buffer
+                .iter()
+                .take(padded_size / 2)
+                .map(|c| c.norm())
+                .collect()
+        })
+        .collect()
+}
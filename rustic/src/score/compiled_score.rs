@@ -1,38 +1,46 @@
 use super::instances::StaffInstance;
 use super::measure::Chord;
-use super::score::Score;
+use super::notes::ticks_to_seconds;
+use super::score::{Score, TimeSignature};
 use crate::Note;
+use crate::core::audio::{Block, CHANNELS};
 
-/// Plays a chord using the given instrument.
+/// Converts a score note to the note played by instruments.
+/// Returns `None` for pauses.
+fn instrument_note(score_note: &super::notes::Note) -> Option<Note> {
+    let note_type = match score_note.note {
+        super::notes::NoteName::A => crate::core::utils::tones::NOTES::A,
+        super::notes::NoteName::B => crate::core::utils::tones::NOTES::B,
+        super::notes::NoteName::C => crate::core::utils::tones::NOTES::C,
+        super::notes::NoteName::D => crate::core::utils::tones::NOTES::D,
+        super::notes::NoteName::E => crate::core::utils::tones::NOTES::E,
+        super::notes::NoteName::F => crate::core::utils::tones::NOTES::F,
+        super::notes::NoteName::G => crate::core::utils::tones::NOTES::G,
+        super::notes::NoteName::Pause => return None,
+    };
+    Some(Note(note_type, score_note.octave))
+}
+
+/// Plays a chord on the instrument of the given staff instance.
 ///
-/// This function converts score notes to the format required by instruments
-/// and plays them using the given instrument.
+/// Each note is held for its duration; notes tied from the previous chord
+/// are extended instead of being struck again.
 ///
 /// # Parameters
-/// * `instrument` - The instrument to play the notes with
+/// * `instance` - The staff instance playing the chord
 /// * `chord` - The chord containing notes to play
-fn play_chord(instrument: &mut Box<dyn crate::instruments::Instrument>, chord: &Chord) {
-    // Play all notes in the chord
-    for score_note in &chord.notes {
-        // Here we need to convert from score::notes::Note to crate::Note
-        // This is a placeholder implementation - you'll need to implement the actual conversion
-        let octave = score_note.octave;
-        // Convert score note to the crate::Note type expected by instruments
-        // This is a simplified conversion and should be implemented properly
-        let note_type = match score_note.note {
-            super::notes::NoteName::A => crate::core::utils::tones::NOTES::A,
-            super::notes::NoteName::B => crate::core::utils::tones::NOTES::B,
-            super::notes::NoteName::C => crate::core::utils::tones::NOTES::C,
-            super::notes::NoteName::D => crate::core::utils::tones::NOTES::D,
-            super::notes::NoteName::E => crate::core::utils::tones::NOTES::E,
-            super::notes::NoteName::F => crate::core::utils::tones::NOTES::F,
-            super::notes::NoteName::G => crate::core::utils::tones::NOTES::G,
-            super::notes::NoteName::Pause => continue, // Skip pauses
-        };
-
-        let note = Note(note_type, octave);
-        instrument.start_note(note, 1.0);
-    }
+/// * `tick` - The tick the chord starts on
+/// * `velocity` - The velocity to play the notes at
+fn play_chord(instance: &mut StaffInstance, chord: &Chord, tick: usize, velocity: f32) {
+    let notes = chord
+        .notes
+        .iter()
+        .filter_map(|score_note| {
+            let end = (tick as f64 + score_note.exact_duration()).round() as usize;
+            instrument_note(score_note).map(|note| (note, end, score_note.tied))
+        })
+        .collect();
+    instance.hold_notes(tick, notes, velocity);
 }
 
 /// A compiled version of the Score optimized for playback.
@@ -64,6 +72,9 @@ fn play_chord(instrument: &mut Box<dyn crate::instruments::Instrument>, chord: &
 pub struct CompiledScore {
     pub name: String,
     pub tempo: usize,
+    pub signature: TimeSignature,
+    /// Whether notes are accented according to their position in the bar
+    pub accented: bool,
     pub staff_instances: Vec<StaffInstance>,
     pub duration: usize, // Total duration in ticks
     current_tick: usize,
@@ -124,6 +135,8 @@ impl CompiledScore {
         Ok(Self {
             name: score.name.clone(),
             tempo: score.tempo,
+            signature: score.signature.clone(),
+            accented: score.accents || score.signature.is_compound(),
             staff_instances,
             duration,
             current_tick: 0,
@@ -195,13 +208,61 @@ impl CompiledScore {
             self.tick();
         }
 
+        self.release_all();
         Ok(())
     }
 
+    /// Renders the compiled score offline into a stereo block.
+    ///
+    /// Instruments are ticked once per frame at `sample_rate`, and each
+    /// staff is placed in the stereo field according to its pan and gain.
+    ///
+    /// # Example
+    /// ```
+    /// # use rustic::prelude::{Score, TimeSignature};
+    /// # use rustic::instruments::prelude::HiHat;
+    /// # use rustic::score::compiled_score::CompiledScore;
+    /// #
+    /// # let mut score = Score::new("Test Score", TimeSignature(4, 4), 120, Vec::new(), Vec::new());
+    /// # score.add_instrument(Box::new(HiHat::new().unwrap()));
+    /// # let mut compiled = CompiledScore::new(&mut score).unwrap();
+    ///
+    /// let frames = compiled.render(44100.0);
+    /// assert!(compiled.is_complete());
+    /// ```
+    pub fn render(&mut self, sample_rate: f32) -> Block {
+        let bpm = self.tempo as f32;
+        let mut block = Block::new();
+
+        while self.current_tick < self.duration {
+            self.process_chords_at_current_tick();
+
+            let tick_end = (ticks_to_seconds(self.current_tick + 1, bpm) * sample_rate).round();
+            while block.len() < tick_end as usize {
+                let mut frame = [0.0; CHANNELS];
+                for instance in &mut self.staff_instances {
+                    let instrument = instance.instrument();
+                    instrument.tick();
+                    let sample = instrument.get_output();
+                    let (left, right) = instance.stereo_gains();
+                    frame[0] += sample * left;
+                    frame[1] += sample * right;
+                }
+                block.push(frame);
+            }
+
+            self.current_tick += 1;
+        }
+
+        self.release_all();
+        block
+    }
+
     /// Process all chords that should be played at the current tick.
     ///
     /// This method finds all chords that should be played at the current
-    /// tick and plays them using their respective instruments.
+    /// tick and plays them using their respective instruments. In accented
+    /// scores, the velocity follows the note's position in the bar.
     ///
     /// The implementation carefully avoids multiple mutable borrows by
     /// first collecting all chords and then playing them.
@@ -220,10 +281,27 @@ impl CompiledScore {
         }
 
         // Now play all the collected chords
+        let velocity = if self.accented {
+            self.signature.accent_at(current_tick)
+        } else {
+            1.0
+        };
         for (idx, chord) in chords_to_play {
             let instance = &mut self.staff_instances[idx];
             // Call play_chord as a separate function to avoid self-borrowing issues
-            play_chord(instance.instrument(), &chord);
+            play_chord(instance, &chord, current_tick, velocity);
+        }
+
+        // Release the notes that ended without being tied to a new chord
+        for instance in &mut self.staff_instances {
+            instance.release_notes(current_tick);
+        }
+    }
+
+    /// Stops every note still held by the instruments.
+    fn release_all(&mut self) {
+        for instance in &mut self.staff_instances {
+            instance.release_notes(usize::MAX);
         }
     }
 
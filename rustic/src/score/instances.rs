@@ -1,8 +1,16 @@
 use super::measure::Chord;
 use super::staff::Staff;
+use crate::Note;
 use crate::instruments::Instrument;
 use std::collections::VecDeque;
 
+/// A note held by the instrument of a staff during playback
+struct HeldNote {
+    note: Note,
+    end: usize, // Tick at which the note is released
+    tied: bool, // Whether the next note of the same pitch continues this one
+}
+
 /// StaffInstance provides a runtime representation of a Staff optimized for playback.
 ///
 /// It contains a queue of chords that need to be played in order, allowing for
@@ -20,8 +28,11 @@ use std::collections::VecDeque;
 /// ```
 pub struct StaffInstance {
     instrument: Box<dyn Instrument>,
-    chords: VecDeque<Chord>, // Using VecDeque for O(1) access to next chord
-    current_position: usize, // Current position in playback (in ticks)
+    chords: VecDeque<Chord>,  // Using VecDeque for O(1) access to next chord
+    current_position: usize,  // Current position in playback (in ticks)
+    exact_position: f64,      // Unrounded position, keeps tuplets on the tick grid
+    stereo_gains: (f32, f32), // Left and right weights of the instrument output
+    held: Vec<HeldNote>,      // Notes currently sounding
 }
 
 impl StaffInstance {
@@ -52,6 +63,9 @@ impl StaffInstance {
             instrument,
             chords,
             current_position: 0,
+            exact_position: 0.0,
+            stereo_gains: staff.stereo_gains(),
+            held: Vec::new(),
         }
     }
 
@@ -81,7 +95,8 @@ impl StaffInstance {
     pub fn next_chord(&mut self) -> Option<Chord> {
         let chord = self.chords.pop_front();
         if let Some(ref chord) = chord {
-            self.current_position += chord.duration();
+            self.exact_position += chord.exact_duration();
+            self.current_position = self.exact_position.round() as usize;
         }
         chord
     }
@@ -184,6 +199,55 @@ impl StaffInstance {
         self.current_position
     }
 
+    /// Starts the given notes on the instrument at `tick`, each held until
+    /// the end tick it comes with.
+    ///
+    /// A note continuing a tied note of the same pitch ending at `tick`
+    /// extends it rather than being struck again, so tied notes sound as a
+    /// single note. The last element of each tuple tells whether the note is
+    /// itself tied to the next one.
+    pub fn hold_notes(&mut self, tick: usize, notes: Vec<(Note, usize, bool)>, velocity: f32) {
+        for (note, end, tied) in notes {
+            let previous = self
+                .held
+                .iter()
+                .position(|held| held.note == note && held.end <= tick);
+            match previous {
+                Some(idx) if self.held[idx].tied => {
+                    self.held[idx].end = end;
+                    self.held[idx].tied = tied;
+                }
+                previous => {
+                    if let Some(idx) = previous {
+                        self.held.swap_remove(idx);
+                        self.instrument.stop_note(note);
+                    }
+                    self.instrument.start_note(note, velocity);
+                    self.held.push(HeldNote { note, end, tied });
+                }
+            }
+        }
+    }
+
+    /// Stops every held note ending at or before `tick`.
+    pub fn release_notes(&mut self, tick: usize) {
+        let instrument = &mut self.instrument;
+        self.held.retain(|held| {
+            if held.end <= tick {
+                instrument.stop_note(held.note);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Returns the left and right channel weights of the staff, given by
+    /// its pan and gain (see [`Staff::stereo_gains`]).
+    pub fn stereo_gains(&self) -> (f32, f32) {
+        self.stereo_gains
+    }
+
     /// Takes ownership of the instrument from this instance.
     ///
     /// This is used when returning the instrument to the Score after playback.
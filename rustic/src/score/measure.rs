@@ -1,7 +1,7 @@
 use log::info;
 use serde::{Deserialize, Serialize};
 
-use super::notes::{Note, NoteDuration};
+use super::notes::{Note, exact_ticks_to_seconds};
 use super::score::TimeSignature;
 
 #[derive(Serialize, Deserialize, Default, Clone)]
@@ -28,6 +28,19 @@ impl Chord {
         self.notes.iter().map(|n| n.duration()).max().unwrap_or(0)
     }
 
+    /// Duration of the chord in fractional ticks, exact for tuplets
+    pub fn exact_duration(&self) -> f64 {
+        self.notes
+            .iter()
+            .map(|n| n.exact_duration())
+            .fold(0.0, f64::max)
+    }
+
+    /// Duration of the chord in seconds at the given tempo
+    pub fn seconds(&self, bpm: f32) -> f32 {
+        exact_ticks_to_seconds(self.exact_duration(), bpm)
+    }
+
     pub fn add_note(&mut self, note: Note) {
         self.notes.push(note);
     }
@@ -41,48 +54,104 @@ impl Chord {
 /// A measure contains a given amount of notes
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Measure {
-    size: usize, // Number of Crotchet notes
-    #[serde(skip)]
-    chords: Vec<Chord>,
+    #[serde(with = "crotchets")]
+    size: usize, // Length of the measure in ticks, stored as a number of crotchets
     chords_set: Vec<(usize, Chord)>, // Sets of notes in the measure (scrambled). Used for serialization
 }
 
+/// (De)serializes a length in ticks as a number of crotchets, the unit
+/// measure sizes have always been saved in. Whole numbers stay integers;
+/// bars such as 7/8 that don't last a whole number of crotchets are saved
+/// as decimals.
+mod crotchets {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::score::notes::NoteDuration;
+
+    pub fn serialize<S: Serializer>(ticks: &usize, serializer: S) -> Result<S::Ok, S::Error> {
+        let crotchet = NoteDuration::Crotchet.duration();
+        if ticks.is_multiple_of(crotchet) {
+            serializer.serialize_u64((ticks / crotchet) as u64)
+        } else {
+            serializer.serialize_f64(*ticks as f64 / crotchet as f64)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+        let crotchets = f64::deserialize(deserializer)?;
+        Ok((crotchets * NoteDuration::Crotchet.duration() as f64).round() as usize)
+    }
+}
+
 impl Measure {
     /// Creates a new measure with no notes.
     pub fn new(signature: &TimeSignature) -> Self {
         Self {
-            size: signature.0,
-            chords: Vec::from_iter(
-                (0..signature.0 * NoteDuration::Crotchet.duration()).map(|_| Chord::default()),
-            ),
+            size: signature.bar_ticks(),
             chords_set: Vec::new(),
         }
     }
 
+    /// Length of the measure in ticks
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
     /// Checks if the measure is full. A measure is full if
-    /// the sum of its chords durations is equal to the time
-    /// signature's numerator times the duration of a Crotchet
+    /// the sum of its chords durations reaches the bar length
+    /// given by its time signature
     pub fn is_full(&self) -> bool {
-        self.chords.iter().map(|c| c.duration()).sum::<usize>()
-            == self.size * NoteDuration::Crotchet.duration()
+        self.current_index() >= self.size
     }
 
-    /// Returns the index of the next available space for a note in the measure
+    /// Returns the index of the next available space for a note in the measure.
+    /// Chord durations are summed exactly and the total rounded, so that
+    /// tuplet groups end on the tick grid.
     pub fn current_index(&self) -> usize {
-        self.chords.iter().map(|c| c.duration()).sum::<usize>()
+        self.chords_set
+            .iter()
+            .map(|(_, c)| c.exact_duration())
+            .sum::<f64>()
+            .round() as usize
+    }
+
+    /// Number of ticks left before the measure is full
+    pub fn remaining_capacity(&self) -> usize {
+        self.size.saturating_sub(self.current_index())
     }
 
     /// Adds a note in the chord at the given time position.
+    /// Returns an error if the note would end past the bar length given by
+    /// the measure's time signature; use [`force_add_note`](Self::force_add_note)
+    /// to overfill the measure on purpose.
     pub fn add_note(&mut self, time_index: usize, note: Note) -> Result<(), String> {
+        let chord_duration = self
+            .chords_set
+            .iter()
+            .find(|(id, _)| *id == time_index)
+            .map(|(_, chord)| chord.exact_duration())
+            .unwrap_or(0.0);
+        let end = (time_index as f64 + chord_duration.max(note.exact_duration())).round() as usize;
+        if end > self.size {
+            return Err(format!(
+                "Note at tick {} ends at tick {}, past the measure length of {} ticks",
+                time_index, end, self.size
+            ));
+        }
+        self.force_add_note(time_index, note);
+        Ok(())
+    }
+
+    /// Adds a note in the chord at the given time position, without checking
+    /// the capacity of the measure.
+    pub fn force_add_note(&mut self, time_index: usize, note: Note) {
         if let Some(index) = self.chords_set.iter().position(|(id, _)| *id == time_index) {
             info!("Adding note to existing chord at time index {}", time_index);
             self.chords_set[index].1.add_note(note);
-            Ok(())
         } else {
             info!("Adding new chord at time index {}", time_index);
             self.chords_set.push((time_index, Chord::default()));
             self.chords_set.last_mut().unwrap().1.add_note(note);
-            Ok(())
         }
     }
 
@@ -119,7 +188,7 @@ impl Measure {
     /// Returns the orderer vector of chords
     pub fn get_orderer_chords(&self) -> Vec<Chord> {
         let mut chords_cpy = self.chords_set.to_vec();
-        chords_cpy.sort_by(|e1, e2| e1.0.cmp(&e2.0));
+        chords_cpy.sort_by_key(|e1| e1.0);
         chords_cpy.iter().map(|e| e.1.clone()).collect()
     }
 }
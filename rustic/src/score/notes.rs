@@ -35,6 +35,43 @@ impl NoteDuration {
             Self::Large => 2048,
         }
     }
+
+    /// Duration in ticks once the given modifier is applied, rounded to the
+    /// nearest tick for tuplets that do not divide the tick grid
+    pub fn ticks(&self, modifier: &DurationModifier) -> usize {
+        self.exact_ticks(modifier).round() as usize
+    }
+
+    /// Duration in (possibly fractional) ticks once the given modifier is
+    /// applied. A triplet quaver lasts 64 / 3 ticks, so that three of them
+    /// make up a crotchet.
+    pub fn exact_ticks(&self, modifier: &DurationModifier) -> f64 {
+        let base = self.duration() as f64;
+        match modifier {
+            DurationModifier::None => base,
+            DurationModifier::Dotted => base * 1.5,
+            DurationModifier::DoubleDotted => base * 1.75,
+            DurationModifier::Tuplet { count, in_space_of } => {
+                base * *in_space_of as f64 / (*count).max(1) as f64
+            }
+        }
+    }
+
+    /// Duration in seconds at the given tempo, in crotchets per minute
+    pub fn seconds(&self, modifier: &DurationModifier, bpm: f32) -> f32 {
+        exact_ticks_to_seconds(self.exact_ticks(modifier), bpm)
+    }
+}
+
+/// Converts a tick count to seconds at the given tempo, in crotchets per minute
+pub fn ticks_to_seconds(ticks: usize, bpm: f32) -> f32 {
+    exact_ticks_to_seconds(ticks as f64, bpm)
+}
+
+/// Converts a fractional tick count to seconds at the given tempo, in
+/// crotchets per minute
+pub fn exact_ticks_to_seconds(ticks: f64, bpm: f32) -> f32 {
+    (ticks / NoteDuration::Crotchet.duration() as f64 * 60.0 / bpm as f64) as f32
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
@@ -43,6 +80,12 @@ pub enum DurationModifier {
     None,
     Dotted,
     DoubleDotted,
+    /// `count` notes played in the time of `in_space_of` notes of the same
+    /// value, e.g. 3 in the space of 2 for triplets
+    Tuplet {
+        count: u8,
+        in_space_of: u8,
+    },
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
@@ -110,14 +153,16 @@ impl Note {
     }
 
     pub fn duration(&self) -> usize {
-        match self.duration_modifier {
-            DurationModifier::None => self.duration.duration(),
-            DurationModifier::Dotted => self.duration.duration() + self.duration.duration() / 2,
-            DurationModifier::DoubleDotted => {
-                self.duration.duration()
-                    + self.duration.duration() / 2
-                    + self.duration.duration() / 4
-            }
-        }
+        self.duration.ticks(&self.duration_modifier)
+    }
+
+    /// Duration of the note in fractional ticks, exact for tuplets
+    pub fn exact_duration(&self) -> f64 {
+        self.duration.exact_ticks(&self.duration_modifier)
+    }
+
+    /// Duration of the note in seconds at the given tempo
+    pub fn seconds(&self, bpm: f32) -> f32 {
+        self.duration.seconds(&self.duration_modifier, bpm)
     }
 }
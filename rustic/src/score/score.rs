@@ -1,18 +1,25 @@
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-use super::notes::Note;
+use super::notes::{Note, NoteDuration, ticks_to_seconds};
 use super::staff::Staff;
+use crate::core::audio::Block;
 use crate::instruments::Instrument;
 
 /// A simple time signature denoted with its numerator and denominator.
+/// The numerator counts pulses, each one lasting a note of the denominator's value.
 /// ```rust
 /// use rustic::prelude::TimeSignature;
 ///
 /// // A simple 4/4 time signature
 /// let time_signature = TimeSignature(4, 4);
+///
+/// // A compound 6/8 bar lasts three crotchets, grouped as 3 + 3 quavers
+/// let compound = TimeSignature(6, 8);
+/// assert_eq!(compound.bar_ticks(), 192);
+/// assert_eq!(compound.accents(), vec![1, 4]);
 /// ```
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct TimeSignature(pub usize, pub usize);
 
 impl Default for TimeSignature {
@@ -21,8 +28,75 @@ impl Default for TimeSignature {
     }
 }
 
+/// Velocity weight of the first pulse of a bar
+const DOWNBEAT_ACCENT: f32 = 1.0;
+/// Velocity weight of the first pulse of the other beat groups
+const BEAT_ACCENT: f32 = 0.8;
+/// Velocity weight of everything else
+const WEAK_ACCENT: f32 = 0.6;
+
 impl TimeSignature {
     pub const C: TimeSignature = TimeSignature(4, 4);
+
+    /// Duration of one pulse (a note of the denominator's value) in ticks
+    pub fn pulse_ticks(&self) -> usize {
+        NoteDuration::SemiBreve.duration() / self.1.max(1)
+    }
+
+    /// Duration of a full bar in ticks
+    pub fn bar_ticks(&self) -> usize {
+        self.0 * self.pulse_ticks()
+    }
+
+    /// Whether the meter is compound (6/8, 9/8, 12/8, ...), i.e. its pulses
+    /// are grouped in threes
+    pub fn is_compound(&self) -> bool {
+        self.1 >= 8 && self.0 > 3 && self.0.is_multiple_of(3)
+    }
+
+    /// Number of pulses in each beat group of the bar.
+    /// Compound meters group pulses in threes, odd meters such as 5/8 or 7/8
+    /// use twos followed by a final three (2+3, 2+2+3) and simple meters
+    /// count each pulse as its own beat.
+    pub fn beat_groups(&self) -> Vec<usize> {
+        if self.is_compound() {
+            vec![3; self.0 / 3]
+        } else if self.1 >= 8 && self.0 > 3 && self.0 % 2 == 1 {
+            let mut groups = vec![2; (self.0 - 3) / 2];
+            groups.push(3);
+            groups
+        } else {
+            vec![1; self.0]
+        }
+    }
+
+    /// Pulses (1-based) starting a beat group, which receive an accent
+    pub fn accents(&self) -> Vec<usize> {
+        self.beat_groups()
+            .iter()
+            .scan(1, |pulse, group| {
+                let start = *pulse;
+                *pulse += group;
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// Default velocity weight for a note starting `tick` ticks into the score:
+    /// strongest on the downbeat, then on the start of each beat group.
+    pub fn accent_at(&self, tick: usize) -> f32 {
+        let pulse_ticks = self.pulse_ticks().max(1);
+        let position = tick % self.bar_ticks().max(1);
+        if position == 0 {
+            DOWNBEAT_ACCENT
+        } else if position.is_multiple_of(pulse_ticks)
+            && self.accents().contains(&(position / pulse_ticks + 1))
+        {
+            BEAT_ACCENT
+        } else {
+            WEAK_ACCENT
+        }
+    }
 }
 
 /// A music score. Has a defined time signature, tempo,
@@ -33,6 +107,10 @@ pub struct Score {
     pub signature: TimeSignature, // Time signature of the score
     pub tempo: usize,             // Tempo in bpm
     pub staves: Vec<Staff>,       // Staves of the score, contains the instruments
+    /// Accent the beat groups of simple meters too; compound meters are
+    /// always accented
+    #[serde(default)]
+    pub accents: bool,
     #[serde(skip)]
     pub instruments: Vec<Box<dyn Instrument>>,
 }
@@ -51,6 +129,7 @@ impl Score {
             tempo,
             staves,
             instruments,
+            accents: false,
         }
     }
 
@@ -74,6 +153,27 @@ impl Score {
         std::fs::write(path, content).map_err(|e| format!("Failed to write file: {}", e))
     }
 
+    /// How long the score plays, in seconds: the measures of its longest
+    /// staff at the score's tempo. An empty score lasts 0 seconds.
+    /// ```rust
+    /// use rustic::prelude::{Score, TimeSignature};
+    ///
+    /// let score = Score::new("Empty", TimeSignature(4, 4), 120, Vec::new(), Vec::new());
+    /// assert_eq!(score.duration_seconds(), 0.0);
+    /// ```
+    pub fn duration_seconds(&self) -> f32 {
+        let ticks = self
+            .staves
+            .iter()
+            .map(|staff| staff.measures_duration())
+            .max()
+            .unwrap_or(0);
+        if ticks == 0 || self.tempo == 0 {
+            return 0.0;
+        }
+        ticks_to_seconds(ticks, self.tempo as f32)
+    }
+
     /// Adds a new staff to the score and returns its index.
     /// The staff will not be associated with any instrument.
     pub fn add_staff(&mut self) -> usize {
@@ -150,13 +250,42 @@ impl Score {
         // Play the compiled score
         let result = compiled.play();
 
-        // Return instruments back to the score
+        self.restore_instruments(compiled);
+        result
+    }
+
+    /// Renders the score offline into a stereo block at the given sample rate.
+    ///
+    /// Each staff is placed in the stereo field according to its pan and gain.
+    /// The instruments are returned to the score once rendered.
+    ///
+    /// # Example
+    /// ```
+    /// use rustic::prelude::{Score, TimeSignature};
+    /// use rustic::instruments::prelude::HiHat;
+    ///
+    /// let mut score = Score::new("Demo", TimeSignature(4, 4), 120, Vec::new(), Vec::new());
+    /// score.add_instrument(Box::new(HiHat::new().unwrap()));
+    /// score.staves[0].set_pan(-0.5);
+    ///
+    /// let frames = score.render(44100.0).unwrap();
+    /// ```
+    pub fn render(&mut self, sample_rate: f32) -> Result<Block, String> {
+        use super::compiled_score::CompiledScore;
+
+        let mut compiled = CompiledScore::new(self)?;
+        let frames = compiled.render(sample_rate);
+
+        self.restore_instruments(compiled);
+        Ok(frames)
+    }
+
+    /// Returns the instruments taken by a compiled score back to the score
+    fn restore_instruments(&mut self, compiled: super::compiled_score::CompiledScore) {
         for (idx, instance) in compiled.staff_instances.into_iter().enumerate() {
             let staff = &self.staves[idx];
             let instrument_idx = staff.get_instrument();
             self.instruments[instrument_idx] = instance.take_instrument();
         }
-
-        result
     }
 }
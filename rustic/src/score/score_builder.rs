@@ -12,6 +12,7 @@ pub struct ScoreBuilder {
     name: String,
     instruments: Vec<Box<dyn Instrument>>,
     staves: Vec<Staff>,
+    accents: bool,
 }
 
 impl Default for ScoreBuilder {
@@ -22,6 +23,7 @@ impl Default for ScoreBuilder {
             name: String::from("New score"),
             instruments: Vec::new(),
             staves: Vec::new(),
+            accents: false,
         }
     }
 }
@@ -34,13 +36,15 @@ impl ScoreBuilder {
     /// Builds the ScoreBuilder into a Score. This consumes
     /// the builder
     pub fn build(self) -> Score {
-        Score::new(
+        let mut score = Score::new(
             self.name,
             self.signature,
             self.tempo,
             self.instruments,
             self.staves,
-        )
+        );
+        score.accents = self.accents;
+        score
     }
 
     /// Sets the tempo of the score in bpm
@@ -54,6 +58,12 @@ impl ScoreBuilder {
         self
     }
 
+    /// Accents the beat groups of the bar even when the meter is simple
+    pub fn accents(mut self, accents: bool) -> Self {
+        self.accents = accents;
+        self
+    }
+
     pub fn name<S: AsRef<str>>(mut self, name: S) -> Self {
         self.name = name.as_ref().to_string();
         self
@@ -1,3 +1,5 @@
+use std::f32::consts::FRAC_PI_4;
+
 use serde::{Deserialize, Serialize};
 
 use super::{
@@ -13,6 +15,12 @@ pub struct Staff {
     instrument: usize, // Instrument index in the intrument map
     measures: Vec<Measure>,
     signature: TimeSignature,
+    /// Stereo position, from -1.0 (hard left) to 1.0 (hard right)
+    #[serde(default)]
+    pan: f32,
+    /// Gain applied to the staff when rendering, in dB
+    #[serde(default)]
+    gain: f32,
 }
 
 impl Staff {
@@ -31,6 +39,32 @@ impl Staff {
         self.instrument
     }
 
+    /// Sets the stereo position of the staff, clamped to `[-1.0, 1.0]`
+    pub fn set_pan(&mut self, pan: f32) {
+        self.pan = pan.clamp(-1.0, 1.0);
+    }
+
+    pub fn get_pan(&self) -> f32 {
+        self.pan
+    }
+
+    /// Sets the gain of the staff, in dB
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+    }
+
+    pub fn get_gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Left and right channel weights of the staff: an equal-power pan law
+    /// (`cos`/`sin` of the position mapped to `[0, PI/2]`) scaled by its gain.
+    pub fn stereo_gains(&self) -> (f32, f32) {
+        let level = 10f32.powf(self.gain / 20.0);
+        let angle = (self.pan + 1.0) * FRAC_PI_4;
+        (angle.cos() * level, angle.sin() * level)
+    }
+
     pub fn add_note(&mut self, note: Note) -> Result<(), String> {
         let measure: &mut Measure =
             if let Some(next_measure_position) = self.measures.iter().position(|m| !m.is_full()) {
@@ -51,9 +85,20 @@ impl Staff {
             .collect()
     }
 
+    /// Duration of the measures of this staff in ticks. Unlike
+    /// [`total_duration`](Self::total_duration), an incomplete last bar
+    /// counts for its full length.
+    pub fn measures_duration(&self) -> usize {
+        self.measures.iter().map(|m| m.size()).sum()
+    }
+
     /// Calculates the total duration of this staff in ticks
     pub fn total_duration(&self) -> usize {
         let chords = self.get_orderer_chords();
-        chords.iter().map(|chord| chord.duration()).sum()
+        chords
+            .iter()
+            .map(|chord| chord.exact_duration())
+            .sum::<f64>()
+            .round() as usize
     }
 }
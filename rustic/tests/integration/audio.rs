@@ -6,18 +6,38 @@
 //! - AudioMessage creation and cloning
 //! - Atomic operations on shared state
 //!
-//! Note: Full integration tests with AudioHandle.shutdown() would require
-//! spawning actual audio threads, which is complex for unit testing.
-//! These tests focus on the data structures and their behavior.
+//! - Render thread lifecycle through a headless AudioHandle (no device)
+//! - Equal-power crossfade used on graph swaps
+//! - The output callback body: no allocations, diagnostics queued lock-free
 
+use crossbeam::queue::ArrayQueue;
 use rustic::Note;
-use rustic::audio::messages::InstrumentAudioMessage;
+use rustic::audio::messages::{GraphAudioMessage, InstrumentAudioMessage};
 use rustic::audio::{
-    AudioEvent, AudioMessage, BackendEvent, DiagnosticsEvent, ErrorEvent, SharedAudioState,
-    StatusEvent,
+    AudioConfig, AudioEvent, AudioHandle, AudioMessage, BackendEvent, CallbackDiagnostic,
+    Crossfade, DiagnosticsEvent, ErrorEvent, EventCategory, EventFilter, MeterReporter,
+    MetricsEvent, SharedAudioState, StatusEvent, fill_output,
 };
+use rustic::core::audio::CHANNELS;
+use rustic::core::filters::prelude::GainFilter;
+use rustic::core::graph::{ConstantSource, ExternalInputSource, SimpleSink, System};
 use rustic::core::utils::NOTES;
+use std::alloc::{GlobalAlloc, Layout};
+use std::cell::Cell;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+/// Polls `handle` until its render thread exits or `timeout` elapses.
+fn wait_for_exit(handle: &AudioHandle, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if handle.is_finished() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    handle.is_finished()
+}
 
 // ============================================================================
 // SharedAudioState Tests
@@ -219,23 +239,234 @@ fn test_backend_event_buffer_underrun() {
 
 #[test]
 fn test_backend_event_metrics() {
-    let event = BackendEvent::Diagnostics(DiagnosticsEvent::Metrics {
-        cpu_usage: 25.5,
-        latency_ms: 12.3,
+    let event = BackendEvent::Metrics(MetricsEvent {
+        callback_load: 0.25,
+        xruns: 3,
+        buffer_fill: 0.5,
     });
+    assert_eq!(event.category(), EventCategory::Diagnostics);
 
     match event {
-        BackendEvent::Diagnostics(DiagnosticsEvent::Metrics {
-            cpu_usage,
-            latency_ms,
+        BackendEvent::Metrics(MetricsEvent {
+            callback_load,
+            xruns,
+            buffer_fill,
         }) => {
-            assert!((cpu_usage - 25.5).abs() < 0.0001, "CPU usage should match");
-            assert!((latency_ms - 12.3).abs() < 0.0001, "Latency should match");
+            assert!((callback_load - 0.25).abs() < 0.0001, "Load should match");
+            assert_eq!(xruns, 3);
+            assert!((buffer_fill - 0.5).abs() < 0.0001, "Fill should match");
         }
         _ => panic!("Expected Metrics event"),
     }
 }
 
+#[test]
+fn test_meter_reporter_rate_and_levels() {
+    // Mock render loop: one second of 512-frame blocks of a 0.5 amplitude sine
+    let sample_rate = 44100;
+    let mut meter = MeterReporter::new(sample_rate, 30.0);
+    let mut events = Vec::new();
+    let mut t = 0usize;
+    while t < sample_rate as usize {
+        let block: Vec<[f32; 2]> = (t..t + 512)
+            .map(|i| {
+                let s = 0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0).sin();
+                [s, s * 0.5]
+            })
+            .collect();
+        t += block.len();
+        if let Some(levels) = meter.observe(&block) {
+            events.push(BackendEvent::Meter(levels));
+        }
+    }
+
+    assert!(
+        (28..=31).contains(&events.len()),
+        "expected ~30 meter events, got {}",
+        events.len()
+    );
+    for event in &events {
+        assert_eq!(event.category(), EventCategory::Meter);
+        let BackendEvent::Meter(levels) = event else {
+            panic!("Expected Meter event");
+        };
+        assert!(
+            (levels.peak[0] - 0.5).abs() < 0.01,
+            "peak {:?}",
+            levels.peak
+        );
+        assert!((levels.peak[1] - 0.25).abs() < 0.01);
+        assert!(
+            (levels.rms[0] - 0.5 / 2f32.sqrt()).abs() < 0.01,
+            "rms {:?}",
+            levels.rms
+        );
+        assert!((levels.rms[1] - 0.25 / 2f32.sqrt()).abs() < 0.01);
+    }
+
+    // Meter events are opt-in
+    assert!(!EventFilter::default().allows(EventCategory::Meter));
+}
+
+#[test]
+fn test_shared_audio_state_callback_metrics() {
+    let state = SharedAudioState::new();
+    state.sample_rate.store(48000, Ordering::Relaxed);
+
+    // 480 frames at 48 kHz is a 10 ms buffer; spending 5 ms is a 50% load
+    for _ in 0..200 {
+        state.record_callback(std::time::Duration::from_millis(5), 480, false);
+    }
+    let load = state.callback_load.load(Ordering::Relaxed);
+    assert!((load - 0.5).abs() < 1e-3, "load {load}");
+    assert_eq!(state.buffer_underruns.load(Ordering::Relaxed), 0);
+
+    // An underrun increments the xrun counter
+    state.record_callback(std::time::Duration::from_millis(1), 480, true);
+    assert_eq!(state.buffer_underruns.load(Ordering::Relaxed), 1);
+    assert!(state.callback_load.load(Ordering::Relaxed) < load);
+
+    let event = BackendEvent::Metrics(MetricsEvent {
+        callback_load: load,
+        xruns: 1,
+        buffer_fill: 0.5,
+    });
+    assert_eq!(event.category(), EventCategory::Diagnostics);
+}
+
+// ============================================================================
+// Output Callback Tests
+// ============================================================================
+
+/// Counts heap allocations made by the current thread, so the callback body
+/// can be checked for allocations without interference from other tests.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_fill_output_does_not_allocate() {
+    let queue = ArrayQueue::new(1024);
+    let state = SharedAudioState::new();
+    for i in 0..512 {
+        queue.push(i as f32).unwrap();
+    }
+    let mut data = [0.0f32; 256];
+
+    let before = allocations();
+    // Two full reads, then an underrun that queues a diagnostic
+    fill_output(&mut data, &queue, &state);
+    fill_output(&mut data, &queue, &state);
+    fill_output(&mut data, &queue, &state);
+    assert_eq!(allocations(), before, "Callback body must not allocate");
+
+    assert_eq!(data[0], 0.0, "Underrun should output silence");
+    assert_eq!(state.buffer_underruns.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_fill_output_reports_underrun_diagnostic() {
+    let queue = ArrayQueue::new(1024);
+    let state = SharedAudioState::new();
+    for _ in 0..100 {
+        queue.push(0.5).unwrap();
+    }
+    let mut data = [0.0f32; 256];
+    fill_output(&mut data, &queue, &state);
+
+    assert_eq!(&data[..100], &[0.5; 100]);
+    assert_eq!(
+        state.diagnostics.pop(),
+        Some(CallbackDiagnostic::Underrun {
+            requested: 256,
+            available: 100
+        })
+    );
+}
+
+#[test]
+fn test_callback_diagnostics_drop_when_full() {
+    let state = SharedAudioState::new();
+    let underrun = CallbackDiagnostic::Underrun {
+        requested: 64,
+        available: 0,
+    };
+    for _ in 0..state.diagnostics.capacity() + 3 {
+        state.report(underrun);
+    }
+    assert_eq!(state.diagnostics.len(), state.diagnostics.capacity());
+    assert_eq!(state.dropped_diagnostics.load(Ordering::Relaxed), 3);
+}
+
+// ============================================================================
+// Crossfade Tests
+// ============================================================================
+
+#[test]
+fn test_crossfade_is_continuous_across_swap() {
+    // A hard switch from +0.8 to -0.8 would jump by 1.6 in one sample
+    let mut crossfade = Crossfade::from_millis(10.0, 44100);
+    let mut output = vec![[0.8; CHANNELS]; 64];
+    for _ in 0..10 {
+        let mut block = vec![[-0.8; CHANNELS]; 64];
+        crossfade.mix_into(&[[0.8; CHANNELS]; 64], &mut block);
+        output.extend(block);
+    }
+    output.extend(vec![[-0.8; CHANNELS]; 64]);
+
+    let max_step = output
+        .windows(2)
+        .map(|pair| (pair[1][0] - pair[0][0]).abs())
+        .fold(0.0, f32::max);
+    assert!(max_step < 0.01, "discontinuity of {max_step}");
+    assert!(crossfade.is_finished());
+}
+
+#[test]
+fn test_crossfade_is_equal_power() {
+    let mut crossfade = Crossfade::new(100);
+    let mut block = vec![[1.0, 0.0]; 100];
+    crossfade.mix_into(&[[0.0, 1.0]; 100], &mut block);
+
+    for frame in &block {
+        let power = frame[0] * frame[0] + frame[1] * frame[1];
+        assert!((power - 1.0).abs() < 1e-4, "power {power}");
+    }
+}
+
+#[test]
+fn test_crossfade_pads_shorter_incoming_block() {
+    // Swapping to a silent graph: the outgoing block still fades out
+    let mut crossfade = Crossfade::new(8);
+    let mut block = Vec::new();
+    crossfade.mix_into(&[[1.0; CHANNELS]; 4], &mut block);
+
+    assert_eq!(block.len(), 4);
+    assert_eq!(block[0], [1.0; CHANNELS]);
+    assert!(block.windows(2).all(|pair| pair[1][0] < pair[0][0]));
+    assert!(!crossfade.is_finished());
+}
+
 #[test]
 fn test_backend_event_clone() {
     let original = BackendEvent::Status(StatusEvent::AudioStarted { sample_rate: 44100 });
@@ -337,6 +568,225 @@ fn test_audiomessage_shutdown() {
     }
 }
 
+#[test]
+fn test_render_thread_exits_on_shutdown_message() {
+    let (handle, message_tx, _events, _output) = AudioHandle::spawn_headless(
+        System::silent(),
+        AudioConfig::default(),
+        EventFilter::none(),
+    );
+    assert!(!handle.is_finished(), "Render thread should be running");
+
+    message_tx.send(AudioMessage::Shutdown).unwrap();
+    assert!(
+        wait_for_exit(&handle, Duration::from_secs(2)),
+        "Render thread should exit after Shutdown"
+    );
+    assert!(handle.shared_state().shutdown.load(Ordering::Relaxed));
+    assert!(handle.shutdown().is_ok());
+}
+
+#[test]
+fn test_render_thread_exits_when_channel_closes() {
+    let (handle, message_tx, _events, _output) = AudioHandle::spawn_headless(
+        System::silent(),
+        AudioConfig::default(),
+        EventFilter::none(),
+    );
+
+    drop(message_tx);
+    assert!(
+        wait_for_exit(&handle, Duration::from_secs(2)),
+        "Render thread should exit once every sender is dropped"
+    );
+    assert!(handle.shutdown().is_ok());
+}
+
+#[test]
+fn test_audio_handle_shutdown_joins_render_thread() {
+    let (handle, _message_tx, _events, _output) = AudioHandle::spawn_headless(
+        System::silent(),
+        AudioConfig::default(),
+        EventFilter::none(),
+    );
+    assert!(handle.shutdown().is_ok());
+}
+
+/// Builds external input → gain → sink, which renders (silent) blocks forever.
+fn build_rendering_system() -> System {
+    let (input, _handle) = ExternalInputSource::new(64);
+    let mut system = System::new();
+    let gain = system.add_filter(Box::new(GainFilter::new(1.0)));
+    let source = system.add_source(Box::new(input));
+    let sink = system.add_sink(Box::new(SimpleSink::new()));
+    system.connect_source(source, gain, 0);
+    system.connect_sink(gain, sink, 0);
+    system.compute().unwrap();
+    system
+}
+
+#[test]
+fn test_render_thread_idles_when_queue_full() {
+    let config = AudioConfig::default();
+    let target = config.calculate_ring_buffer_size(44100) * 2;
+    let (handle, message_tx, _events, output) =
+        AudioHandle::spawn_headless(build_rendering_system(), config, EventFilter::none());
+
+    // Nothing drains the queue, so it fills up to the target latency
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while output.len() < target && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(
+        output.len() >= target,
+        "Render thread should fill the queue"
+    );
+
+    // A blocked (not sleeping in a poll loop) thread wakes on the message itself
+    let sent = Instant::now();
+    message_tx.send(AudioMessage::Shutdown).unwrap();
+    assert!(wait_for_exit(&handle, Duration::from_millis(500)));
+    assert!(sent.elapsed() < Duration::from_millis(500));
+    assert!(handle.shutdown().is_ok());
+}
+
+/// Builds constant → sink, rendering `value` on both channels.
+fn build_constant_system(value: f32) -> System {
+    let mut system = System::new().with_block_size(64);
+    let gain = system.add_filter(Box::new(GainFilter::new(1.0)));
+    let source = system.add_source(Box::new(ConstantSource::new(value)));
+    let sink = system.add_sink(Box::new(SimpleSink::new()));
+    system.connect_source(source, gain, 0);
+    system.connect_sink(gain, sink, 0);
+    system.compute().unwrap();
+    system
+}
+
+#[test]
+fn test_second_swap_during_a_fade_is_continuous() {
+    let config = AudioConfig::default();
+    let target = config.calculate_ring_buffer_size(44100) * 2;
+    let (handle, message_tx, _events, output) =
+        AudioHandle::spawn_headless(build_constant_system(0.5), config, EventFilter::none());
+
+    // Wait for the queue to fill so both swaps land before the same block
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while output.len() < target && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    message_tx
+        .send(AudioMessage::Graph(GraphAudioMessage::Swap(
+            build_constant_system(-0.5),
+        )))
+        .unwrap();
+    message_tx
+        .send(AudioMessage::Graph(GraphAudioMessage::Swap(
+            build_constant_system(0.25),
+        )))
+        .unwrap();
+
+    // Drain past the end of both fades
+    let mut samples = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while samples.len() < 2 * target && Instant::now() < deadline {
+        while let Some(sample) = output.pop() {
+            samples.push(sample);
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    message_tx.send(AudioMessage::Shutdown).unwrap();
+    assert!(handle.shutdown().is_ok());
+    let left: Vec<f32> = samples.iter().step_by(CHANNELS).copied().collect();
+
+    // Dropping the graph still fading out would jump from 0.5 to -0.5
+    assert!((left[0] - 0.5).abs() < 1e-3, "started at {}", left[0]);
+    assert!((left[left.len() - 1] - 0.25).abs() < 1e-3);
+    let max_step = left
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).abs())
+        .fold(0.0, f32::max);
+    assert!(max_step < 0.01, "discontinuity of {max_step}");
+}
+
+#[test]
+fn test_silent_render_thread_does_not_spin() {
+    let (handle, message_tx, events, _output) =
+        AudioHandle::spawn_headless(System::silent(), AudioConfig::default(), EventFilter::all());
+
+    std::thread::sleep(Duration::from_millis(50));
+    // A spinning loop would broadcast a flood of empty chunks
+    assert_eq!(
+        events
+            .try_iter()
+            .filter(|e| matches!(e, BackendEvent::Audio(AudioEvent::Chunk(_))))
+            .count(),
+        0,
+        "Silent system should not broadcast chunks"
+    );
+
+    message_tx.send(AudioMessage::Shutdown).unwrap();
+    assert!(wait_for_exit(&handle, Duration::from_millis(500)));
+    assert!(handle.shutdown().is_ok());
+}
+
+#[test]
+fn test_render_thread_applies_message_burst_within_one_block() {
+    let config = AudioConfig::default();
+    let target = config.calculate_ring_buffer_size(44100) * 2;
+
+    let mut system = System::new().with_block_size(64);
+    let gain = system.add_filter(Box::new(GainFilter::new(0.0)));
+    let source = system.add_source(Box::new(ConstantSource::new(0.5)));
+    let sink = system.add_sink(Box::new(SimpleSink::new()));
+    system.connect_source(source, gain, 0);
+    system.connect_sink(gain, sink, 0);
+    system.compute().unwrap();
+
+    let (handle, message_tx, _events, output) =
+        AudioHandle::spawn_headless(system, config, EventFilter::none());
+
+    // Wait for the queue to fill so the burst lands between two blocks
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while output.len() < target && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(
+        output.len() >= target,
+        "Render thread should fill the queue"
+    );
+
+    for i in 1..=100 {
+        message_tx
+            .send(AudioMessage::Graph(GraphAudioMessage::SetParameter {
+                node_index: gain.index(),
+                param_name: "factor".to_string(),
+                value: i as f32 / 100.0,
+            }))
+            .unwrap();
+    }
+
+    // Drain the queue like a callback would: every sample is either from
+    // before the burst (0.0) or after all 100 messages were applied (0.5)
+    let mut rendered_after = 0;
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while rendered_after < 64 * CHANNELS && Instant::now() < deadline {
+        while let Some(sample) = output.pop() {
+            assert!(
+                sample == 0.0 || (sample - 0.5).abs() < 1e-6,
+                "Sample {sample} rendered with a partially applied burst"
+            );
+            if sample != 0.0 {
+                rendered_after += 1;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(rendered_after >= 64 * CHANNELS, "Burst should take effect");
+
+    drop(message_tx);
+    assert!(handle.shutdown().is_ok());
+}
+
 #[test]
 fn test_audiomessage_clone() {
     // Test that AudioMessage can be cloned
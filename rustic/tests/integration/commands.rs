@@ -6,13 +6,16 @@
 //! - Velocity validation via App::note_on()
 //! - InstrumentAudioMessage field structure
 //! - AudioMessage cloning and debug
+//! - Graph edits sent before App::start()
+//! - Graph topology reported by App::graph_topology()
+//! - Undo/redo of graph edits
 
 use rustic::Note;
-use rustic::app::commands::AudioCommand;
+use rustic::app::commands::{AudioCommand, GraphCommand, NodeKind, TopologyEdge};
 use rustic::audio::AudioMessage;
 use rustic::audio::messages::InstrumentAudioMessage;
 use rustic::core::utils::NOTES;
-use rustic::prelude::App;
+use rustic::prelude::{App, Command};
 
 // AudioCommand struct tests
 
@@ -145,3 +148,323 @@ fn test_audiomessage_clone() {
     let cloned = original.clone();
     assert_eq!(format!("{:?}", original), format!("{:?}", cloned));
 }
+
+// Graph commands
+
+fn add_node(app: &App, id: u64, node_type: &str, kind: NodeKind) {
+    app.send(Command::Graph(GraphCommand::AddNode {
+        id,
+        node_type: node_type.to_string(),
+        kind,
+        position: (0.0, 0.0),
+    }))
+    .unwrap();
+}
+
+fn connect(app: &App, from: u64, from_port: usize, to: u64, to_port: usize) {
+    app.send(Command::Graph(GraphCommand::Connect {
+        from,
+        from_port,
+        to,
+        to_port,
+    }))
+    .unwrap();
+}
+
+#[test]
+fn test_graph_edits_before_start() {
+    let app = App::new();
+    add_node(&app, 1, "sine", NodeKind::Generator);
+    add_node(&app, 2, "GainFilter", NodeKind::Filter);
+    add_node(&app, 3, "sink", NodeKind::Sink);
+    connect(&app, 1, 0, 2, 0);
+    connect(&app, 2, 0, 3, 0);
+    app.send(Command::Graph(GraphCommand::SetParameter {
+        node_id: 2,
+        param_name: "factor".to_string(),
+        value: 0.5,
+    }))
+    .unwrap();
+
+    // Playback control and compiling need the render thread
+    assert!(
+        app.send(Command::Graph(GraphCommand::StartNode { id: 1 }))
+            .is_err()
+    );
+    assert!(app.send(Command::Graph(GraphCommand::Compile)).is_err());
+}
+
+// Graph topology
+
+fn edge(from: u64, to: u64) -> TopologyEdge {
+    TopologyEdge {
+        from,
+        from_port: 0,
+        to,
+        to_port: 0,
+    }
+}
+
+#[test]
+fn test_graph_topology_lists_nodes_and_edges() {
+    let app = App::new();
+    add_node(&app, 1, "sine", NodeKind::Generator);
+    add_node(&app, 2, "LowPassFilter", NodeKind::Filter);
+    add_node(&app, 3, "GainFilter", NodeKind::Filter);
+    add_node(&app, 4, "sink", NodeKind::Sink);
+    add_node(&app, 5, "sine", NodeKind::Generator);
+    connect(&app, 1, 0, 2, 0);
+    connect(&app, 2, 0, 3, 0);
+    connect(&app, 3, 0, 4, 0);
+    connect(&app, 5, 0, 4, 0);
+    app.send(Command::Graph(GraphCommand::SetParameter {
+        node_id: 2,
+        param_name: "cutoff_frequency".to_string(),
+        value: 500.0,
+    }))
+    .unwrap();
+    app.send(Command::Graph(GraphCommand::SetParameter {
+        node_id: 5,
+        param_name: "frequency".to_string(),
+        value: 220.0,
+    }))
+    .unwrap();
+
+    let topology = app.graph_topology();
+
+    let nodes: Vec<(u64, NodeKind, &str)> = topology
+        .nodes
+        .iter()
+        .map(|node| (node.id, node.kind, node.node_type.as_str()))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            (1, NodeKind::Generator, "sine"),
+            (2, NodeKind::Filter, "LowPassFilter"),
+            (3, NodeKind::Filter, "GainFilter"),
+            (4, NodeKind::Sink, "sink"),
+            (5, NodeKind::Generator, "sine"),
+        ]
+    );
+    assert_eq!(topology.nodes[1].parameters["cutoff_frequency"], 500.0);
+    assert!(topology.nodes[2].parameters.contains_key("factor"));
+    assert_eq!(topology.nodes[4].parameters["frequency"], 220.0);
+
+    assert_eq!(
+        topology.edges,
+        vec![edge(1, 2), edge(2, 3), edge(3, 4), edge(5, 4)]
+    );
+}
+
+#[test]
+fn test_graph_connect_refuses_loop_without_delay() {
+    let app = App::new();
+    add_node(&app, 1, "GainFilter", NodeKind::Filter);
+    add_node(&app, 2, "GainFilter", NodeKind::Filter);
+    connect(&app, 1, 0, 2, 0);
+
+    let error = app
+        .send(Command::Graph(GraphCommand::Connect {
+            from: 2,
+            from_port: 0,
+            to: 1,
+            to_port: 0,
+        }))
+        .unwrap_err();
+    assert!(error.to_string().contains("delay"), "{error}");
+    assert_eq!(app.graph_topology().edges, vec![edge(1, 2)]);
+
+    // Nothing was recorded for the refused edit
+    undo(&app);
+    assert!(app.graph_topology().edges.is_empty());
+}
+
+#[test]
+fn test_graph_topology_follows_removed_nodes() {
+    let app = App::new();
+    add_node(&app, 1, "sine", NodeKind::Generator);
+    add_node(&app, 2, "GainFilter", NodeKind::Filter);
+    add_node(&app, 3, "LowPassFilter", NodeKind::Filter);
+    add_node(&app, 4, "sink", NodeKind::Sink);
+    connect(&app, 1, 0, 3, 0);
+    connect(&app, 3, 0, 4, 0);
+
+    // Removing the first filter moves the last one into its graph index
+    app.send(Command::Graph(GraphCommand::RemoveNode { id: 2 }))
+        .unwrap();
+
+    let topology = app.graph_topology();
+    let ids: Vec<u64> = topology.nodes.iter().map(|node| node.id).collect();
+    assert_eq!(ids, vec![1, 3, 4]);
+    assert_eq!(topology.nodes[1].node_type, "LowPassFilter");
+    assert!(
+        topology.nodes[1]
+            .parameters
+            .contains_key("cutoff_frequency")
+    );
+    assert_eq!(topology.edges, vec![edge(1, 3), edge(3, 4)]);
+}
+
+// Graph edit history
+
+fn undo(app: &App) {
+    app.send(Command::Graph(GraphCommand::Undo)).unwrap();
+}
+
+fn redo(app: &App) {
+    app.send(Command::Graph(GraphCommand::Redo)).unwrap();
+}
+
+/// sine(1) → LowPassFilter(2) → sink(3)
+fn chain() -> App {
+    let app = App::new();
+    add_node(&app, 1, "sine", NodeKind::Generator);
+    add_node(&app, 2, "LowPassFilter", NodeKind::Filter);
+    add_node(&app, 3, "sink", NodeKind::Sink);
+    connect(&app, 1, 0, 2, 0);
+    connect(&app, 2, 0, 3, 0);
+    app
+}
+
+#[test]
+fn test_undo_add_node_restores_topology() {
+    let app = chain();
+    let original = app.graph_topology();
+
+    add_node(&app, 4, "GainFilter", NodeKind::Filter);
+    assert_eq!(app.graph_topology().nodes.len(), 4);
+
+    undo(&app);
+    assert_eq!(app.graph_topology(), original);
+
+    redo(&app);
+    let ids: Vec<u64> = app.graph_topology().nodes.iter().map(|n| n.id).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_undo_remove_node_restores_parameters_and_connections() {
+    let app = chain();
+    app.send(Command::Graph(GraphCommand::SetParameter {
+        node_id: 2,
+        param_name: "cutoff_frequency".to_string(),
+        value: 750.0,
+    }))
+    .unwrap();
+    let original = app.graph_topology();
+
+    app.send(Command::Graph(GraphCommand::RemoveNode { id: 2 }))
+        .unwrap();
+    assert!(app.graph_topology().edges.is_empty());
+
+    undo(&app);
+    assert_eq!(app.graph_topology(), original);
+}
+
+#[test]
+fn test_undo_connect_and_disconnect() {
+    let app = chain();
+    let original = app.graph_topology();
+
+    disconnect(&app, 2, 0, 3, 0);
+    assert_eq!(app.graph_topology().edges, vec![edge(1, 2)]);
+    undo(&app);
+    assert_eq!(app.graph_topology(), original);
+
+    add_node(&app, 4, "sink", NodeKind::Sink);
+    connect(&app, 2, 0, 4, 0);
+    undo(&app);
+    undo(&app);
+    assert_eq!(app.graph_topology(), original);
+}
+
+fn disconnect(app: &App, from: u64, from_port: usize, to: u64, to_port: usize) {
+    app.send(Command::Graph(GraphCommand::Disconnect {
+        from,
+        from_port,
+        to,
+        to_port,
+    }))
+    .unwrap();
+}
+
+fn port_edge(from: u64, from_port: usize, to: u64) -> TopologyEdge {
+    TopologyEdge {
+        from,
+        from_port,
+        to,
+        to_port: 0,
+    }
+}
+
+#[test]
+fn test_undo_redo_on_multi_port_node() {
+    // sine(1) → EnvelopeFollower(2), whose two outputs both feed Gain(3) → sink(4)
+    let app = App::new();
+    add_node(&app, 1, "sine", NodeKind::Generator);
+    add_node(&app, 2, "EnvelopeFollower", NodeKind::Filter);
+    add_node(&app, 3, "GainFilter", NodeKind::Filter);
+    add_node(&app, 4, "sink", NodeKind::Sink);
+    connect(&app, 1, 0, 2, 0);
+    connect(&app, 2, 0, 3, 0);
+    connect(&app, 3, 0, 4, 0);
+    let single = app.graph_topology();
+
+    // Undoing the second pipe leaves the first one in place
+    connect(&app, 2, 1, 3, 0);
+    let both = app.graph_topology();
+    assert_eq!(
+        both.edges,
+        vec![
+            edge(1, 2),
+            port_edge(2, 0, 3),
+            port_edge(2, 1, 3),
+            edge(3, 4)
+        ]
+    );
+    undo(&app);
+    assert_eq!(app.graph_topology(), single);
+    redo(&app);
+    assert_eq!(app.graph_topology(), both);
+
+    // Undoing the removal of one pipe restores that pipe only
+    disconnect(&app, 2, 0, 3, 0);
+    let without_first = app.graph_topology();
+    assert_eq!(
+        without_first.edges,
+        vec![edge(1, 2), port_edge(2, 1, 3), edge(3, 4)]
+    );
+    undo(&app);
+    assert_eq!(app.graph_topology(), both);
+    redo(&app);
+    assert_eq!(app.graph_topology(), without_first);
+
+    // Same with the two outputs wired to a sink
+    connect(&app, 2, 0, 4, 0);
+    connect(&app, 2, 1, 4, 0);
+    let wired = app.graph_topology();
+    disconnect(&app, 2, 1, 4, 0);
+    assert!(!app.graph_topology().edges.contains(&port_edge(2, 1, 4)));
+    assert!(app.graph_topology().edges.contains(&port_edge(2, 0, 4)));
+    undo(&app);
+    assert_eq!(app.graph_topology(), wired);
+}
+
+#[test]
+fn test_new_edit_clears_redo() {
+    let app = chain();
+    add_node(&app, 4, "GainFilter", NodeKind::Filter);
+    undo(&app);
+    add_node(&app, 5, "sine", NodeKind::Generator);
+    let after_edit = app.graph_topology();
+
+    redo(&app);
+    assert_eq!(app.graph_topology(), after_edit);
+
+    // Undoing past the start of the history does nothing
+    for _ in 0..10 {
+        undo(&app);
+    }
+    assert!(app.graph_topology().nodes.is_empty());
+}
@@ -5,8 +5,10 @@
 //! - TOML serialization/deserialization
 //! - Configuration validation
 //! - File loading and missing field handling
+//! - Stream configuration resolution against supported device configs
 
-use rustic::audio::{AudioConfig, LogConfig};
+use cpal::{BufferSize, SampleFormat, SampleRate, SupportedBufferSize, SupportedStreamConfigRange};
+use rustic::audio::{AudioConfig, LogConfig, resolve_stream_config};
 use std::io::Write;
 
 // ============================================================================
@@ -157,6 +159,9 @@ fn test_audioconfig_toml_serialization_roundtrip() {
         audio_ring_buffer_size: 44100,
         message_ring_buffer_size: 2048,
         target_latency_ms: 100.0,
+        crossfade_ms: 5.0,
+        device_name: Some("Speakers".to_string()),
+        sample_rate: Some(48000),
     };
 
     // Serialize to TOML
@@ -178,6 +183,9 @@ fn test_audioconfig_toml_serialization_roundtrip() {
         deserialized.message_ring_buffer_size
     );
     assert_eq!(original.target_latency_ms, deserialized.target_latency_ms);
+    assert_eq!(original.crossfade_ms, deserialized.crossfade_ms);
+    assert_eq!(original.device_name, deserialized.device_name);
+    assert_eq!(original.sample_rate, deserialized.sample_rate);
 }
 
 #[test]
@@ -237,6 +245,78 @@ fn test_audioconfig_load_from_file() {
     std::fs::remove_file(&config_file).expect("Failed to remove temp file");
 }
 
+// ============================================================================
+// Stream Config Resolution Tests
+// ============================================================================
+
+fn range(channels: u16, min: u32, max: u32, format: SampleFormat) -> SupportedStreamConfigRange {
+    SupportedStreamConfigRange::new(
+        channels,
+        SampleRate(min),
+        SampleRate(max),
+        SupportedBufferSize::Range { min: 32, max: 4096 },
+        format,
+    )
+}
+
+#[test]
+fn test_resolve_stream_config_defaults_to_44100_stereo() {
+    let supported = [
+        range(1, 8000, 96000, SampleFormat::F32),
+        range(2, 8000, 96000, SampleFormat::I16),
+        range(2, 8000, 96000, SampleFormat::F32),
+    ];
+    let resolved = resolve_stream_config(&supported, &AudioConfig::default()).unwrap();
+
+    assert_eq!(resolved.channels, 2);
+    assert_eq!(resolved.sample_rate, SampleRate(44100));
+    assert_eq!(resolved.buffer_size, BufferSize::Fixed(64));
+}
+
+#[test]
+fn test_resolve_stream_config_prefers_range_with_requested_rate() {
+    let supported = [
+        range(2, 44100, 44100, SampleFormat::F32),
+        range(2, 48000, 48000, SampleFormat::F32),
+    ];
+    let config = AudioConfig {
+        sample_rate: Some(48000),
+        ..Default::default()
+    };
+    let resolved = resolve_stream_config(&supported, &config).unwrap();
+
+    assert_eq!(resolved.sample_rate, SampleRate(48000));
+}
+
+#[test]
+fn test_resolve_stream_config_falls_back_on_unsupported_rate() {
+    let supported = [range(2, 44100, 48000, SampleFormat::F32)];
+    let config = AudioConfig {
+        sample_rate: Some(192000),
+        ..Default::default()
+    };
+    let resolved = resolve_stream_config(&supported, &config).unwrap();
+
+    assert_eq!(resolved.sample_rate, SampleRate(48000));
+}
+
+#[test]
+fn test_resolve_stream_config_falls_back_on_unsupported_buffer_size() {
+    let supported = [range(2, 44100, 48000, SampleFormat::F32)];
+    let config = AudioConfig {
+        cpal_buffer_size: 16,
+        ..Default::default()
+    };
+    let resolved = resolve_stream_config(&supported, &config).unwrap();
+
+    assert_eq!(resolved.buffer_size, BufferSize::Default);
+}
+
+#[test]
+fn test_resolve_stream_config_empty() {
+    assert!(resolve_stream_config(&[], &AudioConfig::default()).is_none());
+}
+
 // ============================================================================
 // LogConfig Tests
 // ============================================================================
@@ -1,8 +1,18 @@
+use rand::{Rng, SeedableRng, rngs::SmallRng};
 use rustic::core::{
-    CHANNELS, Frame,
-    audio::{mono_to_frame, silent_block},
+    Block, CHANNELS, Frame,
+    audio::{
+        LevelMeter, MIN_DB, add_block, add_block_scalar, amplitude_to_db, mono_to_frame,
+        scale_block, scale_block_scalar, silent_block, true_peak,
+    },
 };
 
+fn random_block(rng: &mut SmallRng, len: usize) -> Block {
+    (0..len)
+        .map(|_| std::array::from_fn(|_| rng.gen_range(-1.0..1.0)))
+        .collect()
+}
+
 #[test]
 fn test_mono_to_frame() {
     let mono_sample: f32 = 12.0;
@@ -20,3 +30,127 @@ fn test_silent_block() {
         assert_eq!(*block, [0.0; CHANNELS]);
     }
 }
+
+#[test]
+fn test_amplitude_to_db() {
+    assert!(amplitude_to_db(1.0).abs() < 1e-6);
+    assert!((amplitude_to_db(0.5) + 6.0206).abs() < 1e-3);
+    assert_eq!(amplitude_to_db(0.0), MIN_DB);
+}
+
+#[test]
+fn test_level_meter_rms_tracks_window() {
+    // 100-frame window at 1 kHz
+    let mut meter = LevelMeter::new(1000.0, 0.1);
+
+    // Half the window at 0.5 on the left channel only
+    meter.process(&vec![[0.5, 0.0]; 50]);
+    let rms = meter.rms();
+    assert!((rms[0] - (0.125f32).sqrt()).abs() < 1e-4, "rms {}", rms[0]);
+    assert_eq!(rms[1], 0.0);
+    assert_eq!(meter.rms_db()[1], MIN_DB);
+
+    // A full window of a constant value gives that value
+    meter.process(&vec![[0.5, -0.25]; 100]);
+    let rms = meter.rms();
+    assert!((rms[0] - 0.5).abs() < 1e-4);
+    assert!((rms[1] - 0.25).abs() < 1e-4);
+    assert!((meter.rms_db()[0] + 6.0206).abs() < 1e-2);
+
+    // Silence slides the signal out of the window
+    meter.process(&silent_block(100));
+    assert!(meter.rms()[0] < 1e-4);
+}
+
+#[test]
+fn test_level_meter_peak_hold_and_decay() {
+    // 100 ms hold, 20 dB/s decay at 1 kHz
+    let mut meter = LevelMeter::new(1000.0, 0.05).with_ballistics(1000.0, 0.1, 20.0);
+
+    meter.process(&[[0.8, -1.0]]);
+    assert_eq!(meter.peak(), [0.8, 1.0]);
+
+    // Within the hold time the peak stays put
+    meter.process(&silent_block(100));
+    assert_eq!(meter.peak(), [0.8, 1.0]);
+
+    // After one second of decay the peak dropped by 20 dB
+    meter.process(&silent_block(1000));
+    let peak_db = meter.peak_db();
+    assert!((peak_db[1] + 20.0).abs() < 0.1, "peak {} dB", peak_db[1]);
+    assert!((peak_db[0] - (amplitude_to_db(0.8) - 20.0)).abs() < 0.1);
+
+    // A louder sample resets the hold
+    meter.process(&[[0.9, 0.0]]);
+    assert_eq!(meter.peak()[0], 0.9);
+
+    meter.reset();
+    assert_eq!(meter.peak(), [0.0; CHANNELS]);
+}
+
+#[test]
+fn test_block_kernels_match_scalar() {
+    let mut rng = SmallRng::seed_from_u64(864);
+    // Odd lengths exercise the remainder of the vectorized loops
+    for len in [0, 1, 3, 4, 7, 64, 509, 4096] {
+        let block = random_block(&mut rng, len);
+        let factor = rng.gen_range(-2.0..2.0);
+        let fast = scale_block(&block, factor);
+        let reference = scale_block_scalar(&block, factor);
+        assert_eq!(fast.len(), len);
+        for (f, r) in fast.iter().zip(&reference) {
+            for ch in 0..CHANNELS {
+                assert!((f[ch] - r[ch]).abs() <= f32::EPSILON, "{f:?} != {r:?}");
+            }
+        }
+
+        let other = random_block(&mut rng, len);
+        let mut fast = block.clone();
+        let mut reference = block.clone();
+        add_block(&mut fast, &other);
+        add_block_scalar(&mut reference, &other);
+        for (f, r) in fast.iter().zip(&reference) {
+            for ch in 0..CHANNELS {
+                assert!((f[ch] - r[ch]).abs() <= f32::EPSILON, "{f:?} != {r:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_add_block_stops_at_shortest() {
+    let mut acc = vec![[1.0; CHANNELS]; 5];
+    add_block(&mut acc, &[[0.5; CHANNELS]; 3]);
+    assert_eq!(acc[..3], [[1.5; CHANNELS]; 3]);
+    assert_eq!(acc[3..], [[1.0; CHANNELS]; 2]);
+}
+
+#[test]
+fn test_true_peak_finds_inter_sample_overs() {
+    // A full-scale sine at a quarter of the sample rate, sampled 45° off its
+    // peaks: every sample is at ±0.707 while the waveform reaches ±1.0
+    let sample_rate = 48_000;
+    let samples: Vec<f32> = (0..4800)
+        .map(|i| (std::f32::consts::FRAC_PI_2 * i as f32 + std::f32::consts::FRAC_PI_4).sin())
+        .collect();
+    let sample_peak = amplitude_to_db(samples.iter().fold(0.0_f32, |p, s| p.max(s.abs())));
+    let peak = true_peak(&samples, sample_rate);
+    assert!((sample_peak + 3.01).abs() < 0.01, "{sample_peak}");
+    assert!(peak > sample_peak + 2.5, "true peak {peak} dBTP");
+    assert!(peak.abs() < 0.3, "true peak {peak} dBTP");
+
+    // Near Nyquist, a short burst whose samples straddle its peaks
+    let samples: Vec<f32> = (0..64)
+        .map(|i| (2.0 * std::f32::consts::PI * 19_000.0 * (i as f32 + 0.5) / 44_100.0).sin())
+        .collect();
+    let sample_peak = amplitude_to_db(samples.iter().fold(0.0_f32, |p, s| p.max(s.abs())));
+    assert!(true_peak(&samples, 44_100) > sample_peak);
+}
+
+#[test]
+fn test_true_peak_of_silence_and_high_rates() {
+    assert_eq!(true_peak(&[], 48_000), MIN_DB);
+    assert_eq!(true_peak(&[0.0; 16], 48_000), MIN_DB);
+    // No oversampling from 192 kHz: the sample peak
+    assert!((true_peak(&[0.5, -0.25], 192_000) - amplitude_to_db(0.5)).abs() < 1e-6);
+}
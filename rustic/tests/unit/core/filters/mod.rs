@@ -10,6 +10,103 @@ fn const_block(n: usize, v: f32) -> Arc<Block> {
     Arc::new(vec![[v; CHANNELS]; n])
 }
 
+/// Runs `input` through `filter` as a single block
+fn run_whole(filter: &mut dyn Filter, input: &Block) -> Block {
+    filter.push(Arc::new(input.clone()), 0);
+    filter.transform().remove(0)
+}
+
+/// Runs `input` through `filter` one frame at a time
+fn run_per_frame(filter: &mut dyn Filter, input: &Block) -> Block {
+    input
+        .iter()
+        .flat_map(|frame| {
+            filter.push(Arc::new(vec![*frame]), 0);
+            filter.transform().remove(0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod block_tests {
+    use super::*;
+    use rustic::core::filters::prelude::{GainFilter, HighPassFilter, LowPassFilter};
+
+    /// Filters already process whole blocks; this checks their state carries
+    /// over between blocks so that block size does not change the output.
+    #[test]
+    fn test_block_and_per_frame_outputs_match() {
+        let input: Block = (0..1024)
+            .map(|i| [(i as f32 * 0.05).sin(), (i as f32 * 0.13).cos()])
+            .collect();
+        let filters: Vec<Box<dyn Fn() -> Box<dyn Filter>>> = vec![
+            Box::new(|| Box::new(GainFilter::new(0.7))),
+            Box::new(|| Box::new(LowPassFilter::new(800.0, 44100.0))),
+            Box::new(|| Box::new(HighPassFilter::new(800.0, 44100.0))),
+        ];
+        for make in filters {
+            let whole = run_whole(make().as_mut(), &input);
+            let per_frame = run_per_frame(make().as_mut(), &input);
+            assert_eq!(whole, per_frame, "{}", make());
+        }
+    }
+}
+
+#[cfg(test)]
+mod transform_into_tests {
+    use super::*;
+    use rustic::core::filters::prelude::{Clipper, GainFilter, HighPassFilter, LowPassFilter};
+
+    /// Repeated calls write into the same buffers instead of allocating new ones.
+    #[test]
+    fn test_transform_into_reuses_buffers() {
+        let filters: Vec<Box<dyn Filter>> = vec![
+            Box::new(GainFilter::new(0.5)),
+            Box::new(LowPassFilter::new(800.0, 44100.0)),
+            Box::new(HighPassFilter::new(800.0, 44100.0)),
+        ];
+        for mut filter in filters {
+            let mut outputs = Vec::new();
+            filter.push(const_block(512, 0.5), 0);
+            filter.transform_into(&mut outputs);
+            let ptr = outputs[0].as_ptr();
+            let capacity = outputs[0].capacity();
+
+            for _ in 0..100 {
+                filter.push(const_block(512, 0.5), 0);
+                filter.transform_into(&mut outputs);
+                assert_eq!(outputs.len(), 1);
+                assert_eq!(outputs[0].len(), 512);
+                assert_eq!(outputs[0].as_ptr(), ptr, "{filter}");
+                assert_eq!(outputs[0].capacity(), capacity, "{filter}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_into_matches_transform() {
+        let input: Block = (0..256)
+            .map(|i| [(i as f32 * 0.1).sin(); CHANNELS])
+            .collect();
+        let filters: Vec<Box<dyn Fn() -> Box<dyn Filter>>> = vec![
+            Box::new(|| Box::new(GainFilter::new(0.7))),
+            Box::new(|| Box::new(LowPassFilter::new(800.0, 44100.0))),
+            Box::new(|| Box::new(HighPassFilter::new(800.0, 44100.0))),
+            // Relies on the default implementation
+            Box::new(|| Box::new(Clipper::new(0.5))),
+        ];
+        for make in filters {
+            let expected = run_whole(make().as_mut(), &input);
+            let mut filter = make();
+            // Stale, differently sized contents must not leak into the output
+            let mut outputs = vec![vec![[9.0; CHANNELS]; 1000]; 2];
+            filter.push(Arc::new(input.clone()), 0);
+            filter.transform_into(&mut outputs);
+            assert_eq!(outputs, vec![expected], "{filter}");
+        }
+    }
+}
+
 #[cfg(test)]
 mod amplifier_tests {
     use super::*;
@@ -47,10 +144,65 @@ mod amplifier_tests {
     }
 }
 
+#[cfg(test)]
+mod envelope_follower_tests {
+    use super::*;
+    use rustic::core::filters::prelude::EnvelopeFollower;
+    use rustic_meta::MetaFilter;
+
+    const SAMPLE_RATE: f32 = 1000.0;
+
+    /// Follows a 1 s burst at full scale then 1 s of silence, returning the
+    /// envelope of the left channel.
+    fn follow_burst(attack: f32, release: f32) -> Vec<f32> {
+        let mut follower = EnvelopeFollower::new(attack, release, SAMPLE_RATE);
+        let mut input = vec![[1.0, 0.0]; 1000];
+        input.extend(silent_block(1000));
+        follower.push(Arc::new(input.clone()), 0);
+        let outputs = follower.transform();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0], input, "port 0 passes the input through");
+        outputs[1].iter().map(|frame| frame[0]).collect()
+    }
+
+    #[test]
+    fn test_rises_with_attack() {
+        let envelope = follow_burst(0.01, 0.1);
+        // 63% after one attack time, nearly there after five
+        assert!((envelope[9] - 0.632).abs() < 0.02, "{}", envelope[9]);
+        assert!(envelope[49] > 0.99);
+        assert!(envelope.windows(2).take(999).all(|w| w[1] >= w[0]));
+
+        let slower = follow_burst(0.05, 0.1);
+        assert!((slower[49] - 0.632).abs() < 0.02, "{}", slower[49]);
+    }
+
+    #[test]
+    fn test_falls_with_release() {
+        let envelope = follow_burst(0.01, 0.1);
+        // Down to 37% one release time after the burst
+        assert!((envelope[1099] - 0.368).abs() < 0.02, "{}", envelope[1099]);
+        assert!(envelope[1499] < 0.01);
+        assert!(envelope.windows(2).skip(1000).all(|w| w[1] <= w[0]));
+    }
+
+    #[test]
+    fn test_channels_are_independent() {
+        let mut follower = EnvelopeFollower::new(0.001, 0.1, SAMPLE_RATE);
+        follower.push(Arc::new(vec![[0.5, -0.25]; 100]), 0);
+        follower.transform();
+        let envelope = follower.envelope();
+        assert!((envelope[0] - 0.5).abs() < 1e-3);
+        assert!((envelope[1] - 0.25).abs() < 1e-3);
+        assert_eq!(follower.filter_info().outputs, 2);
+    }
+}
+
 #[cfg(test)]
 mod clipper_tests {
     use super::*;
-    use rustic::core::filters::prelude::Clipper;
+    use rustic::core::filters::prelude::{ClipMode, Clipper};
+    use rustic_meta::{MetaFilter, Parameter};
 
     #[test]
     fn test_clipping_above_threshold() {
@@ -82,6 +234,84 @@ mod clipper_tests {
             assert!((frame[0] + 0.5).abs() < 1e-5);
         }
     }
+
+    #[test]
+    fn test_mode_choice_metadata_lists_options() {
+        let info = Clipper::metadata();
+        let mode = info
+            .inputs
+            .iter()
+            .find_map(|input| match &input.parameter {
+                Some(Parameter::Choice {
+                    field_name: "mode",
+                    options,
+                    default,
+                    ..
+                }) => Some((options.clone(), *default)),
+                _ => None,
+            })
+            .expect("Clipper should expose a mode choice");
+        assert_eq!(mode, (vec!["Hard", "Soft"], 0));
+    }
+
+    #[test]
+    fn test_set_parameter_switches_mode() {
+        let mut f = Clipper::new(0.5);
+        f.set_parameter("mode", 1.0);
+        assert_eq!(f.mode, ClipMode::Soft);
+        assert_eq!(f.get_parameter("mode"), Some(1.0));
+
+        // Soft clipping saturates below the threshold instead of clamping
+        f.push(const_block(4, 0.3), 0);
+        let out = f.transform();
+        let expected = 0.5 * (0.3_f32 / 0.5).tanh();
+        assert!((out[0][0][0] - expected).abs() < 1e-5);
+
+        // Out-of-range indices clamp to the last option
+        f.set_parameter("mode", 7.0);
+        assert_eq!(f.mode, ClipMode::Soft);
+        f.set_parameter("mode", 0.0);
+        assert_eq!(f.mode, ClipMode::Hard);
+    }
+}
+
+#[cfg(test)]
+mod bitcrusher_tests {
+    use super::*;
+    use rustic::core::filters::prelude::BitCrusher;
+
+    #[test]
+    fn test_one_bit_has_few_levels() {
+        let mut f = BitCrusher::new(1, 1);
+        let block: Block = (0..64)
+            .map(|i| [(i as f32 / 64.0 * std::f32::consts::TAU).sin(); CHANNELS])
+            .collect();
+        f.push(Arc::new(block), 0);
+        let out = f.transform();
+        let mut levels: Vec<f32> = out[0].iter().map(|fr| fr[0]).collect();
+        levels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        levels.dedup();
+        assert!(
+            levels.len() <= 2,
+            "1-bit output should have at most 2 levels, got {levels:?}"
+        );
+    }
+
+    #[test]
+    fn test_downsample_holds_over_window() {
+        let mut f = BitCrusher::new(16, 4);
+        let block: Block = (0..32).map(|i| [i as f32 / 32.0; CHANNELS]).collect();
+        f.push(Arc::new(block), 0);
+        let out = f.transform();
+        assert_eq!(out[0].len(), 32);
+        for window in out[0].chunks(4) {
+            assert!(
+                window.iter().all(|fr| fr == &window[0]),
+                "Output should be constant over each 4-frame window: {window:?}"
+            );
+        }
+        assert_ne!(out[0][0], out[0][4]);
+    }
 }
 
 #[cfg(test)]
@@ -121,12 +351,103 @@ mod compressor_tests {
             last_frame[0]
         );
     }
+
+    #[test]
+    fn test_compressor_applies_its_ratio_above_threshold() {
+        // 4x over the threshold comes out 4^(1/4) over it once settled
+        let mut f = Compressor::new(0.2, 4.0, 0.001, 0.1, 1000.0);
+        let out = run_whole(&mut f, &const_block(200, 0.8));
+        let expected = 0.2 * 4.0_f32.powf(0.25);
+        assert!((out[199][0] - expected).abs() < 1e-3, "{}", out[199][0]);
+    }
+}
+
+#[cfg(test)]
+mod multiband_compressor_tests {
+    use super::*;
+    use rustfft::{FftPlanner, num_complex::Complex};
+    use rustic::core::filters::prelude::MultibandCompressor;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+    const FFT_SIZE: usize = 8192;
+
+    fn tones(tones: &[(f32, f32)], len: usize) -> Block {
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE;
+                let sample = tones
+                    .iter()
+                    .map(|(frequency, amplitude)| amplitude * (2.0 * PI * frequency * t).sin())
+                    .sum();
+                [sample; CHANNELS]
+            })
+            .collect()
+    }
+
+    /// Energy of the left channel of the last `FFT_SIZE` frames, between
+    /// `low` and `high` Hz
+    fn band_energy(block: &Block, low: f32, high: f32) -> f32 {
+        let mut spectrum: Vec<Complex<f32>> = block[block.len() - FFT_SIZE..]
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let window = 0.5 - 0.5 * (2.0 * PI * i as f32 / FFT_SIZE as f32).cos();
+                Complex::new(frame[0] * window, 0.0)
+            })
+            .collect();
+        FftPlanner::new()
+            .plan_fft_forward(FFT_SIZE)
+            .process(&mut spectrum);
+        let bin = |frequency: f32| (frequency * FFT_SIZE as f32 / SAMPLE_RATE) as usize;
+        spectrum[bin(low)..bin(high)]
+            .iter()
+            .map(|c| c.norm_sqr())
+            .sum()
+    }
+
+    #[test]
+    fn test_only_the_loud_band_is_compressed() {
+        let mut compressor = MultibandCompressor::new(200.0, 3000.0, SAMPLE_RATE);
+        compressor.set_band(0, 0.2, 10.0);
+        compressor.set_band(1, 0.2, 10.0);
+        compressor.set_band(2, 0.2, 10.0);
+
+        // Loud bass, quiet mids and highs
+        let input = tones(&[(80.0, 0.9), (1000.0, 0.05), (8000.0, 0.05)], 4 * FFT_SIZE);
+        let output = run_whole(&mut compressor, &input);
+
+        let low = band_energy(&output, 40.0, 120.0) / band_energy(&input, 40.0, 120.0);
+        let mid = band_energy(&output, 700.0, 1400.0) / band_energy(&input, 700.0, 1400.0);
+        let high = band_energy(&output, 6000.0, 10000.0) / band_energy(&input, 6000.0, 10000.0);
+        assert!(low < 0.25, "low band kept {low} of its energy");
+        assert!((mid - 1.0).abs() < 0.02, "mid band changed by {mid}");
+        assert!((high - 1.0).abs() < 0.02, "high band changed by {high}");
+    }
+
+    #[test]
+    fn test_crossover_parameters_move_the_bands() {
+        use rustic_meta::MetaFilter;
+
+        let mut compressor = MultibandCompressor::new(200.0, 3000.0, SAMPLE_RATE);
+        compressor.set_band(0, 0.2, 10.0);
+        compressor.set_band(1, 1.0, 1.0);
+        // 1 kHz starts in the uncompressed mid band, then falls in the low one
+        compressor.set_parameter("low_crossover", 2000.0);
+        compressor.set_parameter("high_crossover", 8000.0);
+
+        let input = tones(&[(1000.0, 0.9)], 4 * FFT_SIZE);
+        let output = run_whole(&mut compressor, &input);
+        let kept = band_energy(&output, 700.0, 1400.0) / band_energy(&input, 700.0, 1400.0);
+        assert!(kept < 0.25, "1kHz kept {kept} of its energy");
+    }
 }
 
 #[cfg(test)]
 mod delay_tests {
     use super::*;
     use rustic::core::filters::prelude::DelayFilter;
+    use rustic_meta::MetaFilter;
 
     #[test]
     fn test_delay_outputs_silence_initially() {
@@ -160,6 +481,311 @@ mod delay_tests {
             assert!(frame[0].abs() < 1e-5, "Expected delayed silence");
         }
     }
+
+    /// An impulse at index 0 followed by `len - 1` silent frames.
+    fn impulse(len: usize) -> Block {
+        let mut block = silent_block(len);
+        block[0] = [1.0; CHANNELS];
+        block
+    }
+
+    fn impulse_position(block: &Block) -> Option<usize> {
+        block.iter().position(|frame| frame[0] > 0.5)
+    }
+
+    #[test]
+    fn test_delay_by_whole_frames() {
+        let mut f = DelayFilter::new(10.0, 0.3);
+        let out = run_whole(&mut f, &impulse(10));
+        assert_eq!(impulse_position(&out), Some(3));
+        assert_eq!(out[3], [1.0; CHANNELS]);
+
+        let mut f = DelayFilter::new(10.0, 0.0);
+        assert_eq!(run_whole(&mut f, &impulse(4)), impulse(4));
+    }
+
+    #[test]
+    fn test_fractional_delay_interpolates() {
+        let mut f = DelayFilter::new(10.0, 0.25);
+        let out = run_whole(&mut f, &impulse(6));
+        assert!((out[2][0] - 0.5).abs() < 1e-5, "{:?}", out);
+        assert!((out[3][0] - 0.5).abs() < 1e-5, "{:?}", out);
+    }
+
+    #[test]
+    fn test_changing_delay_tracks_new_time_without_reallocating() {
+        let mut f = DelayFilter::with_max_delay(10.0, 0.2, 1.0);
+        assert!((f.max_delay() - 1.0).abs() < 1e-5);
+        assert_eq!(impulse_position(&run_whole(&mut f, &impulse(10))), Some(2));
+
+        // The buffer is already sized for the new delay
+        f.set_parameter("delay", 0.7);
+        assert_eq!(f.delay(), 0.7);
+        assert!((f.max_delay() - 1.0).abs() < 1e-5);
+        assert_eq!(impulse_position(&run_whole(&mut f, &impulse(10))), Some(7));
+
+        // Shortening plays the recent past again
+        f.set_parameter("delay", 0.1);
+        assert_eq!(impulse_position(&run_whole(&mut f, &impulse(10))), Some(1));
+
+        // Beyond the buffer, the delay is clamped to its maximum: the previous
+        // impulse, 10 frames ago, comes out first
+        f.set_parameter("delay", 5.0);
+        let out = run_whole(&mut f, &impulse(20));
+        let positions: Vec<usize> = (0..20).filter(|&i| out[i][0] > 0.5).collect();
+        assert_eq!(positions, vec![0, 10]);
+        assert!((f.max_delay() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_default_max_delay_covers_initial_delay() {
+        assert!((DelayFilter::new(100.0, 0.5).max_delay() - 2.0).abs() < 1e-5);
+        assert!((DelayFilter::new(100.0, 3.0).max_delay() - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_advertised_range_fits_default_buffer() {
+        use rustic::core::filters::prelude::DEFAULT_MAX_DELAY;
+        use rustic_meta::Parameter;
+
+        let f = DelayFilter::new(100.0, 0.5);
+        let max = f
+            .filter_info()
+            .inputs
+            .iter()
+            .find_map(|input| match input.parameter {
+                Some(Parameter::Range {
+                    field_name: "delay",
+                    max,
+                    ..
+                }) => Some(max),
+                _ => None,
+            })
+            .expect("delay should be a range parameter");
+        assert_eq!(max, DEFAULT_MAX_DELAY);
+        assert!((f.max_delay() - max).abs() < 1e-5);
+    }
+}
+
+#[cfg(test)]
+mod ping_pong_tests {
+    use super::*;
+    use rustic::core::filters::prelude::PingPongDelay;
+
+    #[test]
+    fn test_echoes_alternate_between_channels() {
+        // 10 frames of delay
+        let mut f = PingPongDelay::new(0.01, 0.5, 0.5, 1000.0);
+        let mut impulse = silent_block(40);
+        impulse[0] = [1.0; CHANNELS];
+        f.push(Arc::new(impulse), 0);
+        let out = f.transform().remove(0);
+
+        // The dry impulse stays centered
+        assert_eq!(out[0], [0.5, 0.5]);
+        // First echo on the left, then bouncing right and back left
+        assert_eq!(out[10], [0.5, 0.0]);
+        assert_eq!(out[20], [0.0, 0.25]);
+        assert_eq!(out[30], [0.125, 0.0]);
+        for (n, frame) in out.iter().enumerate() {
+            if n % 10 != 0 {
+                assert_eq!(*frame, [0.0; CHANNELS], "frame {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_dry_signal_keeps_stereo_image() {
+        let mut f = PingPongDelay::new(0.01, 0.5, 0.0, 1000.0);
+        let input: Block = vec![[1.0, -0.5]; 32];
+        f.push(Arc::new(input.clone()), 0);
+        assert_eq!(f.transform().remove(0), input);
+    }
+}
+
+#[cfg(test)]
+mod convolution_reverb_tests {
+    use super::*;
+    use rustic::core::Frame;
+    use rustic::core::filters::prelude::ConvolutionReverb;
+
+    /// A decaying stereo IR spanning several partitions, different per channel
+    fn synthetic_ir(len: usize) -> Vec<Frame> {
+        (0..len)
+            .map(|i| [0.8_f32.powi(i as i32), if i % 3 == 0 { -0.5 } else { 0.25 }])
+            .collect()
+    }
+
+    fn impulse(len: usize) -> Block {
+        let mut block = silent_block(len);
+        block[0] = [1.0; CHANNELS];
+        block
+    }
+
+    fn assert_close(a: &[Frame], b: &[Frame]) {
+        assert_eq!(a.len(), b.len());
+        for (i, (x, y)) in a.iter().zip(b).enumerate() {
+            for ch in 0..CHANNELS {
+                assert!((x[ch] - y[ch]).abs() < 1e-4, "frame {i}: {x:?} != {y:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_impulse_outputs_the_ir() {
+        let ir = synthetic_ir(20);
+        let mut f = ConvolutionReverb::with_partition_size(&ir, 1.0, 8);
+        assert_eq!(f.latency(), 7);
+        let out = run_whole(&mut f, &impulse(64));
+
+        let latency = f.latency();
+        assert_close(&out[..latency], &silent_block(latency));
+        assert_close(&out[latency..latency + ir.len()], &ir);
+        assert_close(
+            &out[latency + ir.len()..],
+            &silent_block(64 - latency - ir.len()),
+        );
+    }
+
+    #[test]
+    fn test_output_does_not_depend_on_block_size() {
+        let ir = synthetic_ir(20);
+        let input: Block = (0..48).map(|i| [(i as f32 * 0.3).sin(), 0.5]).collect();
+        let whole = run_whole(
+            &mut ConvolutionReverb::with_partition_size(&ir, 0.5, 8),
+            &input,
+        );
+        let per_frame = run_per_frame(
+            &mut ConvolutionReverb::with_partition_size(&ir, 0.5, 8),
+            &input,
+        );
+        assert_close(&whole, &per_frame);
+    }
+
+    #[test]
+    fn test_dry_signal_is_delayed_by_the_latency() {
+        let mut f = ConvolutionReverb::with_partition_size(&synthetic_ir(20), 0.0, 8);
+        let input: Block = (0..32).map(|i| [i as f32, -(i as f32)]).collect();
+        let out = run_whole(&mut f, &input);
+        assert_close(&out[..7], &silent_block(7));
+        assert_close(&out[7..], &input[..25]);
+    }
+
+    #[test]
+    fn test_loads_ir_from_wav() {
+        let path = std::env::temp_dir().join(format!("rustic_ir_{}.wav", std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 1000,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let ir = synthetic_ir(300);
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for frame in &ir {
+            writer.write_sample(frame[0]).unwrap();
+            writer.write_sample(frame[1]).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut f = ConvolutionReverb::from_wav(&path, 1000, 1.0).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(f.ir_len(), 512);
+
+        let latency = f.latency();
+        let out = run_whole(&mut f, &impulse(latency + ir.len()));
+        assert_close(&out[latency..], &ir);
+    }
+}
+
+#[cfg(test)]
+mod crossover_tests {
+    use super::*;
+    use rustic::core::filters::prelude::Crossover;
+    use rustic_meta::MetaFilter;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    fn sine(frequency: f32, len: usize) -> Block {
+        (0..len)
+            .map(|i| {
+                [(2.0 * std::f32::consts::PI * frequency * i as f32 / SAMPLE_RATE).sin(); CHANNELS]
+            })
+            .collect()
+    }
+
+    /// RMS of the left channel, once the filters have settled
+    fn rms(block: &[[f32; CHANNELS]]) -> f32 {
+        let settled = &block[block.len() / 2..];
+        (settled.iter().map(|frame| frame[0] * frame[0]).sum::<f32>() / settled.len() as f32).sqrt()
+    }
+
+    fn split(crossover: &mut Crossover, input: &Block) -> Vec<Block> {
+        crossover.push(Arc::new(input.clone()), 0);
+        crossover.transform()
+    }
+
+    fn sum(bands: &[Block]) -> Block {
+        (0..bands[0].len())
+            .map(|i| std::array::from_fn(|ch| bands.iter().map(|band| band[i][ch]).sum()))
+            .collect()
+    }
+
+    #[test]
+    fn test_one_output_per_band() {
+        let crossover = Crossover::new(&[2000.0, 200.0], SAMPLE_RATE);
+        assert_eq!(crossover.frequencies(), &[200.0, 2000.0]);
+        assert_eq!(crossover.band_count(), 3);
+        assert_eq!(crossover.filter_info().outputs, 3);
+        assert_eq!(Crossover::metadata().outputs, 2);
+
+        let mut crossover = crossover;
+        crossover.set_frequencies(&[500.0]);
+        assert_eq!(crossover.filter_info().outputs, 2);
+    }
+
+    #[test]
+    fn test_two_bands_sum_back_flat() {
+        for frequency in [50.0, 500.0, 1000.0, 2000.0, 10000.0] {
+            let mut crossover = Crossover::new(&[1000.0], SAMPLE_RATE);
+            let input = sine(frequency, 8820);
+            let bands = split(&mut crossover, &input);
+            assert_eq!(bands.len(), 2);
+            let ratio = rms(&sum(&bands)) / rms(&input);
+            assert!((ratio - 1.0).abs() < 0.01, "{frequency}Hz: {ratio}");
+        }
+    }
+
+    #[test]
+    fn test_bands_hold_their_frequencies() {
+        let mut crossover = Crossover::new(&[1000.0], SAMPLE_RATE);
+        let low = split(&mut crossover, &sine(100.0, 8820));
+        assert!(rms(&low[0]) > 0.69, "{}", rms(&low[0]));
+        assert!(rms(&low[1]) < 0.01, "{}", rms(&low[1]));
+
+        let mut crossover = Crossover::new(&[1000.0], SAMPLE_RATE);
+        let high = split(&mut crossover, &sine(10000.0, 8820));
+        assert!(rms(&high[0]) < 0.01, "{}", rms(&high[0]));
+        assert!(rms(&high[1]) > 0.69, "{}", rms(&high[1]));
+
+        // Each side is 6dB down at the crossover frequency
+        let mut crossover = Crossover::new(&[1000.0], SAMPLE_RATE);
+        let edge = split(&mut crossover, &sine(1000.0, 8820));
+        for band in &edge {
+            assert!((rms(band) - 0.5 * rms(&sine(1000.0, 8820))).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_three_bands_sum_back_flat() {
+        for frequency in [60.0, 200.0, 800.0, 3000.0, 12000.0] {
+            let mut crossover = Crossover::new(&[200.0, 3000.0], SAMPLE_RATE);
+            let input = sine(frequency, 17640);
+            let bands = split(&mut crossover, &input);
+            let ratio = rms(&sum(&bands)) / rms(&input);
+            assert!((ratio - 1.0).abs() < 0.01, "{frequency}Hz: {ratio}");
+        }
+    }
 }
 
 #[cfg(test)]
@@ -202,6 +828,144 @@ mod lowpass_tests {
     }
 }
 
+#[cfg(test)]
+mod sample_rate_tests {
+    use super::*;
+    use rustic::core::filters::prelude::{
+        BandPass, Crossover, DelayFilter, LowPassFilter, Oversample, ParallelMix, PingPongDelay,
+        ResonantBandpassFilter,
+    };
+    use std::f32::consts::PI;
+
+    /// Steady-state peak level of a 1 kHz sine sampled at `sample_rate`
+    fn level_at_1khz(filter: &mut dyn Filter, sample_rate: f32) -> f32 {
+        let sine: Block = (0..sample_rate as usize / 2)
+            .map(|i| [(2.0 * PI * 1000.0 * i as f32 / sample_rate).sin(); CHANNELS])
+            .collect();
+        let output = run_whole(filter, &sine);
+        output[output.len() / 2..]
+            .iter()
+            .map(|frame| frame[0].abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_lowpass_cutoff_follows_the_sample_rate() {
+        let reference = level_at_1khz(&mut LowPassFilter::new(1000.0, 44100.0), 44100.0);
+
+        let mut moved = LowPassFilter::new(1000.0, 44100.0);
+        moved.set_sample_rate(96000.0);
+        let level = level_at_1khz(&mut moved, 96000.0);
+        assert!(
+            (level - reference).abs() < 0.02,
+            "{level} at 96 kHz, {reference} at 44.1 kHz"
+        );
+
+        // Left at 44.1 kHz, the cutoff would have moved up to about 2.2 kHz
+        let stale = level_at_1khz(&mut LowPassFilter::new(1000.0, 44100.0), 96000.0);
+        assert!(stale - reference > 0.1, "{stale} vs {reference}");
+    }
+
+    /// Filters moved to a new rate behave as if they were built for it
+    #[test]
+    fn test_moved_filters_match_fresh_ones() {
+        let input: Block = (0..4096)
+            .map(|i| [(i as f32 * 0.05).sin(), (i as f32 * 0.13).cos()])
+            .collect();
+        let filters: [fn(f32) -> Box<dyn Filter>; 6] = [
+            |sr| Box::new(BandPass::new(200.0, 3000.0, sr)),
+            |sr| Box::new(Crossover::new(&[500.0, 4000.0], sr)),
+            |sr| Box::new(DelayFilter::new(sr, 0.01)),
+            |sr| Box::new(PingPongDelay::new(0.01, 0.5, 0.5, sr)),
+            |sr| Box::new(ResonantBandpassFilter::new(1000.0, 5.0, sr)),
+            |sr| Box::new(ParallelMix::new(Box::new(DelayFilter::new(sr, 0.01)), 0.5)),
+        ];
+        for make in filters {
+            let mut moved = make(44100.0);
+            moved.set_sample_rate(48000.0);
+            let mut fresh = make(48000.0);
+            moved.push(Arc::new(input.clone()), 0);
+            fresh.push(Arc::new(input.clone()), 0);
+            assert_eq!(moved.transform(), fresh.transform(), "{fresh}");
+        }
+    }
+
+    /// The inner filter of an `Oversample` runs at the oversampled rate
+    #[test]
+    fn test_oversample_moves_its_inner_filter_to_the_oversampled_rate() {
+        let input: Block = (0..4096)
+            .map(|i| [(i as f32 * 0.05).sin(), (i as f32 * 0.13).cos()])
+            .collect();
+        let mut moved = Oversample::new(Box::new(LowPassFilter::new(1000.0, 88200.0)), 2);
+        moved.set_sample_rate(96000.0);
+        let mut fresh = Oversample::new(Box::new(LowPassFilter::new(1000.0, 192000.0)), 2);
+        moved.push(Arc::new(input.clone()), 0);
+        fresh.push(Arc::new(input), 0);
+        assert_eq!(moved.transform(), fresh.transform());
+    }
+}
+
+#[cfg(test)]
+mod ladder_tests {
+    use super::*;
+    use rustic::core::filters::prelude::LadderFilter;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    fn run(filter: &mut LadderFilter, input: Block) -> Vec<f32> {
+        input
+            .chunks(512)
+            .flat_map(|chunk| {
+                filter.push(Arc::new(chunk.to_vec()), 0);
+                filter.transform().remove(0)
+            })
+            .map(|frame| frame[0])
+            .collect()
+    }
+
+    /// Steady-state peak level of a sine at `frequency` through the filter
+    fn level(frequency: f32) -> f32 {
+        let mut filter = LadderFilter::new(500.0, 0.0, SAMPLE_RATE);
+        let sine: Block = (0..SAMPLE_RATE as usize / 2)
+            .map(|i| [0.1 * (2.0 * PI * frequency * i as f32 / SAMPLE_RATE).sin(); CHANNELS])
+            .collect();
+        let output = run(&mut filter, sine);
+        output[output.len() / 2..]
+            .iter()
+            .fold(0.0, |max, sample| f32::max(max, sample.abs()))
+    }
+
+    #[test]
+    fn test_self_oscillation_near_cutoff() {
+        let mut filter = LadderFilter::new(1000.0, 1.0, SAMPLE_RATE);
+        let output = run(&mut filter, silent_block(SAMPLE_RATE as usize));
+        let tail = &output[output.len() / 2..];
+
+        let peak = tail.iter().fold(0.0, |max: f32, s| max.max(s.abs()));
+        assert!(peak > 0.1, "no self-oscillation, peak {peak}");
+
+        let crossings = tail
+            .windows(2)
+            .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+            .count();
+        let frequency = crossings as f32 / (tail.len() as f32 / SAMPLE_RATE);
+        assert!(
+            (frequency - 1000.0).abs() < 150.0,
+            "oscillating at {frequency}Hz"
+        );
+    }
+
+    #[test]
+    fn test_low_resonance_rolls_off_24db_per_octave() {
+        let passband = level(100.0);
+        assert!((passband - 0.1).abs() < 0.01, "passband level {passband}");
+
+        let octave = 20.0 * (level(2000.0) / level(4000.0)).log10();
+        assert!((20.0..28.0).contains(&octave), "{octave}dB per octave");
+    }
+}
+
 #[cfg(test)]
 mod highpass_tests {
     use super::*;
@@ -276,6 +1040,81 @@ mod tremolo_tests {
     }
 }
 
+#[cfg(test)]
+mod phaser_tests {
+    use super::*;
+    use rustic::core::filters::prelude::Phaser;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+    /// 10ms analysis windows
+    const WINDOW: usize = 441;
+
+    /// RMS of each channel of the phaser's output for a sine at `frequency`,
+    /// per analysis window over one LFO cycle
+    fn envelope(phaser: &mut Phaser, frequency: f32) -> Vec<[f32; CHANNELS]> {
+        let sine: Block = (0..2 * SAMPLE_RATE as usize)
+            .map(|i| [(2.0 * PI * frequency * i as f32 / SAMPLE_RATE).sin(); CHANNELS])
+            .collect();
+        sine.chunks(WINDOW)
+            .map(|chunk| {
+                phaser.push(Arc::new(chunk.to_vec()), 0);
+                let out = phaser.transform().remove(0);
+                std::array::from_fn(|ch| {
+                    (out.iter().map(|frame| frame[ch].powi(2)).sum::<f32>() / WINDOW as f32).sqrt()
+                })
+            })
+            .collect()
+    }
+
+    /// Index of the quietest window of the left channel, skipping the first
+    /// one while the stages settle
+    fn quietest(envelope: &[[f32; CHANNELS]]) -> (usize, f32, f32) {
+        let levels: Vec<f32> = envelope[1..].iter().map(|rms| rms[0]).collect();
+        let (idx, min) = levels
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        let max = levels.iter().copied().fold(0.0, f32::max);
+        (idx, min, max)
+    }
+
+    #[test]
+    fn test_phaser_notches_move_with_lfo() {
+        let low = envelope(&mut Phaser::new(4, 0.5, 1.0, 0.0, SAMPLE_RATE), 150.0);
+        let high = envelope(&mut Phaser::new(4, 0.5, 1.0, 0.0, SAMPLE_RATE), 2500.0);
+
+        // A notch sweeps over each tone at some point of the cycle...
+        let (low_idx, low_min, low_max) = quietest(&low);
+        let (high_idx, high_min, high_max) = quietest(&high);
+        assert!(low_min < 0.2 * low_max, "{low_min} vs {low_max}");
+        assert!(high_min < 0.2 * high_max, "{high_min} vs {high_max}");
+        // ...but not at the same time, as the notch frequency follows the LFO
+        assert!(
+            low_idx.abs_diff(high_idx) > 5,
+            "notches at windows {low_idx} and {high_idx}"
+        );
+
+        // The right channel sweeps with a phase offset
+        assert!(
+            low.iter()
+                .any(|rms| (rms[0] - rms[1]).abs() > 0.1 * low_max)
+        );
+    }
+
+    #[test]
+    fn test_phaser_zero_depth_passthrough() {
+        let mut f = Phaser::new(6, 2.0, 0.0, 0.5, SAMPLE_RATE);
+        let input: Block = (0..512)
+            .map(|i| [(i as f32 * 0.1).sin(); CHANNELS])
+            .collect();
+        f.push(Arc::new(input.clone()), 0);
+        assert_eq!(f.transform().remove(0), input);
+    }
+}
+
 #[cfg(test)]
 mod moving_average_tests {
     use super::*;
@@ -299,3 +1138,237 @@ mod moving_average_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod channel_tests {
+    use super::*;
+    use rustic::core::filters::prelude::{MonoToStereo, StereoToMono};
+
+    #[test]
+    fn test_mono_to_stereo_duplicates_first_channel() {
+        let mut f = MonoToStereo::new();
+        let mono: Block = (0..8).map(|i| [i as f32 * 0.1, 0.0]).collect();
+        f.push(Arc::new(mono.clone()), 0);
+        let out = f.transform();
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].len(), 8);
+        for (frame, input) in out[0].iter().zip(&mono) {
+            assert_eq!(frame[0], frame[1]);
+            assert_eq!(frame[0], input[0]);
+        }
+    }
+
+    #[test]
+    fn test_stereo_to_mono_averages_channels() {
+        let mut f = StereoToMono::new();
+        f.push(Arc::new(vec![[1.0, 0.0], [0.5, -0.5], [0.2, 0.6]]), 0);
+        let out = f.transform();
+        assert_eq!(out[0].len(), 3);
+        for (frame, expected) in out[0].iter().zip([0.5, 0.0, 0.4]) {
+            assert!((frame[0] - expected).abs() < 1e-6);
+            assert_eq!(frame[0], frame[1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod oversample_tests {
+    use super::*;
+    use rustic::core::filters::prelude::{Clipper, GainFilter, Oversample};
+    use rustic_meta::MetaFilter;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+    const WINDOW: usize = 4096;
+
+    /// Clips a near-Nyquist sine through `filter` and returns the share of
+    /// the output energy outside the fundamental's DFT bin. The third and
+    /// higher harmonics lie above Nyquist, so that energy is aliasing.
+    fn aliased_energy(filter: &mut dyn Filter) -> f32 {
+        // Exactly on a DFT bin, so the fundamental does not leak
+        let bin = 1400;
+        let frequency = bin as f32 * SAMPLE_RATE / WINDOW as f32;
+        let sine: Block = (0..2 * WINDOW)
+            .map(|i| [(2.0 * PI * frequency * i as f32 / SAMPLE_RATE).sin(); CHANNELS])
+            .collect();
+
+        let mut output = Vec::new();
+        for chunk in sine.chunks(256) {
+            filter.push(Arc::new(chunk.to_vec()), 0);
+            output.extend(filter.transform().remove(0));
+        }
+        // Skip the warm-up of the anti-aliasing filters
+        let signal: Vec<f32> = output[WINDOW..].iter().map(|frame| frame[0]).collect();
+
+        let (mut re, mut im) = (0.0, 0.0);
+        for (n, sample) in signal.iter().enumerate() {
+            let phase = 2.0 * PI * (bin * n % WINDOW) as f32 / WINDOW as f32;
+            re += sample * phase.cos();
+            im += sample * phase.sin();
+        }
+        let total: f32 = signal.iter().map(|s| s * s).sum();
+        let fundamental = 2.0 * (re * re + im * im) / WINDOW as f32;
+        (total - fundamental) / total
+    }
+
+    #[test]
+    fn test_oversampling_reduces_aliasing() {
+        let plain = aliased_energy(&mut Clipper::new(0.5));
+        let oversampled = aliased_energy(&mut Oversample::new(Box::new(Clipper::new(0.5)), 4));
+        assert!(plain > 0.01, "clipping should alias, got {plain}");
+        assert!(
+            oversampled < plain / 10.0,
+            "oversampled aliasing {oversampled} vs {plain}"
+        );
+    }
+
+    #[test]
+    fn test_oversample_preserves_length_and_level() {
+        let mut f = Oversample::new(Box::new(GainFilter::new(1.0)), 2);
+        let mut last = Vec::new();
+        for _ in 0..4 {
+            f.push(const_block(64, 0.5), 0);
+            last = f.transform().remove(0);
+            assert_eq!(last.len(), 64);
+        }
+        // Once the filters settled, DC passes through unchanged
+        assert!(last.iter().all(|frame| (frame[0] - 0.5).abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_oversample_exposes_inner_parameters() {
+        let mut f = Oversample::new(Box::new(Clipper::new(0.5)), 2);
+        f.set_parameter("max_ampl", 0.25);
+        assert_eq!(f.get_parameter("max_ampl"), Some(0.25));
+        let info = f.filter_info();
+        assert_eq!(info.type_id, "Oversample");
+        assert_eq!(
+            info.param_port_count(),
+            Clipper::metadata().param_port_count()
+        );
+    }
+}
+
+#[cfg(test)]
+mod parallel_mix_tests {
+    use super::*;
+    use rustic::core::filters::prelude::{GainFilter, ParallelMix};
+    use rustic_meta::MetaFilter;
+
+    fn mixed(mix: f32) -> Block {
+        let mut filter = ParallelMix::new(Box::new(GainFilter::new(3.0)), mix);
+        run_whole(&mut filter, &vec![[0.5, -0.25]; 16])
+    }
+
+    #[test]
+    fn test_mix_zero_is_dry() {
+        assert_eq!(mixed(0.0), vec![[0.5, -0.25]; 16]);
+    }
+
+    #[test]
+    fn test_mix_one_is_wet() {
+        assert_eq!(mixed(1.0), vec![[1.5, -0.75]; 16]);
+    }
+
+    #[test]
+    fn test_half_mix_averages() {
+        for frame in mixed(0.5) {
+            assert!((frame[0] - 1.0).abs() < 1e-6, "{frame:?}");
+            assert!((frame[1] + 0.5).abs() < 1e-6, "{frame:?}");
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let mut filter = ParallelMix::new(Box::new(GainFilter::new(1.0)), 0.5);
+        filter.set_parameter("mix", 2.0);
+        assert_eq!(filter.get_parameter("mix"), Some(1.0));
+
+        // Other parameters reach the inner filter
+        filter.set_parameter("factor", 0.25);
+        assert_eq!(filter.get_parameter("factor"), Some(0.25));
+        assert_eq!(
+            run_whole(&mut filter, &vec![[1.0; CHANNELS]; 4]),
+            vec![[0.25; CHANNELS]; 4]
+        );
+
+        let info = filter.filter_info();
+        assert_eq!(info.audio_port_count(), 1);
+        assert_eq!(info.param_port_count(), 2);
+    }
+}
+
+#[cfg(test)]
+mod comb_allpass_tests {
+    use super::*;
+    use rustic::core::filters::prelude::{AllPassFilter, CombFilter};
+    use std::f32::consts::PI;
+
+    /// Response of `filter` to a unit impulse, over `len` frames
+    fn impulse_response(filter: &mut dyn Filter, len: usize) -> Vec<f32> {
+        let mut impulse = silent_block(len);
+        impulse[0] = [1.0; CHANNELS];
+        filter.push(Arc::new(impulse), 0);
+        let out = filter.transform().remove(0);
+        assert!(out.iter().all(|frame| frame[0] == frame[1]));
+        out.iter().map(|frame| frame[0]).collect()
+    }
+
+    #[test]
+    fn test_feedforward_comb_impulse_response() {
+        let response = impulse_response(&mut CombFilter::feedforward(10, 0.5), 40);
+        for (n, sample) in response.iter().enumerate() {
+            let expected = match n {
+                0 => 1.0,
+                10 => 0.5,
+                _ => 0.0,
+            };
+            assert_eq!(*sample, expected, "sample {n}");
+        }
+    }
+
+    #[test]
+    fn test_feedback_comb_peaks_at_multiples_of_delay() {
+        let response = impulse_response(&mut CombFilter::feedback(10, 0.5), 45);
+        for (n, sample) in response.iter().enumerate() {
+            if n % 10 == 0 {
+                assert!((sample - 0.5f32.powi(n as i32 / 10)).abs() < 1e-6);
+            } else {
+                assert_eq!(*sample, 0.0, "sample {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_allpass_flat_magnitude_with_phase_shift() {
+        let response = impulse_response(&mut AllPassFilter::new(7, 0.6), 4096);
+        // Not a plain impulse: the signal is smeared in time
+        assert!((response[0] + 0.6).abs() < 1e-6);
+
+        for frequency in [0.01f32, 0.05, 0.13, 0.31, 0.47] {
+            let (re, im) = response
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (n, sample)| {
+                    let phase = 2.0 * PI * frequency * n as f32;
+                    (re + sample * phase.cos(), im - sample * phase.sin())
+                });
+            let magnitude = (re * re + im * im).sqrt();
+            assert!(
+                (magnitude - 1.0).abs() < 1e-3,
+                "|H({frequency})| = {magnitude}"
+            );
+            let phase = im.atan2(re);
+            assert!(phase.abs() > 0.05, "no phase shift at {frequency}");
+        }
+    }
+
+    #[test]
+    fn test_comb_delay_parameter_resizes_line() {
+        use rustic_meta::MetaFilter;
+        let mut comb = CombFilter::feedforward(10, 1.0);
+        comb.set_parameter("delay", 4.0);
+        let response = impulse_response(&mut comb, 8);
+        assert_eq!(response, vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+    }
+}
@@ -22,7 +22,93 @@ mod waveform_tests {
 
 #[cfg(test)]
 mod composite_generator_tests {
-    use rustic::core::generator::prelude::builder::MultiToneGeneratorBuilder;
+    use rustic::core::generator::prelude::builder::{
+        MultiToneGeneratorBuilder, ToneGeneratorBuilder,
+    };
+    use rustic::core::generator::prelude::{FrequencyRelation, MixMode, Waveform};
+
+    /// Peak of one second of five full-scale sines summed together.
+    fn five_sines_peak(auto_normalize: bool) -> f32 {
+        let mut builder = MultiToneGeneratorBuilder::new()
+            .frequency(110.0)
+            .mix_mode(MixMode::Sum)
+            .with_auto_normalize(auto_normalize);
+        for harmonic in 1..=5 {
+            builder = builder.add_generator(
+                ToneGeneratorBuilder::new()
+                    .waveform(Waveform::Sine)
+                    .frequency_relation(FrequencyRelation::Harmonic(harmonic))
+                    .build(),
+            );
+        }
+        let mut generator = builder.build();
+        generator.start();
+        generator
+            .tick_block(44100, 1.0 / 44100.0)
+            .into_iter()
+            .fold(0.0, |peak, sample| peak.max(sample.abs()))
+    }
+
+    #[test]
+    fn test_opposite_phases_cancel() {
+        let sine = |phase: f32| {
+            ToneGeneratorBuilder::new()
+                .waveform(Waveform::Sine)
+                .frequency_relation(FrequencyRelation::Identity)
+                .phase(phase)
+                .build()
+        };
+        let mut generator = MultiToneGeneratorBuilder::new()
+            .frequency(440.0)
+            .mix_mode(MixMode::Sum)
+            .add_generator(sine(0.0))
+            .add_generator(sine(std::f32::consts::PI))
+            .build();
+        generator.start();
+        for sample in generator.tick_block(4410, 1.0 / 44100.0) {
+            assert!(sample.abs() < 1e-4, "{sample}");
+        }
+
+        // In phase, the same tones add up
+        let mut generator = MultiToneGeneratorBuilder::new()
+            .frequency(440.0)
+            .mix_mode(MixMode::Sum)
+            .add_generator(sine(0.0))
+            .add_generator(sine(0.0))
+            .build();
+        generator.start();
+        let peak = generator
+            .tick_block(4410, 1.0 / 44100.0)
+            .into_iter()
+            .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+        assert!(peak > 1.9, "{peak}");
+    }
+
+    #[test]
+    fn test_initial_phase_is_restored_on_start() {
+        let mut tone = ToneGeneratorBuilder::new()
+            .waveform(Waveform::Sine)
+            .frequency(100.0)
+            .phase(std::f32::consts::FRAC_PI_2)
+            .build();
+        assert_eq!(tone.initial_phase(), Some(std::f32::consts::FRAC_PI_2));
+        tone.start();
+        let first = tone.tick(1e-6);
+        for _ in 0..1234 {
+            tone.tick(1.0 / 44100.0);
+        }
+        tone.start();
+        assert_eq!(tone.tick(1e-6), first);
+        assert!((first - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_auto_normalize_keeps_sum_in_range() {
+        assert!(five_sines_peak(false) > 1.5);
+        let peak = five_sines_peak(true);
+        assert!(peak <= 1.0, "peak {peak}");
+        assert!(peak > 0.3, "peak {peak}");
+    }
 
     #[test]
     pub fn test_tick_block_consistency() {
@@ -49,3 +135,234 @@ mod builder_tests {
     // - Test default values
     // - Test parameter validation
 }
+
+#[cfg(test)]
+mod granular_tests {
+    use rustic::core::generator::prelude::GranularSampler;
+
+    const SAMPLE_RATE: f32 = 1000.0;
+
+    fn ramp(len: usize) -> Vec<f32> {
+        (0..len).map(|i| i as f32).collect()
+    }
+
+    fn render(sampler: &mut GranularSampler, n: usize) -> Vec<f32> {
+        (0..n).map(|_| sampler.tick(1.0 / SAMPLE_RATE)).collect()
+    }
+
+    #[test]
+    fn test_density_one_reproduces_source() {
+        let source: Vec<f32> = (0..500).map(|i| (i as f32 * 0.37).sin()).collect();
+        let mut sampler = GranularSampler::new(source.clone(), SAMPLE_RATE)
+            .with_grain_size(0.02)
+            .with_density(1.0);
+
+        let output = render(&mut sampler, source.len());
+        for (i, (out, expected)) in output.iter().zip(&source).enumerate() {
+            assert!(
+                (out - expected).abs() < 1e-3,
+                "sample {i}: {out} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_overlapping_grains_reproduce_source() {
+        let source: Vec<f32> = (0..500).map(|i| (i as f32 * 0.11).cos()).collect();
+        let mut sampler = GranularSampler::new(source.clone(), SAMPLE_RATE)
+            .with_grain_size(0.04)
+            .with_density(4.0);
+
+        // The first grain fades in alone; skip it
+        let output = render(&mut sampler, source.len());
+        assert!(sampler.active_grains() >= 3);
+        for (out, expected) in output.iter().zip(&source).skip(40) {
+            assert!((out - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_pitch_speeds_up_grains() {
+        let mut normal = GranularSampler::new(ramp(1000), SAMPLE_RATE)
+            .with_grain_size(0.1)
+            .with_density(1.0);
+        let mut doubled = GranularSampler::new(ramp(1000), SAMPLE_RATE)
+            .with_grain_size(0.1)
+            .with_density(1.0)
+            .with_pitch(2.0);
+
+        // Within the first grain, each sample advances the read position by the pitch
+        let normal = render(&mut normal, 50);
+        let doubled = render(&mut doubled, 50);
+        assert!((normal[49] - normal[0] - 49.0).abs() < 1e-2);
+        assert!((doubled[49] - doubled[0] - 98.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_stop_lets_grains_finish() {
+        let mut sampler = GranularSampler::new(ramp(100), SAMPLE_RATE).with_grain_size(0.01);
+        render(&mut sampler, 5);
+        sampler.stop();
+        assert!(!sampler.completed());
+        render(&mut sampler, 10);
+        assert!(sampler.completed());
+        assert_eq!(sampler.tick(1.0 / SAMPLE_RATE), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod wavetable_tests {
+    use rustic::core::generator::prelude::{WavetableInterpolation, WavetableOscillator};
+    use std::f32::consts::TAU;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    fn sine_oscillator(interpolation: WavetableInterpolation) -> WavetableOscillator {
+        WavetableOscillator::from_fn(2048, 441.0, |phase| (TAU * phase).sin())
+            .with_interpolation(interpolation)
+    }
+
+    #[test]
+    fn test_sine_table_produces_sine() {
+        for (interpolation, tolerance) in [
+            (WavetableInterpolation::Linear, 1e-4),
+            (WavetableInterpolation::Cubic, 1e-4),
+        ] {
+            let mut oscillator = sine_oscillator(interpolation);
+            for n in 0..1000 {
+                let expected = (TAU * 441.0 * n as f32 / SAMPLE_RATE).sin();
+                let sample = oscillator.tick(1.0 / SAMPLE_RATE);
+                assert!(
+                    (sample - expected).abs() < tolerance,
+                    "{interpolation:?} sample {n}: {sample} != {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_frequency_changes_period() {
+        let mut oscillator = sine_oscillator(WavetableInterpolation::Linear);
+        oscillator.set_frequency(882.0);
+        let samples: Vec<f32> = (0..100)
+            .map(|_| oscillator.tick(1.0 / SAMPLE_RATE))
+            .collect();
+        // 50 samples per period: back to the start after one period
+        assert!((samples[50] - samples[0]).abs() < 1e-3);
+        assert!((samples[25] - samples[0]).abs() < 1e-3);
+        assert!((samples[12] - 1.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_morphing_interpolates_tables() {
+        let a = vec![0.0, 1.0, 0.0, -1.0];
+        let b = vec![1.0, 1.0, -1.0, -1.0];
+        // A quarter of the sample rate reads exactly one point per sample
+        let mut oscillator =
+            WavetableOscillator::new(vec![a.clone(), b.clone()], SAMPLE_RATE / 4.0)
+                .with_position(0.25);
+
+        for i in 0..8 {
+            let expected = 0.75 * a[i % 4] + 0.25 * b[i % 4];
+            assert!((oscillator.tick(1.0 / SAMPLE_RATE) - expected).abs() < 1e-5);
+        }
+
+        oscillator.start();
+        oscillator.set_position(1.0);
+        assert_eq!(oscillator.tick(1.0 / SAMPLE_RATE), b[0]);
+    }
+}
+
+#[cfg(test)]
+mod fm_tests {
+    use rustic::core::envelope::prelude::{
+        ADSREnvelope, ADSREnvelopeBuilder, ConstantSegment, LinearSegment,
+    };
+    use rustic::core::generator::prelude::FmVoice;
+    use std::f32::consts::TAU;
+
+    const SAMPLE_RATE: f32 = 8000.0;
+
+    /// Envelope reaching full level almost immediately and holding it.
+    fn held() -> ADSREnvelope {
+        ADSREnvelopeBuilder::new()
+            .attack(Box::new(LinearSegment::new(0.0, 1.0, 0.001)))
+            .decay(Box::new(LinearSegment::new(1.0, 1.0, 0.001)))
+            .sustain(Box::new(ConstantSegment::new(1.0, None)))
+            .build()
+    }
+
+    /// One second of a voice at 1 kHz, skipping the attack.
+    fn render(ratio: f32, modulation_index: f32) -> Vec<f32> {
+        let mut voice = FmVoice::new(1000.0, ratio, modulation_index)
+            .with_carrier_envelope(held())
+            .with_modulator_envelope(held());
+        voice.start();
+        (0..SAMPLE_RATE as usize + 100)
+            .map(|_| voice.tick(1.0 / SAMPLE_RATE))
+            .skip(100)
+            .collect()
+    }
+
+    /// Normalized DFT magnitude of `samples` at `frequency` (1 for a unit sine).
+    fn magnitude_at(samples: &[f32], frequency: f32) -> f32 {
+        let (re, im) = samples
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (n, s)| {
+                let angle = TAU * frequency * n as f32 / SAMPLE_RATE;
+                (re + s * angle.cos(), im - s * angle.sin())
+            });
+        2.0 * (re * re + im * im).sqrt() / samples.len() as f32
+    }
+
+    #[test]
+    fn test_zero_index_is_pure_carrier() {
+        let samples = render(2.0, 0.0);
+        assert!((magnitude_at(&samples, 1000.0) - 1.0).abs() < 0.01);
+        for sideband in [2000.0, 3000.0, 500.0] {
+            assert!(magnitude_at(&samples, sideband) < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_modulation_broadens_spectrum() {
+        // Ratio 0.5: sidebands at 1000 ± 500 k Hz
+        let samples = render(0.5, 2.0);
+        let carrier = magnitude_at(&samples, 1000.0);
+        let sidebands = [500.0, 1500.0, 2000.0].map(|f| magnitude_at(&samples, f));
+        assert!(carrier < 0.5, "carrier energy should spread: {carrier}");
+        assert!(sidebands.iter().all(|&m| m > 0.2), "{sidebands:?}");
+    }
+
+    #[test]
+    fn test_set_frequency_scales_both_operators() {
+        let mut voice = FmVoice::new(1000.0, 0.5, 2.0)
+            .with_carrier_envelope(held())
+            .with_modulator_envelope(held());
+        voice.set_frequency(2000.0);
+        voice.start();
+        let samples: Vec<f32> = (0..SAMPLE_RATE as usize + 100)
+            .map(|_| voice.tick(1.0 / SAMPLE_RATE))
+            .skip(100)
+            .collect();
+        // Sidebands follow the new base frequency: 2000 ± 1000 k Hz
+        assert!(magnitude_at(&samples, 3000.0) > 0.2);
+        assert!(magnitude_at(&samples, 1500.0) < 0.01);
+    }
+
+    #[test]
+    fn test_stop_completes_after_release() {
+        let mut voice = FmVoice::new(440.0, 1.0, 1.0);
+        voice.start();
+        (0..100).for_each(|_| {
+            voice.tick(1.0 / SAMPLE_RATE);
+        });
+        voice.stop();
+        assert!(!voice.completed());
+        (0..SAMPLE_RATE as usize * 2).for_each(|_| {
+            voice.tick(1.0 / SAMPLE_RATE);
+        });
+        assert!(voice.completed());
+    }
+}
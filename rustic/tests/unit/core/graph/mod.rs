@@ -2,26 +2,18 @@
 
 use rustic::core::audio::{Block, CHANNELS};
 use rustic::core::filters::prelude::{DelayFilter, GainFilter};
-use rustic::core::graph::{SimpleSink, Source, System};
-
-/// A trivial source that emits a constant stereo block.
-#[derive(Debug, Clone)]
-struct ConstantSource {
-    value: f32,
-}
-
-impl Source for ConstantSource {
-    fn pull(&mut self, block_size: usize) -> Block {
-        vec![[self.value; CHANNELS]; block_size]
-    }
-}
+use rustic::core::graph::{
+    AudioGraphError, ConstantSource, ExternalInputSource, Filter, Priority, SimpleSink, Source,
+    System,
+};
+use rustic::meta::GraphDescriptor;
 
 /// Helper: build a minimal system: source → gain → sink.
 fn build_simple_system(gain: f32, block_size: usize) -> System {
     let mut system = System::new().with_block_size(block_size);
 
     let gain_filter = system.add_filter(Box::new(GainFilter::new(gain)));
-    let source_idx = system.add_source(Box::new(ConstantSource { value: 0.5 }));
+    let source_idx = system.add_source(Box::new(ConstantSource::new(0.5)));
     let sink_idx = system.add_sink(Box::new(SimpleSink::new()));
 
     system.connect_source(source_idx, gain_filter, 0);
@@ -113,11 +105,11 @@ mod system_tests {
         let g1 = system.add_filter(Box::new(GainFilter::new(2.0)));
         let g2 = system.add_filter(Box::new(GainFilter::new(2.0)));
         let g3 = system.add_filter(Box::new(GainFilter::new(2.0)));
-        let src = system.add_source(Box::new(ConstantSource { value: 1.0 }));
+        let src = system.add_source(Box::new(ConstantSource::new(1.0)));
         let snk = system.add_sink(Box::new(SimpleSink::new()));
 
-        system.connect(g1, g2, 0, 0);
-        system.connect(g2, g3, 0, 0);
+        system.connect(g1, g2, 0, 0).unwrap();
+        system.connect(g2, g3, 0, 0).unwrap();
         system.connect_source(src, g1, 0);
         system.connect_sink(g3, snk, 0);
         system.compute().unwrap();
@@ -139,14 +131,36 @@ mod system_tests {
         let mut system = System::new();
         let a = system.add_filter(Box::new(GainFilter::new(1.0)));
         let b = system.add_filter(Box::new(GainFilter::new(1.0)));
-        // Create a cycle: a → b → a (no postponable filter to break it)
-        system.connect(a, b, 0, 0);
-        system.connect(b, a, 0, 0);
-        let result = system.compute();
+        // Closing a cycle a → b → a (no postponable filter to break it) is refused
+        system.connect(a, b, 0, 0).unwrap();
+        let result = system.connect(b, a, 0, 0);
         assert!(
-            result.is_err(),
-            "Cycle without postponable filter should error"
+            matches!(result, Err(AudioGraphError::FeedbackWithoutDelay { .. })),
+            "Cycle without postponable filter should error, got {:?}",
+            result
         );
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("delay"), "unhelpful message: {message}");
+
+        // So is a filter feeding itself
+        assert!(system.connect(a, a, 0, 0).is_err());
+
+        // The refused edges were not added
+        assert!(system.compute().is_ok());
+    }
+
+    #[test]
+    fn test_system_cycle_broken_by_delay_in_any_order() {
+        // Closing the loop on the delay's input is accepted as well
+        let mut system = System::new();
+        let mixer = system.add_filter(Box::new(GainFilter::new(1.0)));
+        let gain = system.add_filter(Box::new(GainFilter::new(0.5)));
+        let delay = system.add_filter(Box::new(DelayFilter::new(44100.0, 0.001)));
+
+        system.connect(delay, mixer, 0, 0).unwrap();
+        system.connect(mixer, gain, 0, 0).unwrap();
+        system.connect(gain, delay, 0, 0).unwrap();
+        assert!(system.compute().is_ok());
     }
 
     #[test]
@@ -158,14 +172,14 @@ mod system_tests {
         let gain = system.add_filter(Box::new(GainFilter::new(0.5)));
         let delay = system.add_filter(Box::new(DelayFilter::new(44100.0, 0.001)));
 
-        let src = system.add_source(Box::new(ConstantSource { value: 1.0 }));
+        let src = system.add_source(Box::new(ConstantSource::new(1.0)));
         let snk = system.add_sink(Box::new(SimpleSink::new()));
 
         // source → mixer (port 0); delayed feedback also → mixer (port 0)
         system.connect_source(src, mixer, 0);
-        system.connect(mixer, gain, 0, 0);
-        system.connect(gain, delay, 0, 0);
-        system.connect(delay, mixer, 0, 0); // feedback on same port — DelayFilter breaks cycle
+        system.connect(mixer, gain, 0, 0).unwrap();
+        system.connect(gain, delay, 0, 0).unwrap();
+        system.connect(delay, mixer, 0, 0).unwrap(); // feedback on same port — DelayFilter breaks cycle
         system.connect_sink(gain, snk, 0);
 
         // Should succeed because DelayFilter is postponable
@@ -182,8 +196,8 @@ mod system_tests {
         // Two sources connect to port 0 of a GainFilter; the run loop sums them.
         let mut system = System::new().with_block_size(4);
         let mixer = system.add_filter(Box::new(GainFilter::new(1.0)));
-        let src1 = system.add_source(Box::new(ConstantSource { value: 0.3 }));
-        let src2 = system.add_source(Box::new(ConstantSource { value: 0.7 }));
+        let src1 = system.add_source(Box::new(ConstantSource::new(0.3)));
+        let src2 = system.add_source(Box::new(ConstantSource::new(0.7)));
         let snk = system.add_sink(Box::new(SimpleSink::new()));
 
         system.connect_source(src1, mixer, 0);
@@ -215,4 +229,495 @@ mod system_tests {
         system.start_source(0);
         system.stop_source(0);
     }
+
+    #[test]
+    fn test_system_budget_skips_low_priority() {
+        // source → main gain → sink 0
+        //        ↘ tail gain → sink 1 (low priority)
+        let mut system = System::new().with_block_size(8);
+        let main = system.add_filter(Box::new(GainFilter::new(2.0)));
+        let tail = system.add_filter(Box::new(GainFilter::new(3.0)));
+        let src = system.add_source(Box::new(ConstantSource::new(0.5)));
+        let main_sink = system.add_sink(Box::new(SimpleSink::new()));
+        let tail_sink = system.add_sink(Box::new(SimpleSink::new()));
+
+        system.connect_source(src, main, 0);
+        system.connect_source(src, tail, 0);
+        system.connect_sink(main, main_sink, 0);
+        system.connect_sink(tail, tail_sink, 0);
+        system.set_priority(tail, Priority::Low);
+        system.compute().unwrap();
+
+        // Without a budget every node runs
+        system.run();
+        assert_eq!(system.skipped_nodes(), 0);
+        assert!((system.get_sink(tail_sink).unwrap().consume()[0][0] - 1.5).abs() < 1e-5);
+        system.get_sink(main_sink).unwrap().consume();
+
+        // A zero budget is always exceeded: the low-priority tail is skipped
+        system.set_budget(Some(std::time::Duration::ZERO));
+        system.run();
+        assert_eq!(system.skipped_nodes(), 1);
+
+        let main_frames = system.get_sink(main_sink).unwrap().consume();
+        assert_eq!(main_frames.len(), 8);
+        assert!(main_frames.iter().all(|f| (f[0] - 1.0).abs() < 1e-5));
+
+        let tail_frames = system.get_sink(tail_sink).unwrap().consume();
+        assert_eq!(tail_frames.len(), 8);
+        assert!(tail_frames.iter().all(|f| f[0] == 0.0 && f[1] == 0.0));
+    }
+
+    #[test]
+    fn test_external_input_reaches_sink() {
+        let mut system = System::new().with_block_size(4);
+        let gain = system.add_filter(Box::new(GainFilter::new(1.0)));
+        let (source, handle) = ExternalInputSource::new(16);
+        let src = system.add_source(Box::new(source));
+        let snk = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(src, gain, 0);
+        system.connect_sink(gain, snk, 0);
+        system.compute().unwrap();
+
+        // The handle keeps feeding the source after it moved into the system
+        let block = [[0.1, -0.1], [0.2, -0.2], [0.3, -0.3]];
+        assert_eq!(handle.push(&block), 3);
+        system.run();
+
+        let frames = system.get_sink(snk).unwrap().consume();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(&frames[..3], &block[..]);
+        // Underrun is rendered as silence
+        assert_eq!(frames[3], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_external_input_capacity() {
+        let (source, handle) = ExternalInputSource::new(2);
+        assert_eq!(handle.push_interleaved(&[0.1, 0.2, 0.3, 0.4, 0.5, 0.6]), 2);
+        assert_eq!(handle.available(), 0);
+        assert_eq!(source.buffered(), 2);
+    }
+
+    #[test]
+    fn test_system_descriptor_round_trip() {
+        let mut system = System::new().with_block_size(16);
+        let g1 = system.add_filter(Box::new(GainFilter::new(0.5)));
+        let g2 = system.add_filter(Box::new(GainFilter::new(3.0)));
+        let src = system.add_source(Box::new(ConstantSource::new(0.4)));
+        let snk = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(src, g1, 0);
+        system.connect(g1, g2, 0, 0).unwrap();
+        system.connect_sink(g2, snk, 0);
+        system.compute().unwrap();
+
+        let descriptor = system.to_descriptor();
+        assert_eq!(descriptor.nodes.len(), 2);
+        assert_eq!(descriptor.nodes[1].type_id, "GainFilter");
+        assert_eq!(descriptor.nodes[1].parameters.get("factor"), Some(&3.0));
+
+        let json = descriptor.to_json().unwrap();
+        let parsed = GraphDescriptor::from_json(&json).unwrap();
+        assert_eq!(parsed, descriptor);
+
+        let factory = |type_id: &str| match type_id {
+            "GainFilter" => Some(Box::new(GainFilter::default()) as Box<dyn Filter>),
+            _ => None,
+        };
+        let mut rebuilt = System::from_descriptor(
+            &parsed,
+            factory,
+            vec![Box::new(ConstantSource::new(0.4))],
+            vec![Box::new(SimpleSink::new())],
+        )
+        .unwrap();
+
+        system.run();
+        rebuilt.run();
+        let expected = system.get_sink(0).unwrap().consume();
+        assert_eq!(rebuilt.get_sink(0).unwrap().consume(), expected);
+        assert!((expected[0][0] - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_system_from_descriptor_errors() {
+        let descriptor = build_simple_system(1.0, 8).to_descriptor();
+
+        let unknown = System::from_descriptor(
+            &descriptor,
+            |_| None,
+            vec![Box::new(ConstantSource::new(0.0))],
+            vec![Box::new(SimpleSink::new())],
+        );
+        assert!(
+            matches!(unknown, Err(AudioGraphError::UnknownFilter(name)) if name == "GainFilter")
+        );
+
+        let missing_sink = System::from_descriptor(
+            &descriptor,
+            |_| Some(Box::new(GainFilter::default()) as Box<dyn Filter>),
+            vec![Box::new(ConstantSource::new(0.0))],
+            vec![],
+        );
+        assert!(matches!(
+            missing_sink,
+            Err(AudioGraphError::DescriptorMismatch {
+                kind: "sinks",
+                expected: 1,
+                got: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parameter_ports_drive_parameters() {
+        use rustic::core::filters::prelude::EnvelopeFollower;
+
+        // The envelope of a constant 0.5 sets the gain applied to a constant 1.0
+        let mut system = System::new().with_block_size(512);
+        let follower = system.add_filter(Box::new(EnvelopeFollower::new(0.0001, 0.1, 44100.0)));
+        let gain = system.add_filter(Box::new(GainFilter::new(1.0)));
+        let control = system.add_source(Box::new(ConstantSource::new(0.5)));
+        let signal = system.add_source(Box::new(ConstantSource::new(1.0)));
+        let sink = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(control, follower, 0);
+        system.connect_source(signal, gain, 0);
+        // Output port 1 of the follower to the `factor` port of the gain
+        system.connect(follower, gain, 1, 1).unwrap();
+        system.connect_sink(gain, sink, 0);
+        system.compute().unwrap();
+
+        system.run();
+        system.run();
+        let frames = system.get_sink(0).unwrap().consume();
+        let last = frames.last().unwrap();
+        assert!((last[0] - 0.5).abs() < 1e-3, "{last:?}");
+        let factor = system.get_filter_mut(gain).unwrap().get_parameter("factor");
+        assert!((factor.unwrap() - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_set_sample_rate_reaches_every_filter() {
+        // 4 ms of delay is 4 frames at 1 kHz, and 8 frames at 2 kHz
+        let mut system = System::new().with_block_size(16);
+        let delay = system.add_filter(Box::new(DelayFilter::new(1000.0, 0.004)));
+        let source = system.add_source(Box::new(ConstantSource::new(0.5)));
+        let sink = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(source, delay, 0);
+        system.connect_sink(delay, sink, 0);
+        system.compute().expect("compute should succeed");
+
+        system.set_sample_rate(2000.0);
+        system.run();
+        let frames = system.get_sink(0).unwrap().consume();
+        let silent = frames.iter().take_while(|frame| frame[0] == 0.0).count();
+        assert_eq!(silent, 8);
+    }
+    #[test]
+    fn test_remove_filter_keeps_moved_filter_wired() {
+        // Removing the first filter moves the second into its index
+        let mut system = System::new().with_block_size(4);
+        let removed = system.add_filter(Box::new(GainFilter::new(3.0)));
+        let kept = system.add_filter(Box::new(GainFilter::new(2.0)));
+        let source = system.add_source(Box::new(ConstantSource::new(0.5)));
+        let sink = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(source, removed, 0);
+        system.connect_source(source, kept, 0);
+        system.connect_sink(kept, sink, 0);
+
+        assert!(system.remove_filter(removed).is_some());
+        assert_eq!(system.filters_len(), 1);
+        system.compute().expect("compute should succeed");
+        system.run();
+
+        let frames = system.get_sink(0).unwrap().consume();
+        assert_eq!(frames.len(), 4);
+        for frame in &frames {
+            assert!(
+                (frame[0] - 1.0).abs() < 1e-5,
+                "0.5 * gain(2.0) = 1.0, got {}",
+                frame[0]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod validation_tests {
+    use super::*;
+    use rustic::core::graph::Entry;
+    use rustic_meta::{FilterInfo, FilterInput, MetaFilter};
+    use std::fmt;
+    use std::sync::Arc;
+
+    /// Sums its two inputs, optionally working on fixed-size blocks.
+    #[derive(Debug, Clone, Default)]
+    struct Combinator {
+        inputs: [Arc<Block>; 2],
+        frames: Option<usize>,
+    }
+
+    impl Entry for Combinator {
+        fn push(&mut self, block: Arc<Block>, port: usize) {
+            self.inputs[port] = block;
+        }
+    }
+
+    impl fmt::Display for Combinator {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Combinator")
+        }
+    }
+
+    impl MetaFilter for Combinator {
+        fn set_parameter(&mut self, _name: &str, _value: f32) {}
+
+        fn get_parameter(&self, _name: &str) -> Option<f32> {
+            None
+        }
+
+        fn filter_info(&self) -> FilterInfo {
+            Self::metadata()
+        }
+
+        fn metadata() -> FilterInfo {
+            let input = FilterInput {
+                label: None,
+                parameter: None,
+            };
+            FilterInfo {
+                name: "Combinator",
+                type_id: "Combinator",
+                description: "Sums two inputs",
+                inputs: vec![input.clone(), input],
+                outputs: 1,
+            }
+        }
+    }
+
+    impl Filter for Combinator {
+        fn transform(&mut self) -> Vec<Block> {
+            let [a, b] = &self.inputs;
+            vec![
+                a.iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| std::array::from_fn(|ch| a[ch] + b[ch]))
+                    .collect(),
+            ]
+        }
+
+        fn block_size(&self) -> Option<usize> {
+            self.frames
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    /// Two sources into a combinator, the second one on `second_port`.
+    fn combinator_system(combinator: Combinator, second_port: Option<usize>) -> System {
+        let mut system = System::new().with_block_size(8);
+        let node = system.add_filter(Box::new(combinator));
+        let a = system.add_source(Box::new(ConstantSource::new(0.25)));
+        let b = system.add_source(Box::new(ConstantSource::new(0.5)));
+        let sink = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(a, node, 0);
+        if let Some(port) = second_port {
+            system.connect_source(b, node, port);
+        }
+        system.connect_sink(node, sink, 0);
+        system
+    }
+
+    #[test]
+    fn test_fully_connected_combinator() {
+        let mut system = combinator_system(Combinator::default(), Some(1));
+        system.compute().unwrap();
+        system.run();
+        let frames = system.get_sink(0).unwrap().consume();
+        assert_eq!(frames, vec![[0.75; CHANNELS]; 8]);
+    }
+
+    #[test]
+    fn test_missing_input_is_reported() {
+        let mut system = combinator_system(Combinator::default(), None);
+        let err = system.compute().unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                AudioGraphError::MissingInput { filter, port: 1, expected: 2 } if filter == "Combinator"
+            ),
+            "{err:?}"
+        );
+        assert_eq!(
+            err.to_string(),
+            "filter `Combinator` expects 2 audio inputs, but its port 1 is not connected"
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_ports_are_reported() {
+        let mut system = combinator_system(Combinator::default(), Some(2));
+        assert!(matches!(
+            system.compute(),
+            Err(AudioGraphError::InputPortOutOfRange {
+                port: 2,
+                inputs: 2,
+                ..
+            })
+        ));
+
+        let mut system = System::new();
+        let a = system.add_filter(Box::new(GainFilter::new(1.0)));
+        let b = system.add_filter(Box::new(GainFilter::new(1.0)));
+        system.connect(a, b, 1, 0).unwrap();
+        assert!(matches!(
+            system.compute(),
+            Err(AudioGraphError::OutputPortOutOfRange {
+                port: 1,
+                outputs: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_block_size_must_fit_filter() {
+        let fits = Combinator {
+            frames: Some(4),
+            ..Default::default()
+        };
+        assert!(combinator_system(fits, Some(1)).compute().is_ok());
+
+        let too_large = Combinator {
+            frames: Some(16),
+            ..Default::default()
+        };
+        assert!(matches!(
+            combinator_system(too_large, Some(1)).compute(),
+            Err(AudioGraphError::BlockSizeMismatch {
+                expected: 16,
+                got: 8,
+                ..
+            })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod constant_source_tests {
+    use super::*;
+    use rustic::core::graph::SilenceSource;
+
+    #[test]
+    fn test_constant_source_blocks() {
+        let mut source = ConstantSource::new(0.25);
+        assert_eq!(source.pull(32), vec![[0.25; CHANNELS]; 32]);
+        assert!(source.is_active());
+
+        let mut source = ConstantSource::per_channel([0.5, -1.0]);
+        let block = source.pull(7);
+        assert_eq!(block.len(), 7);
+        assert!(block.iter().all(|frame| *frame == [0.5, -1.0]));
+    }
+
+    #[test]
+    fn test_silence_source_blocks() {
+        let mut source = SilenceSource;
+        let block: Block = source.pull(64);
+        assert_eq!(block, vec![[0.0; CHANNELS]; 64]);
+        assert!(source.pull(0).is_empty());
+        assert!(!source.is_active());
+        assert!(!ConstantSource::new(0.0).is_active());
+    }
+}
+
+#[cfg(test)]
+mod file_source_tests {
+    use super::*;
+    use hound::{SampleFormat, WavSpec, WavWriter};
+    use rustic::core::graph::FileSource;
+    use std::path::PathBuf;
+
+    /// Writes 16-bit samples to a temporary WAV file and returns its path.
+    fn write_wav(name: &str, channels: u16, sample_rate: u32, samples: &[i16]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rustic_{name}_{}.wav", std::process::id()));
+        let spec = WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).unwrap();
+        for &sample in samples {
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_pulled_frames_match_stereo_file() {
+        let samples: Vec<i16> = (0..16).map(|i| i * 1024 - 8192).collect();
+        let path = write_wav("stereo", 2, 44100, &samples);
+        let mut source = FileSource::open(&path, 44100).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(source.len(), 8);
+        let block = source.pull(8);
+        for (frame, chunk) in block.iter().zip(samples.chunks(2)) {
+            assert_eq!(frame[0], chunk[0] as f32 / 32768.0);
+            assert_eq!(frame[1], chunk[1] as f32 / 32768.0);
+        }
+
+        // Without looping the source falls silent at the end of the file
+        assert_eq!(source.pull(4), vec![[0.0; CHANNELS]; 4]);
+        assert!(!source.is_active());
+    }
+
+    #[test]
+    fn test_mono_file_is_duplicated_to_stereo() {
+        let path = write_wav("mono", 1, 44100, &[16384, -16384, 8192]);
+        let mut source = FileSource::open(&path, 44100).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(source.pull(3), vec![[0.5; 2], [-0.5; 2], [0.25; 2]]);
+    }
+
+    #[test]
+    fn test_looping_wraps_around() {
+        let path = write_wav("loop", 1, 44100, &[0, 8192, 16384]);
+        let mut source = FileSource::open(&path, 44100).unwrap().with_looping(true);
+        std::fs::remove_file(&path).ok();
+
+        let left: Vec<f32> = source.pull(7).iter().map(|frame| frame[0]).collect();
+        assert_eq!(left, vec![0.0, 0.25, 0.5, 0.0, 0.25, 0.5, 0.0]);
+        assert_eq!(source.position(), 1);
+        assert!(source.is_active());
+    }
+
+    #[test]
+    fn test_file_is_resampled_to_system_rate() {
+        let samples: Vec<i16> = (0..100).map(|i| i * 100).collect();
+        let path = write_wav("resample", 1, 22050, &samples);
+        let mut source = FileSource::open(&path, 44100).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(source.len(), 200);
+        let block = source.pull(4);
+        let expected = [0.0, 50.0, 100.0, 150.0].map(|s| s / 32768.0);
+        for (frame, expected) in block.iter().zip(expected) {
+            assert!((frame[0] - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_start_rewinds() {
+        let mut source = FileSource::from_frames(vec![[0.1; CHANNELS], [0.2; CHANNELS]], false);
+        source.pull(2);
+        source.start();
+        assert_eq!(source.position(), 0);
+        assert_eq!(source.pull(1), vec![[0.1; CHANNELS]]);
+    }
 }
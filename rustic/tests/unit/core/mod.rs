@@ -7,4 +7,5 @@ pub mod envelope;
 pub mod filters;
 pub mod generator;
 pub mod graph;
+pub mod modulation;
 pub mod utils;
@@ -0,0 +1,256 @@
+//! LFO and modulation router tests
+
+use rustic::core::modulation::{Lfo, LfoPolarity, LfoRate, LfoShape, ModulationRouter};
+
+#[cfg(test)]
+mod lfo_tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 1000.0;
+
+    fn values(lfo: &mut Lfo, n: usize) -> Vec<f32> {
+        (0..n).map(|_| lfo.tick_block(1)).collect()
+    }
+
+    #[test]
+    fn test_output_is_periodic_at_rate() {
+        // 4 Hz at 1 kHz: a period of 250 frames
+        for shape in [LfoShape::Sine, LfoShape::Triangle, LfoShape::Square] {
+            let mut lfo = Lfo::new(shape, 4.0, SAMPLE_RATE);
+            let output = values(&mut lfo, 1000);
+            for i in 0..750 {
+                assert!(
+                    (output[i] - output[i + 250]).abs() < 1e-3,
+                    "{shape:?} frame {i}"
+                );
+            }
+            assert!(output.iter().any(|&v| v > 0.99));
+            assert!(output.iter().any(|&v| v < -0.99));
+        }
+    }
+
+    #[test]
+    fn test_shapes_start_of_cycle() {
+        let quarter = |shape| {
+            let mut lfo = Lfo::new(shape, 1.0, SAMPLE_RATE);
+            let start = lfo.value();
+            lfo.advance(250);
+            (start, lfo.value())
+        };
+        let (start, peak) = quarter(LfoShape::Sine);
+        assert!(start.abs() < 1e-6 && (peak - 1.0).abs() < 1e-6);
+        let (start, peak) = quarter(LfoShape::Triangle);
+        assert!(start.abs() < 1e-6 && (peak - 1.0).abs() < 1e-6);
+        assert_eq!(quarter(LfoShape::Square), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_depth_phase_and_polarity() {
+        let lfo = Lfo::new(LfoShape::Sine, 1.0, SAMPLE_RATE)
+            .with_depth(0.5)
+            .with_phase(0.25);
+        assert!((lfo.value() - 0.5).abs() < 1e-6);
+
+        let mut lfo = Lfo::new(LfoShape::Sine, 1.0, SAMPLE_RATE)
+            .with_polarity(LfoPolarity::Unipolar)
+            .with_depth(2.0);
+        let output = values(&mut lfo, 1000);
+        assert!(output.iter().all(|&v| (0.0..=2.0).contains(&v)));
+        assert!((output[0] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sample_and_hold_holds_each_period() {
+        // 8 Hz at 1024 Hz: a period of exactly 128 frames
+        let mut lfo = Lfo::new(LfoShape::SampleAndHold, 8.0, 1024.0);
+        let output = values(&mut lfo, 384);
+        for period in output.chunks(128) {
+            assert!(period.iter().all(|&v| v == period[0]));
+            assert!((-1.0..=1.0).contains(&period[0]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod lfo_rate_tests {
+    use super::*;
+
+    #[test]
+    fn test_quarter_note_at_120_bpm_is_2hz() {
+        assert_eq!(LfoRate::note(4).to_hz(120.0), 2.0);
+        assert_eq!(LfoRate::note(1).to_hz(120.0), 0.5);
+        assert_eq!(LfoRate::Hz(3.5).to_hz(120.0), 3.5);
+    }
+
+    #[test]
+    fn test_dotted_and_triplet_divisions() {
+        // A dotted eighth lasts 0.75 beats: 2 / 0.75 Hz at 120 BPM
+        assert!((LfoRate::dotted(8).to_hz(120.0) - 8.0 / 3.0).abs() < 1e-6);
+        // Three eighth-note triplets per beat
+        assert!((LfoRate::triplet(8).to_hz(120.0) - 6.0).abs() < 1e-6);
+        assert!((LfoRate::note(16).to_hz(90.0) - 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_synced_lfo_follows_tempo() {
+        let mut lfo = Lfo::new(LfoShape::Sine, LfoRate::note(4), 1000.0);
+        assert_eq!(lfo.frequency(), 2.0);
+        lfo.set_bpm(60.0);
+        assert_eq!(lfo.frequency(), 1.0);
+
+        // A quarter of a one-second cycle reaches the peak
+        lfo.advance(250);
+        assert!((lfo.value() - 1.0).abs() < 1e-6);
+
+        lfo.set_rate(5.0);
+        assert_eq!(lfo.rate(), LfoRate::Hz(5.0));
+    }
+}
+
+#[cfg(test)]
+mod router_tests {
+    use super::*;
+    use rustic::core::audio::{Block, CHANNELS};
+    use rustic::core::filters::prelude::GainFilter;
+    use rustic::core::graph::{Filter, SimpleSink, Source, System};
+    use rustic::meta::GraphDescriptor;
+
+    #[derive(Debug, Clone)]
+    struct DcSource;
+
+    impl Source for DcSource {
+        fn pull(&mut self, block_size: usize) -> Block {
+            vec![[1.0; CHANNELS]; block_size]
+        }
+    }
+
+    #[test]
+    fn test_router_applies_base_plus_lfo() {
+        let mut router = ModulationRouter::new();
+        let target = petgraph::prelude::NodeIndex::new(3);
+        router.add_route(
+            Lfo::new(LfoShape::Square, 1.0, 100.0),
+            target,
+            "factor",
+            0.5,
+        );
+
+        let mut applied = Vec::new();
+        for _ in 0..2 {
+            router.apply(50, |node, name, value| {
+                applied.push((node.index(), name.to_string(), value))
+            });
+        }
+        assert_eq!(
+            applied,
+            vec![
+                (3, "factor".to_string(), 1.5),
+                (3, "factor".to_string(), -0.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lfo_modulates_gain_over_time() {
+        let block_size = 32;
+        let mut system = System::new().with_block_size(block_size);
+        let gain = system.add_filter(Box::new(GainFilter::new(1.0)));
+        let source = system.add_source(Box::new(DcSource));
+        let sink = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(source, gain, 0);
+        system.connect_sink(gain, sink, 0);
+        system.compute().unwrap();
+
+        // One cycle over 16 blocks, swinging the gain between 0.25 and 0.75
+        let lfo = Lfo::new(LfoShape::Sine, 1.0, (16 * block_size) as f32).with_depth(0.25);
+        system.add_lfo(lfo, gain, "factor", 0.5);
+
+        let mut levels = Vec::new();
+        for _ in 0..16 {
+            system.run();
+            let block = system.get_sink(sink).unwrap().consume();
+            levels.push(block[0][0]);
+        }
+
+        assert!((levels[0] - 0.5).abs() < 1e-5);
+        assert!((levels[4] - 0.75).abs() < 1e-5);
+        assert!((levels[12] - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_lfo_routes_survive_absorbing_the_system() {
+        let block_size = 32;
+        let mut instrument = System::new().with_block_size(block_size);
+        let gain = instrument.add_filter(Box::new(GainFilter::new(1.0)));
+        let source = instrument.add_source(Box::new(DcSource));
+        let sink = instrument.add_sink(Box::new(SimpleSink::new()));
+        instrument.connect_source(source, gain, 0);
+        instrument.connect_sink(gain, sink, 0);
+        let lfo = Lfo::new(LfoShape::Sine, 1.0, (16 * block_size) as f32).with_depth(0.25);
+        instrument.add_lfo(lfo, gain, "factor", 0.5);
+
+        // The main graph already holds a filter, so the gain moves to a new index
+        let mut main = System::new().with_block_size(block_size);
+        main.add_filter(Box::new(GainFilter::new(1.0)));
+        let output = main.absorb(instrument).unwrap();
+        assert_ne!(output, gain);
+        let sink = main.add_sink(Box::new(SimpleSink::new()));
+        main.connect_sink(output, sink, 0);
+        main.compute().unwrap();
+
+        assert_eq!(main.modulation().routes()[0].target, output);
+        let mut levels = Vec::new();
+        for _ in 0..16 {
+            main.run();
+            let block = main.get_sink(sink).unwrap().consume();
+            levels.push(block[0][0]);
+        }
+        assert!((levels[0] - 0.5).abs() < 1e-5);
+        assert!((levels[4] - 0.75).abs() < 1e-5);
+        assert!((levels[12] - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_lfo_routes_survive_a_descriptor_round_trip() {
+        let block_size = 32;
+        let mut system = System::new().with_block_size(block_size);
+        let gain = system.add_filter(Box::new(GainFilter::new(1.0)));
+        let source = system.add_source(Box::new(DcSource));
+        let sink = system.add_sink(Box::new(SimpleSink::new()));
+        system.connect_source(source, gain, 0);
+        system.connect_sink(gain, sink, 0);
+        system.compute().unwrap();
+        let lfo = Lfo::new(LfoShape::Triangle, LfoRate::note(8), 44100.0)
+            .with_depth(0.25)
+            .with_phase(0.25)
+            .with_polarity(LfoPolarity::Unipolar)
+            .with_bpm(90.0);
+        system.add_lfo(lfo, gain, "factor", 0.5);
+
+        let descriptor = system.to_descriptor();
+        let parsed = GraphDescriptor::from_json(&descriptor.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, descriptor);
+        assert_eq!(parsed.lfo_routes.len(), 1);
+
+        let mut rebuilt = System::from_descriptor(
+            &parsed,
+            |_| Some(Box::new(GainFilter::default()) as Box<dyn Filter>),
+            vec![Box::new(DcSource)],
+            vec![Box::new(SimpleSink::new())],
+        )
+        .unwrap();
+        let route = &rebuilt.modulation().routes()[0];
+        assert_eq!(route.param_name, "factor");
+        assert_eq!(route.lfo.rate(), LfoRate::note(8));
+        assert_eq!(route.lfo.polarity(), LfoPolarity::Unipolar);
+
+        for _ in 0..8 {
+            system.run();
+            rebuilt.run();
+            assert_eq!(
+                rebuilt.get_sink(sink).unwrap().consume(),
+                system.get_sink(sink).unwrap().consume()
+            );
+        }
+    }
+}
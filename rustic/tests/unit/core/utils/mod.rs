@@ -95,3 +95,70 @@ mod tones_tests {
     // - Test tone frequency calculations
     // - Test tone mappings
 }
+
+#[cfg(test)]
+mod tuning_tests {
+    use pretty_assertions::assert_eq;
+    use rustic::core::utils::{NOTES, Note, TuningError, TuningTable};
+
+    #[test]
+    fn test_default_is_equal_temperament() {
+        let tuning = TuningTable::default();
+        assert!(tuning.is_equal_temperament());
+        for midi in 24..96 {
+            let note = Note::from_midi(midi);
+            assert_eq!(tuning.frequency(note), note.frequency());
+        }
+    }
+
+    #[test]
+    fn test_quarter_tone_offset_ratio() {
+        let tuning = TuningTable::equal().with_cents(NOTES::E, -50.0);
+        let quarter_tone = 2f32.powf(-50.0 / 1200.0);
+
+        for octave in 2..7 {
+            let e = Note(NOTES::E, octave);
+            let ratio = tuning.frequency(e) / e.frequency();
+            assert!((ratio - quarter_tone).abs() < 1e-6, "ratio {ratio}");
+        }
+        // Other pitch classes are untouched
+        let f = Note(NOTES::F, 4);
+        assert_eq!(tuning.frequency(f), f.frequency());
+    }
+
+    #[test]
+    fn test_parse_tuning_file() {
+        let tuning = TuningTable::parse(
+            "! rast.txt\n\
+             # Quarter-tone flat third and seventh\n\
+             \n\
+             E   -50\n\
+             Bb  -50.0\n\
+             f#  +2\n",
+        )
+        .unwrap();
+        assert_eq!(tuning.cents(NOTES::E), -50.0);
+        assert_eq!(tuning.cents(NOTES::AS), -50.0);
+        assert_eq!(tuning.cents(NOTES::FS), 2.0);
+        assert_eq!(tuning.cents(NOTES::C), 0.0);
+    }
+
+    #[test]
+    fn test_parse_errors_report_lines() {
+        assert_eq!(
+            TuningTable::parse("C 0\nH 10"),
+            Err(TuningError::UnknownPitchClass {
+                line: 2,
+                name: "H".to_string()
+            })
+        );
+        assert_eq!(
+            TuningTable::parse("E flat"),
+            Err(TuningError::InvalidLine {
+                line: 1,
+                content: "E flat".to_string()
+            })
+        );
+        assert!(TuningTable::parse("E -50 cents").is_err());
+    }
+}
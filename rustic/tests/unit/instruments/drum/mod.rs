@@ -27,3 +27,86 @@ mod hihat_tests {
     // - Test noise filtering
     // - Test decay characteristics
 }
+
+#[cfg(test)]
+mod drum_kit_tests {
+    use rustic::core::utils::{NOTES, Note};
+    use rustic::instruments::Instrument;
+    use rustic::instruments::prelude::{DrumKit, Kick, Snare};
+
+    const KICK: Note = Note(NOTES::C, 2);
+
+    fn tick_n(kit: &mut DrumKit, n: usize) -> f32 {
+        (0..n)
+            .map(|_| {
+                kit.tick();
+                kit.get_output().abs()
+            })
+            .fold(0.0, f32::max)
+    }
+
+    fn envelope(kit: &DrumKit, name: &str) -> f32 {
+        kit.pad(name).unwrap().instrument().envelope_level()
+    }
+
+    #[test]
+    fn test_default_kit_uses_general_midi_notes() {
+        let kit = DrumKit::new().unwrap();
+        assert_eq!(kit.note_of("kick").map(|n| n.to_midi()), Some(36));
+        assert_eq!(kit.note_of("snare").map(|n| n.to_midi()), Some(38));
+        assert_eq!(kit.note_of("hihat").map(|n| n.to_midi()), Some(42));
+        assert_eq!(kit.note_of("cowbell"), None);
+    }
+
+    #[test]
+    fn test_kick_note_triggers_kick() {
+        let mut kit = DrumKit::new().unwrap();
+        kit.start_note(KICK, 1.0);
+        assert!(tick_n(&mut kit, 200) > 0.0);
+        assert!(envelope(&kit, "kick") > 0.0);
+        assert_eq!(envelope(&kit, "snare"), 0.0);
+    }
+
+    #[test]
+    fn test_unmapped_note_is_ignored() {
+        let mut kit = DrumKit::new().unwrap();
+        kit.start_note(Note(NOTES::A, 4), 1.0);
+        assert_eq!(tick_n(&mut kit, 200), 0.0);
+        assert!(!kit.trigger("cowbell", 1.0));
+    }
+
+    #[test]
+    fn test_pad_gain_and_velocity_scale_output() {
+        let peak = |gain: f32, velocity: f32| {
+            let mut kit = DrumKit::empty().with_pad("snare", KICK, gain, Box::new(Snare::new()));
+            assert!(kit.trigger("snare", velocity));
+            tick_n(&mut kit, 2000)
+        };
+        let full = peak(1.0, 1.0);
+        assert!(full > 0.0);
+        // The snare mixes in white noise, so compare loosely
+        assert!(peak(0.25, 1.0) < full * 0.5);
+        assert!(peak(1.0, 0.25) < full * 0.5);
+    }
+
+    #[test]
+    fn test_compiled_kit_routes_notes_to_pads() {
+        let kit = DrumKit::empty().with_pad("kick", KICK, 1.0, Box::new(Kick::new()));
+        let mut system = Box::new(kit).into_system(44100.0);
+
+        let peak = |system: &mut rustic::core::graph::System| {
+            system.run();
+            system
+                .get_sink(0)
+                .unwrap()
+                .consume()
+                .iter()
+                .map(|frame| frame[0].abs())
+                .fold(0.0, f32::max)
+        };
+        system.start_note(0, Note(NOTES::D, 2), 1.0);
+        assert_eq!(peak(&mut system), 0.0);
+        system.start_note(0, KICK, 1.0);
+        assert!(peak(&mut system) > 0.0);
+    }
+}
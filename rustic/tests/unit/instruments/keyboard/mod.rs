@@ -10,6 +10,147 @@ mod keyboard_tests {
     // - Test voice stealing
 }
 
+#[cfg(test)]
+mod envelope_level_tests {
+    use rustic::core::envelope::prelude::{ADSREnvelopeBuilder, ConstantSegment, LinearSegment};
+    use rustic::instruments::Instrument;
+    use rustic::instruments::prelude::{Keyboard, PolyVoiceAllocator};
+    use rustic::core::utils::{NOTES, Note};
+
+    fn tick_n(keyboard: &mut Keyboard, n: usize) {
+        for _ in 0..n {
+            keyboard.tick();
+        }
+    }
+
+    #[test]
+    fn test_envelope_level_rises_in_attack_and_falls_in_release() {
+        let envelope = ADSREnvelopeBuilder::new()
+            .attack(Box::new(LinearSegment::new(0.0, 1.0, 0.1)))
+            .decay(Box::new(LinearSegment::new(1.0, 0.8, 0.1)))
+            .sustain(Box::new(ConstantSegment::new(0.8, None)))
+            .release(Box::new(LinearSegment::new(0.8, 0.0, 0.2)))
+            .build();
+        let mut keyboard = Keyboard::new(2, PolyVoiceAllocator::default(), envelope);
+        let meter = keyboard.envelope_meter().expect("Keyboard exposes a meter");
+        let note = Note(NOTES::A, 4);
+
+        assert_eq!(keyboard.envelope_level(), 0.0);
+        keyboard.start_note(note, 1.0);
+
+        // Attack: 0.1s = 4410 ticks
+        tick_n(&mut keyboard, 1000);
+        let early = keyboard.envelope_level();
+        tick_n(&mut keyboard, 2000);
+        let late = keyboard.envelope_level();
+        assert!(late > early, "attack should rise: {early} -> {late}");
+        assert_eq!(late, meter.load(std::sync::atomic::Ordering::Relaxed));
+
+        // Reach sustain, then release
+        tick_n(&mut keyboard, 10_000);
+        let sustain = keyboard.envelope_level();
+        keyboard.stop_note(note);
+        tick_n(&mut keyboard, 4410);
+        let mid_release = keyboard.envelope_level();
+        assert!(
+            mid_release < sustain,
+            "release should fall: {sustain} -> {mid_release}"
+        );
+        tick_n(&mut keyboard, 6000);
+        assert!(
+            keyboard.envelope_level() < 1e-3,
+            "level should reach zero after release, got {}",
+            keyboard.envelope_level()
+        );
+    }
+}
+
+#[cfg(test)]
+mod voice_filter_tests {
+    use rustic::core::envelope::prelude::{ADSREnvelopeBuilder, ConstantSegment, LinearSegment};
+    use rustic::core::generator::prelude::FilterConfig;
+    use rustic::core::utils::{NOTES, Note};
+    use rustic::instruments::Instrument;
+    use rustic::instruments::prelude::{Keyboard, PolyVoiceAllocator};
+
+    const SAMPLE_RATE: f32 = 44100.0;
+    const WINDOW: usize = 1024;
+
+    fn render(keyboard: &mut Keyboard, n: usize) -> Vec<f32> {
+        (0..n)
+            .map(|_| {
+                keyboard.tick();
+                keyboard.get_output()
+            })
+            .collect()
+    }
+
+    /// Magnitude of a single DFT bin at `frequency`.
+    fn magnitude_at(samples: &[f32], frequency: f32) -> f32 {
+        let w = std::f32::consts::TAU * frequency / SAMPLE_RATE;
+        let (re, im) = samples
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (i, s)| {
+                (re + s * (w * i as f32).cos(), im - s * (w * i as f32).sin())
+            });
+        (re * re + im * im).sqrt() / samples.len() as f32
+    }
+
+    #[test]
+    fn test_each_voice_sweeps_its_own_filter() {
+        let amplitude = ADSREnvelopeBuilder::new()
+            .attack(Box::new(LinearSegment::new(0.0, 1.0, 0.001)))
+            .decay(Box::new(LinearSegment::new(1.0, 1.0, 0.001)))
+            .sustain(Box::new(ConstantSegment::new(1.0, None)))
+            .release(Box::new(LinearSegment::new(1.0, 0.0, 0.1)))
+            .build();
+        let sweep = ADSREnvelopeBuilder::new()
+            .attack(Box::new(LinearSegment::new(0.0, 1.0, 1.0)))
+            .decay(Box::new(LinearSegment::new(1.0, 1.0, 0.01)))
+            .sustain(Box::new(ConstantSegment::new(1.0, None)))
+            .release(Box::new(LinearSegment::new(1.0, 0.0, 0.1)))
+            .build();
+
+        let mut keyboard = Keyboard::new(2, PolyVoiceAllocator::default(), amplitude);
+        keyboard.set_voice_filter(FilterConfig {
+            cutoff: 200.0,
+            envelope: Box::new(sweep),
+            envelope_amount: 8000.0,
+            ..Default::default()
+        });
+
+        let high = Note(NOTES::E, 6);
+        let low = Note(NOTES::A, 5);
+        let high_freq = high.frequency();
+        let low_freq = low.frequency();
+
+        // First note: let its filter sweep fully open
+        keyboard.start_note(high, 1.0);
+        let _ = render(&mut keyboard, (1.2 * SAMPLE_RATE) as usize);
+        let high_before = magnitude_at(&render(&mut keyboard, WINDOW), high_freq);
+
+        // Second note starts its own sweep from the closed position
+        keyboard.start_note(low, 1.0);
+        let early = render(&mut keyboard, WINDOW);
+        let _ = render(&mut keyboard, (1.2 * SAMPLE_RATE) as usize);
+        let late = render(&mut keyboard, WINDOW);
+
+        let high_after = magnitude_at(&early, high_freq);
+        let low_early = magnitude_at(&early, low_freq);
+        let low_late = magnitude_at(&late, low_freq);
+
+        assert!(
+            high_after > 0.8 * high_before,
+            "first voice's filter should stay open: {high_before} -> {high_after}"
+        );
+        assert!(
+            low_late > 3.0 * low_early,
+            "second voice's filter should sweep open: {low_early} -> {low_late}"
+        );
+    }
+}
+
 #[cfg(test)]
 mod voice_tests {
     // TODO: Add tests for Voice management
@@ -17,3 +158,54 @@ mod voice_tests {
     // - Test voice rendering
     // - Test voice pooling
 }
+
+#[cfg(test)]
+mod tuning_tests {
+    use rustic::core::envelope::prelude::{ADSREnvelopeBuilder, ConstantSegment, LinearSegment};
+    use rustic::core::utils::{NOTES, Note, TuningTable};
+    use rustic::instruments::Instrument;
+    use rustic::instruments::prelude::KeyboardBuilder;
+
+    fn render(tuning: TuningTable, note: Note) -> Vec<f32> {
+        let mut keyboard = KeyboardBuilder::new()
+            .with_voices(1)
+            .with_note_envelope(
+                ADSREnvelopeBuilder::new()
+                    .attack(Box::new(LinearSegment::new(0.0, 1.0, 0.01)))
+                    .sustain(Box::new(ConstantSegment::new(1.0, None)))
+                    .build(),
+            )
+            .with_tuning(tuning)
+            .build();
+        keyboard.start_note(note, 1.0);
+        (0..2048)
+            .map(|_| {
+                keyboard.tick();
+                keyboard.get_output()
+            })
+            .collect()
+    }
+
+    /// Magnitude of the `frequency` component of `signal` (Goertzel).
+    fn magnitude(signal: &[f32], frequency: f32) -> f32 {
+        let coeff = 2.0 * (2.0 * std::f32::consts::PI * frequency / 44100.0).cos();
+        let (mut s1, mut s2) = (0.0, 0.0);
+        for &x in signal {
+            let s0 = x + coeff * s1 - s2;
+            s2 = s1;
+            s1 = s0;
+        }
+        (s1 * s1 + s2 * s2 - coeff * s1 * s2).sqrt()
+    }
+
+    #[test]
+    fn test_keyboard_plays_through_tuning() {
+        // An octave of detune down on A sounds like the previous A: the 440 Hz
+        // fundamental appears, while an untuned A5 starts at 880 Hz
+        let a5 = Note(NOTES::A, 5);
+        let tuned = render(TuningTable::equal().with_cents(NOTES::A, -1200.0), a5);
+        let untuned = render(TuningTable::equal(), a5);
+        assert!(magnitude(&tuned, 440.0) > 10.0 * magnitude(&untuned, 440.0));
+        assert!(magnitude(&untuned, 880.0) > 10.0 * magnitude(&untuned, 440.0));
+    }
+}
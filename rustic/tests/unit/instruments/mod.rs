@@ -3,6 +3,7 @@
 
 pub mod drum;
 pub mod keyboard;
+pub mod sampler;
 
 #[cfg(test)]
 mod instrument_trait_tests {
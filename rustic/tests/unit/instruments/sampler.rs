@@ -0,0 +1,110 @@
+//! Multi-Sample Instrument Unit Tests
+//! Tests for velocity layers and round-robin sample selection
+
+#[cfg(test)]
+mod multi_sample_tests {
+    use rustic::core::graph::{FileSource, Source};
+    use rustic::core::utils::{NOTES, Note};
+    use rustic::instruments::Instrument;
+    use rustic::instruments::prelude::{MultiSampleInstrument, MultiSampleSource, VelocityLayer};
+
+    const SNARE: Note = Note(NOTES::D, 2);
+
+    /// A short sample holding a constant `level`.
+    fn hit(level: f32) -> FileSource {
+        FileSource::from_frames(vec![[level; 2]; 8], false)
+    }
+
+    fn snare() -> MultiSampleInstrument {
+        MultiSampleInstrument::new()
+            .with_layer(SNARE, 0.0..=0.4, vec![hit(0.1), hit(0.15)])
+            .with_layer(SNARE, 0.4..=1.0, vec![hit(0.8), hit(0.85), hit(0.9)])
+    }
+
+    #[test]
+    fn test_silent_until_hit() {
+        let mut snare = snare();
+        snare.tick();
+        assert_eq!(snare.get_output(), 0.0);
+        assert!(!snare.source().is_active());
+    }
+
+    #[test]
+    fn test_low_velocity_selects_quiet_layer() {
+        let mut snare = snare();
+        snare.start_note(SNARE, 0.2);
+        snare.tick();
+        assert_eq!(snare.source().last_hit(SNARE), Some((0, 0)));
+        assert_eq!(snare.get_output(), 0.1);
+
+        let mut snare = self::snare();
+        snare.start_note(SNARE, 1.0);
+        snare.tick();
+        assert_eq!(snare.source().last_hit(SNARE), Some((1, 0)));
+        assert_eq!(snare.get_output(), 0.8);
+    }
+
+    #[test]
+    fn test_repeated_hits_cycle_round_robin() {
+        let mut snare = snare();
+        let mut hits = Vec::new();
+        for _ in 0..4 {
+            snare.start_note(SNARE, 0.9);
+            hits.push(snare.source().last_hit(SNARE).unwrap());
+        }
+        assert_eq!(hits, vec![(1, 0), (1, 1), (1, 2), (1, 0)]);
+
+        // The quiet layer keeps its own rotation
+        snare.start_note(SNARE, 0.1);
+        snare.start_note(SNARE, 0.1);
+        assert_eq!(snare.source().last_hit(SNARE), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_overlapping_hits_ring_together() {
+        let mut source = MultiSampleSource::new();
+        source.add_layer(
+            SNARE,
+            VelocityLayer::new(0.0..=1.0, vec![hit(0.25), hit(0.5)]),
+        );
+        source.start_note(SNARE, 1.0);
+        assert_eq!(source.pull(4), vec![[0.25; 2]; 4]);
+
+        source.start_note(SNARE, 1.0);
+        assert_eq!(source.pull(4), vec![[0.75; 2]; 4]);
+        // The first sample ended, the second rings on
+        assert_eq!(source.pull(4), vec![[0.5; 2]; 4]);
+        assert_eq!(source.pull(1), vec![[0.0; 2]]);
+        assert!(!source.is_active());
+    }
+
+    #[test]
+    fn test_note_offs_and_unmapped_notes() {
+        let mut snare = snare();
+        snare.start_note(Note(NOTES::C, 2), 1.0);
+        snare.tick();
+        assert_eq!(snare.get_output(), 0.0);
+
+        // One-shot samples ignore note-offs
+        snare.start_note(SNARE, 1.0);
+        snare.stop_note(SNARE);
+        snare.tick();
+        assert_eq!(snare.get_output(), 0.8);
+
+        let mut snare = self::snare().with_one_shot(false);
+        snare.start_note(SNARE, 1.0);
+        snare.stop_note(SNARE);
+        snare.tick();
+        assert_eq!(snare.get_output(), 0.0);
+    }
+
+    #[test]
+    fn test_default_is_one_shot() {
+        let mut snare =
+            MultiSampleInstrument::default().with_layer(SNARE, 0.0..=1.0, vec![hit(0.5)]);
+        snare.start_note(SNARE, 1.0);
+        snare.stop_note(SNARE);
+        snare.tick();
+        assert_eq!(snare.get_output(), 0.5);
+    }
+}
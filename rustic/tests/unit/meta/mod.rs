@@ -0,0 +1,201 @@
+//! Meta Unit Tests
+//! Tests for the filter and generator registries and parameter scaling
+
+#[cfg(test)]
+mod registry_tests {
+    use rustic::meta::{FilterRegistry, get_filters};
+
+    #[test]
+    fn test_registry_creates_every_listed_filter() {
+        let registry = FilterRegistry::new();
+        let filters = get_filters();
+        assert!(!filters.is_empty());
+        assert_eq!(registry.len(), filters.len());
+
+        for info in filters {
+            let filter = registry
+                .create(info.type_id)
+                .unwrap_or_else(|| panic!("{} is not in the registry", info.type_id));
+            assert_eq!(filter.filter_info().type_id, info.type_id);
+        }
+    }
+
+    #[test]
+    fn test_registry_by_name() {
+        let registry = FilterRegistry::global();
+        assert!(registry.contains("LowPassFilter"));
+        assert!(registry.create("LowPassFilter").is_some());
+        assert!(registry.create("NotAFilter").is_none());
+
+        let names = registry.names();
+        assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
+
+#[cfg(test)]
+mod parameter_scale_tests {
+    use rustic::meta::get_filters;
+    use rustic_meta::{Parameter, ParameterScale};
+
+    fn cutoff(scale: ParameterScale) -> Parameter<&'static str> {
+        Parameter::Range {
+            title: "Cutoff",
+            field_name: "cutoff_frequency",
+            min: 20.0,
+            max: 20000.0,
+            default: 1000.0,
+            value: 1000.0,
+            unit: Some("Hz"),
+            scale,
+        }
+    }
+
+    #[test]
+    fn test_log_scale_maps_decades_evenly() {
+        let param = cutoff(ParameterScale::Logarithmic);
+
+        // 20 Hz - 20 kHz spans three decades
+        assert_eq!(param.normalize(20.0), Some(0.0));
+        assert!((param.normalize(200.0).unwrap() - 1.0 / 3.0).abs() < 1e-5);
+        assert!((param.normalize(2000.0).unwrap() - 2.0 / 3.0).abs() < 1e-5);
+        assert!((param.normalize(20000.0).unwrap() - 1.0).abs() < 1e-5);
+
+        assert!((param.denormalize(0.5).unwrap() - 632.455_5).abs() < 0.01);
+        assert!((param.denormalize(1.0).unwrap() - 20000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_scales_round_trip() {
+        for scale in [
+            ParameterScale::Linear,
+            ParameterScale::Logarithmic,
+            ParameterScale::Exponential,
+        ] {
+            let param = cutoff(scale);
+            for value in [20.0, 55.0, 440.0, 1000.0, 12345.0, 20000.0] {
+                let position = param.normalize(value).unwrap();
+                assert!((0.0..=1.0).contains(&position));
+                let back = param.denormalize(position).unwrap();
+                assert!(
+                    (back - value).abs() / value < 1e-4,
+                    "{scale:?}: {value} -> {back}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_midpoint_and_clamping() {
+        let param = cutoff(ParameterScale::Linear);
+        assert!((param.denormalize(0.5).unwrap() - 10010.0).abs() < 0.01);
+        assert_eq!(param.normalize(-5.0), Some(0.0));
+        assert_eq!(param.normalize(1e6), Some(1.0));
+        assert_eq!(param.denormalize(2.0), Some(20000.0));
+    }
+
+    #[test]
+    fn test_unbounded_parameters_have_no_position() {
+        let param = Parameter::Float {
+            title: "Factor",
+            field_name: "factor",
+            default: 1.0,
+            value: 1.0,
+            unit: None,
+        };
+        assert_eq!(param.normalize(1.0), None);
+        assert_eq!(param.denormalize(0.5), None);
+    }
+
+    #[test]
+    fn test_cutoff_filters_declare_log_scale() {
+        let lowpass = get_filters()
+            .into_iter()
+            .find(|info| info.type_id == "LowPassFilter")
+            .unwrap();
+        assert!(lowpass.inputs.iter().any(|input| matches!(
+            input.parameter,
+            Some(Parameter::Range {
+                scale: ParameterScale::Logarithmic,
+                unit: Some("Hz"),
+                ..
+            })
+        )));
+    }
+}
+
+#[cfg(test)]
+mod generator_tests {
+    use rustic::core::generator::prelude::{SawtoothWave, SineWave, Waveform, WhiteNoise};
+    use rustic::meta::{GeneratorRegistry, get_generators};
+    use rustic_meta::{GeneratorInfo, Parameter};
+
+    fn field_names(parameters: &[Parameter<&'static str>]) -> Vec<&'static str> {
+        parameters.iter().map(|p| *p.field_name()).collect()
+    }
+
+    #[test]
+    fn test_every_waveform_has_a_generator() {
+        let generators = get_generators();
+        let type_ids: Vec<_> = generators.iter().map(|g| g.type_id).collect();
+        let expected: Vec<_> = Waveform::all().iter().map(|w| w.type_id()).collect();
+        assert_eq!(type_ids, expected);
+
+        for (generator, waveform) in generators.iter().zip(Waveform::all()) {
+            assert_eq!(generator.name, waveform.display_name());
+            assert_eq!(generator.description, waveform.description());
+            assert_eq!(generator.output_count, 1);
+
+            let fields = field_names(&generator.parameters);
+            assert_eq!(fields.contains(&"frequency"), waveform.has_frequency());
+            for envelope in ["amplitude", "attack", "decay", "sustain", "release"] {
+                assert!(
+                    fields.contains(&envelope),
+                    "{} lacks {envelope}",
+                    generator.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_derived_metadata() {
+        let sine = SineWave::metadata();
+        assert_eq!((sine.name, sine.type_id), ("Sine Wave", "sine"));
+        assert_eq!(sine.description, "Smooth periodic oscillation");
+        assert_eq!(
+            &field_names(&sine.parameters)[..3],
+            ["frequency", "amplitude", "attack"]
+        );
+        assert!(matches!(
+            sine.parameters[0],
+            Parameter::Range {
+                min: 1.0,
+                max: 20000.0,
+                default: 440.0,
+                unit: Some("Hz"),
+                ..
+            }
+        ));
+
+        assert_eq!(SawtoothWave::metadata().name, "Sawtooth");
+        assert_eq!(
+            field_names(&WhiteNoise::metadata().parameters)[0],
+            "amplitude"
+        );
+    }
+
+    #[test]
+    fn test_registry_creates_sources_by_type_id() {
+        let registry = GeneratorRegistry::global();
+        assert_eq!(registry.len(), Waveform::all().len());
+        assert!(registry.contains("sine"));
+        assert!(registry.create("not-a-generator", 44100.0).is_none());
+        assert_eq!(registry.metadata("saw").unwrap().name, "Sawtooth");
+
+        let mut source = registry.create("sine", 44100.0).unwrap();
+        source.start();
+        let block = source.pull(256);
+        assert_eq!(block.len(), 256);
+        assert!(block.iter().any(|frame| frame[0] != 0.0));
+    }
+}
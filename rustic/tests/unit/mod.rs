@@ -5,5 +5,8 @@
 
 pub mod core;
 pub mod instruments;
+pub mod meta;
+#[cfg(feature = "plotting")]
+pub mod plotting;
 pub mod score;
 pub mod utils;
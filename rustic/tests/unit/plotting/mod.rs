@@ -0,0 +1,248 @@
+//! Plotting Unit Tests
+//! Tests for line annotations and the spectrum, spectrogram and envelope
+//! helpers of the plotting module
+
+#[cfg(test)]
+mod spectrum_tests {
+    use rustic::plotting::{PlotBuilder, WindowType, magnitude_spectrum};
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    fn sine(frequency: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (2.0 * PI * frequency * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_spectrum_peaks_at_sine_frequency() {
+        let spectrum = magnitude_spectrum(&sine(1000.0, 8192), SAMPLE_RATE, WindowType::Hann);
+        let bin_width = SAMPLE_RATE as f32 / 8192.0;
+        assert_eq!(spectrum.len(), 8192 / 2 - 1);
+        assert!((spectrum[0].0 - bin_width).abs() < 1e-3);
+
+        let (frequency, db) = spectrum
+            .iter()
+            .copied()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        assert!(
+            (frequency - 1000.0).abs() <= bin_width,
+            "peak at {frequency} Hz"
+        );
+        assert!(
+            db.abs() < 3.0,
+            "full-scale sine should peak near 0 dB, got {db}"
+        );
+
+        // Far from the tone the Hann window keeps leakage low
+        let far = spectrum.iter().find(|(f, _)| *f > 10000.0).unwrap();
+        assert!(far.1 < db - 60.0);
+    }
+
+    #[test]
+    fn test_plot_spectrum_renders_png() {
+        let path = std::env::temp_dir().join(format!("rustic_spectrum_{}.png", std::process::id()));
+
+        PlotBuilder::new()
+            .title("1 kHz sine")
+            .plot_spectrum(&sine(1000.0, 4096), SAMPLE_RATE, WindowType::Hann)
+            .resolution(640, 480)
+            .save(&path)
+            .unwrap();
+
+        let size = std::fs::metadata(&path).unwrap().len();
+        std::fs::remove_file(&path).ok();
+        assert!(size > 0);
+    }
+
+    #[test]
+    fn test_empty_input_has_empty_spectrum() {
+        assert!(magnitude_spectrum(&[], SAMPLE_RATE, WindowType::Hann).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod envelope_tests {
+    use rustic::core::envelope::prelude::{ADSREnvelopeBuilder, LinearSegment};
+    use rustic::plotting::{ENVELOPE_POINTS_PER_STAGE, PlotBuilder, envelope_points};
+
+    fn adsr() -> rustic::core::envelope::prelude::ADSREnvelope {
+        ADSREnvelopeBuilder::new()
+            .attack(Box::new(LinearSegment::new(0.0, 1.0, 0.2)))
+            .decay(Box::new(LinearSegment::new(1.0, 0.6, 0.3)))
+            .release(Box::new(LinearSegment::new(0.6, 0.0, 0.5)))
+            .build()
+    }
+
+    #[test]
+    fn test_envelope_points_cover_every_stage() {
+        let points = envelope_points(&adsr(), 1.0);
+        assert_eq!(points.len(), 4 * ENVELOPE_POINTS_PER_STAGE + 1);
+        assert_eq!(points[0], (0.0, 0.0));
+        assert!((points.last().unwrap().0 - 1.5).abs() < 1e-5);
+        assert!(points.last().unwrap().1.abs() < 1e-5);
+        assert!(points.windows(2).all(|w| w[0].0 <= w[1].0));
+
+        // Sustain level is held until the note off
+        let sustain = points.iter().find(|(t, _)| (0.6..1.0).contains(t)).unwrap();
+        assert!((sustain.1 - 0.6).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_envelope_peaks_at_end_of_attack() {
+        let points = envelope_points(&adsr(), 1.0);
+        let (time, level) = points
+            .iter()
+            .copied()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        assert!((time - 0.2).abs() < 1e-5, "peak at {time}s");
+        assert!((level - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_plot_envelope_renders_png() {
+        let path = std::env::temp_dir().join(format!("rustic_envelope_{}.png", std::process::id()));
+
+        PlotBuilder::new()
+            .title("ADSR")
+            .plot_envelope(&adsr(), 1.0)
+            .resolution(640, 480)
+            .save(&path)
+            .unwrap();
+
+        let size = std::fs::metadata(&path).unwrap().len();
+        std::fs::remove_file(&path).ok();
+        assert!(size > 0);
+    }
+}
+
+#[cfg(test)]
+mod line_tests {
+    use rustic::plotting::{PlotBuilder, PlotError};
+
+    #[test]
+    fn test_threshold_line_renders() {
+        let path = std::env::temp_dir().join(format!("rustic_lines_{}.png", std::process::id()));
+        let data: Vec<(f32, f32)> = (0..100)
+            .map(|i| (i as f32, (i as f32 / 10.0).sin()))
+            .collect();
+
+        PlotBuilder::new()
+            .title("Threshold")
+            .x_range(0.0, 100.0)
+            .y_range(-1.0, 1.0)
+            .add_series(data, "Signal", None)
+            .add_horizontal_line(0.5, Some((255, 0, 0)))
+            .add_line((0.0, -1.0), (100.0, 1.0), None)
+            .resolution(640, 480)
+            .save(&path)
+            .unwrap();
+
+        let size = std::fs::metadata(&path).unwrap().len();
+        std::fs::remove_file(&path).ok();
+        assert!(size > 0);
+    }
+
+    #[test]
+    fn test_lines_alone_are_enough_to_render() {
+        let path =
+            std::env::temp_dir().join(format!("rustic_only_lines_{}.png", std::process::id()));
+
+        let result = PlotBuilder::new()
+            .add_vertical_line(0.5, None)
+            .resolution(320, 240)
+            .save(&path);
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_ok());
+
+        assert!(matches!(
+            PlotBuilder::new().save(&path),
+            Err(PlotError::EmptyData)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod spectrogram_tests {
+    use rustic::plotting::{
+        ColorMap, PlotBuilder, PlotError, ScaleMode, WindowType, compute_spectrogram,
+    };
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = 16000.0;
+
+    /// Linear chirp from 200 Hz to 6 kHz over one second
+    fn chirp() -> Vec<f32> {
+        let (f0, f1) = (200.0, 6000.0);
+        (0..SAMPLE_RATE as usize)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE;
+                (2.0 * PI * (f0 * t + (f1 - f0) * t * t / 2.0)).sin()
+            })
+            .collect()
+    }
+
+    fn peak_bin(frame: &[f32]) -> usize {
+        frame
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn test_chirp_spectrogram_rises() {
+        let frames = compute_spectrogram(&chirp(), 512, 256, WindowType::Hann);
+        assert_eq!(frames.len(), (16000 - 512) / 256 + 1);
+        assert!(frames.iter().all(|frame| frame.len() == 256));
+
+        let bin_width = SAMPLE_RATE / 512.0;
+        let first = peak_bin(&frames[0]) as f32 * bin_width;
+        let last = peak_bin(frames.last().unwrap()) as f32 * bin_width;
+        assert!(first < 500.0, "first frame peaks at {first} Hz");
+        assert!(last > 5500.0, "last frame peaks at {last} Hz");
+    }
+
+    #[test]
+    fn test_render_chirp_spectrogram() {
+        let path =
+            std::env::temp_dir().join(format!("rustic_spectrogram_{}.png", std::process::id()));
+        let frames = compute_spectrogram(&chirp(), 512, 256, WindowType::Hann);
+
+        PlotBuilder::new()
+            .title("Chirp")
+            .x_range(0.0, 1.0)
+            .y_range(0.0, SAMPLE_RATE / 2.0)
+            .resolution(640, 480)
+            .render_spectrogram(&frames, ColorMap::Viridis, ScaleMode::Decibel, &path)
+            .unwrap();
+
+        let size = std::fs::metadata(&path).unwrap().len();
+        std::fs::remove_file(&path).ok();
+        assert!(size > 0);
+    }
+
+    #[test]
+    fn test_empty_spectrogram_is_rejected() {
+        let path = std::env::temp_dir().join("rustic_empty_spectrogram.png");
+        let result =
+            PlotBuilder::new().render_spectrogram(&[], ColorMap::Heat, ScaleMode::Linear, &path);
+        assert!(matches!(result, Err(PlotError::EmptyData)));
+    }
+
+    #[test]
+    fn test_scale_and_color_mapping() {
+        assert_eq!(ScaleMode::Linear.intensity(0.5, 1.0), 0.5);
+        assert_eq!(ScaleMode::Decibel.intensity(1.0, 1.0), 1.0);
+        assert!((ScaleMode::Decibel.intensity(0.01, 1.0) - 0.5).abs() < 1e-5);
+        assert_eq!(ScaleMode::Decibel.intensity(1e-6, 1.0), 0.0);
+
+        assert_eq!(ColorMap::Grayscale.color(0.0), (0, 0, 0));
+        assert_eq!(ColorMap::Grayscale.color(1.0), (255, 255, 255));
+        assert_eq!(ColorMap::Viridis.color(1.0), (0xfd, 0xe7, 0x25));
+    }
+}
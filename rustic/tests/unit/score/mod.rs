@@ -37,14 +37,183 @@ mod note_tests {
     // - Test dotted notes
     // - Test tuplets
     // - Test rest notes
+    use rustic::prelude::*;
+    use rustic::score::measure::Measure;
+
+    fn tuplet(duration: NoteDuration, count: u8, in_space_of: u8) -> Note {
+        Note::new(
+            duration,
+            DurationModifier::Tuplet { count, in_space_of },
+            NoteName::E,
+            NoteModifier::None,
+            4,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_triplet_quavers_fill_a_crotchet() {
+        let triplet = tuplet(NoteDuration::Quaver, 3, 2);
+        let crotchet = NoteDuration::Crotchet.duration();
+        assert!((3.0 * triplet.exact_duration() - crotchet as f64).abs() < 1e-9);
+        assert!((triplet.seconds(120.0) * 3.0 - 0.5).abs() < 1e-6);
+
+        // Positions are rounded, not durations, so the group ends on the beat
+        let mut measure = Measure::new(&TimeSignature::C);
+        let mut positions = Vec::new();
+        for _ in 0..3 {
+            positions.push(measure.current_index());
+            measure
+                .add_note(measure.current_index(), triplet.clone())
+                .unwrap();
+        }
+        assert_eq!(positions, vec![0, 21, 43]);
+        assert_eq!(measure.current_index(), crotchet);
+
+        let chord = Chord::new(vec![triplet], ChordModifier::None);
+        assert_eq!(chord.duration(), 21);
+    }
+
+    #[test]
+    fn test_quintuplet_scaling() {
+        // Five semiquavers in the space of four
+        let quintuplet = tuplet(NoteDuration::SemiQuaver, 5, 4);
+        assert!((quintuplet.exact_duration() - 12.8).abs() < 1e-9);
+        assert_eq!(quintuplet.duration(), 13);
+
+        let mut staff = Staff::new(&TimeSignature::C);
+        for _ in 0..5 {
+            staff.add_note(quintuplet.clone()).unwrap();
+        }
+        assert_eq!(staff.total_duration(), NoteDuration::Crotchet.duration());
+    }
 }
 
 #[cfg(test)]
 mod measure_tests {
     // TODO: Add tests for Measure
-    // - Test time signature enforcement
-    // - Test measure capacity
-    // - Test note addition/removal
+    // - Test note removal
+    use rustic::prelude::*;
+    use rustic::score::measure::Measure;
+
+    fn quaver() -> Note {
+        Note::new(
+            NoteDuration::Quaver,
+            DurationModifier::None,
+            NoteName::C,
+            NoteModifier::None,
+            4,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_compound_bar_duration_and_accents() {
+        let signature = TimeSignature(6, 8);
+        assert!(signature.is_compound());
+        // Six quavers, i.e. a dotted minim
+        assert_eq!(
+            signature.bar_ticks(),
+            NoteDuration::Minim.ticks(&DurationModifier::Dotted)
+        );
+        assert_eq!(signature.beat_groups(), vec![3, 3]);
+        assert_eq!(signature.accents(), vec![1, 4]);
+
+        let mut measure = Measure::new(&signature);
+        for _ in 0..5 {
+            measure.add_note(measure.current_index(), quaver()).unwrap();
+            assert!(!measure.is_full());
+        }
+        measure.add_note(measure.current_index(), quaver()).unwrap();
+        assert!(measure.is_full());
+    }
+
+    fn note(duration: NoteDuration) -> Note {
+        Note::new(
+            duration,
+            DurationModifier::None,
+            NoteName::C,
+            NoteModifier::None,
+            4,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_exactly_filling_a_measure() {
+        let mut measure = Measure::new(&TimeSignature::C);
+        assert_eq!(measure.remaining_capacity(), 256);
+        measure.add_note(0, note(NoteDuration::Minim)).unwrap();
+        measure
+            .add_note(measure.current_index(), note(NoteDuration::Crotchet))
+            .unwrap();
+        assert_eq!(measure.remaining_capacity(), 64);
+        measure
+            .add_note(measure.current_index(), note(NoteDuration::Crotchet))
+            .unwrap();
+        assert!(measure.is_full());
+        assert_eq!(measure.remaining_capacity(), 0);
+    }
+
+    #[test]
+    fn test_overfilling_a_measure_is_rejected() {
+        let mut measure = Measure::new(&TimeSignature::C);
+        measure.add_note(0, note(NoteDuration::Minim)).unwrap();
+        measure.add_note(128, note(NoteDuration::Crotchet)).unwrap();
+        // A minim on the fourth beat would spill over the bar line
+        assert!(measure.add_note(192, note(NoteDuration::Minim)).is_err());
+        // So would growing the chord of the third beat
+        assert!(
+            measure
+                .add_note(128, note(NoteDuration::SemiBreve))
+                .is_err()
+        );
+        assert_eq!(measure.remaining_capacity(), 64);
+
+        measure.force_add_note(192, note(NoteDuration::Minim));
+        assert!(measure.is_full());
+        assert_eq!(measure.remaining_capacity(), 0);
+    }
+
+    #[test]
+    fn test_measure_size_is_saved_in_crotchets() {
+        let measure = Measure::new(&TimeSignature::C);
+        let saved = toml::to_string(&measure).unwrap();
+        assert!(saved.contains("size = 4"), "{saved}");
+
+        // Scores saved before sizes were tracked in ticks load unchanged
+        let loaded: Measure = toml::from_str("size = 3\nchords_set = []").unwrap();
+        assert_eq!(loaded.size(), 3 * NoteDuration::Crotchet.duration());
+
+        // A 7/8 bar lasts three and a half crotchets
+        let measure = Measure::new(&TimeSignature(7, 8));
+        let saved = toml::to_string(&measure).unwrap();
+        assert!(saved.contains("size = 3.5"), "{saved}");
+        let loaded: Measure = toml::from_str(&saved).unwrap();
+        assert_eq!(loaded.size(), measure.size());
+    }
+
+    #[test]
+    fn test_odd_meter_grouping() {
+        let signature = TimeSignature(7, 8);
+        assert!(!signature.is_compound());
+        assert_eq!(signature.bar_ticks(), 7 * NoteDuration::Quaver.duration());
+        assert_eq!(signature.beat_groups(), vec![2, 2, 3]);
+        assert_eq!(signature.accents(), vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn test_accent_weights() {
+        let signature = TimeSignature(6, 8);
+        let quaver = NoteDuration::Quaver.duration();
+        assert!(signature.accent_at(0) > signature.accent_at(3 * quaver));
+        assert!(signature.accent_at(3 * quaver) > signature.accent_at(quaver));
+        // The pattern repeats every bar
+        assert_eq!(
+            signature.accent_at(signature.bar_ticks()),
+            signature.accent_at(0)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -53,6 +222,23 @@ mod staff_tests {
     // - Test staff initialization
     // - Test measure management
     // - Test staff compilation
+    use rustic::prelude::*;
+
+    #[test]
+    fn test_default_pan_is_centered() {
+        let mut staff = Staff::new(&TimeSignature::C);
+        let (left, right) = staff.stereo_gains();
+        assert!((left - right).abs() < 1e-6);
+        // Equal power: the center sits 3dB below either side
+        assert!((left * left + right * right - 1.0).abs() < 1e-6);
+
+        staff.set_gain(-6.0);
+        staff.set_pan(-2.0);
+        assert_eq!(staff.get_pan(), -1.0);
+        let (left, right) = staff.stereo_gains();
+        assert!((left - 0.501).abs() < 1e-3);
+        assert!(right.abs() < 1e-6);
+    }
 }
 
 #[cfg(test)]
@@ -62,4 +248,253 @@ mod score_tests {
     // - Test multi-staff management
     // - Test tempo changes
     // - Test score compilation
+    use std::sync::{Arc, Mutex};
+
+    use rustic::core::graph::FileSource;
+    use rustic::instruments::Instrument;
+    use rustic::instruments::prelude::MultiSampleInstrument;
+    use rustic::prelude::*;
+
+    /// An instrument holding `level` for a while whenever C4 is played
+    fn constant(level: f32) -> Box<MultiSampleInstrument> {
+        let sample = FileSource::from_frames(vec![[level; 2]; 200], false);
+        Box::new(MultiSampleInstrument::new().with_layer(
+            rustic::Note(rustic::NOTES::C, 4),
+            0.0..=1.0,
+            vec![sample],
+        ))
+    }
+
+    fn c4() -> Note {
+        Note::new(
+            NoteDuration::Crotchet,
+            DurationModifier::None,
+            NoteName::C,
+            NoteModifier::None,
+            4,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_staves_panned_hard_left_and_right() {
+        let mut score = Score::new("Panning", TimeSignature::C, 120, Vec::new(), Vec::new());
+        let left_staff = score.add_instrument(constant(0.5));
+        let right_staff = score.add_instrument(constant(0.25));
+        score.staves[left_staff].set_pan(-1.0);
+        score.staves[right_staff].set_pan(1.0);
+        for _ in 0..2 {
+            score.add_note(left_staff, c4()).unwrap();
+            score.add_note(right_staff, c4()).unwrap();
+        }
+
+        let frames = score.render(1000.0).unwrap();
+        // Two crotchets at 120bpm
+        assert_eq!(frames.len(), 1000);
+
+        let is_either =
+            |sample: f32, level: f32| sample.abs() < 1e-6 || (sample - level).abs() < 1e-6;
+        assert!(frames.iter().all(|frame| is_either(frame[0], 0.5)));
+        assert!(frames.iter().all(|frame| is_either(frame[1], 0.25)));
+        assert!(frames.iter().any(|frame| frame[0] > 0.0));
+        assert!(frames.iter().any(|frame| frame[1] > 0.0));
+    }
+
+    type Events = Arc<Mutex<Vec<(&'static str, rustic::Note)>>>;
+
+    /// Instrument sounding a constant level while any note is held, and
+    /// recording the notes struck and released
+    #[derive(Debug, Default)]
+    struct Recorder {
+        held: Vec<rustic::Note>,
+        output: f32,
+        events: Events,
+        velocities: Arc<Mutex<Vec<f32>>>,
+    }
+
+    impl Instrument for Recorder {
+        fn start_note(&mut self, note: rustic::Note, velocity: f32) {
+            self.velocities.lock().unwrap().push(velocity);
+            self.held.push(note);
+            self.events.lock().unwrap().push(("on", note));
+        }
+
+        fn stop_note(&mut self, note: rustic::Note) {
+            self.held.retain(|held| *held != note);
+            self.events.lock().unwrap().push(("off", note));
+        }
+
+        fn get_output(&mut self) -> f32 {
+            self.output
+        }
+
+        fn tick(&mut self) {
+            self.output = if self.held.is_empty() { 0.0 } else { 1.0 };
+        }
+
+        fn into_system(self: Box<Self>, _sample_rate: f32) -> rustic::core::graph::System {
+            rustic::core::graph::System::silent()
+        }
+    }
+
+    fn crotchet_c4(tied: bool) -> Note {
+        Note::new(
+            NoteDuration::Crotchet,
+            DurationModifier::None,
+            NoteName::C,
+            NoteModifier::None,
+            4,
+            tied,
+        )
+    }
+
+    /// A score whose first bar ends with a C4 crotchet, followed by a C4
+    /// crotchet on the downbeat of the second bar
+    fn across_bar_line(tied: bool) -> (Score, Events) {
+        let recorder = Recorder::default();
+        let events = recorder.events.clone();
+        let mut score = Score::new("Ties", TimeSignature::C, 120, Vec::new(), Vec::new());
+        let staff = score.add_instrument(Box::new(recorder));
+        score
+            .add_note(staff, Note::new_pause(NoteDuration::Minim).unwrap())
+            .unwrap();
+        score
+            .add_note(staff, Note::new_pause(NoteDuration::Crotchet).unwrap())
+            .unwrap();
+        score.add_note(staff, crotchet_c4(tied)).unwrap();
+        score.add_note(staff, crotchet_c4(false)).unwrap();
+        score
+            .add_note(staff, Note::new_pause(NoteDuration::Crotchet).unwrap())
+            .unwrap();
+        (score, events)
+    }
+
+    #[test]
+    fn test_tied_notes_across_bar_line_sound_once() {
+        let (mut score, events) = across_bar_line(true);
+        let frames = score.render(1000.0).unwrap();
+        assert_eq!(frames.len(), 3000);
+
+        let c4 = rustic::Note(rustic::NOTES::C, 4);
+        assert_eq!(*events.lock().unwrap(), vec![("on", c4), ("off", c4)]);
+
+        // One continuous tone lasting a minim (1s at 120bpm), starting on
+        // the fourth beat of the first bar
+        let sounding: Vec<usize> = (0..frames.len()).filter(|&i| frames[i][0] > 0.0).collect();
+        assert_eq!(sounding.len(), 1000);
+        assert_eq!(sounding.first(), Some(&1500));
+        assert_eq!(sounding.last(), Some(&2499));
+    }
+
+    #[test]
+    fn test_untied_notes_are_struck_again() {
+        let (mut score, events) = across_bar_line(false);
+        score.render(1000.0).unwrap();
+
+        let c4 = rustic::Note(rustic::NOTES::C, 4);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![("on", c4), ("off", c4), ("on", c4), ("off", c4)]
+        );
+    }
+
+    /// Velocities a bar of `count` notes of `duration` is played with
+    fn bar_velocities(
+        signature: TimeSignature,
+        duration: NoteDuration,
+        count: usize,
+        accents: bool,
+    ) -> Vec<f32> {
+        let recorder = Recorder::default();
+        let velocities = recorder.velocities.clone();
+        let mut score = Score::new("Accents", signature, 120, Vec::new(), Vec::new());
+        score.accents = accents;
+        let staff = score.add_instrument(Box::new(recorder));
+        for _ in 0..count {
+            let note = Note::new(
+                duration.clone(),
+                DurationModifier::None,
+                NoteName::C,
+                NoteModifier::None,
+                4,
+                false,
+            );
+            score.add_note(staff, note).unwrap();
+        }
+        score.render(1000.0).unwrap();
+        velocities.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn test_simple_meters_are_not_accented_by_default() {
+        let velocities = bar_velocities(TimeSignature::C, NoteDuration::Crotchet, 4, false);
+        assert_eq!(velocities, vec![1.0; 4]);
+    }
+
+    #[test]
+    fn test_compound_meters_are_accented() {
+        let velocities = bar_velocities(TimeSignature(6, 8), NoteDuration::Quaver, 6, false);
+        assert_eq!(velocities, vec![1.0, 0.6, 0.6, 0.8, 0.6, 0.6]);
+    }
+
+    #[test]
+    fn test_simple_meters_accented_on_request() {
+        let velocities = bar_velocities(TimeSignature::C, NoteDuration::Crotchet, 4, true);
+        assert_eq!(velocities, vec![1.0, 0.8, 0.8, 0.8]);
+    }
+
+    fn four_bars(signature: TimeSignature) -> Score {
+        let mut score = Score::new("Bars", signature.clone(), 120, Vec::new(), Vec::new());
+        let staff = score.add_instrument(constant(1.0));
+        for _ in 0..4 * signature.0 {
+            score.add_note(staff, c4()).unwrap();
+        }
+        score
+    }
+
+    #[test]
+    fn test_duration_seconds() {
+        let empty = Score::new("Empty", TimeSignature::C, 120, Vec::new(), Vec::new());
+        assert_eq!(empty.duration_seconds(), 0.0);
+
+        assert!((four_bars(TimeSignature(4, 4)).duration_seconds() - 8.0).abs() < 1e-6);
+        assert!((four_bars(TimeSignature(3, 4)).duration_seconds() - 6.0).abs() < 1e-6);
+
+        // An incomplete bar still lasts its full length
+        let mut score = four_bars(TimeSignature(4, 4));
+        score.add_note(0, c4()).unwrap();
+        assert!((score.duration_seconds() - 10.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+pub fn test_duration_in_seconds() {
+    let crotchet = NoteDuration::Crotchet;
+    assert!((crotchet.seconds(&DurationModifier::None, 120.0) - 0.5).abs() < 1e-6);
+    assert!((crotchet.seconds(&DurationModifier::Dotted, 120.0) - 0.75).abs() < 1e-6);
+    assert!((NoteDuration::Minim.seconds(&DurationModifier::None, 120.0) - 1.0).abs() < 1e-6);
+
+    let chord = Chord::new(
+        vec![
+            Note::new(
+                NoteDuration::Crotchet,
+                DurationModifier::Dotted,
+                NoteName::A,
+                NoteModifier::None,
+                4,
+                false,
+            ),
+            Note::new(
+                NoteDuration::Crotchet,
+                DurationModifier::None,
+                NoteName::C,
+                NoteModifier::None,
+                4,
+                false,
+            ),
+        ],
+        ChordModifier::None,
+    );
+    assert!((chord.notes[1].seconds(120.0) - 0.5).abs() < 1e-6);
+    assert!((chord.seconds(120.0) - 0.75).abs() < 1e-6);
 }